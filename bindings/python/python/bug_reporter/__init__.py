@@ -0,0 +1,10 @@
+"""Python bindings for the `bug` crate.
+
+Templates declared here produce the same issue URLs as the Rust
+`BugReportHandle`, so mixed Rust/Python tooling can share one set of
+issue templates.
+"""
+
+from ._native import BugHandle, init
+
+__all__ = ["BugHandle", "init"]
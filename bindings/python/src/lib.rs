@@ -0,0 +1,106 @@
+//! Python bindings for `bug`.
+//!
+//! Exposes `BugReportHandle` to Python as `bug_reporter.BugHandle`, so
+//! mixed Rust/Python tooling can share one set of issue templates:
+//!
+//! ```python
+//! import bug_reporter
+//!
+//! handle = bug_reporter.init("owner", "repo")
+//! handle.add_template("crash", "Crash in {tool}", "Exit code {code}", labels=["bug"])
+//!
+//! url = handle.generate_url("crash", {"tool": "formatter", "code": "3"})
+//! handle.report("crash", {"tool": "formatter", "code": "3"})  # prints the banner to stderr
+//! ```
+//!
+//! Errors that `bug` reports as `Err(String)` are raised as `ValueError`.
+
+use bug::{IssueTemplate, Params};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// A bug report handle for one GitHub repository.
+#[pyclass(name = "BugHandle", module = "bug_reporter")]
+struct BugHandle {
+    inner: bug::BugReportHandle,
+}
+
+#[pymethods]
+impl BugHandle {
+    /// Create a handle for a GitHub repository.
+    #[new]
+    fn new(owner: &str, repo: &str) -> Self {
+        Self {
+            inner: bug::init_handle(owner, repo),
+        }
+    }
+
+    /// Add a template, replacing any template with the same name.
+    #[pyo3(signature = (name, title, body, labels = None, assignees = None))]
+    fn add_template(
+        &mut self,
+        name: &str,
+        title: &str,
+        body: &str,
+        labels: Option<Vec<String>>,
+        assignees: Option<Vec<String>>,
+    ) {
+        let mut template = IssueTemplate::new(title, body);
+        if let Some(labels) = labels {
+            template = template.with_labels(labels);
+        }
+        if let Some(assignees) = assignees {
+            template = template.with_assignees(assignees);
+        }
+        self.inner.register_template(name, template);
+    }
+
+    /// Generate the issue URL for a template.
+    #[pyo3(signature = (template, params = None))]
+    fn generate_url(&self, template: &str, params: Option<HashMap<String, String>>) -> PyResult<String> {
+        self.inner
+            .generate_url(template, &to_params(params))
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Print the bug banner to stderr and return the issue URL.
+    ///
+    /// The banner names the Python file and line that called `report`.
+    /// Returns an empty string if the report couldn't be generated.
+    #[pyo3(signature = (template, params = None))]
+    fn report(&self, py: Python<'_>, template: &str, params: Option<HashMap<String, String>>) -> PyResult<String> {
+        // Rust functions push no Python frame, so frame 0 is the caller
+        let frame = py.import("sys")?.call_method1("_getframe", (0,))?;
+        let file: String = frame.getattr("f_code")?.getattr("co_filename")?.extract()?;
+        let line: u32 = frame.getattr("f_lineno")?.extract()?;
+        Ok(self.inner.report_bug_stderr(template, &to_params(params), &file, line))
+    }
+
+    /// Names of the registered templates, in registration order.
+    fn template_names(&self) -> Vec<String> {
+        self.inner.config().template_names().map(str::to_string).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let config = self.inner.config();
+        format!("BugHandle({:?}, {:?})", config.github_owner, config.github_repo)
+    }
+}
+
+/// Create a handle for a GitHub repository, like `bug::init_handle`.
+#[pyfunction]
+fn init(owner: &str, repo: &str) -> BugHandle {
+    BugHandle::new(owner, repo)
+}
+
+fn to_params(params: Option<HashMap<String, String>>) -> Params {
+    params.unwrap_or_default().into_iter().collect()
+}
+
+#[pymodule]
+fn _native(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<BugHandle>()?;
+    module.add_function(wrap_pyfunction!(init, module)?)?;
+    Ok(())
+}
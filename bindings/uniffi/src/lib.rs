@@ -0,0 +1,145 @@
+//! UniFFI bindings for `bug`.
+//!
+//! Exposes the handle, template, and report API to the Kotlin and Swift
+//! shells of Rust-core mobile apps. The shell generates a report and opens
+//! its URL in the system browser, which gives "tap to report" from a crash
+//! screen or a settings menu.
+//!
+//! Built for Android or iOS, templates can also use `{device}`,
+//! `{device_model}`, `{os_version}`, and the other placeholders of
+//! `bug::device`, which are filled from the phone the app runs on.
+//!
+//! Generate the foreign bindings from the built library with:
+//!
+//! ```sh
+//! cargo build --release
+//! cargo run --features cli --bin uniffi-bindgen -- generate \
+//!     --library target/release/libbug_uniffi.so --language kotlin --out-dir out
+//! ```
+//!
+//! Kotlin:
+//!
+//! ```kotlin
+//! val handle = BugHandle("owner", "repo")
+//! handle.addTemplate("crash", "Crash on {screen}", "Steps: {steps}", listOf("mobile"))
+//! val report = handle.createReport("crash", mapOf("screen" to "Checkout", "steps" to "Tap pay"))
+//! startActivity(Intent(Intent.ACTION_VIEW, Uri.parse(report.url)))
+//! ```
+//!
+//! Swift:
+//!
+//! ```swift
+//! let handle = BugHandle(owner: "owner", repo: "repo")
+//! handle.addTemplate(name: "crash", title: "Crash on {screen}", body: "Steps: {steps}", labels: ["mobile"])
+//! let report = try handle.createReport(template: "crash", params: ["screen": "Checkout", "steps": "Tap pay"])
+//! UIApplication.shared.open(URL(string: report.url)!)
+//! ```
+
+use bug::{BugReportHandle, IssueTemplate, Params};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+uniffi::setup_scaffolding!();
+
+/// Why a report couldn't be generated.
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum BugError {
+    /// The template is unknown or its placeholders couldn't be filled
+    Report(String),
+}
+
+impl fmt::Display for BugError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BugError::Report(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for BugError {}
+
+/// A generated issue, ready to open in the browser.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BugReport {
+    /// The GitHub new-issue URL with all fields pre-filled
+    pub url: String,
+    /// The filled issue title
+    pub title: String,
+    /// The filled issue body
+    pub body: String,
+    /// Labels applied to the issue
+    pub labels: Vec<String>,
+}
+
+impl From<bug::BugReport> for BugReport {
+    fn from(report: bug::BugReport) -> Self {
+        Self {
+            url: report.url,
+            title: report.title,
+            body: report.body,
+            labels: report.labels,
+        }
+    }
+}
+
+/// A bug report handle for one GitHub repository.
+///
+/// Foreign objects are shared, so templates are added through a lock and
+/// the handle can be used from any thread.
+#[derive(uniffi::Object)]
+pub struct BugHandle {
+    inner: RwLock<BugReportHandle>,
+}
+
+#[uniffi::export]
+impl BugHandle {
+    /// Create a handle for a GitHub repository.
+    #[uniffi::constructor]
+    pub fn new(owner: String, repo: String) -> Arc<Self> {
+        Arc::new(Self {
+            inner: RwLock::new(bug::init_handle(owner, repo)),
+        })
+    }
+
+    /// Add a template, replacing any template with the same name.
+    pub fn add_template(&self, name: String, title: String, body: String, labels: Vec<String>) {
+        let template = IssueTemplate::new(title, body).with_labels(labels);
+        self.write().register_template(name, template);
+    }
+
+    /// Generate a report from a template.
+    pub fn create_report(&self, template: String, params: HashMap<String, String>) -> Result<BugReport, BugError> {
+        self.read()
+            .create_report(&template, &to_params(params))
+            .map(BugReport::from)
+            .map_err(BugError::Report)
+    }
+
+    /// Generate the issue URL for a template.
+    pub fn generate_url(&self, template: String, params: HashMap<String, String>) -> Result<String, BugError> {
+        self.read()
+            .generate_url(&template, &to_params(params))
+            .map_err(BugError::Report)
+    }
+
+    /// Names of the registered templates, in registration order.
+    pub fn template_names(&self) -> Vec<String> {
+        self.read().config().template_names().map(str::to_string).collect()
+    }
+}
+
+impl BugHandle {
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, BugReportHandle> {
+        self.inner.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, BugReportHandle> {
+        self.inner.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn to_params(params: HashMap<String, String>) -> Params {
+    params.into_iter().collect()
+}
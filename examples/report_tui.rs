@@ -0,0 +1,25 @@
+//! An interactive menu for filing reports by hand, as a debug menu in a
+//! dev build might embed it.
+//!
+//! Run with `cargo run --example report_tui`.
+
+use bug::tui::DebugMenu;
+use bug::{init_handle, IssueTemplate};
+
+fn main() -> std::io::Result<()> {
+    let handle = init_handle("myorg", "myapp")
+        .add_template("crash", IssueTemplate::new(
+            "Crash on {screen}",
+            "## Steps to Reproduce\n{steps}\n\n## Build\n{build}"
+        ).with_labels(vec!["bug".to_string(), "crash".to_string()]))
+        .add_template("ui_glitch", IssueTemplate::new(
+            "Rendering glitch on {screen}",
+            "What looks wrong: {description}"
+        ).with_labels(vec!["ui".to_string()]))
+        .add_template("feedback", IssueTemplate::new("Feedback from a dev build", "Thanks for testing!"));
+
+    if let Some(url) = DebugMenu::new(&handle).run()? {
+        println!("Last report: {}", url);
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+#ifndef BUG_H
+#define BUG_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ The call succeeded.
+ */
+#define BUG_OK 0
+
+/*
+ A required pointer argument was null.
+ */
+#define BUG_ERROR_NULL -1
+
+/*
+ A string argument was not valid UTF-8.
+ */
+#define BUG_ERROR_UTF8 -2
+
+/*
+ An opaque bug report handle.
+ */
+typedef struct BugHandle BugHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Create a handle for a GitHub repository.
+
+ Returns null if either argument is null or not valid UTF-8. Release the
+ handle with [`bug_handle_free`].
+
+ # Safety
+
+ `owner` and `repo` must be null or point to NUL-terminated strings.
+ */
+BugHandle *bug_handle_new(const char *owner, const char *repo);
+
+/*
+ Release a handle created by [`bug_handle_new`]. Null is ignored.
+
+ # Safety
+
+ `handle` must be null or a handle from [`bug_handle_new`] that has not
+ been freed.
+ */
+void bug_handle_free(BugHandle *handle);
+
+/*
+ Add a template to a handle, replacing any template with the same name.
+
+ Returns [`BUG_OK`], [`BUG_ERROR_NULL`] if an argument is null, or
+ [`BUG_ERROR_UTF8`] if a string is not valid UTF-8.
+
+ # Safety
+
+ `handle` must be a live handle from [`bug_handle_new`] not used by
+ another thread during the call, and the strings must be null or
+ NUL-terminated.
+ */
+int bug_handle_add_template(BugHandle *handle, const char *name, const char *title, const char *body);
+
+/*
+ Generate the issue URL for a template.
+
+ `keys` and `values` are parallel arrays of `count` parameter names and
+ values; both may be null when `count` is 0. Returns null if an argument
+ is null or not valid UTF-8, or the template can't be filled. Release
+ the URL with [`bug_string_free`].
+
+ # Safety
+
+ `handle` must be a live handle from [`bug_handle_new`], `template_name`
+ must be null or NUL-terminated, and `keys` and `values` must each point
+ to `count` pointers that are null or NUL-terminated.
+
+ # Examples
+
+ ```
+ use bug::ffi::{bug_generate_url, bug_handle_add_template, bug_handle_free, bug_handle_new, bug_string_free, BUG_OK};
+ use std::ffi::CStr;
+
+ unsafe {
+     let handle = bug_handle_new(c"owner".as_ptr(), c"repo".as_ptr());
+     assert_eq!(bug_handle_add_template(handle, c"crash".as_ptr(), c"Crash in {plugin}".as_ptr(), c"".as_ptr()), BUG_OK);
+
+     let keys = [c"plugin".as_ptr()];
+     let values = [c"reverb".as_ptr()];
+     let url = bug_generate_url(handle, c"crash".as_ptr(), keys.as_ptr(), values.as_ptr(), 1);
+     assert!(CStr::from_ptr(url).to_str().unwrap().contains("title=Crash+in+reverb"));
+     bug_string_free(url);
+
+     assert!(bug_generate_url(handle, c"unknown".as_ptr(), std::ptr::null(), std::ptr::null(), 0).is_null());
+     bug_handle_free(handle);
+ }
+ ```
+ */
+char *bug_generate_url(const BugHandle *handle,
+                       const char *template_name,
+                       const char *const *keys,
+                       const char *const *values,
+                       size_t count);
+
+/*
+ Release a string returned by this API. Null is ignored.
+
+ # Safety
+
+ `s` must be null or a string returned by this API that has not been
+ freed.
+ */
+void bug_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BUG_H */
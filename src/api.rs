@@ -0,0 +1,145 @@
+//! Finding existing issues through the GitHub API (feature `api`).
+//!
+//! End users who hit the same bug tend to file it again and again. With a
+//! [`GitHubApi`] set on the configuration, each report first searches the
+//! repository's open issues, and if one matches, the banner prints that
+//! issue's URL instead of a new-issue link. Templates whose body contains
+//! a `{fingerprint}` placeholder are matched on the fingerprint marker,
+//! which is the same for every report of the same template and
+//! parameters; other templates are matched on their filled title.
+//!
+//! The crate makes no network requests itself: supply an [`HttpClient`]
+//! around whatever HTTP library the application already uses. A failed
+//! request is treated as no match, so reporting never depends on the
+//! network.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, Params};
+//! use bug::api::GitHubApi;
+//!
+//! // Stands in for a real client such as `ureq` or `reqwest::blocking`.
+//! let client = |url: &str, _headers: &[(&str, &str)]| -> Result<String, String> {
+//!     assert!(url.starts_with("https://api.github.com/search/issues?q=repo%3Aowner%2Frepo+is%3Aissue+is%3Aopen"));
+//!     Ok(r#"{"total_count": 1, "items": [
+//!         {"number": 42, "title": "Crash", "html_url": "https://github.com/owner/repo/issues/42"}
+//!     ]}"#.to_string())
+//! };
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash", "<!-- {fingerprint} -->"))
+//!     .github_api(GitHubApi::new(client));
+//!
+//! let issue = handle.find_existing("Crash").unwrap();
+//! assert_eq!(issue.number, 42);
+//!
+//! let url = handle.report_bug_at("crash", &Params::new(), Location::new(file!(), line!()));
+//! assert_eq!(url, "https://github.com/owner/repo/issues/42");
+//! ```
+
+use crate::url_encode;
+use serde_json::Value;
+use std::fmt;
+use std::sync::Arc;
+
+pub use crate::http::HttpClient;
+
+/// An open issue found on GitHub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    /// The issue number
+    pub number: u64,
+    /// The issue title
+    pub title: String,
+    /// The issue's page on github.com
+    pub url: String,
+}
+
+/// Access to the GitHub search API.
+#[derive(Clone)]
+pub struct GitHubApi {
+    client: Arc<dyn HttpClient>,
+    token: Option<String>,
+    base_url: String,
+}
+
+impl GitHubApi {
+    /// Search GitHub through `client`, without authentication.
+    ///
+    /// Unauthenticated searches are limited to 10 a minute; set a token
+    /// with [`token`](Self::token) for more.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Sends the requests
+    pub fn new(client: impl HttpClient + 'static) -> Self {
+        Self {
+            client: Arc::new(client),
+            token: None,
+            base_url: "https://api.github.com".to_string(),
+        }
+    }
+
+    /// Authenticate with a token that can read the repository's issues.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - A GitHub personal access or app token
+    pub fn token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Send requests to a GitHub Enterprise server instead of github.com.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The API root, such as `https://github.example.com/api/v3`
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// The first open issue in `owner/repo` containing `query` in its title or body.
+    pub(crate) fn find_open_issue(&self, owner: &str, repo: &str, query: &str) -> Option<IssueRef> {
+        let search = format!(
+            "repo:{}/{} is:issue is:open in:title,body \"{}\"",
+            owner,
+            repo,
+            query.replace('"', "")
+        );
+        let url = format!("{}/search/issues?q={}&per_page=1", self.base_url, url_encode::encode(&search));
+        let authorization = self.token.as_ref().map(|token| format!("Bearer {}", token));
+        let mut headers = vec![
+            ("Accept", "application/vnd.github+json"),
+            ("User-Agent", crate::http::USER_AGENT),
+        ];
+        if let Some(authorization) = &authorization {
+            headers.push(("Authorization", authorization));
+        }
+
+        let body = self.client.get(&url, &headers).ok()?;
+        parse_first_issue(&body)
+    }
+}
+
+impl fmt::Debug for GitHubApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GitHubApi")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("base_url", &self.base_url)
+            .finish()
+    }
+}
+
+/// The first item of a search response, if any.
+fn parse_first_issue(body: &str) -> Option<IssueRef> {
+    let response: Value = serde_json::from_str(body).ok()?;
+    let item = response.get("items")?.as_array()?.first()?;
+    Some(IssueRef {
+        number: item.get("number")?.as_u64()?,
+        title: item.get("title")?.as_str()?.to_string(),
+        url: item.get("html_url")?.as_str()?.to_string(),
+    })
+}
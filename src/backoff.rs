@@ -0,0 +1,240 @@
+//! Backing off from bugs that keep recurring.
+//!
+//! A bug hit in a loop prints the same banner over and over, burying
+//! everything else on the terminal. With a [`BackoffPolicy`] on the
+//! configuration, the first occurrence of each distinct report prints the
+//! full banner, the next few print a one-line reminder, and after that only
+//! every Nth occurrence (or occurrences at doubling intervals) print at all.
+//! Occurrences are counted per template and parameter values, so the same
+//! bug with different parameters starts over.
+//!
+//! Backoff only affects what is printed. Every occurrence still generates
+//! its report and returns its URL.
+//!
+//! Backoff doesn't help when many different bugs fire at once. A quiet
+//! period, set with `quiet_period`, caps the console at one full banner
+//! per interval whatever the report: the banners that would follow within
+//! the interval are left out and added to the summary `flush_summary`
+//! prints, and the next full banner starts with a line counting them.
+
+use crate::sync::Lock;
+use crate::time::Timestamp;
+use crate::{cache, FxHashMap, Params};
+use core::fmt;
+use core::time::Duration;
+
+/// How often a recurring report is printed after its brief reminders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    /// Print every Nth occurrence.
+    Every(u64),
+    /// Print when the count of occurrences has doubled since the last print.
+    Doubling,
+}
+
+/// How much of the banner an occurrence gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// The full banner with parameters and URL
+    Full,
+    /// One line naming the template and the occurrence count
+    Brief,
+    /// Nothing
+    Silent,
+}
+
+/// When occurrences of the same report are printed.
+///
+/// # Examples
+///
+/// ```
+/// use bug::backoff::{BackoffPolicy, Interval, Verbosity};
+///
+/// let policy = BackoffPolicy::new();
+/// assert_eq!(policy.verbosity(1), Verbosity::Full);
+/// assert_eq!(policy.verbosity(2), Verbosity::Brief);
+/// assert_eq!(policy.verbosity(10), Verbosity::Brief);
+/// assert_eq!(policy.verbosity(11), Verbosity::Silent);
+/// assert_eq!(policy.verbosity(20), Verbosity::Brief);
+/// assert_eq!(policy.verbosity(40), Verbosity::Brief);
+/// assert_eq!(policy.verbosity(41), Verbosity::Silent);
+///
+/// let policy = BackoffPolicy::new().brief_until(3).then(Interval::Every(100));
+/// assert_eq!(policy.verbosity(4), Verbosity::Silent);
+/// assert_eq!(policy.verbosity(103), Verbosity::Brief);
+/// assert_eq!(policy.verbosity(203), Verbosity::Brief);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    /// The last occurrence printed as a one-line reminder before `interval` applies
+    pub brief_until: u64,
+    /// Which later occurrences are printed as reminders
+    pub interval: Interval,
+}
+
+impl BackoffPolicy {
+    /// The default policy: the first occurrence in full, reminders through
+    /// the 10th, then at doubling intervals (20th, 40th, 80th, ...).
+    pub const fn new() -> Self {
+        Self {
+            brief_until: 10,
+            interval: Interval::Doubling,
+        }
+    }
+
+    /// Set the last occurrence printed as a reminder before `interval` applies.
+    ///
+    /// # Arguments
+    ///
+    /// * `occurrence` - The occurrence, counting from 1; 1 skips reminders
+    pub const fn brief_until(mut self, occurrence: u64) -> Self {
+        self.brief_until = occurrence;
+        self
+    }
+
+    /// Set which later occurrences are printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Every Nth occurrence, or doubling intervals
+    pub const fn then(mut self, interval: Interval) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// How much of the banner the given occurrence gets.
+    ///
+    /// # Arguments
+    ///
+    /// * `occurrence` - How many times the report has occurred, counting this one
+    pub fn verbosity(&self, occurrence: u64) -> Verbosity {
+        let brief_until = self.brief_until.max(1);
+        if occurrence <= 1 {
+            return Verbosity::Full;
+        }
+        if occurrence <= brief_until {
+            return Verbosity::Brief;
+        }
+
+        let printed = match self.interval {
+            Interval::Every(n) => n > 0 && (occurrence - brief_until).is_multiple_of(n),
+            Interval::Doubling => {
+                occurrence.is_multiple_of(brief_until) && (occurrence / brief_until).is_power_of_two()
+            }
+        };
+        if printed { Verbosity::Brief } else { Verbosity::Silent }
+    }
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How often each distinct report has occurred.
+pub(crate) struct Occurrences {
+    counts: Lock<FxHashMap<u64, u64>>,
+}
+
+impl Occurrences {
+    pub(crate) fn new() -> Self {
+        Self {
+            counts: Lock::new(FxHashMap::default()),
+        }
+    }
+
+    /// Count one occurrence of a report.
+    ///
+    /// # Returns
+    ///
+    /// How many times the report has occurred, counting this one.
+    pub(crate) fn record(&self, template_name: &str, params: &Params) -> u64 {
+        let fingerprint = cache::fingerprint(template_name, params);
+        self.counts.with(|counts| {
+            let count = counts.entry(fingerprint).or_insert(0);
+            *count += 1;
+            *count
+        })
+    }
+}
+
+impl Clone for Occurrences {
+    /// Clones start counting from zero.
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Occurrences {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Occurrences")
+            .field("distinct", &self.counts.with(|counts| counts.len()))
+            .finish()
+    }
+}
+
+/// When the last full banner was printed, for the quiet period.
+pub(crate) struct QuietState {
+    /// The time of the last full banner and the banners held since
+    last: Lock<(Option<Duration>, u64)>,
+    /// Read when the configuration has no clock
+    #[cfg(feature = "std")]
+    clock: crate::time::MonotonicClock,
+}
+
+impl QuietState {
+    pub(crate) fn new() -> Self {
+        Self {
+            last: Lock::new((None, 0)),
+            #[cfg(feature = "std")]
+            clock: crate::time::MonotonicClock::new(),
+        }
+    }
+
+    /// Decide whether a full banner can be printed now.
+    ///
+    /// Without a clock (no_std builds without `clock`) every banner is.
+    ///
+    /// # Arguments
+    ///
+    /// * `period` - The least time between two full banners
+    /// * `now` - The configured clock's time, if it has one
+    ///
+    /// # Returns
+    ///
+    /// How many banners were held since the last one printed, or `None`
+    /// if this one falls in the quiet period and is held too.
+    pub(crate) fn admit(&self, period: Duration, now: Option<Timestamp>) -> Option<u64> {
+        #[cfg(feature = "std")]
+        let now = now.or_else(|| Some(crate::time::Clock::now(&self.clock)));
+        let Some(now) = now.map(|now| now.elapsed()) else {
+            return Some(0);
+        };
+        self.last.with(|(last, held)| match last {
+            Some(last) if now.saturating_sub(*last) < period => {
+                *held += 1;
+                None
+            }
+            _ => {
+                *last = Some(now);
+                Some(core::mem::take(held))
+            }
+        })
+    }
+}
+
+impl Clone for QuietState {
+    /// Clones start a new quiet period.
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for QuietState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuietState")
+            .field("held", &self.last.with(|(_, held)| *held))
+            .finish()
+    }
+}
@@ -0,0 +1,35 @@
+//! Standard base64 with padding, for `ParamValue::Bytes` and the OSC 52
+//! clipboard sequence.
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `bytes`, starting a new line every `line_len` characters if set.
+pub(crate) fn encode(bytes: &[u8], line_len: Option<usize>) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    let mut column = 0;
+    for chunk in bytes.chunks(3) {
+        let group = (u32::from(chunk[0]) << 16)
+            | (u32::from(chunk.get(1).copied().unwrap_or(0)) << 8)
+            | u32::from(chunk.get(2).copied().unwrap_or(0));
+        for position in 0..4 {
+            if line_len == Some(column) {
+                output.push('\n');
+                column = 0;
+            }
+            let ch = if position <= chunk.len() {
+                ALPHABET[((group >> (18 - 6 * position)) & 0x3f) as usize] as char
+            } else {
+                '='
+            };
+            output.push(ch);
+            column += 1;
+        }
+    }
+    output
+}
@@ -0,0 +1,188 @@
+//! Combining several findings into a single issue.
+//!
+//! Tools that run periodically, such as linters or static analysers, tend to
+//! find many problems per run but want to file one issue for the run rather
+//! than one per problem. A [`ReportBatch`] collects findings, each rendered
+//! from its own template, and renders them as sections of one combined
+//! issue.
+
+use crate::{BugReport, BugReportHandle, IssueTemplate, Params};
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Placeholder of the batch template that receives the rendered findings.
+pub const FINDINGS_PARAM: &str = "findings";
+
+/// Placeholder of the batch template that receives the number of findings.
+pub const COUNT_PARAM: &str = "count";
+
+/// Findings collected into one combined bug report.
+///
+/// Each finding is rendered from its own template and becomes a section of
+/// the batch template's `{findings}` placeholder: a `### title` heading
+/// followed by the finding's body. `{count}` is replaced with the number of
+/// findings. The combined issue carries the batch template's labels and
+/// assignees followed by every finding's, without duplicates.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, ReportBatch, Params};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("weekly_findings", IssueTemplate::new("Weekly findings ({count})", "{findings}")
+///         .with_labels(vec!["lint".to_string()]))
+///     .add_template("unused_import", IssueTemplate::new("Unused import in {file}", "`{import}` is never used")
+///         .with_labels(vec!["cleanup".to_string()]));
+///
+/// let mut batch = ReportBatch::new(&handle, "weekly_findings");
+/// for (file, import) in [("main.rs", "std::fs"), ("lib.rs", "std::io")] {
+///     let mut params = Params::new();
+///     params.insert("file".to_string(), file.to_string());
+///     params.insert("import".to_string(), import.to_string());
+///     batch.add_finding("unused_import", &params).unwrap();
+/// }
+///
+/// let report = batch.finish_report().unwrap();
+/// assert_eq!(report.title, "Weekly findings (2)");
+/// assert_eq!(
+///     report.body,
+///     "### Unused import in main.rs\n\n`std::fs` is never used\n\n### Unused import in lib.rs\n\n`std::io` is never used"
+/// );
+/// assert_eq!(report.labels, vec!["lint".to_string(), "cleanup".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReportBatch<'a> {
+    handle: &'a BugReportHandle,
+    template_name: String,
+    findings: Vec<BugReport>,
+}
+
+impl<'a> ReportBatch<'a> {
+    /// Start an empty batch that will be filed with the given template.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle whose templates render the batch and its findings
+    /// * `template_name` - Template of the combined issue, usually with `{findings}` and `{count}` placeholders
+    pub fn new(handle: &'a BugReportHandle, template_name: impl Into<String>) -> Self {
+        Self {
+            handle,
+            template_name: template_name.into(),
+            findings: Vec::new(),
+        }
+    }
+
+    /// Render a finding and append it to the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template describing this finding
+    /// * `params` - Parameters to substitute in the template
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The finding was added
+    /// * `Err(String)` - Error message if the template is not found or validation fails
+    pub fn add_finding(&mut self, template_name: &str, params: &Params) -> Result<(), String> {
+        let finding = self.handle.create_report(template_name, params)?;
+        self.findings.push(finding);
+        Ok(())
+    }
+
+    /// Number of findings added so far.
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    /// Whether no findings have been added.
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// The findings added so far, in order.
+    pub fn findings(&self) -> &[BugReport] {
+        &self.findings
+    }
+
+    /// Render the combined issue and return its GitHub issue URL.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The combined issue URL
+    /// * `Err(String)` - Error message if the batch is empty or its template fails to render
+    pub fn finish(self) -> Result<String, String> {
+        self.finish_report().map(|report| report.url)
+    }
+
+    /// Render the combined issue as a structured report.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BugReport)` - The combined report and its GitHub issue URL
+    /// * `Err(String)` - Error message if the batch is empty or its template fails to render
+    pub fn finish_report(self) -> Result<BugReport, String> {
+        if self.findings.is_empty() {
+            return Err(format!("Batch '{}' has no findings", self.template_name));
+        }
+
+        let sections: Vec<String> = self
+            .findings
+            .iter()
+            .map(|finding| format!("### {}\n\n{}", finding.title, finding.body))
+            .collect();
+
+        // Template files reject parameters they don't use, so only pass the
+        // batch placeholders the template actually has.
+        let config = self.handle.config();
+        let mut params = Params::new();
+        for placeholder in config.placeholders(&self.template_name).unwrap_or_default() {
+            if placeholder == FINDINGS_PARAM {
+                params.insert(placeholder, sections.join("\n\n"));
+            } else if placeholder == COUNT_PARAM {
+                params.insert(placeholder, self.findings.len().to_string());
+            }
+        }
+
+        let combined = config.create_report(&self.template_name, &params)?;
+        let mut labels = combined.labels;
+        for label in self.findings.iter().flat_map(|finding| &finding.labels) {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+
+        let mut assignees = combined.assignees;
+        for assignee in self.findings.iter().flat_map(|finding| &finding.assignees) {
+            if !assignees.contains(assignee) {
+                assignees.push(assignee.clone());
+            }
+        }
+
+        let filled_template = IssueTemplate {
+            title: combined.title,
+            body: combined.body,
+            labels,
+            assignees,
+            teams: Vec::new(),
+            milestone: combined.milestone,
+            projects: combined.projects,
+            fallback: None,
+            description: None,
+            param_docs: Vec::new(),
+        };
+        Ok(config.finish_report(&combined.template_name, filled_template, combined.warnings))
+    }
+}
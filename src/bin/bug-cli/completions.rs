@@ -0,0 +1,112 @@
+//! Shell completion scripts for `bug-cli`.
+//!
+//! Template names are completed from the configuration file: the scripts
+//! run `bug-cli --template-names`, passing on the `--config` of the command
+//! line being completed, so they stay right as templates are added.
+
+/// A shell `--completions` writes a script for.
+#[derive(Clone, Copy)]
+pub(crate) enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    /// The shell named `name`, as given on the command line.
+    pub(crate) fn from_name(name: &str) -> Option<Shell> {
+        match name {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+
+    /// The completion script, for sourcing or saving where the shell loads completions.
+    pub(crate) fn script(self) -> &'static str {
+        match self {
+            Shell::Bash => BASH,
+            Shell::Zsh => ZSH,
+            Shell::Fish => FISH,
+        }
+    }
+}
+
+const BASH: &str = r#"# bash completion for bug-cli
+# Source it, or save it as /usr/share/bash-completion/completions/bug-cli
+_bug_cli() {
+    local cur prev i
+    local -a config=()
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+    for ((i = 1; i < COMP_CWORD - 1; i++)); do
+        case "${COMP_WORDS[i]}" in
+            -c|--config) config=(--config "${COMP_WORDS[i+1]}") ;;
+        esac
+    done
+
+    case "$prev" in
+        -t|--template)
+            COMPREPLY=($(compgen -W "$(bug-cli "${config[@]}" --template-names 2>/dev/null)" -- "$cur"))
+            return ;;
+        -c|--config)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return ;;
+        --completions)
+            COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur"))
+            return ;;
+        -p|--param)
+            return ;;
+    esac
+    COMPREPLY=($(compgen -W "--config --template --param --open --prompt --list-templates --template-names --completions --help" -- "$cur"))
+}
+complete -F _bug_cli bug-cli
+"#;
+
+const ZSH: &str = r#"#compdef bug-cli
+# zsh completion for bug-cli; save it as _bug-cli in a directory on $fpath
+
+_bug_cli_templates() {
+    local -a config names
+    local i=${words[(I)(-c|--config)]}
+    (( i )) && config=(--config "${words[i+1]}")
+    names=(${(f)"$(bug-cli $config --template-names 2>/dev/null)"})
+    compadd -a names
+}
+
+_arguments \
+    '(-c --config)'{-c,--config}'[configuration file]:file:_files' \
+    '(-t --template --prompt)'{-t,--template}'[template to fill]:template:_bug_cli_templates' \
+    '*'{-p,--param}'[value for a placeholder]:KEY=VALUE: ' \
+    '--open[open the URL in the browser instead of printing it]' \
+    '(-t --template)--prompt[choose the template and fill it in interactively]' \
+    '--list-templates[list the templates and their parameters]' \
+    '--template-names[print the template names, one per line]' \
+    '--completions[print a shell completion script]:shell:(bash zsh fish)' \
+    '(- *)'{-h,--help}'[print help]'
+"#;
+
+const FISH: &str = r#"# fish completion for bug-cli; save it as ~/.config/fish/completions/bug-cli.fish
+function __bug_cli_templates
+    set -l tokens (commandline -opc)
+    set -l config
+    for i in (seq (count $tokens))
+        if contains -- $tokens[$i] -c --config; and test $i -lt (count $tokens)
+            set config --config $tokens[(math $i + 1)]
+        end
+    end
+    bug-cli $config --template-names 2>/dev/null
+end
+
+complete -c bug-cli -f
+complete -c bug-cli -s c -l config -r -F -d 'Configuration file'
+complete -c bug-cli -s t -l template -x -a '(__bug_cli_templates)' -d 'Template to fill'
+complete -c bug-cli -s p -l param -x -d 'Value for a placeholder, KEY=VALUE'
+complete -c bug-cli -l open -d 'Open the URL in the browser instead of printing it'
+complete -c bug-cli -l prompt -d 'Choose the template and fill it in interactively'
+complete -c bug-cli -l list-templates -d 'List the templates and their parameters'
+complete -c bug-cli -l template-names -d 'Print the template names, one per line'
+complete -c bug-cli -l completions -x -a 'bash zsh fish' -d 'Print a shell completion script'
+complete -c bug-cli -s h -l help -d 'Print help'
+"#;
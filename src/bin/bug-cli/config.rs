@@ -0,0 +1,556 @@
+//! The configuration file `bug-cli` builds its handle from.
+//!
+//! The file is TOML. The crate has no TOML dependency, so this reads the
+//! part of TOML such a file needs: comments, bare and quoted keys, `[table]`
+//! headers, basic and literal strings (also the multi-line `"""` and `'''`
+//! forms, handy for bodies), and arrays of strings.
+//!
+//! ```toml
+//! owner = "octocat"
+//! repo = "Hello-World"
+//! # Every file in the directory, named as `add_template_dir` names them
+//! templates_dir = "templates"
+//!
+//! [templates.slow]
+//! description = "An operation took much longer than it should"
+//! title = "Slow {operation}"
+//! body = """
+//! {operation} took {ms} ms.
+//! """
+//! labels = ["performance"]
+//!
+//! [templates.crash]
+//! file = "templates/crash_report.md"
+//! labels = ["bug", "crash"]
+//!
+//! # What each placeholder of `crash` means, shown by `--list-templates` and `--prompt`
+//! [templates.crash.param_docs]
+//! error_type = "The Rust error type name"
+//! ```
+//!
+//! Paths are relative to the directory of the configuration file.
+
+use bug::{init_handle, BugReportHandle, IssueTemplate, TemplateFile};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A template declared in a `[templates.NAME]` table.
+#[derive(Default)]
+struct TemplateConfig {
+    description: Option<String>,
+    title: Option<String>,
+    body: Option<String>,
+    file: Option<PathBuf>,
+    labels: Vec<String>,
+    assignees: Vec<String>,
+    param_docs: Vec<(String, String)>,
+    /// Whether the `[templates.NAME]` header was seen, not only `[templates.NAME.param_docs]`
+    declared: bool,
+}
+
+/// The table keys are read into.
+#[derive(Clone, Copy)]
+enum Table {
+    Top,
+    Template(usize),
+    ParamDocs(usize),
+}
+
+/// A configuration file.
+pub(crate) struct Config {
+    owner: Option<String>,
+    repo: Option<String>,
+    templates_dir: Option<PathBuf>,
+    templates: Vec<(String, TemplateConfig)>,
+}
+
+impl Config {
+    /// Read and parse the configuration file at `path`.
+    pub(crate) fn load(path: &Path) -> Result<Config, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path.display(), e))?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        Config::parse(&text, base).map_err(|e| format!("{}:{}", path.display(), e))
+    }
+
+    /// Parse configuration `text`, resolving paths against `base`.
+    ///
+    /// Errors start with the line they are on.
+    fn parse(text: &str, base: &Path) -> Result<Config, String> {
+        let mut config = Config { owner: None, repo: None, templates_dir: None, templates: Vec::new() };
+        let mut parser = Parser { rest: text, line: 1 };
+        let mut table = Table::Top;
+
+        loop {
+            parser.skip_blank();
+            if parser.rest.is_empty() {
+                break;
+            }
+            let line = parser.line;
+
+            if parser.eat("[") {
+                parser.skip_space();
+                let path = parser.key_path()?;
+                if !parser.eat("]") {
+                    return Err(parser.error("expected ']' after the table name"));
+                }
+                parser.end_of_line()?;
+                let (name, param_docs) = match path.as_slice() {
+                    [templates, name] if templates == "templates" => (name, false),
+                    [templates, name, param_docs] if templates == "templates" && param_docs == "param_docs" => (name, true),
+                    _ => {
+                        return Err(format!(
+                            "{}: unknown table [{}]; only [templates.NAME] and [templates.NAME.param_docs] are read",
+                            line,
+                            path.join(".")
+                        ));
+                    }
+                };
+                let index = match config.templates.iter().position(|(existing, _)| existing == name) {
+                    Some(index) => index,
+                    None => {
+                        config.templates.push((name.clone(), TemplateConfig::default()));
+                        config.templates.len() - 1
+                    }
+                };
+                let template = &mut config.templates[index].1;
+                table = if param_docs {
+                    Table::ParamDocs(index)
+                } else if template.declared {
+                    return Err(format!("{}: template '{}' is declared twice", line, name));
+                } else {
+                    template.declared = true;
+                    Table::Template(index)
+                };
+                continue;
+            }
+
+            let key = parser.key()?;
+            parser.skip_space();
+            if !parser.eat("=") {
+                return Err(parser.error(&format!("expected '=' after '{}'", key)));
+            }
+            parser.skip_space();
+            let value = parser.value()?;
+            parser.end_of_line()?;
+
+            let wrong_type = |expected: &str| format!("{}: '{}' must be {}", line, key, expected);
+            match (table, key.as_str(), value) {
+                (Table::Top, "owner", Value::String(owner)) => config.owner = Some(owner),
+                (Table::Top, "repo", Value::String(repo)) => config.repo = Some(repo),
+                (Table::Top, "templates_dir", Value::String(dir)) => config.templates_dir = Some(base.join(dir)),
+                (Table::Top, "owner" | "repo" | "templates_dir", _) => return Err(wrong_type("a string")),
+                (Table::Top, _, _) => return Err(format!("{}: unknown key '{}'", line, key)),
+                (Table::ParamDocs(index), _, Value::String(doc)) => {
+                    let param_docs = &mut config.templates[index].1.param_docs;
+                    match param_docs.iter_mut().find(|(placeholder, _)| *placeholder == key) {
+                        Some(entry) => entry.1 = doc,
+                        None => param_docs.push((key, doc)),
+                    }
+                }
+                (Table::ParamDocs(_), _, _) => return Err(wrong_type("a string")),
+                (Table::Template(index), _, value) => {
+                    let template = &mut config.templates[index].1;
+                    match (key.as_str(), value) {
+                        ("description", Value::String(description)) => template.description = Some(description),
+                        ("title", Value::String(title)) => template.title = Some(title),
+                        ("body", Value::String(body)) => template.body = Some(body),
+                        ("file", Value::String(file)) => template.file = Some(base.join(file)),
+                        ("labels", Value::Array(labels)) => template.labels = labels,
+                        ("assignees", Value::Array(assignees)) => template.assignees = assignees,
+                        ("description" | "title" | "body" | "file", _) => return Err(wrong_type("a string")),
+                        ("labels" | "assignees", _) => return Err(wrong_type("an array of strings")),
+                        _ => return Err(format!("{}: templates have no key '{}'", line, key)),
+                    }
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    /// Build a handle with every template of the configuration.
+    pub(crate) fn handle(&self) -> Result<BugReportHandle, String> {
+        let owner = self.owner.as_deref().ok_or("the configuration has no 'owner'")?;
+        let repo = self.repo.as_deref().ok_or("the configuration has no 'repo'")?;
+        let mut handle = init_handle(owner, repo);
+
+        if let Some(dir) = &self.templates_dir {
+            let mut paths = Vec::new();
+            template_paths(dir, &mut paths).map_err(|e| format!("can't read {}: {}", dir.display(), e))?;
+            paths.sort();
+            for path in paths {
+                // Files that are not UTF-8 are not templates, as for `add_template_dir`
+                let Ok(content) = fs::read_to_string(&path) else { continue };
+                handle = handle.add_template_file(template_name(dir, &path), TemplateFile::new(leak(content)));
+            }
+        }
+
+        for (name, template) in &self.templates {
+            handle = match (&template.file, &template.title) {
+                (Some(_), Some(_)) => return Err(format!("template '{}' has both 'file' and 'title'", name)),
+                (Some(file), None) => {
+                    let content = fs::read_to_string(file).map_err(|e| format!("can't read {}: {}", file.display(), e))?;
+                    let mut template_file = TemplateFile::new(leak(content))
+                        .with_labels(template.labels.clone())
+                        .with_assignees(template.assignees.clone());
+                    template_file.description = template.description.clone();
+                    template_file.param_docs = template.param_docs.clone();
+                    handle.add_template_file(name.as_str(), template_file)
+                }
+                (None, Some(title)) => {
+                    let mut issue_template = IssueTemplate::new(title.as_str(), template.body.as_deref().unwrap_or(""))
+                        .with_labels(template.labels.clone())
+                        .with_assignees(template.assignees.clone());
+                    issue_template.description = template.description.clone();
+                    issue_template.param_docs = template.param_docs.clone();
+                    handle.add_template(name.as_str(), issue_template)
+                }
+                (None, None) if !template.declared => {
+                    return Err(format!("template '{}' has param_docs but no [templates.{}] table", name, name));
+                }
+                (None, None) => return Err(format!("template '{}' needs a 'title' or a 'file'", name)),
+            };
+        }
+        Ok(handle)
+    }
+}
+
+/// Template files borrow their contents for 'static.
+fn leak(content: String) -> &'static str {
+    Box::leak(content.into_boxed_str())
+}
+
+/// Collect the paths of the files under `dir`, skipping dotfiles.
+fn template_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.')) {
+            continue;
+        }
+        if path.is_dir() {
+            template_paths(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The name `add_template_dir` registers the file at `path` under.
+fn template_name(dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(dir).unwrap_or(path).with_extension("");
+    let parts: Vec<_> = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect();
+    parts.join("/")
+}
+
+/// A value in the configuration file.
+enum Value {
+    String(String),
+    Array(Vec<String>),
+}
+
+/// Reads the configuration text from the front.
+struct Parser<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{}: {}", self.line, message)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.rest = &self.rest[ch.len_utf8()..];
+        if ch == '\n' {
+            self.line += 1;
+        }
+        Some(ch)
+    }
+
+    /// Consume `token` if the text starts with it.
+    fn eat(&mut self, token: &str) -> bool {
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.line += token.matches('\n').count();
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Skip spaces and tabs.
+    fn skip_space(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.bump();
+        }
+    }
+
+    /// Skip a comment, if one starts here.
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) {
+                self.bump();
+            }
+        }
+    }
+
+    /// Skip whitespace, newlines, and comments.
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_space();
+            self.skip_comment();
+            if !(self.eat("\n") || self.eat("\r\n")) {
+                break;
+            }
+        }
+    }
+
+    /// Require the rest of the line to be blank or a comment.
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_space();
+        self.skip_comment();
+        if self.rest.is_empty() || self.eat("\n") || self.eat("\r\n") {
+            Ok(())
+        } else {
+            Err(self.error("expected the end of the line"))
+        }
+    }
+
+    /// A bare or quoted key.
+    fn key(&mut self) -> Result<String, String> {
+        if matches!(self.peek(), Some('"' | '\'')) {
+            return self.string();
+        }
+        let len = self
+            .rest
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'))
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return Err(self.error("expected a key"));
+        }
+        let key = self.rest[..len].to_string();
+        self.rest = &self.rest[len..];
+        Ok(key)
+    }
+
+    /// Keys separated by dots, as in a table header.
+    fn key_path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = vec![self.key()?];
+        loop {
+            self.skip_space();
+            if !self.eat(".") {
+                return Ok(path);
+            }
+            self.skip_space();
+            path.push(self.key()?);
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        if !self.eat("[") {
+            return self.string().map(Value::String);
+        }
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.eat("]") {
+                return Ok(Value::Array(items));
+            }
+            items.push(self.string()?);
+            self.skip_blank();
+            if self.eat("]") {
+                return Ok(Value::Array(items));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected ',' or ']' in the array"));
+            }
+        }
+    }
+
+    /// A string in any of TOML's four forms.
+    fn string(&mut self) -> Result<String, String> {
+        let (delimiter, escapes) = if self.eat("\"\"\"") {
+            ("\"\"\"", true)
+        } else if self.eat("'''") {
+            ("'''", false)
+        } else if self.eat("\"") {
+            ("\"", true)
+        } else if self.eat("'") {
+            ("'", false)
+        } else {
+            return Err(self.error("expected a string"));
+        };
+        let multiline = delimiter.len() == 3;
+        let line = self.line;
+        // A newline right after the opening delimiter is not part of the string
+        if multiline && !self.eat("\n") {
+            self.eat("\r\n");
+        }
+
+        let mut value = String::new();
+        loop {
+            if self.eat(delimiter) {
+                // Up to two quotes right before the closing delimiter belong to the string
+                if multiline {
+                    let quote = &delimiter[..1];
+                    for _ in 0..2 {
+                        if self.eat(quote) {
+                            value.push_str(quote);
+                        }
+                    }
+                }
+                return Ok(value);
+            }
+            match self.bump() {
+                None => return Err(format!("{}: unterminated string", line)),
+                Some('\n') if !multiline => return Err(format!("{}: unterminated string", line)),
+                Some('\\') if escapes && multiline && self.line_ending_backslash() => {}
+                Some('\\') if escapes => value.push(self.escape()?),
+                Some(ch) => value.push(ch),
+            }
+        }
+    }
+
+    /// Skip the line break and the whitespace after it if a backslash ended the line.
+    fn line_ending_backslash(&mut self) -> bool {
+        let after_space = self.rest.trim_start_matches([' ', '\t']);
+        if !(after_space.starts_with('\n') || after_space.starts_with("\r\n")) {
+            return false;
+        }
+        while matches!(self.peek(), Some(' ' | '\t' | '\r' | '\n')) {
+            self.bump();
+        }
+        true
+    }
+
+    /// The character of the escape sequence after a backslash.
+    fn escape(&mut self) -> Result<char, String> {
+        let unicode = |parser: &mut Self, digits: usize| {
+            let hex = parser.rest.get(..digits).ok_or_else(|| parser.error("incomplete unicode escape"))?;
+            let ch = Some(hex)
+                .filter(|hex| hex.chars().all(|ch| ch.is_ascii_hexdigit()))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32)
+                .ok_or_else(|| parser.error(&format!("invalid unicode escape '{}'", hex)))?;
+            parser.rest = &parser.rest[digits..];
+            Ok(ch)
+        };
+        match self.bump() {
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('t') => Ok('\t'),
+            Some('r') => Ok('\r'),
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('u') => unicode(self, 4),
+            Some('U') => unicode(self, 8),
+            Some(other) => Err(self.error(&format!("unknown escape '\\{}'", other))),
+            None => Err(self.error("unterminated string")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Config, String> {
+        Config::parse(text, Path::new("base"))
+    }
+
+    /// The only template of the configuration `text`.
+    fn only_template(text: &str) -> TemplateConfig {
+        let mut config = parse(text).unwrap();
+        assert_eq!(config.templates.len(), 1);
+        config.templates.remove(0).1
+    }
+
+    #[test]
+    fn basic_and_literal_strings() {
+        let config = parse("owner = \"octo # cat\" # the owner\nrepo = 'C:\\repo'\ntemplates_dir = \"templates\"\n").unwrap();
+        assert_eq!(config.owner.as_deref(), Some("octo # cat"));
+        assert_eq!(config.repo.as_deref(), Some("C:\\repo"));
+        assert_eq!(config.templates_dir, Some(Path::new("base").join("templates")));
+    }
+
+    #[test]
+    fn escapes() {
+        let template = only_template(r#"
+[templates.crash]
+title = "tab\there \"quoted\" back\\slash \u00e9\U0001F41B"
+body = "\b\f\n\r"
+"#);
+        assert_eq!(template.title.as_deref(), Some("tab\there \"quoted\" back\\slash é🐛"));
+        assert_eq!(template.body.as_deref(), Some("\u{8}\u{c}\n\r"));
+
+        assert_eq!(parse("owner = \"\\q\"").err().as_deref(), Some("1: unknown escape '\\q'"));
+        assert_eq!(parse("owner = \"\\u+041\"").err().as_deref(), Some("1: invalid unicode escape '+041'"));
+    }
+
+    #[test]
+    fn multi_line_strings() {
+        let template = only_template(concat!(
+            "[templates.crash]\n",
+            "title = \"Crash\"\n",
+            "body = \"\"\"\n",
+            "First line\n",
+            "continued \\\n",
+            "    here\n",
+            "\"\"\"\n",
+            "description = '''\n",
+            "raw \\n ''text'''''\n",
+        ));
+        assert_eq!(template.body.as_deref(), Some("First line\ncontinued here\n"));
+        assert_eq!(template.description.as_deref(), Some("raw \\n ''text''"));
+
+        let quoted = only_template("[templates.crash]\ntitle = \"\"\"say \"\"hi\"\"\"\"\"\n");
+        assert_eq!(quoted.title.as_deref(), Some("say \"\"hi\"\""));
+    }
+
+    #[test]
+    fn tables_and_arrays() {
+        let config = parse(
+            "owner = \"octocat\"\n\n[templates.slow]\ntitle = \"Slow\"\nlabels = [\n  \"performance\", # why\n  'ui',\n]\n\n[ templates . \"crash.v2\" ]\nfile = \"crash.md\"\nassignees = []\n",
+        )
+        .unwrap();
+        let names: Vec<_> = config.templates.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["slow", "crash.v2"]);
+        assert_eq!(config.templates[0].1.labels, ["performance", "ui"]);
+        assert_eq!(config.templates[1].1.file, Some(Path::new("base").join("crash.md")));
+        assert!(config.templates[1].1.assignees.is_empty());
+    }
+
+    #[test]
+    fn param_docs() {
+        let template = only_template(
+            "[templates.crash.param_docs]\nmodule = \"Where it crashed\"\n\n[templates.crash]\ntitle = \"Crash in {module}\"\n\n[templates.crash.param_docs]\nmodule = \"The crashing module\"\ncode = 'The error code'\n",
+        );
+        assert!(template.declared);
+        assert_eq!(
+            template.param_docs,
+            [
+                ("module".to_string(), "The crashing module".to_string()),
+                ("code".to_string(), "The error code".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_name_their_line() {
+        let error = |text: &str| parse(text).err().unwrap();
+        assert_eq!(error("owner = \"a\"\n\nrepo = 5\n"), "3: expected a string");
+        assert_eq!(error("# comment\n[other]\n"), "2: unknown table [other]; only [templates.NAME] and [templates.NAME.param_docs] are read");
+        assert_eq!(error("owner = [\"a\"]\n"), "1: 'owner' must be a string");
+        assert_eq!(error("\n\nowner = \"a\" repo\n"), "3: expected the end of the line");
+        assert_eq!(error("body = \"\"\"\nline\n\n"), "1: unterminated string");
+        assert_eq!(error("[templates.a]\ntitle = \"A\"\n[templates.a]\n"), "3: template 'a' is declared twice");
+        assert_eq!(error("[templates.a]\ntitle = \"\"\"\n\n\\x\"\"\"\n"), "4: unknown escape '\\x'");
+    }
+}
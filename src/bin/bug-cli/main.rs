@@ -0,0 +1,224 @@
+//! `bug-cli`: generate bug report URLs from shell scripts and non-Rust programs.
+//!
+//! The templates come from a configuration file, `bug.toml` unless
+//! `--config` names another (see `config.rs` for its format). With
+//! `--template` the report is filled from the `--param` values and its URL
+//! printed, or opened in the browser with `--open`; with `--prompt` the
+//! `DebugMenu` asks for the template and each placeholder instead.
+//! `--list-templates` shows what can be reported, and `--completions`
+//! prints a bash, zsh, or fish completion script that completes template
+//! names from the configuration.
+//!
+//! The URL is the only thing written to stdout, so `url=$(bug-cli ...)`
+//! works. Warnings go to stderr. The exit code is 0 on success, 1 when the
+//! report can't be generated or opened, and 2 when the arguments or the
+//! configuration are wrong.
+//!
+//! Built with the `cli` feature: `cargo install bug --features cli`.
+
+mod completions;
+mod config;
+
+use bug::tui::DebugMenu;
+use bug::{browser, BugReportHandle, Params};
+use completions::Shell;
+use config::Config;
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: bug-cli [OPTIONS] --template NAME [--param KEY=VALUE]...
+       bug-cli [OPTIONS] --prompt
+       bug-cli [OPTIONS] --list-templates
+       bug-cli --completions SHELL
+
+Print the GitHub issue URL for a bug report.
+
+Options:
+  -c, --config FILE        Configuration file (default: bug.toml)
+  -t, --template NAME      Template to fill
+  -p, --param KEY=VALUE    Value for a placeholder; repeatable
+      --open               Open the URL in the browser instead of printing it
+      --prompt             Choose the template and fill it in interactively
+      --list-templates     List the templates with their descriptions and parameters
+      --template-names     Print the template names, one per line
+      --completions SHELL  Print a completion script for bash, zsh, or fish
+  -h, --help               Print this help
+
+Exit status: 0 on success, 1 if the report fails, 2 on usage or configuration errors.";
+
+/// What the command line asks for.
+enum Mode {
+    Report(String),
+    Prompt,
+    ListTemplates,
+    TemplateNames,
+    Completions(Shell),
+    Help,
+}
+
+/// The command line.
+struct Options {
+    config: PathBuf,
+    mode: Mode,
+    params: Params,
+    open: bool,
+}
+
+impl Options {
+    /// Parse the arguments after the program name.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut config = PathBuf::from("bug.toml");
+        let mut modes = Vec::new();
+        let mut params = Params::new();
+        let mut open = false;
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+            match arg.as_str() {
+                "-h" | "--help" => modes.push(Mode::Help),
+                "-c" | "--config" => config = PathBuf::from(value("--config")?),
+                "-t" | "--template" => modes.push(Mode::Report(value("--template")?)),
+                "-p" | "--param" => {
+                    let param = value("--param")?;
+                    let (key, value) = param
+                        .split_once('=')
+                        .ok_or_else(|| format!("--param must be KEY=VALUE, not '{}'", param))?;
+                    params.insert(key, value);
+                }
+                "--open" => open = true,
+                "--prompt" => modes.push(Mode::Prompt),
+                "--list-templates" => modes.push(Mode::ListTemplates),
+                "--template-names" => modes.push(Mode::TemplateNames),
+                "--completions" => {
+                    let shell = value("--completions")?;
+                    let shell = Shell::from_name(&shell)
+                        .ok_or_else(|| format!("No completions for '{}'; the shells are bash, zsh, and fish", shell))?;
+                    modes.push(Mode::Completions(shell));
+                }
+                _ => return Err(format!("Unknown argument '{}'\n\n{}", arg, USAGE)),
+            }
+        }
+        // Help wins over everything else on the line
+        if modes.iter().any(|mode| matches!(mode, Mode::Help)) {
+            modes = vec![Mode::Help];
+        }
+        let mode = match modes.len() {
+            0 => return Err(format!("Either --template or --prompt is needed\n\n{}", USAGE)),
+            1 => modes.remove(0),
+            _ => {
+                return Err(
+                    "--template, --prompt, --list-templates, --template-names, and --completions can't be combined"
+                        .to_string(),
+                );
+            }
+        };
+        Ok(Options { config, mode, params, open })
+    }
+}
+
+/// Print every template with its description and parameters.
+fn list_templates(handle: &BugReportHandle) {
+    let config = handle.config();
+    let names: Vec<&str> = config.template_names().collect();
+    let width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+    for name in names {
+        let placeholders: Vec<String> = config
+            .placeholders(name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|placeholder| match config.param_doc(name, &placeholder) {
+                Some(doc) => format!("{} ({})", placeholder, doc),
+                None => placeholder,
+            })
+            .collect();
+        let mut details = Vec::new();
+        if let Some(description) = config.description(name) {
+            details.push(description.to_string());
+        }
+        if !placeholders.is_empty() {
+            details.push(format!("Parameters: {}", placeholders.join(", ")));
+        }
+        if details.is_empty() {
+            println!("{}", name);
+        }
+        for (index, detail) in details.iter().enumerate() {
+            let label = if index == 0 { name } else { "" };
+            println!("{:width$}  {}", label, detail, width = width);
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+    match &options.mode {
+        Mode::Help => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Mode::Completions(shell) => {
+            print!("{}", shell.script());
+            return ExitCode::SUCCESS;
+        }
+        _ => {}
+    }
+
+    let handle = match Config::load(&options.config).and_then(|config| config.handle()) {
+        Ok(loaded) => loaded,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let template = match options.mode {
+        Mode::Report(template) => template,
+        Mode::Prompt => {
+            // The menu offers to open or copy the URL itself
+            return match DebugMenu::new(&handle).run() {
+                Ok(_) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    ExitCode::FAILURE
+                }
+            };
+        }
+        Mode::ListTemplates => {
+            list_templates(&handle);
+            return ExitCode::SUCCESS;
+        }
+        Mode::TemplateNames => {
+            for name in handle.config().template_names() {
+                println!("{}", name);
+            }
+            return ExitCode::SUCCESS;
+        }
+        Mode::Completions(_) | Mode::Help => unreachable!("handled before loading the configuration"),
+    };
+
+    let report = match handle.create_report(&template, &options.params) {
+        Ok(report) => report,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    for warning in &report.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    if options.open {
+        if let Err(e) = browser::open(&report.url) {
+            eprintln!("error: can't open the browser: {}", e);
+            return ExitCode::FAILURE;
+        }
+    } else {
+        println!("{}", report.url);
+    }
+    ExitCode::SUCCESS
+}
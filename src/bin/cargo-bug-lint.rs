@@ -0,0 +1,217 @@
+//! `cargo bug-lint`: check a directory of issue templates in CI.
+//!
+//! Every UTF-8 file in the directory (recursively, skipping dotfiles) is loaded
+//! as a `TemplateFile` named after its path without the extension, as
+//! `add_template_dir` names them, and put through the checks the library
+//! runs:
+//!
+//! - parse and placeholder syntax, with `TemplateFile::lint`
+//! - labels and assignees, with `BugReportConfig::validate`
+//! - GitHub's length limits, with `github::fits_github_limits` on the
+//!   report generated with every placeholder empty, so a template that is
+//!   already too long before any value is filled in is caught
+//!
+//! Problems are printed one per line as `path:line:column: message`, the
+//! format editors and CI annotations pick up. The exit code is 0 when
+//! every template passes, 1 when any has a problem, and 2 when the
+//! arguments are wrong or the directory can't be read.
+//!
+//! Built with the `cli` feature: `cargo install bug --features cli`.
+
+use bug::github::fits_github_limits;
+use bug::{init_handle, Params, TemplateFile};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs, io};
+
+const USAGE: &str = "\
+Usage: cargo bug-lint [OPTIONS] [DIR]
+
+Check the issue templates in DIR (default: templates).
+
+Options:
+      --repo OWNER/REPO           Repository the issue URLs are for (default: owner/repo)
+      --label LABEL               Label every template is filed with; repeatable
+      --allow-label LABEL         Only allow these labels; repeatable
+      --allow-label-prefix PREFIX Also allow labels starting with PREFIX; repeatable
+  -q, --quiet                     Print only the problems
+  -h, --help                      Print this help
+
+Exit status: 0 if every template passes, 1 if any has problems, 2 on usage or I/O errors.";
+
+/// The command line.
+struct Options {
+    dir: PathBuf,
+    owner: String,
+    repo: String,
+    labels: Vec<String>,
+    allowed_labels: Vec<String>,
+    allowed_label_prefixes: Vec<String>,
+    quiet: bool,
+    help: bool,
+}
+
+impl Options {
+    /// Parse the arguments after the program name.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            dir: PathBuf::from("templates"),
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            labels: Vec::new(),
+            allowed_labels: Vec::new(),
+            allowed_label_prefixes: Vec::new(),
+            quiet: false,
+            help: false,
+        };
+        let mut dir = None;
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "-q" | "--quiet" => options.quiet = true,
+                "--repo" => {
+                    let repo = value("--repo")?;
+                    let (owner, name) = repo
+                        .split_once('/')
+                        .filter(|(owner, name)| !owner.is_empty() && !name.is_empty())
+                        .ok_or_else(|| format!("--repo must be OWNER/REPO, not '{}'", repo))?;
+                    options.owner = owner.to_string();
+                    options.repo = name.to_string();
+                }
+                "--label" => options.labels.push(value("--label")?),
+                "--allow-label" => options.allowed_labels.push(value("--allow-label")?),
+                "--allow-label-prefix" => options.allowed_label_prefixes.push(value("--allow-label-prefix")?),
+                _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'\n\n{}", arg, USAGE)),
+                _ if dir.is_some() => return Err(format!("Only one directory can be checked\n\n{}", USAGE)),
+                _ => dir = Some(PathBuf::from(arg)),
+            }
+        }
+        if let Some(dir) = dir {
+            options.dir = dir;
+        }
+        Ok(options)
+    }
+}
+
+/// Collect the paths of the template files under `dir`.
+fn template_paths(dir: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('.')) {
+            continue;
+        }
+        if path.is_dir() {
+            template_paths(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The name `add_template_dir` registers the file at `path` under.
+fn template_name(dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(dir).unwrap_or(path).with_extension("");
+    let parts: Vec<_> = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect();
+    parts.join("/")
+}
+
+/// Every problem with the template at `path`, as `path:line:column: message` lines.
+fn lint(options: &Options, name: &str, path: &Path, content: &'static str) -> Vec<String> {
+    let file = path.display();
+    let template = TemplateFile::new(content).with_labels(options.labels.clone());
+
+    // Later checks need a template that parses
+    let diagnostics = template.lint();
+    if !diagnostics.is_empty() {
+        return diagnostics
+            .iter()
+            .map(|diagnostic| format!("{}:{}:{}: {}", file, diagnostic.line, diagnostic.column, diagnostic.kind))
+            .collect();
+    }
+
+    let mut handle = init_handle(options.owner.as_str(), options.repo.as_str());
+    if !options.allowed_labels.is_empty() || !options.allowed_label_prefixes.is_empty() {
+        handle = handle.allowed_labels(&options.allowed_labels);
+        for prefix in &options.allowed_label_prefixes {
+            handle = handle.allow_label_prefix(prefix.as_str());
+        }
+    }
+    let handle = handle.add_template_file(name, template.clone());
+    if let Err(e) = handle.config().validate() {
+        return vec![format!("{}:1:1: {}", file, e)];
+    }
+
+    let mut params = Params::new();
+    for placeholder in template.placeholders() {
+        params.insert(placeholder, "");
+    }
+    match handle.create_report(name, &params) {
+        Err(e) => vec![format!("{}:1:1: {}", file, e)],
+        Ok(report) => match fits_github_limits(&report) {
+            Err(overflow) => vec![format!("{}:1:1: With every placeholder empty, {}", file, overflow)],
+            Ok(()) => Vec::new(),
+        },
+    }
+}
+
+fn main() -> ExitCode {
+    // Run as `cargo bug-lint`, cargo passes the subcommand name first
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().is_some_and(|arg| arg == "bug-lint") {
+        args.next();
+    }
+    let options = match Options::parse(args) {
+        Ok(options) if options.help => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut paths = Vec::new();
+    if let Err(e) = template_paths(&options.dir, &mut paths) {
+        eprintln!("error: can't read {}: {}", options.dir.display(), e);
+        return ExitCode::from(2);
+    }
+    paths.sort();
+
+    let mut checked = 0;
+    let mut failed = 0;
+    for path in &paths {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            // Not a template, as for `add_template_dir`
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => continue,
+            Err(e) => {
+                eprintln!("error: can't read {}: {}", path.display(), e);
+                return ExitCode::from(2);
+            }
+        };
+        // Template files borrow their contents for 'static
+        let content: &'static str = Box::leak(content.into_boxed_str());
+        let problems = lint(&options, &template_name(&options.dir, path), path, content);
+        checked += 1;
+        if !problems.is_empty() {
+            failed += 1;
+        }
+        for problem in problems {
+            println!("{}", problem);
+        }
+    }
+
+    if !options.quiet {
+        println!(
+            "{} of {} templates in {} have problems",
+            failed,
+            checked,
+            options.dir.display()
+        );
+    }
+    if failed == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
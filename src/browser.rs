@@ -0,0 +1,78 @@
+//! Opening issue URLs in the system browser, and copying text to the
+//! clipboard (std only).
+
+use crate::base64;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Open a URL with the platform's default handler.
+///
+/// This spawns `open` on macOS, `rundll32 url.dll,FileProtocolHandler` on
+/// Windows, and `xdg-open` everywhere else, and does not wait for the
+/// browser to exit.
+///
+/// # Arguments
+///
+/// * `url` - The URL to open
+///
+/// # Returns
+///
+/// * `Ok(())` - The opener process was started
+/// * `Err(io::Error)` - The opener could not be spawned
+///
+/// # Examples
+///
+/// ```no_run
+/// bug::browser::open("https://github.com/octocat/Hello-World/issues/new")
+///     .expect("failed to open browser");
+/// ```
+pub fn open(url: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    } else if cfg!(target_os = "windows") {
+        // Not `cmd /C start`: cmd would split the URL at each `&` and
+        // expand `%VAR%` in parameter values.
+        let mut command = Command::new("rundll32");
+        command.args(["url.dll,FileProtocolHandler", url]);
+        command
+    } else {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Copy text to the system clipboard through the terminal.
+///
+/// This writes an OSC 52 escape sequence to stderr, which most terminal
+/// emulators (and tmux with `set-clipboard on`) pass to the system
+/// clipboard, including over SSH. Terminals without OSC 52 support ignore
+/// it, so print the text as well when it matters.
+///
+/// # Arguments
+///
+/// * `text` - The text to copy
+///
+/// # Returns
+///
+/// * `Ok(())` - The sequence was written
+/// * `Err(io::Error)` - Writing to stderr failed
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stderr = io::stderr().lock();
+    stderr.write_all(osc52(text).as_bytes())?;
+    stderr.flush()
+}
+
+/// The OSC 52 escape sequence that sets the clipboard to `text`.
+pub(crate) fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64::encode(text.as_bytes(), None))
+}
@@ -0,0 +1,213 @@
+//! Least-recently-used cache of generated reports.
+
+use crate::siphash::SipHasher13;
+use crate::sync::Lock;
+use crate::{BugReport, FxHashMap, Params};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use rustc_hash::FxHasher;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// Hash a template name and parameters independently of parameter order.
+pub(crate) fn fingerprint(template_name: &str, params: &Params) -> u64 {
+    let mut combined: u64 = 0;
+    for (key, value) in params {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+        combined = combined.wrapping_add(hasher.finish());
+    }
+
+    let mut hasher = FxHasher::default();
+    template_name.hash(&mut hasher);
+    combined.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Like `fingerprint`, but the same on every platform and in every process.
+///
+/// Used for the `{fingerprint}` marker, which is compared across the
+/// machines of everyone who files a report.
+pub(crate) fn stable_fingerprint(template_name: &str, params: &Params) -> u64 {
+    let hash = |parts: &[&[u8]]| {
+        let mut hasher = SipHasher13::new_with_keys(0, 0);
+        for part in parts {
+            hasher.write(part);
+            hasher.write_u8(0xff);
+        }
+        hasher.finish()
+    };
+    let combined = params.iter().fold(0u64, |combined, (key, value)| {
+        combined.wrapping_add(hash(&[key.as_bytes(), value.as_bytes()]))
+    });
+    hash(&[template_name.as_bytes(), &combined.to_le_bytes()])
+}
+
+struct Entry {
+    template_name: String,
+    params: Params,
+    report: BugReport,
+    fingerprint: u64,
+    /// Index of the entry used next after this one
+    newer: Option<usize>,
+    /// Index of the entry used last before this one
+    older: Option<usize>,
+}
+
+/// Cached reports in a doubly linked list from most to least recently used,
+/// threaded through `slots` by index, so using and evicting are O(1).
+#[derive(Default)]
+struct Entries {
+    slots: Vec<Entry>,
+    by_fingerprint: FxHashMap<u64, Vec<usize>>,
+    newest: Option<usize>,
+    oldest: Option<usize>,
+}
+
+/// Reports keyed by template name and parameters, evicting the least recently used.
+///
+/// A capacity of zero disables caching.
+pub(crate) struct ReportCache {
+    capacity: usize,
+    entries: Lock<Entries>,
+}
+
+impl ReportCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Lock::new(Entries {
+                slots: Vec::new(),
+                by_fingerprint: FxHashMap::default(),
+                newest: None,
+                oldest: None,
+            }),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.with(|entries| entries.slots.len())
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.with(|entries| {
+            entries.slots.clear();
+            entries.by_fingerprint.clear();
+            entries.newest = None;
+            entries.oldest = None;
+        });
+    }
+
+    pub(crate) fn get(&self, template_name: &str, params: &Params) -> Option<BugReport> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let fingerprint = fingerprint(template_name, params);
+        self.entries.with(|entries| {
+            let index = *entries.by_fingerprint.get(&fingerprint)?.iter().find(|index| {
+                let entry = &entries.slots[**index];
+                entry.template_name == template_name && entry.params == *params
+            })?;
+            entries.unlink(index);
+            entries.push_newest(index);
+            Some(entries.slots[index].report.clone())
+        })
+    }
+
+    pub(crate) fn insert(&self, template_name: &str, params: &Params, report: &BugReport) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let fingerprint = fingerprint(template_name, params);
+        let capacity = self.capacity;
+        self.entries.with(|entries| {
+            let entry = Entry {
+                template_name: template_name.into(),
+                params: params.clone(),
+                report: report.clone(),
+                fingerprint,
+                newer: None,
+                older: None,
+            };
+            let index = match entries.oldest {
+                Some(oldest) if entries.slots.len() >= capacity => {
+                    // Reuse the least recently used entry's slot
+                    entries.unlink(oldest);
+                    entries.forget(oldest);
+                    entries.slots[oldest] = entry;
+                    oldest
+                }
+                _ => {
+                    entries.slots.push(entry);
+                    entries.slots.len() - 1
+                }
+            };
+            entries.by_fingerprint.entry(fingerprint).or_default().push(index);
+            entries.push_newest(index);
+        });
+    }
+}
+
+impl Entries {
+    /// Take the entry at `index` out of the recency list.
+    fn unlink(&mut self, index: usize) {
+        let (newer, older) = (self.slots[index].newer, self.slots[index].older);
+        match newer {
+            Some(newer) => self.slots[newer].older = older,
+            None => self.newest = older,
+        }
+        match older {
+            Some(older) => self.slots[older].newer = newer,
+            None => self.oldest = newer,
+        }
+    }
+
+    /// Put the unlinked entry at `index` at the front of the recency list.
+    fn push_newest(&mut self, index: usize) {
+        self.slots[index].newer = None;
+        self.slots[index].older = self.newest;
+        match self.newest {
+            Some(newest) => self.slots[newest].newer = Some(index),
+            None => self.oldest = Some(index),
+        }
+        self.newest = Some(index);
+    }
+
+    /// Remove the entry at `index` from the fingerprint index.
+    fn forget(&mut self, index: usize) {
+        let fingerprint = self.slots[index].fingerprint;
+        if let Some(bucket) = self.by_fingerprint.get_mut(&fingerprint) {
+            bucket.retain(|other| *other != index);
+            if bucket.is_empty() {
+                self.by_fingerprint.remove(&fingerprint);
+            }
+        }
+    }
+}
+
+impl Clone for ReportCache {
+    /// Clones start empty; cached reports are not shared between handles.
+    fn clone(&self) -> Self {
+        Self::new(self.capacity)
+    }
+}
+
+impl fmt::Debug for ReportCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReportCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
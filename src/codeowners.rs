@@ -0,0 +1,205 @@
+//! Owner-based assignees from a CODEOWNERS file (requires the `codeowners` feature).
+//!
+//! The reporting call site names the source file a bug was found in, and a
+//! repository's `CODEOWNERS` file names who owns that file. With a
+//! [`CodeOwners`] registered on the configuration, reports made with a
+//! location are assigned to the file's owners:
+//!
+//! - user owners (`@alice`) become assignees;
+//! - team owners (`@org/ui-team`) become labels named after the team
+//!   (`ui-team`), since GitHub issues can't be assigned to teams;
+//! - email owners are ignored.
+//!
+//! Patterns follow GitHub's CODEOWNERS rules: the last matching line wins,
+//! a pattern starting with or containing `/` is relative to the repository
+//! root, other patterns match at any depth, a trailing `/` matches
+//! directories, and `*`, `?`, and `**` are wildcards.
+//!
+//! Call sites report paths as `file!()` gives them, relative to the
+//! directory cargo was invoked in, which is usually the repository root.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, Params};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .codeowners("
+//!         # Default owners
+//!         *                @lead
+//!         /src/ui/         @alice @org/ui-team
+//!     ")
+//!     .add_template("glitch", IssueTemplate::new("Glitch", ""));
+//!
+//! let url = handle.report_bug_at("glitch", &Params::new(), Location::new("src/ui/button.rs", 12));
+//! assert!(url.contains("labels=ui-team"));
+//! assert!(url.contains("assignees=alice"));
+//! ```
+
+use crate::glob;
+
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A parsed CODEOWNERS file.
+///
+/// # Examples
+///
+/// ```
+/// use bug::codeowners::CodeOwners;
+///
+/// let owners = CodeOwners::parse("
+///     *.rs        @rustacean
+///     /docs/      @writer
+///     docs/*.rs   @doc-tester
+/// ");
+///
+/// assert_eq!(owners.owners_of("src/main.rs"), ["@rustacean"]);
+/// assert_eq!(owners.owners_of("docs/guide/intro.md"), ["@writer"]);
+/// assert_eq!(owners.owners_of("docs/example.rs"), ["@doc-tester"]);
+/// assert!(owners.owners_of("README.md").is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: Pattern,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Parse the contents of a CODEOWNERS file.
+    ///
+    /// Blank lines and `#` comments are ignored. A pattern with no owners
+    /// is kept, so files it matches have no owners.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The file contents, usually from `include_str!`
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let line = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => line,
+            };
+            let mut fields = line.split_whitespace();
+            let Some(pattern) = fields.next() else {
+                continue;
+            };
+            let owners: Vec<String> = fields.map(|owner| owner.to_string()).collect();
+            rules.push(Rule {
+                pattern: Pattern::new(pattern),
+                owners,
+            });
+        }
+        Self { rules }
+    }
+
+    /// The owners of a file, as written in the CODEOWNERS file.
+    ///
+    /// Returns an empty slice if no rule matches, or if the last matching
+    /// rule has no owners (which removes ownership).
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path of the file relative to the repository root
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        let path = normalize(path);
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.pattern.matches(&segments))
+            .map(|rule| rule.owners.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// Split owners into GitHub usernames to assign and team slugs to label.
+pub(crate) fn assignees_and_labels(owners: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut assignees = Vec::new();
+    let mut labels = Vec::new();
+    for owner in owners {
+        let Some(name) = owner.strip_prefix('@') else {
+            continue;
+        };
+        match name.split_once('/') {
+            Some((_, team)) => labels.push(team.to_string()),
+            None => assignees.push(name.to_string()),
+        }
+    }
+    (assignees, labels)
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+#[derive(Debug, Clone)]
+struct Pattern {
+    segments: Vec<String>,
+    directory_only: bool,
+    /// Whether the pattern also matches everything below what it names.
+    matches_contents: bool,
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Self {
+        let directory_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        let anchored = trimmed.contains('/');
+        let trimmed = trimmed.trim_start_matches('/');
+
+        let mut segments: Vec<String> = Vec::new();
+        if !anchored {
+            segments.push("**".to_string());
+        }
+        segments.extend(trimmed.split('/').filter(|s| !s.is_empty()).map(|s| s.to_string()));
+
+        // `docs/*` owns the files directly in `docs`, not those in its
+        // subdirectories, while `docs` or `docs/` own everything below.
+        let matches_contents = directory_only || segments.last().is_none_or(|last| last == "**" || !last.contains(['*', '?']));
+
+        Self {
+            segments,
+            directory_only,
+            matches_contents,
+        }
+    }
+
+    fn matches(&self, path: &[&str]) -> bool {
+        let segments: Vec<&str> = self.segments.iter().map(|s| s.as_str()).collect();
+        self.match_from(&segments, path)
+    }
+
+    fn match_from(&self, pattern: &[&str], path: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => {
+                if path.is_empty() {
+                    !self.directory_only
+                } else {
+                    self.matches_contents
+                }
+            }
+            Some((&"**", rest)) => (0..=path.len()).any(|skip| self.match_from(rest, &path[skip..])),
+            Some((segment, rest)) => match path.split_first() {
+                Some((name, path_rest)) => glob::matches(segment, name) && self.match_from(rest, path_rest),
+                None => false,
+            },
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Gating reports on the user's consent to share them.
+//!
+//! Privacy-sensitive products may only share bug details once the user has
+//! opted in. A configuration built with `require_consent(true)` still
+//! counts, summarizes, and backs off reports as usual, but until
+//! [`set_consent`] grants consent the banner leaves out the parameters and
+//! the issue URL (which carries them) and asks the user to enable bug
+//! reporting instead. The reporting functions then return an empty string
+//! rather than the URL, since callers commonly show it.
+//!
+//! Consent is process-wide: it belongs to the user, not to one handle.
+//!
+//! # Examples
+//!
+//! ```standalone_crate
+//! use bug::{init_handle, IssueTemplate, Location, Params};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {file_name}", ""))
+//!     .require_consent(true);
+//!
+//! let mut params = Params::new();
+//! params.insert("file_name", "tax-return-2024.pdf");
+//! let loc = Location::new(file!(), line!());
+//!
+//! assert_eq!(handle.report_bug_at("crash", &params, loc), "");
+//!
+//! bug::set_consent(true);
+//! assert!(handle.report_bug_at("crash", &params, loc).contains("tax-return-2024.pdf"));
+//! ```
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static CONSENT: AtomicBool = AtomicBool::new(false);
+
+/// The line printed in place of a report's details while consent is withheld.
+pub const CONSENT_REQUIRED_MESSAGE: &str = "Enable bug reporting to share details of this bug.";
+
+/// Grant or withdraw the user's consent to share bug reports.
+///
+/// Only configurations built with `require_consent(true)` are affected.
+///
+/// # Arguments
+///
+/// * `consent` - Whether the user consents
+pub fn set_consent(consent: bool) {
+    CONSENT.store(consent, Ordering::Relaxed);
+}
+
+/// Whether the user has consented to sharing bug reports.
+pub fn has_consent() -> bool {
+    CONSENT.load(Ordering::Relaxed)
+}
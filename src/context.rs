@@ -0,0 +1,185 @@
+//! Environment context filled into reports automatically (std only).
+//!
+//! Most bug reports need the same few facts about where the program ran,
+//! and passing them into every `bug!` call is tedious. With an
+//! [`AutoContext`] on the configuration, templates can use these
+//! placeholders without any parameter:
+//!
+//! - `{os}` - The operating system, such as `linux` or `windows`
+//! - `{arch}` - The CPU architecture, such as `x86_64` or `aarch64`
+//! - `{rust_version}` - The compiler the program was built with, as `rustc --version` prints it
+//! - `{crate_version}` - The program's version, set with `AutoContext::crate_version`
+//! - `{hostname}` - The machine's name, or `unknown`
+//!
+//! Values are only looked up for placeholders the template uses, and
+//! parameters passed in take precedence. Further placeholders come from a
+//! [`ContextProvider`], tried before the built-in ones.
+//!
+//! The host name can identify a user's machine, so leave `{hostname}` out
+//! of templates for issues filed in public repositories.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::context::AutoContext;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new(
+//!         "Crash in {crate_version}",
+//!         "OS: {os} ({arch})\nBuilt with: {rust_version}\nDeployment: {deployment}",
+//!     ))
+//!     .with_auto_context(
+//!         AutoContext::new()
+//!             .crate_version("2.4.1")
+//!             .provider(|placeholder: &str| (placeholder == "deployment").then(|| "staging".to_string())),
+//!     );
+//!
+//! let report = handle.create_report("crash", &Params::new()).unwrap();
+//! assert_eq!(report.title, "Crash in 2.4.1");
+//! assert!(report.body.starts_with(&format!("OS: {} ({})\nBuilt with: rustc ", std::env::consts::OS, std::env::consts::ARCH)));
+//! assert!(report.body.ends_with("\nDeployment: staging"));
+//! ```
+
+use crate::Params;
+use once_cell::sync::OnceCell;
+use std::fmt;
+use std::string::{String, ToString};
+use std::sync::Arc;
+use std::vec::Vec;
+
+/// Placeholder filled with the operating system.
+pub const OS_PARAM: &str = "os";
+
+/// Placeholder filled with the CPU architecture.
+pub const ARCH_PARAM: &str = "arch";
+
+/// Placeholder filled with the version of the compiler the program was built with.
+pub const RUST_VERSION_PARAM: &str = "rust_version";
+
+/// Placeholder filled with the version set with `AutoContext::crate_version`.
+pub const CRATE_VERSION_PARAM: &str = "crate_version";
+
+/// Placeholder filled with the machine's host name.
+pub const HOSTNAME_PARAM: &str = "hostname";
+
+/// Supplies values for placeholders when a bug is reported.
+///
+/// Implemented for closures taking the placeholder name.
+pub trait ContextProvider: Send + Sync {
+    /// The value for `placeholder`, or `None` if this provider doesn't know it.
+    fn provide(&self, placeholder: &str) -> Option<String>;
+}
+
+impl<F: Fn(&str) -> Option<String> + Send + Sync> ContextProvider for F {
+    fn provide(&self, placeholder: &str) -> Option<String> {
+        self(placeholder)
+    }
+}
+
+/// The placeholders a configuration fills from the environment.
+#[derive(Clone, Default)]
+pub struct AutoContext {
+    crate_version: Option<String>,
+    providers: Vec<Arc<dyn ContextProvider>>,
+}
+
+impl AutoContext {
+    /// Fill the built-in placeholders only.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fill `{crate_version}` with `version`.
+    ///
+    /// Pass `env!("CARGO_PKG_VERSION")` from the program's own crate;
+    /// without this `{crate_version}` is `unknown`.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The program's version
+    pub fn crate_version(mut self, version: impl Into<String>) -> Self {
+        self.crate_version = Some(version.into());
+        self
+    }
+
+    /// Fill more placeholders from `provider`.
+    ///
+    /// Providers are tried in the order they were added, before the
+    /// built-in placeholders, so they can also replace those.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - A `ContextProvider` or a closure taking the placeholder name
+    pub fn provider(mut self, provider: impl ContextProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// The value for `placeholder`, or `None` if nothing provides it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::context::AutoContext;
+    ///
+    /// let context = AutoContext::new();
+    /// assert_eq!(context.value("arch").as_deref(), Some(std::env::consts::ARCH));
+    /// assert_eq!(context.value("crate_version").as_deref(), Some("unknown"));
+    /// assert_eq!(context.value("stage"), None);
+    /// ```
+    pub fn value(&self, placeholder: &str) -> Option<String> {
+        if let Some(value) = self.providers.iter().find_map(|provider| provider.provide(placeholder)) {
+            return Some(value);
+        }
+        let value = match placeholder {
+            OS_PARAM => std::env::consts::OS,
+            ARCH_PARAM => std::env::consts::ARCH,
+            RUST_VERSION_PARAM => env!("BUG_RUSTC_VERSION"),
+            CRATE_VERSION_PARAM => self.crate_version.as_deref().unwrap_or("unknown"),
+            HOSTNAME_PARAM => hostname(),
+            _ => return None,
+        };
+        Some(value.to_string())
+    }
+
+    /// Whether any providers were added.
+    pub(crate) fn has_providers(&self) -> bool {
+        !self.providers.is_empty()
+    }
+
+    /// Add the placeholders among `placeholders` that `params` lacks and this context provides.
+    pub(crate) fn fill(&self, placeholders: &[String], params: &Params, extended: &mut Option<Params>) {
+        for placeholder in placeholders {
+            if params.contains_key(placeholder) {
+                continue;
+            }
+            if let Some(value) = self.value(placeholder) {
+                extended.get_or_insert_with(|| params.clone()).insert(placeholder, value);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for AutoContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutoContext")
+            .field("crate_version", &self.crate_version)
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+/// The machine's host name, read once.
+fn hostname() -> &'static str {
+    static HOSTNAME: OnceCell<String> = OnceCell::new();
+    HOSTNAME.get_or_init(|| {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .into_iter()
+            .find_map(|var| std::env::var(var).ok())
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+}
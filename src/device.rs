@@ -0,0 +1,202 @@
+//! Device context for reports from mobile apps (features `android` and `ios`).
+//!
+//! A crash on one phone model or OS version is often invisible on every
+//! other, so mobile reports need the device they came from. With the
+//! `android` or `ios` feature, templates can use these placeholders, filled
+//! in every report from the device the app runs on:
+//!
+//! - `{device}`, a one-line summary such as `Google Pixel 8 (Android 14, API 34)`
+//! - `{device_manufacturer}` and `{device_model}`
+//! - `{os_version}`
+//! - `{android_api_level}`, empty on iOS
+//!
+//! On Android the values are system properties read with the NDK's
+//! `__system_property_get`; on iOS they come from `sysctlbyname`, with the
+//! simulated model when running in the simulator. Both are part of the
+//! platform's C library, so no extra dependency is needed, and bindings
+//! such as the UniFFI one get the placeholders without any code on the
+//! Kotlin or Swift side. Parameters passed in take precedence, and
+//! `device_context` replaces what is read, for tests and for hosts that
+//! know better. On other platforms the placeholders are only filled from
+//! `device_context`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::device::DeviceContext;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash on {device_model}", "Device: {device}\nAPI level: {android_api_level}"))
+//!     .device_context(DeviceContext {
+//!         os: "Android".to_string(),
+//!         os_version: "14".to_string(),
+//!         manufacturer: "Google".to_string(),
+//!         model: "Pixel 8".to_string(),
+//!         api_level: Some(34),
+//!     });
+//!
+//! let report = handle.create_report("crash", &Params::new()).unwrap();
+//! assert_eq!(report.title, "Crash on Pixel 8");
+//! assert_eq!(report.body, "Device: Google Pixel 8 (Android 14, API 34)\nAPI level: 34");
+//! ```
+
+use crate::Params;
+use once_cell::sync::OnceCell;
+use std::fmt;
+use std::string::{String, ToString};
+
+/// Placeholder filled with `DeviceContext`'s one-line summary.
+pub const DEVICE_PARAM: &str = "device";
+
+/// Placeholder filled with the device manufacturer.
+pub const DEVICE_MANUFACTURER_PARAM: &str = "device_manufacturer";
+
+/// Placeholder filled with the device model.
+pub const DEVICE_MODEL_PARAM: &str = "device_model";
+
+/// Placeholder filled with the OS version, such as `14` or `17.4`.
+pub const OS_VERSION_PARAM: &str = "os_version";
+
+/// Placeholder filled with the Android API level.
+pub const ANDROID_API_LEVEL_PARAM: &str = "android_api_level";
+
+/// The device an app runs on.
+///
+/// Its `Display` text is the one-line summary used for `{device}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceContext {
+    /// The operating system, `Android` or `iOS`
+    pub os: String,
+    /// The OS version, such as `14` or `17.4`
+    pub os_version: String,
+    /// Who made the device, such as `Google` or `Apple`
+    pub manufacturer: String,
+    /// The device model, such as `Pixel 8` or `iPhone15,2`
+    pub model: String,
+    /// The Android API level, `None` on other platforms
+    pub api_level: Option<u32>,
+}
+
+impl DeviceContext {
+    /// The device this program runs on, read once.
+    ///
+    /// `None` when not built for Android or iOS, or when nothing could be
+    /// read.
+    pub fn current() -> Option<&'static DeviceContext> {
+        static CURRENT: OnceCell<Option<DeviceContext>> = OnceCell::new();
+        CURRENT.get_or_init(read).as_ref()
+    }
+
+    /// Add the device placeholders among `placeholders` that `params` lacks.
+    pub(crate) fn fill(&self, placeholders: &[String], params: &Params, extended: &mut Option<Params>) {
+        let wanted = |key: &str| placeholders.iter().any(|p| p == key) && !params.contains_key(key);
+        let values = [
+            (DEVICE_PARAM, self.to_string()),
+            (DEVICE_MANUFACTURER_PARAM, self.manufacturer.clone()),
+            (DEVICE_MODEL_PARAM, self.model.clone()),
+            (OS_VERSION_PARAM, self.os_version.clone()),
+            (ANDROID_API_LEVEL_PARAM, self.api_level.map(|level| level.to_string()).unwrap_or_default()),
+        ];
+        for (key, value) in values {
+            if wanted(key) {
+                extended.get_or_insert_with(|| params.clone()).insert(key, value);
+            }
+        }
+    }
+}
+
+impl fmt::Display for DeviceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Some models already start with the manufacturer
+        if self.model.starts_with(&self.manufacturer) {
+            f.write_str(&self.model)?;
+        } else {
+            write!(f, "{} {}", self.manufacturer, self.model)?;
+        }
+        write!(f, " ({} {}", self.os, self.os_version)?;
+        if let Some(level) = self.api_level {
+            write!(f, ", API {}", level)?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(target_os = "android")]
+fn read() -> Option<DeviceContext> {
+    use std::ffi::{c_char, c_int, CStr};
+
+    // The most a system property value can hold, with its terminating NUL
+    const PROP_VALUE_MAX: usize = 92;
+
+    unsafe extern "C" {
+        fn __system_property_get(name: *const c_char, value: *mut c_char) -> c_int;
+    }
+
+    fn property(name: &CStr) -> Option<String> {
+        let mut value = [0 as c_char; PROP_VALUE_MAX];
+        // SAFETY: `name` is NUL-terminated, and `value` has room for the
+        // PROP_VALUE_MAX bytes the call writes at most.
+        let len = unsafe { __system_property_get(name.as_ptr(), value.as_mut_ptr()) };
+        if len <= 0 {
+            return None;
+        }
+        // SAFETY: the call NUL-terminated what it wrote into `value`.
+        let value = unsafe { CStr::from_ptr(value.as_ptr()) };
+        Some(value.to_string_lossy().into_owned())
+    }
+
+    Some(DeviceContext {
+        os: "Android".to_string(),
+        os_version: property(c"ro.build.version.release").unwrap_or_default(),
+        manufacturer: property(c"ro.product.manufacturer").unwrap_or_default(),
+        model: property(c"ro.product.model")?,
+        api_level: property(c"ro.build.version.sdk").and_then(|level| level.parse().ok()),
+    })
+}
+
+#[cfg(target_os = "ios")]
+fn read() -> Option<DeviceContext> {
+    use std::ffi::{c_char, c_int, c_void, CStr};
+    use std::ptr;
+
+    unsafe extern "C" {
+        fn sysctlbyname(
+            name: *const c_char,
+            oldp: *mut c_void,
+            oldlenp: *mut usize,
+            newp: *mut c_void,
+            newlen: usize,
+        ) -> c_int;
+    }
+
+    fn sysctl(name: &CStr) -> Option<String> {
+        let mut len = 0;
+        // SAFETY: a null buffer asks only for the length, written to `len`.
+        if unsafe { sysctlbyname(name.as_ptr(), ptr::null_mut(), &mut len, ptr::null_mut(), 0) } != 0 || len == 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; len];
+        // SAFETY: `buf` holds `len` bytes, the size the call is told it has.
+        if unsafe { sysctlbyname(name.as_ptr(), buf.as_mut_ptr().cast(), &mut len, ptr::null_mut(), 0) } != 0 {
+            return None;
+        }
+        buf.truncate(len);
+        CStr::from_bytes_until_nul(&buf).ok().map(|value| value.to_string_lossy().into_owned())
+    }
+
+    // In the simulator `hw.machine` is the Mac's architecture
+    let model = std::env::var("SIMULATOR_MODEL_IDENTIFIER").ok().or_else(|| sysctl(c"hw.machine"))?;
+    Some(DeviceContext {
+        os: "iOS".to_string(),
+        os_version: sysctl(c"kern.osproductversion").unwrap_or_default(),
+        manufacturer: "Apple".to_string(),
+        model,
+        api_level: None,
+    })
+}
+
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn read() -> Option<DeviceContext> {
+    None
+}
@@ -0,0 +1,62 @@
+//! Ready-made [egui](https://docs.rs/egui) bug report dialog (requires the `egui` feature).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use bug::{init_handle, IssueTemplate, ReportPresentation, Params};
+//!
+//! fn ui(ctx: &egui::Context, open: &mut bool) {
+//!     let handle = init_handle("owner", "repo")
+//!         .add_template("crash", IssueTemplate::new("Crash", "The renderer crashed"));
+//!     let report = handle.create_report("crash", &Params::new()).unwrap();
+//!     let presentation = ReportPresentation::from_report(&report);
+//!
+//!     bug::egui::report_dialog(ctx, &presentation, open);
+//! }
+//! ```
+
+use crate::ReportPresentation;
+
+/// Default window title for the report dialog.
+pub const DIALOG_TITLE: &str = "Report this problem";
+
+/// Show a bug report dialog window.
+///
+/// The window shows the report title, its summary lines, and a button that
+/// opens the issue URL in the browser. It is hidden once `open` is set to
+/// `false`, either by the window's close button or the dialog's "Close"
+/// button.
+///
+/// # Arguments
+///
+/// * `ctx` - The egui context to draw into
+/// * `presentation` - The report to present
+/// * `open` - Whether the dialog is visible; set to `false` when dismissed
+pub fn report_dialog(ctx: &::egui::Context, presentation: &ReportPresentation, open: &mut bool) {
+    let mut close_clicked = false;
+
+    ::egui::Window::new(DIALOG_TITLE)
+        .open(open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.heading(&presentation.title);
+            ui.add_space(4.0);
+            for line in &presentation.summary {
+                ui.label(line);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(&presentation.button_label).clicked() {
+                    ui.ctx().open_url(::egui::OpenUrl::new_tab(&presentation.url));
+                }
+                if ui.button("Close").clicked() {
+                    close_clicked = true;
+                }
+            });
+        });
+
+    if close_clicked {
+        *open = false;
+    }
+}
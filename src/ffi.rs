@@ -0,0 +1,199 @@
+//! A C API for hosts written in C or C++ (feature `capi`).
+//!
+//! A C++ application that hosts Rust plugins can drive the same reporting
+//! configuration the plugins use. A handle is created with
+//! [`bug_handle_new`], given templates with [`bug_handle_add_template`],
+//! and asked for issue URLs with [`bug_generate_url`]. Strings cross the
+//! boundary as NUL-terminated UTF-8, and every pointer this module returns
+//! must be released with the matching `*_free` function.
+//!
+//! The declarations are in `include/bug.h`, generated from this module by
+//! cbindgen with the `cbindgen.toml` at the crate root. The functions are
+//! exported from whichever library links this crate with `capi` enabled,
+//! so build the plugin or a wrapper crate as a `staticlib` or `cdylib`.
+//!
+//! ```c
+//! BugHandle *handle = bug_handle_new("owner", "repo");
+//! bug_handle_add_template(handle, "crash", "Crash in {plugin}", "Plugin {plugin} crashed");
+//!
+//! const char *keys[] = {"plugin"};
+//! const char *values[] = {"reverb"};
+//! char *url = bug_generate_url(handle, "crash", keys, values, 1);
+//! if (url) {
+//!     puts(url);
+//!     bug_string_free(url);
+//! }
+//! bug_handle_free(handle);
+//! ```
+
+use crate::{init_handle, BugReportHandle, IssueTemplate, Params};
+use core::ffi::{c_char, c_int, CStr};
+
+#[cfg(feature = "std")]
+use std::{boxed::Box, ffi::CString};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, ffi::CString};
+
+/// The call succeeded.
+pub const BUG_OK: c_int = 0;
+
+/// A required pointer argument was null.
+pub const BUG_ERROR_NULL: c_int = -1;
+
+/// A string argument was not valid UTF-8.
+pub const BUG_ERROR_UTF8: c_int = -2;
+
+/// An opaque bug report handle.
+pub struct BugHandle(BugReportHandle);
+
+/// Borrow a C string as UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string that outlives `'a`.
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, c_int> {
+    if s.is_null() {
+        return Err(BUG_ERROR_NULL);
+    }
+    unsafe { CStr::from_ptr(s) }.to_str().map_err(|_| BUG_ERROR_UTF8)
+}
+
+/// Create a handle for a GitHub repository.
+///
+/// Returns null if either argument is null or not valid UTF-8. Release the
+/// handle with [`bug_handle_free`].
+///
+/// # Safety
+///
+/// `owner` and `repo` must be null or point to NUL-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bug_handle_new(owner: *const c_char, repo: *const c_char) -> *mut BugHandle {
+    match unsafe { (str_arg(owner), str_arg(repo)) } {
+        (Ok(owner), Ok(repo)) => Box::into_raw(Box::new(BugHandle(init_handle(owner, repo)))),
+        _ => core::ptr::null_mut(),
+    }
+}
+
+/// Release a handle created by [`bug_handle_new`]. Null is ignored.
+///
+/// # Safety
+///
+/// `handle` must be null or a handle from [`bug_handle_new`] that has not
+/// been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bug_handle_free(handle: *mut BugHandle) {
+    if !handle.is_null() {
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+/// Add a template to a handle, replacing any template with the same name.
+///
+/// Returns [`BUG_OK`], [`BUG_ERROR_NULL`] if an argument is null, or
+/// [`BUG_ERROR_UTF8`] if a string is not valid UTF-8.
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`bug_handle_new`] not used by
+/// another thread during the call, and the strings must be null or
+/// NUL-terminated.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bug_handle_add_template(
+    handle: *mut BugHandle,
+    name: *const c_char,
+    title: *const c_char,
+    body: *const c_char,
+) -> c_int {
+    let Some(handle) = (unsafe { handle.as_mut() }) else {
+        return BUG_ERROR_NULL;
+    };
+    let (name, title, body) = match unsafe { (str_arg(name), str_arg(title), str_arg(body)) } {
+        (Ok(name), Ok(title), Ok(body)) => (name, title, body),
+        (Err(code), _, _) | (_, Err(code), _) | (_, _, Err(code)) => return code,
+    };
+    handle.0.register_template(name, IssueTemplate::new(title, body));
+    BUG_OK
+}
+
+/// Generate the issue URL for a template.
+///
+/// `keys` and `values` are parallel arrays of `count` parameter names and
+/// values; both may be null when `count` is 0. Returns null if an argument
+/// is null or not valid UTF-8, or the template can't be filled. Release
+/// the URL with [`bug_string_free`].
+///
+/// # Safety
+///
+/// `handle` must be a live handle from [`bug_handle_new`], `template_name`
+/// must be null or NUL-terminated, and `keys` and `values` must each point
+/// to `count` pointers that are null or NUL-terminated.
+///
+/// # Examples
+///
+/// ```
+/// use bug::ffi::{bug_generate_url, bug_handle_add_template, bug_handle_free, bug_handle_new, bug_string_free, BUG_OK};
+/// use std::ffi::CStr;
+///
+/// unsafe {
+///     let handle = bug_handle_new(c"owner".as_ptr(), c"repo".as_ptr());
+///     assert_eq!(bug_handle_add_template(handle, c"crash".as_ptr(), c"Crash in {plugin}".as_ptr(), c"".as_ptr()), BUG_OK);
+///
+///     let keys = [c"plugin".as_ptr()];
+///     let values = [c"reverb".as_ptr()];
+///     let url = bug_generate_url(handle, c"crash".as_ptr(), keys.as_ptr(), values.as_ptr(), 1);
+///     assert!(CStr::from_ptr(url).to_str().unwrap().contains("title=Crash+in+reverb"));
+///     bug_string_free(url);
+///
+///     assert!(bug_generate_url(handle, c"unknown".as_ptr(), std::ptr::null(), std::ptr::null(), 0).is_null());
+///     bug_handle_free(handle);
+/// }
+/// ```
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bug_generate_url(
+    handle: *const BugHandle,
+    template_name: *const c_char,
+    keys: *const *const c_char,
+    values: *const *const c_char,
+    count: usize,
+) -> *mut c_char {
+    let Some(handle) = (unsafe { handle.as_ref() }) else {
+        return core::ptr::null_mut();
+    };
+    let Ok(template_name) = (unsafe { str_arg(template_name) }) else {
+        return core::ptr::null_mut();
+    };
+    if count > 0 && (keys.is_null() || values.is_null()) {
+        return core::ptr::null_mut();
+    }
+
+    let mut params = Params::new();
+    for i in 0..count {
+        match unsafe { (str_arg(*keys.add(i)), str_arg(*values.add(i))) } {
+            (Ok(key), Ok(value)) => {
+                params.insert(key, value);
+            }
+            _ => return core::ptr::null_mut(),
+        }
+    }
+
+    handle
+        .0
+        .generate_url(template_name, &params)
+        .ok()
+        .and_then(|url| CString::new(url).ok())
+        .map_or(core::ptr::null_mut(), CString::into_raw)
+}
+
+/// Release a string returned by this API. Null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this API that has not been
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bug_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
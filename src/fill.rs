@@ -0,0 +1,240 @@
+//! Placeholder substitution shared by every templated field.
+//!
+//! Title, body, labels, assignees, milestone, and projects are all filled by
+//! the same single pass over `{name}` placeholders, so a field added to
+//! templates behaves like the others by construction:
+//!
+//! - Only `{name}` where `name` is alphanumeric or `_` is a placeholder;
+//!   other braces are left as written. A parameter whose key has other
+//!   characters, such as `{my-key}`, is never substituted.
+//! - A `{` that doesn't start a placeholder is kept and scanning resumes
+//!   right after it, so `{{name}}` becomes `{value}`.
+//! - `{name|filter}` formats the value with a filter from `format`:
+//!   `bytes` or `duration`. Values the filter can't parse, and unknown
+//!   filters, are inserted unchanged.
+//! - Values are inserted verbatim and never scanned again, so a value
+//!   containing `{name}` is not substituted a second time.
+//! - Values are not escaped while filling. URL encoding happens once, when
+//!   the issue URL is built from the filled fields.
+//! - Inline `IssueTemplate`s are lenient: placeholders without a value are
+//!   left in place. `TemplateFile`s are strict: every placeholder in any
+//!   field needs a value and every value must be used by some field.
+
+use crate::{format as formatting, IssueTemplate, Params};
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// How to treat placeholders and parameters that don't match up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Policy {
+    /// Leave unfilled placeholders in place and ignore unused parameters.
+    Lenient,
+    /// Reject missing and unused parameters.
+    Strict,
+}
+
+/// The templated fields of an issue, borrowed from a template.
+pub(crate) struct Fields<'a, L> {
+    pub(crate) title: &'a str,
+    pub(crate) body: &'a str,
+    pub(crate) labels: &'a [L],
+    pub(crate) assignees: &'a [String],
+    /// Team names, copied as they are; teams are expanded into assignees later.
+    pub(crate) teams: &'a [String],
+    pub(crate) milestone: Option<&'a str>,
+    pub(crate) projects: &'a [String],
+}
+
+impl<'a, L: AsRef<str>> Fields<'a, L> {
+    /// Every templated field, in order.
+    fn texts(&self) -> impl Iterator<Item = &'a str> + '_ {
+        [self.title, self.body]
+            .into_iter()
+            .chain(self.labels.iter().map(|label| label.as_ref()))
+            .chain(self.assignees.iter().map(|assignee| assignee.as_str()))
+            .chain(self.milestone)
+            .chain(self.projects.iter().map(|project| project.as_str()))
+    }
+
+    /// Unique placeholder names across all fields, in order of appearance.
+    pub(crate) fn placeholders(&self) -> Vec<String> {
+        let mut placeholders = Vec::new();
+        for text in self.texts() {
+            collect_placeholders(text, &mut placeholders);
+        }
+        placeholders
+    }
+
+    /// Fill every field with `params`, leaving unknown placeholders in place.
+    pub(crate) fn fill(&self, params: &Params) -> IssueTemplate {
+        IssueTemplate {
+            title: fill(self.title, params),
+            body: fill(self.body, params),
+            labels: self.labels.iter().map(|label| fill(label.as_ref(), params)).collect(),
+            assignees: self.assignees.iter().map(|assignee| fill(assignee, params)).collect(),
+            teams: self.teams.to_vec(),
+            milestone: self.milestone.map(|milestone| fill(milestone, params)),
+            projects: self.projects.iter().map(|project| fill(project, params)).collect(),
+            fallback: None,
+            description: None,
+            param_docs: Vec::new(),
+        }
+    }
+
+    /// Fill every field with `params` under `policy`.
+    pub(crate) fn fill_with(&self, params: &Params, policy: Policy) -> Result<IssueTemplate, String> {
+        if policy == Policy::Strict {
+            check_strict(&self.placeholders(), params)?;
+        }
+        Ok(self.fill(params))
+    }
+}
+
+/// Check that `params` provides exactly the given placeholders.
+pub(crate) fn check_strict(placeholders: &[String], params: &Params) -> Result<(), String> {
+    for placeholder in placeholders {
+        if !params.contains_key(placeholder) {
+            return Err(format!("Missing required parameter: {}", placeholder));
+        }
+    }
+
+    for param_key in params.keys() {
+        if !placeholders.iter().any(|p| p == param_key) {
+            return Err(format!("Unused parameter: {}", param_key));
+        }
+    }
+
+    Ok(())
+}
+
+/// Pair up `params` in the order their placeholders first appear.
+///
+/// `placeholders` is the template's placeholder list, in order of
+/// appearance. Parameters the template has no placeholder for follow,
+/// sorted by name, so the order never depends on hashing.
+pub(crate) fn in_template_order<'a>(params: &'a Params, placeholders: &'a [String]) -> Vec<(&'a str, &'a str)> {
+    let mut ordered: Vec<(&str, &str)> = placeholders
+        .iter()
+        .filter_map(|placeholder| params.get(placeholder).map(|value| (placeholder.as_str(), value)))
+        .collect();
+    let mut rest: Vec<(&str, &str)> = params
+        .iter()
+        .filter(|(key, _)| !placeholders.iter().any(|p| p == key))
+        .collect();
+    rest.sort_unstable();
+    ordered.extend(rest);
+    ordered
+}
+
+/// The template placeholders still present in filled `texts`, in template order.
+///
+/// A lenient fill leaves placeholders without a value in place; this finds
+/// them again after the fact.
+pub(crate) fn unfilled<'p, 't>(placeholders: &'p [String], texts: impl IntoIterator<Item = &'t str>) -> Vec<&'p str> {
+    let mut remaining = Vec::new();
+    for text in texts {
+        collect_placeholders(text, &mut remaining);
+    }
+    placeholders
+        .iter()
+        .filter(|placeholder| remaining.contains(placeholder))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Append the placeholder names in `text` that aren't in `placeholders` yet.
+pub(crate) fn collect_placeholders(text: &str, placeholders: &mut Vec<String>) {
+    scan(text, |segment| {
+        if let Segment::Placeholder { name, .. } = segment
+            && !placeholders.iter().any(|p| p == name)
+        {
+            placeholders.push(name.to_string());
+        }
+    });
+}
+
+/// Substitute `params` into the placeholders of `text` in a single pass.
+pub(crate) fn fill(text: &str, params: &Params) -> String {
+    let mut filled = String::with_capacity(text.len());
+    scan(text, |segment| match segment {
+        Segment::Literal(literal) => filled.push_str(literal),
+        Segment::Placeholder { name, filter, raw } => match params.get(name) {
+            Some(value) => match filter.and_then(|filter| formatting::apply_filter(filter, value)) {
+                Some(formatted) => filled.push_str(&formatted),
+                None => filled.push_str(value),
+            },
+            None => filled.push_str(raw),
+        },
+    });
+    filled
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder {
+        name: &'a str,
+        filter: Option<&'a str>,
+        /// The whole placeholder including braces, kept when there is no value
+        raw: &'a str,
+    },
+}
+
+/// Split `text` into literal runs and `{name}` or `{name|filter}` placeholders.
+fn scan<'a>(text: &'a str, mut visit: impl FnMut(Segment<'a>)) {
+    let mut literal_start = 0;
+
+    for (start, ch) in text.char_indices() {
+        if ch != '{' || start < literal_start {
+            continue;
+        }
+
+        // Scan ahead without consuming, so a `{` ending this run can
+        // still start the next placeholder, as in `{{name}}`.
+        let mut end = None;
+        let mut bar = None;
+        for (offset, inner_ch) in text[start + 1..].char_indices() {
+            let index = start + 1 + offset;
+            if inner_ch == '}' {
+                end = Some(index);
+                break;
+            } else if inner_ch == '|' && bar.is_none() {
+                bar = Some(index);
+            } else if !(inner_ch.is_alphanumeric() || inner_ch == '_') {
+                break;
+            }
+        }
+
+        let Some(end) = end else {
+            continue;
+        };
+        let name_end = bar.unwrap_or(end);
+        let filter = bar.map(|bar| &text[bar + 1..end]);
+        if name_end > start + 1 && filter.is_none_or(|filter| !filter.is_empty()) {
+            if literal_start < start {
+                visit(Segment::Literal(&text[literal_start..start]));
+            }
+            visit(Segment::Placeholder {
+                name: &text[start + 1..name_end],
+                filter,
+                raw: &text[start..=end],
+            });
+            literal_start = end + 1;
+        }
+    }
+
+    if literal_start < text.len() {
+        visit(Segment::Literal(&text[literal_start..]));
+    }
+}
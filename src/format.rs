@@ -0,0 +1,211 @@
+//! Helpers for rendering program context into report parameters.
+//!
+//! [`humanize_bytes`] and [`humanize_duration`] are also available as
+//! placeholder filters, `{name|bytes}` and `{name|duration}`, which format a
+//! parameter holding a byte count or a number of seconds.
+
+use core::fmt::Write;
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::{format, string::{String, ToString}, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Render a buffer as a canonical hexdump inside a Markdown code fence.
+///
+/// Each line shows the offset, sixteen bytes in hex split into two groups
+/// of eight, and the printable ASCII characters of those bytes, like
+/// `hexdump -C`. At most `max_len` bytes are dumped; a final line says how
+/// many were left out.
+///
+/// # Arguments
+///
+/// * `bytes` - The buffer to dump
+/// * `max_len` - The most bytes to include
+///
+/// # Examples
+///
+/// ````
+/// let dump = bug::format::hexdump(b"GET / HTTP/1.1\r\nHost: example\r\n", 20);
+/// assert_eq!(dump, "\
+/// ```text
+/// 00000000  47 45 54 20 2f 20 48 54  54 50 2f 31 2e 31 0d 0a  |GET / HTTP/1.1..|
+/// 00000010  48 6f 73 74                                       |Host|
+/// ... 11 more bytes
+/// ```");
+/// ````
+pub fn hexdump(bytes: &[u8], max_len: usize) -> String {
+    let shown = &bytes[..bytes.len().min(max_len)];
+    let mut output = String::from("```text\n");
+
+    for (line, chunk) in shown.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(output, "{:08x} ", line * BYTES_PER_LINE);
+        for index in 0..BYTES_PER_LINE {
+            if index % 8 == 0 {
+                output.push(' ');
+            }
+            match chunk.get(index) {
+                Some(byte) => {
+                    let _ = write!(output, "{:02x} ", byte);
+                }
+                None => output.push_str("   "),
+            }
+        }
+        output.push_str(" |");
+        for &byte in chunk {
+            output.push(if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' });
+        }
+        output.push_str("|\n");
+    }
+
+    if shown.len() < bytes.len() {
+        let _ = writeln!(output, "... {} more bytes", bytes.len() - shown.len());
+    }
+    output.push_str("```");
+    output
+}
+
+/// Read the lines around `line` of a source file, fenced as Rust (std only).
+///
+/// The file is looked up as given and, failing that, relative to
+/// `CARGO_MANIFEST_DIR`, which `cargo run` and `cargo test` set. The
+/// reported line is marked with `>`.
+///
+/// # Arguments
+///
+/// * `file` - Path of the source file, as given by `file!()`
+/// * `line` - The line to show, counting from 1
+/// * `context` - How many lines to show before and after it
+///
+/// # Returns
+///
+/// The excerpt, or `None` if the file can't be read or is shorter than `line`.
+///
+/// # Examples
+///
+/// ````
+/// let excerpt = bug::format::source_excerpt(file!(), line!(), 1).unwrap();
+/// assert!(excerpt.starts_with("```rust\n"));
+/// assert!(excerpt.contains("> "));
+/// assert!(excerpt.contains("source_excerpt(file!(), line!(), 1)"));
+/// ````
+#[cfg(feature = "std")]
+pub fn source_excerpt(file: &str, line: u32, context: u32) -> Option<String> {
+    let source = std::fs::read_to_string(file).ok().or_else(|| {
+        let root = std::env::var_os("CARGO_MANIFEST_DIR")?;
+        std::fs::read_to_string(std::path::Path::new(&root).join(file)).ok()
+    })?;
+
+    let line = line as usize;
+    if line == 0 {
+        return None;
+    }
+    let first = line.saturating_sub(context as usize).max(1);
+    let last = line + context as usize;
+    let lines: Vec<&str> = source.lines().skip(first - 1).take(last + 1 - first).collect();
+    if lines.len() <= line - first {
+        return None;
+    }
+
+    let width = (first + lines.len() - 1).to_string().len();
+    let mut output = String::from("```rust\n");
+    for (offset, text) in lines.iter().enumerate() {
+        let number = first + offset;
+        let marker = if number == line { '>' } else { ' ' };
+        let _ = writeln!(output, "{} {:>width$} | {}", marker, number, text, width = width);
+    }
+    output.push_str("```");
+    Some(output)
+}
+
+/// Format a byte count with binary units.
+///
+/// Counts below 1 KiB are shown exactly; larger ones with one decimal.
+///
+/// # Arguments
+///
+/// * `bytes` - Number of bytes
+///
+/// # Examples
+///
+/// ```
+/// use bug::format::humanize_bytes;
+///
+/// assert_eq!(humanize_bytes(512), "512 B");
+/// assert_eq!(humanize_bytes(1536), "1.5 KiB");
+/// assert_eq!(humanize_bytes(1_503_238_554), "1.4 GiB");
+/// ```
+pub fn humanize_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Format a duration in the largest fitting unit.
+///
+/// Durations under a minute are shown in one unit with one decimal;
+/// longer ones as minutes and seconds, or hours and minutes.
+///
+/// # Arguments
+///
+/// * `duration` - The duration to format
+///
+/// # Examples
+///
+/// ```
+/// use bug::format::humanize_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(humanize_duration(Duration::from_nanos(250)), "250 ns");
+/// assert_eq!(humanize_duration(Duration::from_micros(1500)), "1.5 ms");
+/// assert_eq!(humanize_duration(Duration::from_millis(2300)), "2.3 s");
+/// assert_eq!(humanize_duration(Duration::from_secs(245)), "4 min 5 s");
+/// assert_eq!(humanize_duration(Duration::from_secs(7380)), "2 h 3 min");
+/// ```
+pub fn humanize_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    let secs = duration.as_secs();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1} ms", nanos as f64 / 1e6)
+    } else if secs < 60 {
+        format!("{:.1} s", duration.as_secs_f64())
+    } else if secs < 3600 {
+        format!("{} min {} s", secs / 60, secs % 60)
+    } else {
+        format!("{} h {} min", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+/// Apply a placeholder filter to a value.
+///
+/// Values that don't parse for the filter, and unknown filters, leave the
+/// value unchanged.
+pub(crate) fn apply_filter(filter: &str, value: &str) -> Option<String> {
+    match filter {
+        "bytes" => value.trim().parse().ok().map(humanize_bytes),
+        "duration" => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+            .map(humanize_duration),
+        _ => None,
+    }
+}
@@ -0,0 +1,207 @@
+//! The limits GitHub puts on prefilled new-issue URLs.
+//!
+//! A report is only useful if GitHub accepts it. The issue form rejects
+//! titles, bodies, and labels over their length limits, and a URL past
+//! about 8 KB is refused before the form even loads. [`fits_github_limits`]
+//! checks a generated report against all of them, so a program can switch
+//! to another way of delivering the report, such as asking the user to
+//! attach a crash file, instead of handing over a link that fails.
+//!
+//! The limits are not published as one list, and some are enforced by
+//! infrastructure in front of GitHub rather than by the issue form. The
+//! values here are the ones GitHub reports when they are exceeded, and the
+//! tests on each constant record them so a change is noticed.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::github::{fits_github_limits, Limit};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash", "{log}"));
+//!
+//! let mut params = Params::new();
+//! params.insert("log", "short log");
+//! let report = handle.create_report("crash", &params).unwrap();
+//! assert!(fits_github_limits(&report).is_ok());
+//!
+//! params.insert("log", "x".repeat(10_000));
+//! let report = handle.create_report("crash", &params).unwrap();
+//! let overflow = fits_github_limits(&report).unwrap_err();
+//! assert_eq!(overflow.limit, Limit::Url);
+//! assert_eq!(overflow.to_string(), format!(
+//!     "the URL is {} bytes, over GitHub's limit of 8192",
+//!     report.url.len()
+//! ));
+//! ```
+
+use crate::teams::MAX_ASSIGNEES;
+use crate::BugReport;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// The longest issue title GitHub accepts, in characters.
+///
+/// # Examples
+///
+/// ```
+/// // "title is too long (maximum is 256 characters)"
+/// assert_eq!(bug::github::MAX_TITLE_LEN, 256);
+/// ```
+pub const MAX_TITLE_LEN: usize = 256;
+
+/// The longest issue body GitHub accepts, in characters.
+///
+/// A body this long never fits in a URL; the limit matters for bodies
+/// filed through the API or pasted into the form.
+///
+/// # Examples
+///
+/// ```
+/// // "body is too long (maximum is 65536 characters)"
+/// assert_eq!(bug::github::MAX_BODY_LEN, 65_536);
+/// ```
+pub const MAX_BODY_LEN: usize = 65_536;
+
+/// The longest label name GitHub accepts, in characters.
+///
+/// # Examples
+///
+/// ```
+/// // "name is too long (maximum is 50 characters)"
+/// assert_eq!(bug::github::MAX_LABEL_LEN, 50);
+/// ```
+pub const MAX_LABEL_LEN: usize = 50;
+
+/// The host issue URLs point at unless `base_url` or a `TemplateTarget` names another.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(bug::github::HOST, "https://github.com");
+/// ```
+pub const HOST: &str = "https://github.com";
+
+/// The longest new-issue URL GitHub serves, in bytes.
+///
+/// Longer URLs are answered with `414 URI Too Long` instead of the issue
+/// form. Browsers accept much longer URLs, so this is the limit that
+/// matters.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(bug::github::MAX_URL_LEN, 8_192);
+/// ```
+pub const MAX_URL_LEN: usize = 8_192;
+
+/// How many bytes of query string fit in a new-issue URL for `owner/repo`.
+///
+/// This is `MAX_URL_LEN` less the `https://github.com/{owner}/{repo}/issues/new?`
+/// prefix, the budget for the encoded title, body, labels, and assignees
+/// together.
+///
+/// # Arguments
+///
+/// * `owner` - GitHub repository owner
+/// * `repo` - GitHub repository name
+///
+/// # Examples
+///
+/// ```
+/// let budget = bug::github::max_prefill_len("octocat", "Hello-World");
+/// assert_eq!(budget, 8_192 - "https://github.com/octocat/Hello-World/issues/new?".len());
+/// ```
+pub const fn max_prefill_len(owner: &str, repo: &str) -> usize {
+    let prefix = "https://github.com/".len() + owner.len() + 1 + repo.len() + "/issues/new?".len();
+    MAX_URL_LEN.saturating_sub(prefix)
+}
+
+/// Which of GitHub's limits a report is over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Limit {
+    /// The title is longer than `MAX_TITLE_LEN` characters.
+    Title,
+    /// The body is longer than `MAX_BODY_LEN` characters.
+    Body,
+    /// This label is longer than `MAX_LABEL_LEN` characters.
+    Label(String),
+    /// There are more than `teams::MAX_ASSIGNEES` assignees.
+    Assignees,
+    /// The URL is longer than `MAX_URL_LEN` bytes.
+    Url,
+}
+
+/// How a report goes over one of GitHub's limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowInfo {
+    /// The limit that is exceeded
+    pub limit: Limit,
+    /// The length of the field, in the limit's unit
+    pub len: usize,
+    /// The largest length GitHub accepts
+    pub max: usize,
+}
+
+impl fmt::Display for OverflowInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.limit {
+            Limit::Title => write!(f, "the title is {} characters", self.len)?,
+            Limit::Body => write!(f, "the body is {} characters", self.len)?,
+            Limit::Label(label) => write!(f, "label '{}' is {} characters", label, self.len)?,
+            Limit::Assignees => write!(f, "there are {} assignees", self.len)?,
+            Limit::Url => write!(f, "the URL is {} bytes", self.len)?,
+        }
+        write!(f, ", over GitHub's limit of {}", self.max)
+    }
+}
+
+/// Check `report` against GitHub's limits, reporting the first one it exceeds.
+///
+/// The title, body, labels, and assignees are checked before the URL, so a
+/// field that is too long on its own is named even though it also makes
+/// the URL too long.
+///
+/// # Arguments
+///
+/// * `report` - A generated report
+///
+/// # Returns
+///
+/// * `Ok(())` - GitHub accepts the report's URL
+/// * `Err(OverflowInfo)` - The limit the report exceeds and by how much
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+/// use bug::github::{fits_github_limits, Limit, OverflowInfo};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash: {message}", ""));
+/// let mut params = Params::new();
+/// params.insert("message", "é".repeat(255));
+/// let report = handle.create_report("crash", &params).unwrap();
+///
+/// assert_eq!(
+///     fits_github_limits(&report),
+///     Err(OverflowInfo { limit: Limit::Title, len: 262, max: 256 })
+/// );
+/// ```
+pub fn fits_github_limits(report: &BugReport) -> Result<(), OverflowInfo> {
+    let over = |limit, len, max| if len > max { Err(OverflowInfo { limit, len, max }) } else { Ok(()) };
+    over(Limit::Title, report.title.chars().count(), MAX_TITLE_LEN)?;
+    over(Limit::Body, report.body.chars().count(), MAX_BODY_LEN)?;
+    if let Some(label) = report.labels.iter().find(|label| label.chars().count() > MAX_LABEL_LEN) {
+        over(Limit::Label(label.clone()), label.chars().count(), MAX_LABEL_LEN)?;
+    }
+    over(Limit::Assignees, report.assignees.len(), MAX_ASSIGNEES)?;
+    over(Limit::Url, report.url.len(), MAX_URL_LEN)
+}
@@ -0,0 +1,33 @@
+//! `*` and `?` wildcard matching shared by code owners and suppression rules.
+
+/// Whether `text` matches `pattern`, where `*` matches any run of
+/// characters and `?` matches exactly one.
+pub(crate) fn matches(pattern: &str, text: &str) -> bool {
+    let mut pattern = pattern.chars();
+    let mut text_chars = text.chars();
+    // Position to resume from after the last `*`: the pattern after it and
+    // the text one character further than last time.
+    let mut backtrack: Option<(core::str::Chars<'_>, core::str::Chars<'_>)> = None;
+
+    loop {
+        match (pattern.next(), text_chars.clone().next()) {
+            (Some('*'), _) => {
+                backtrack = Some((pattern.clone(), text_chars.clone()));
+            }
+            (Some(p), Some(t)) if p == '?' || p == t => {
+                text_chars.next();
+            }
+            (None, None) => return true,
+            _ => {
+                let Some((after_star, resume)) = &mut backtrack else {
+                    return false;
+                };
+                if resume.next().is_none() {
+                    return false;
+                }
+                pattern = after_star.clone();
+                text_chars = resume.clone();
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! Tower middleware that reports internal gRPC errors (requires the `grpc` feature).
+//!
+//! tonic's `Interceptor` only sees requests, so internal errors are captured
+//! by a tower layer instead, added with `Server::builder().layer(...)`. The
+//! layer inspects the `grpc-status` of each response and, for
+//! `Status::internal` (code 13) or any other configured code, fills a
+//! template and logs the issue URL.
+//!
+//! The template may use any of these placeholders:
+//!
+//! - `{method}` - The full gRPC method path, e.g. `/shop.Orders/Create`
+//! - `{code}` - The numeric status code, e.g. `13`
+//! - `{message}` - The status message sent to the client
+//!
+//! Placeholders the template doesn't use are not passed to it.
+//!
+//! Errors returned by handlers (`Err(Status::internal(..))`) are sent as
+//! trailers-only responses and are always captured. A status sent in the
+//! trailers of a stream that already produced messages is not visible to
+//! the layer.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use bug::{init_handle, IssueTemplate};
+//! use bug::grpc::GrpcBugReportLayer;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("grpc_internal", IssueTemplate::new(
+//!         "Internal error in {method}",
+//!         "The server returned: {message}",
+//!     ));
+//!
+//! tonic::transport::Server::builder()
+//!     .layer(GrpcBugReportLayer::new(handle, "grpc_internal"))
+//!     .add_service(OrdersServer::new(orders))
+//!     .serve(addr)
+//!     .await?;
+//! ```
+
+use crate::{url_encode, BugReport, BugReportHandle, Output};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use http::{HeaderMap, Request, Response};
+use std::sync::Arc;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The gRPC status code for internal errors.
+pub const CODE_INTERNAL: i32 = 13;
+
+/// Details about the failed call, passed to report callbacks.
+#[derive(Debug, Clone)]
+pub struct CallInfo {
+    /// The full gRPC method path
+    pub method: String,
+    /// The numeric gRPC status code
+    pub code: i32,
+    /// The decoded status message
+    pub message: String,
+}
+
+type ReportCallback = dyn Fn(&BugReport, &CallInfo) + Send + Sync;
+
+#[derive(Clone)]
+struct Shared {
+    handle: BugReportHandle,
+    template: String,
+    codes: Vec<i32>,
+    on_report: Option<Arc<ReportCallback>>,
+}
+
+/// Layer that reports internal errors from the wrapped gRPC service.
+///
+/// By default reports are logged to stderr. Use
+/// [`GrpcBugReportLayer::on_report`] to send them to your own logging instead.
+#[derive(Clone)]
+pub struct GrpcBugReportLayer {
+    shared: Arc<Shared>,
+}
+
+impl GrpcBugReportLayer {
+    /// Create a layer that fills `template` from `handle` for internal errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle holding the report template
+    /// * `template` - Name of the template to fill
+    pub fn new(handle: BugReportHandle, template: impl Into<String>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                handle,
+                template: template.into(),
+                codes: vec![CODE_INTERNAL],
+                on_report: None,
+            }),
+        }
+    }
+
+    /// Replace the set of status codes that produce a report.
+    ///
+    /// # Arguments
+    ///
+    /// * `codes` - Numeric gRPC status codes to report (default: `[13]`)
+    pub fn report_codes(mut self, codes: impl IntoIterator<Item = i32>) -> Self {
+        self.shared_mut().codes = codes.into_iter().collect();
+        self
+    }
+
+    /// Send generated reports to a callback instead of stderr.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Called with the report and the failed call's details
+    pub fn on_report(mut self, callback: impl Fn(&BugReport, &CallInfo) + Send + Sync + 'static) -> Self {
+        self.shared_mut().on_report = Some(Arc::new(callback));
+        self
+    }
+
+    /// The settings for changing, copied first if a clone of the layer shares them.
+    fn shared_mut(&mut self) -> &mut Shared {
+        Arc::make_mut(&mut self.shared)
+    }
+}
+
+impl<S> Layer<S> for GrpcBugReportLayer {
+    type Service = GrpcBugReportService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcBugReportService {
+            inner,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Service produced by [`GrpcBugReportLayer`].
+#[derive(Clone)]
+pub struct GrpcBugReportService<S> {
+    inner: S,
+    shared: Arc<Shared>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for GrpcBugReportService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = GrpcBugReportFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        GrpcBugReportFuture {
+            method: request.uri().path().to_string(),
+            shared: self.shared.clone(),
+            future: self.inner.call(request),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Response future of [`GrpcBugReportService`].
+    pub struct GrpcBugReportFuture<F> {
+        #[pin]
+        future: F,
+        method: String,
+        shared: Arc<Shared>,
+    }
+}
+
+impl<F, ResBody, E> Future for GrpcBugReportFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.future.poll(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(result) => result,
+        };
+
+        if let Ok(response) = &result
+            && let Some(code) = grpc_status(response.headers())
+            && this.shared.codes.contains(&code)
+        {
+            let info = CallInfo {
+                method: this.method.clone(),
+                code,
+                message: grpc_message(response.headers()),
+            };
+            report(this.shared, &info);
+        }
+
+        Poll::Ready(result)
+    }
+}
+
+fn report(shared: &Shared, info: &CallInfo) {
+    let available = [
+        ("method", info.method.clone()),
+        ("code", info.code.to_string()),
+        ("message", info.message.clone()),
+    ];
+    let params = shared.handle.config().used_params(&shared.template, available);
+
+    match shared.handle.create_report(&shared.template, &params) {
+        Ok(report) => match &shared.on_report {
+            Some(callback) => callback(&report, info),
+            // Not eprintln!, which panics when stderr is a closed pipe
+            None => Output::write_fmt(
+                &mut std::io::stderr(),
+                format_args!(
+                    "🐛 BUG ENCOUNTERED in gRPC call {}\n   Status: {} {}\n   File a bug report: {}\n\n",
+                    info.method, info.code, info.message, report.url
+                ),
+            ),
+        },
+        Err(e) => Output::write_fmt(
+            &mut std::io::stderr(),
+            format_args!(
+                "🐛 BUG ENCOUNTERED in gRPC call {}\n   Error generating bug report: {}\n\n",
+                info.method, e
+            ),
+        ),
+    }
+}
+
+fn grpc_status(headers: &HeaderMap) -> Option<i32> {
+    headers.get("grpc-status")?.to_str().ok()?.trim().parse().ok()
+}
+
+/// Decode the percent-encoded `grpc-message` header.
+///
+/// A message with a malformed escape is kept as it was sent.
+fn grpc_message(headers: &HeaderMap) -> String {
+    let raw = match headers.get("grpc-message") {
+        Some(value) => value.as_bytes(),
+        None => return String::new(),
+    };
+    let decoded = url_encode::decode_bytes(raw, false);
+    String::from_utf8_lossy(decoded.as_deref().unwrap_or(raw)).into_owned()
+}
@@ -0,0 +1,52 @@
+//! The HTTP clients the crate's network features make requests through.
+
+/// `User-Agent` header sent with every request.
+pub(crate) const USER_AGENT: &str = concat!("bug/", env!("CARGO_PKG_VERSION"));
+
+/// Makes the HTTP GET requests of `GitHubApi` and remote templates.
+///
+/// The crate makes no network requests itself; implement this around
+/// whatever HTTP library the application already uses. It is implemented
+/// for closures taking the URL and request headers and returning the
+/// response body.
+#[cfg(any(feature = "api", feature = "remote"))]
+pub trait HttpClient: Send + Sync {
+    /// Send a GET request and return the response body.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to request
+    /// * `headers` - Header names and values to send
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, String>;
+}
+
+#[cfg(any(feature = "api", feature = "remote"))]
+impl<F: Fn(&str, &[(&str, &str)]) -> Result<String, String> + Send + Sync> HttpClient for F {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<String, String> {
+        self(url, headers)
+    }
+}
+
+/// Makes the HTTP POST requests of webhook sinks.
+///
+/// Like `HttpClient`, implement this around the application's HTTP
+/// library. It is implemented for closures taking the URL, the request
+/// headers, and the body, and returning whether the post succeeded.
+#[cfg(feature = "webhook")]
+pub trait HttpPost: Send + Sync {
+    /// Send a POST request with `body`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL to post to
+    /// * `headers` - Header names and values to send
+    /// * `body` - The request body
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<(), String>;
+}
+
+#[cfg(feature = "webhook")]
+impl<F: Fn(&str, &[(&str, &str)], &str) -> Result<(), String> + Send + Sync> HttpPost for F {
+    fn post(&self, url: &str, headers: &[(&str, &str)], body: &str) -> Result<(), String> {
+        self(url, headers, body)
+    }
+}
@@ -0,0 +1,465 @@
+//! Linking to GitHub issue forms.
+//!
+//! Repositories increasingly describe their issues with YAML issue forms
+//! in `.github/ISSUE_TEMPLATE` rather than Markdown templates. A form
+//! can't be prefilled with a `body`: GitHub opens it from its file name in
+//! a `template` parameter and fills each field from a parameter named
+//! after the field's `id`. An [`IssueForm`] parsed from the same YAML file
+//! knows those ids, so `form_url` on a handle links straight into the
+//! structured form with the fields a program knows already filled in.
+//!
+//! Only the parts of YAML issue forms use are read: block mappings and
+//! sequences, quoted and plain scalars, `[a, b]` lists, and `|` and `>`
+//! block scalars.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, Params};
+//! use bug::issue_form::IssueForm;
+//!
+//! // Usually include_str!("../.github/ISSUE_TEMPLATE/bug.yml")
+//! let yaml = r#"
+//! name: Bug report
+//! description: Something doesn't work
+//! title: "[Bug]: "
+//! labels: ["bug", "triage"]
+//! body:
+//!   - type: markdown
+//!     attributes:
+//!       value: |
+//!         Thanks for taking the time to fill out this report!
+//!   - type: input
+//!     id: version
+//!     attributes:
+//!       label: Version
+//!   - type: textarea
+//!     id: what-happened
+//!     attributes:
+//!       label: What happened?
+//!     validations:
+//!       required: true
+//! "#;
+//! let form = IssueForm::parse("bug.yml", yaml).unwrap();
+//! assert_eq!(form.name, "Bug report");
+//! assert_eq!(form.labels, ["bug", "triage"]);
+//! assert_eq!(form.field_ids().collect::<Vec<_>>(), ["version", "what-happened"]);
+//!
+//! let handle = init_handle("owner", "repo");
+//! let mut params = Params::new();
+//! params.insert("what-happened", "Sync stopped");
+//! params.insert("version", "2.4.1");
+//! assert_eq!(
+//!     handle.form_url(&form, &params).unwrap(),
+//!     "https://github.com/owner/repo/issues/new?template=bug.yml&version=2.4.1&what-happened=Sync%20stopped"
+//! );
+//! ```
+
+use crate::url_encode::{self, SpaceEncoding};
+use crate::{Output, Params};
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Parameter that sets the issue title in place of the form's.
+pub const FORM_TITLE_PARAM: &str = "title";
+
+/// An issue form from a repository's `.github/ISSUE_TEMPLATE` directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IssueForm {
+    /// Name of the form's file, such as `bug.yml`, used in the `template` parameter
+    pub file_name: String,
+    /// The form's name in the template chooser
+    pub name: String,
+    /// The form's description in the template chooser
+    pub description: String,
+    /// The title new issues start with
+    pub title: String,
+    /// Labels GitHub adds to issues filed with the form
+    pub labels: Vec<String>,
+    /// Users GitHub assigns to issues filed with the form
+    pub assignees: Vec<String>,
+    /// The form's elements, in order
+    pub fields: Vec<FormField>,
+}
+
+/// One element of an issue form's `body`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormField {
+    /// The element type: `markdown`, `input`, `textarea`, `dropdown`, or `checkboxes`
+    pub kind: String,
+    /// The field's id, which names the parameter that prefills it
+    pub id: Option<String>,
+    /// The field's label, empty for `markdown` elements
+    pub label: String,
+    /// Whether GitHub requires the field before the issue can be submitted
+    pub required: bool,
+}
+
+impl IssueForm {
+    /// Parse an issue form.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - Name of the form's file in `.github/ISSUE_TEMPLATE`, such as `bug.yml`
+    /// * `yaml` - The form's content
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IssueForm)` - The parsed form
+    /// * `Err(String)` - The YAML can't be read, or the form lacks a `name` or `body`
+    pub fn parse(file_name: impl Into<String>, yaml: &str) -> Result<Self, String> {
+        let file_name = file_name.into();
+        let error = |message: String| format!("Issue form '{}': {}", file_name, message);
+        let root = Parser::new(yaml).parse_document().map_err(error)?;
+        let Value::Map(root) = root else {
+            return Err(error("expected a mapping at the top level".to_string()));
+        };
+
+        let scalar = |key: &str| lookup(&root, key).map(Value::text).unwrap_or_default();
+        let name = scalar("name");
+        if name.is_empty() {
+            return Err(error("missing 'name'".to_string()));
+        }
+        let Some(Value::List(body)) = lookup(&root, "body") else {
+            return Err(error("missing 'body' list".to_string()));
+        };
+        let fields = body
+            .iter()
+            .enumerate()
+            .map(|(index, element)| FormField::from_value(element).ok_or_else(|| error(format!("body element {} has no 'type'", index + 1))))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            name,
+            description: scalar("description"),
+            title: scalar("title"),
+            labels: lookup(&root, "labels").map(Value::texts).unwrap_or_default(),
+            assignees: lookup(&root, "assignees").map(Value::texts).unwrap_or_default(),
+            fields,
+            file_name,
+        })
+    }
+
+    /// The ids of the fields that can be prefilled, in form order.
+    pub fn field_ids(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().filter_map(|field| field.id.as_deref())
+    }
+
+    /// Write the query string opening this form with `params` prefilled.
+    ///
+    /// Parameters are written in form order, so the URL doesn't depend on
+    /// the order they were inserted in.
+    pub(crate) fn write_query(&self, params: &Params, spaces: SpaceEncoding, output: &mut dyn Output) -> Result<(), String> {
+        if let Some(key) = params
+            .keys()
+            .find(|key| *key != FORM_TITLE_PARAM && !self.field_ids().any(|id| id == *key))
+        {
+            return Err(format!("Issue form '{}' has no field with id '{}'", self.file_name, key));
+        }
+
+        output.write_str("?template=");
+        url_encode::encode_to(&self.file_name, SpaceEncoding::Percent, output);
+        if let Some(title) = params.get(FORM_TITLE_PARAM) {
+            output.write_str("&title=");
+            url_encode::encode_to(title, SpaceEncoding::Plus, output);
+        }
+        for id in self.field_ids() {
+            if let Some(value) = params.get(id) {
+                output.write_str("&");
+                url_encode::encode_to(id, SpaceEncoding::Percent, output);
+                output.write_str("=");
+                url_encode::encode_to(value, spaces, output);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FormField {
+    fn from_value(value: &Value) -> Option<Self> {
+        let Value::Map(entries) = value else {
+            return None;
+        };
+        let kind = lookup(entries, "type")?.text();
+        let nested = |section: &str, key: &str| match lookup(entries, section) {
+            Some(Value::Map(section)) => lookup(section, key).map(Value::text),
+            _ => None,
+        };
+        Some(Self {
+            kind,
+            id: lookup(entries, "id").map(Value::text),
+            label: nested("attributes", "label").unwrap_or_default(),
+            required: nested("validations", "required").is_some_and(|required| required == "true"),
+        })
+    }
+}
+
+/// A parsed YAML node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Scalar(String),
+    List(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// The scalar's text, or an empty string for lists and mappings.
+    fn text(&self) -> String {
+        match self {
+            Value::Scalar(text) => text.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// The scalars of a list, or a single scalar as a list of one.
+    fn texts(&self) -> Vec<String> {
+        match self {
+            Value::Scalar(text) if text.is_empty() => Vec::new(),
+            Value::Scalar(text) => text.split(',').map(|item| item.trim().to_string()).collect(),
+            Value::List(items) => items.iter().map(Value::text).collect(),
+            Value::Map(_) => Vec::new(),
+        }
+    }
+}
+
+fn lookup<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    entries.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+}
+
+/// A line-based parser for the block-style YAML issue forms are written in.
+struct Parser {
+    lines: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(yaml: &str) -> Self {
+        Self {
+            lines: yaml.lines().map(|line| line.trim_end().to_string()).collect(),
+            pos: 0,
+        }
+    }
+
+    fn parse_document(&mut self) -> Result<Value, String> {
+        self.skip_blank();
+        if self.lines.get(self.pos).is_some_and(|line| line == "---") {
+            self.pos += 1;
+        }
+        let value = self.parse_node(0)?;
+        self.skip_blank();
+        match self.lines.get(self.pos) {
+            Some(_) => Err(format!("unexpected indentation on line {}", self.pos + 1)),
+            None => Ok(value),
+        }
+    }
+
+    /// Skip blank and comment lines.
+    fn skip_blank(&mut self) {
+        while self
+            .lines
+            .get(self.pos)
+            .is_some_and(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// The indentation and content of the next meaningful line.
+    fn peek(&mut self) -> Option<(usize, &str)> {
+        self.skip_blank();
+        let line = self.lines.get(self.pos)?;
+        let content = line.trim_start();
+        Some((line.len() - content.len(), content))
+    }
+
+    /// `peek`, for loops that move on to later lines.
+    fn peek_owned(&mut self) -> Option<(usize, String)> {
+        self.peek().map(|(indent, content)| (indent, content.to_string()))
+    }
+
+    /// Parse the node whose lines are indented at least `min_indent`.
+    fn parse_node(&mut self, min_indent: usize) -> Result<Value, String> {
+        match self.peek() {
+            Some((indent, content)) if indent >= min_indent => {
+                if is_item(content) {
+                    self.parse_list(indent)
+                } else {
+                    self.parse_map(indent)
+                }
+            }
+            _ => Ok(Value::Scalar(String::new())),
+        }
+    }
+
+    fn parse_list(&mut self, indent: usize) -> Result<Value, String> {
+        let mut items = Vec::new();
+        while let Some((line_indent, content)) = self.peek_owned() {
+            if line_indent != indent || !is_item(&content) {
+                break;
+            }
+            let rest = content[1..].trim_start();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.parse_node(indent + 1)?);
+            } else if split_key(rest).is_some() {
+                // Read `- key: value` as a mapping starting where `key` is
+                let offset = content.len() - rest.len();
+                let line = &mut self.lines[self.pos];
+                line.replace_range(indent..indent + offset, &" ".repeat(offset));
+                items.push(self.parse_map(indent + offset)?);
+            } else {
+                let item = inline(rest)?;
+                self.pos += 1;
+                items.push(item);
+            }
+        }
+        Ok(Value::List(items))
+    }
+
+    fn parse_map(&mut self, indent: usize) -> Result<Value, String> {
+        let mut entries = Vec::new();
+        while let Some((line_indent, content)) = self.peek_owned() {
+            if line_indent != indent || is_item(&content) {
+                break;
+            }
+            let line_number = self.pos + 1;
+            let (key, rest) = split_key(&content).ok_or_else(|| format!("expected 'key: value' on line {}", line_number))?;
+            let (key, rest) = (unquote(key), rest.to_string());
+            self.pos += 1;
+
+            let value = if rest.is_empty() {
+                // A list may sit at the same indentation as its key
+                match self.peek() {
+                    Some((next, content)) if next == indent && is_item(content) => self.parse_list(indent)?,
+                    _ => self.parse_node(indent + 1)?,
+                }
+            } else if rest.starts_with('|') || rest.starts_with('>') {
+                self.block_scalar(indent, rest.starts_with('>'))
+            } else {
+                inline(&rest)?
+            };
+            entries.push((key, value));
+        }
+        Ok(Value::Map(entries))
+    }
+
+    /// Read the lines of a `|` or `>` block scalar below a key at `indent`.
+    fn block_scalar(&mut self, indent: usize, folded: bool) -> Value {
+        let mut lines = Vec::new();
+        let mut block_indent = None;
+        while let Some(line) = self.lines.get(self.pos) {
+            let content = line.trim_start();
+            let line_indent = line.len() - content.len();
+            if !content.is_empty() && line_indent <= indent {
+                break;
+            }
+            let block_indent = *block_indent.get_or_insert(line_indent);
+            lines.push(line.get(block_indent.min(line_indent)..).unwrap_or("").to_string());
+            self.pos += 1;
+        }
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let separator = if folded { " " } else { "\n" };
+        Value::Scalar(lines.join(separator))
+    }
+}
+
+/// Whether a line starts a list item.
+fn is_item(content: &str) -> bool {
+    content == "-" || content.starts_with("- ")
+}
+
+/// Split `key: value` at the colon, outside quotes.
+fn split_key(content: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (index, c) in content.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') if index == 0 => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, ':') => {
+                let rest = &content[index + 1..];
+                if rest.is_empty() || rest.starts_with(' ') {
+                    return Some((content[..index].trim(), rest.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse a value written on the same line as its key or list marker.
+fn inline(text: &str) -> Result<Value, String> {
+    if let Some(items) = text.strip_prefix('[') {
+        let items = items
+            .strip_suffix(']')
+            .ok_or_else(|| format!("unterminated list '{}'", text))?;
+        if items.trim().is_empty() {
+            return Ok(Value::List(Vec::new()));
+        }
+        return Ok(Value::List(split_items(items).into_iter().map(|item| Value::Scalar(unquote(item))).collect()));
+    }
+    Ok(Value::Scalar(unquote(text)))
+}
+
+/// Split the items of a `[a, "b, c"]` list, outside quotes.
+fn split_items(items: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (index, c) in items.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, ',') => {
+                parts.push(items[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(items[start..].trim());
+    parts
+}
+
+/// The text of a quoted or plain scalar, without a trailing comment.
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    if let Some(inner) = text.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        let mut unescaped = String::new();
+        let mut chars = inner.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                unescaped.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('t') => unescaped.push('\t'),
+                Some(other) => unescaped.push(other),
+                None => {}
+            }
+        }
+        return unescaped;
+    }
+    if let Some(inner) = text.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')) {
+        return inner.replace("''", "'");
+    }
+    match text.find(" #") {
+        Some(comment) => text[..comment].trim_end().to_string(),
+        None => text.to_string(),
+    }
+}
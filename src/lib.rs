@@ -52,6 +52,143 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod url_encode;
+pub mod backoff;
+pub mod report;
+pub mod presentation;
+pub mod testing;
+pub mod batch;
+pub mod consent;
+pub mod lint;
+pub mod meta;
+pub mod format;
+pub mod github;
+pub mod issue_form;
+pub mod param;
+pub mod prerender;
+pub mod teams;
+pub mod reporter;
+pub mod repro;
+pub mod sampling;
+pub mod suppress;
+pub mod summary;
+pub mod suggestion;
+pub mod time;
+pub mod source;
+pub mod static_table;
+pub mod typed;
+pub mod warning;
+pub mod messages;
+pub mod middleware;
+pub mod output;
+pub mod sink;
+pub mod target;
+
+mod base64;
+mod cache;
+mod fill;
+mod glob;
+mod minify;
+mod registry;
+mod siphash;
+mod sync;
+mod wire;
+
+#[cfg(feature = "std")]
+pub mod browser;
+
+#[cfg(feature = "std")]
+pub mod context;
+
+#[cfg(feature = "std")]
+pub mod screenshot;
+
+#[cfg(feature = "std")]
+pub mod scoped;
+
+#[cfg(feature = "std")]
+pub mod panic_hook;
+
+#[cfg(feature = "std")]
+pub mod metadata;
+
+#[cfg(feature = "std")]
+pub mod tui;
+
+#[cfg(feature = "egui")]
+pub mod egui;
+
+#[cfg(feature = "tauri")]
+pub mod tauri;
+
+#[cfg(feature = "web")]
+pub mod web;
+
+#[cfg(feature = "grpc")]
+pub mod grpc;
+
+#[cfg(feature = "include_dir")]
+pub mod template_dir;
+
+#[cfg(feature = "usage-stats")]
+pub mod usage;
+
+#[cfg(feature = "codeowners")]
+pub mod codeowners;
+
+#[cfg(feature = "capi")]
+pub mod ffi;
+
+#[cfg(feature = "api")]
+pub mod api;
+
+#[cfg(feature = "remote")]
+pub mod remote;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(any(feature = "android", feature = "ios"))]
+pub mod device;
+
+#[cfg(any(feature = "api", feature = "remote", feature = "webhook"))]
+mod http;
+
+mod sha256;
+
+#[cfg(feature = "include_dir")]
+pub use include_dir;
+
+pub use report::{BugReport, CommentReport, ParsedReport};
+pub use param::Params;
+pub use output::{FmtOutput, TeeOutput};
+pub use prerender::PrerenderedReport;
+pub use presentation::ReportPresentation;
+pub use registry::{NameMatching, OnMissingTemplate, TemplateId};
+pub use source::TemplateSource;
+pub use batch::ReportBatch;
+pub use teams::TeamAssignment;
+pub use target::TemplateTarget;
+pub use reporter::{Reporter, NoopReporter, Location, ReportOutcome};
+pub use sampling::SampleRng;
+pub use consent::{has_consent, set_consent};
+
+#[cfg(feature = "usage-stats")]
+pub use usage::{usage_report, reset_usage, UsageReport};
+
+#[cfg(feature = "std")]
+pub use output::{IoOutput, StderrOrFile};
+
+#[cfg(feature = "std")]
+pub use panic_hook::install_panic_hook;
+
+#[cfg(feature = "std")]
+pub use metadata::from_metadata;
+
+#[cfg(all(feature = "usage-stats", feature = "std"))]
+pub use usage::unreferenced_templates;
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -66,9 +203,16 @@ use alloc::{
     format,
 };
 
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
 use hashbrown::HashMap;
 use rustc_hash::FxHasher;
-use core::hash::BuildHasherDefault;
+use warning::{Warning, Warnings};
+use core::hash::{BuildHasherDefault, Hasher};
 
 /// A fast HashMap implementation using FxHasher.
 ///
@@ -86,14 +230,33 @@ use core::hash::BuildHasherDefault;
 /// ```
 pub type FxHashMap<K, V> = HashMap<K, V, BuildHasherDefault<FxHasher>>;
 
+
 #[cfg(feature = "std")]
 use once_cell::sync::OnceCell;
 
 #[cfg(feature = "std")]
-static CONFIG: OnceCell<BugReportConfig> = OnceCell::new();
+static CONFIG: OnceCell<Arc<BugReportConfig>> = OnceCell::new();
 
-#[cfg(not(feature = "std"))]
-static mut CONFIG: Option<BugReportConfig> = None;
+#[cfg(all(not(feature = "std"), feature = "global-nostd", not(loom)))]
+static CONFIG: sync::OnceSlot<BugReportConfig> = sync::OnceSlot::new();
+
+// Loom's atomics can't be created in a `static`
+#[cfg(all(not(feature = "std"), feature = "global-nostd", loom))]
+loom::lazy_static! {
+    static ref CONFIG: sync::OnceSlot<BugReportConfig> = sync::OnceSlot::new();
+}
+
+/// The global configuration, if it has been installed.
+#[cfg(feature = "std")]
+fn global_config() -> Option<&'static BugReportConfig> {
+    CONFIG.get().map(|config| &**config)
+}
+
+/// The global configuration, if it has been installed.
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+fn global_config() -> Option<&'static BugReportConfig> {
+    CONFIG.get()
+}
 
 /// Trait for outputting bug report information in no_std environments.
 ///
@@ -120,6 +283,51 @@ static mut CONFIG: Option<BugReportConfig> = None;
 ///     }
 /// }
 /// ```
+/// 
+/// Write errors can't be returned, so an implementation should drop them
+/// rather than panic. Reporting carries on whatever the output does:
+/// 
+/// ```
+/// use bug::{init_handle, IssueTemplate, Location, Output, Params};
+/// use core::fmt;
+/// 
+/// // Holds 16 bytes and fails every write after that
+/// struct Full {
+///     buf: [u8; 16],
+///     len: usize,
+/// }
+/// 
+/// impl fmt::Write for Full {
+///     fn write_str(&mut self, s: &str) -> fmt::Result {
+///         let end = self.len + s.len();
+///         if end > self.buf.len() {
+///             return Err(fmt::Error);
+///         }
+///         self.buf[self.len..end].copy_from_slice(s.as_bytes());
+///         self.len = end;
+///         Ok(())
+///     }
+/// }
+/// 
+/// impl Output for Full {
+///     fn write_str(&mut self, s: &str) {
+///         let _ = fmt::Write::write_str(self, s);
+///     }
+///     fn write_fmt(&mut self, args: fmt::Arguments) {
+///         let _ = fmt::Write::write_fmt(self, args);
+///     }
+/// }
+/// 
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash in {module}", "{details}"));
+/// let mut params = Params::new();
+/// params.insert("module", "parser");
+/// params.insert("details", "a long description that does not fit");
+/// 
+/// let mut output = Full { buf: [0; 16], len: 0 };
+/// let url = handle.report_bug_with_output_at("crash", &params, Location::new(file!(), line!()), &mut output);
+/// assert!(url.starts_with("https://github.com/owner/repo/issues/new?title=Crash+in+parser"));
+/// ```
 pub trait Output {
     /// Write a string to the output destination.
     ///
@@ -136,14 +344,18 @@ pub trait Output {
     fn write_fmt(&mut self, args: core::fmt::Arguments);
 }
 
+/// Writes to stderr, ignoring write errors.
+/// 
+/// Unlike `eprint!`, a closed or full stderr doesn't panic: reporting a bug
+/// must never cause one.
 #[cfg(feature = "std")]
 impl Output for std::io::Stderr {
     fn write_str(&mut self, s: &str) {
-        eprint!("{}", s);
+        let _ = std::io::Write::write_all(self, s.as_bytes());
     }
     
     fn write_fmt(&mut self, args: core::fmt::Arguments) {
-        eprint!("{}", args);
+        let _ = std::io::Write::write_fmt(self, args);
     }
 }
 
@@ -181,18 +393,13 @@ impl Output for NoOutput {
 /// # Examples
 /// 
 /// ```
-/// use bug::{BugReportConfig, HyperlinkMode, FxHashMap};
+/// use bug::{BugReportConfig, HyperlinkMode};
 /// 
-/// let config = BugReportConfig {
-///     github_owner: "octocat".to_string(),
-///     github_repo: "Hello-World".to_string(),
-///     templates: FxHashMap::default(),
-///     template_files: FxHashMap::default(),
-///     use_hyperlinks: HyperlinkMode::Auto,
-/// };
+/// let config = BugReportConfig::new("octocat", "Hello-World");
 /// 
 /// assert_eq!(config.github_owner, "octocat");
 /// assert_eq!(config.github_repo, "Hello-World");
+/// assert!(matches!(config.use_hyperlinks, HyperlinkMode::Auto));
 /// ```
 #[derive(Debug, Clone)]
 pub struct BugReportConfig {
@@ -200,884 +407,5309 @@ pub struct BugReportConfig {
     pub github_owner: String,
     /// The GitHub repository name
     pub github_repo: String,
-    /// Map of template names to issue templates
-    pub templates: FxHashMap<String, IssueTemplate>,
-    /// Map of template file names to template files
-    pub template_files: FxHashMap<String, TemplateFile>,
+    /// Scheme and host issue URLs start with, such as a GitHub Enterprise server, or `None` for `github::HOST`
+    pub base_url: Option<String>,
+    /// Prefix the templates get when another handle merges this one, usually the crate name
+    pub namespace: Option<String>,
     /// How to handle hyperlinks in terminal output
     pub use_hyperlinks: HyperlinkMode,
+    /// Map of error codes to the names of the templates they report with
+    pub error_codes: FxHashMap<String, String>,
+    /// Labels reports may carry, or `None` to allow any label
+    pub allowed_labels: Option<Vec<String>>,
+    /// Prefixes of dynamic labels allowed in addition to `allowed_labels`
+    pub allowed_label_prefixes: Vec<String>,
+    /// Whether to warn on stderr when a disallowed label is dropped (std only)
+    pub warn_on_disallowed_labels: bool,
+    /// Map of team names to the GitHub users in each team
+    pub teams: FxHashMap<String, Vec<String>>,
+    /// How teams are expanded into assignees
+    pub team_assignment: TeamAssignment,
+    /// Owners that reports with a location are assigned to
+    #[cfg(feature = "codeowners")]
+    pub codeowners: Option<codeowners::CodeOwners>,
+    /// Fraction of occurrences reported, per template name
+    pub sample_rates: FxHashMap<String, f64>,
+    /// Where reports are filed instead of the handle's repository, per template name
+    pub template_targets: FxHashMap<String, target::TemplateTarget>,
+    /// RNG that sample rates roll against
+    sampler: sampling::Sampler,
+    /// Conditions under which reports are never emitted
+    suppressions: suppress::Suppressions,
+    /// Steps every reported bug passes through, in order
+    middleware: middleware::Pipeline,
+    /// Where reported bugs are written besides the banner
+    sinks: sink::Sinks,
+    /// Clock read for `{timestamp}`
+    time: time::TimeSource,
+    /// Whether `{source_excerpt}` is filled from the source file (std debug builds only)
+    pub include_source_excerpt: bool,
+    /// Whether reported bugs are collected for `flush_summary` instead of printed
+    pub summarize: bool,
+    /// Whether the banner lists the warnings found while generating a report
+    pub show_warnings: bool,
+    /// Whether the banner prints the configuration's `digest`
+    pub show_config_digest: bool,
+    /// Whether a template left with unfilled placeholders fails instead of warning
+    pub deny_unfilled_placeholders: bool,
+    /// Whether inline templates are validated before filling, as template files are
+    pub strict_params: bool,
+    /// Whether issue bodies are minified before they are encoded into the URL
+    pub minify_body: bool,
+    /// How spaces in the issue body are encoded into the URL
+    pub body_space_encoding: url_encode::SpaceEncoding,
+    /// How labels are written into the URL
+    pub label_encoding: url_encode::LabelEncoding,
+    /// What reporting a template name that matches no template does
+    pub on_missing_template: OnMissingTemplate,
+    /// The text written into issue bodies by the crate rather than by templates
+    pub messages: messages::Messages,
+    /// Whether report details are withheld until `set_consent(true)`
+    pub require_consent: bool,
+    /// How parameters are substituted, per placeholder
+    param_policies: param::ParamPolicies,
+    /// How often repeated occurrences of the same report are printed, or `None` to print all
+    pub backoff: Option<backoff::BackoffPolicy>,
+    /// Occurrences counted for `backoff`
+    occurrences: backoff::Occurrences,
+    /// The least time between two full banners, or `None` for no limit
+    pub quiet_period: Option<core::time::Duration>,
+    /// When the last full banner was printed, for `quiet_period`
+    quiet: backoff::QuietState,
+    /// GitHub API searched for existing issues before a new one is suggested
+    #[cfg(feature = "api")]
+    github_api: Option<api::GitHubApi>,
+    /// Where `add_remote_template` downloads templates from
+    #[cfg(feature = "remote")]
+    remote_source: Option<remote::RemoteSource>,
+    /// Where `{user_agent}`, `{platform}`, and `{viewport}` are read from
+    #[cfg(feature = "wasm")]
+    browser: Option<wasm::BrowserSource>,
+    /// Device filled into `{device}` and friends instead of the one read
+    #[cfg(any(feature = "android", feature = "ios"))]
+    device: Option<device::DeviceContext>,
+    /// Where `{os}`, `{arch}`, and other environment placeholders are filled from
+    #[cfg(feature = "std")]
+    auto_context: Option<context::AutoContext>,
+    /// Where the screenshot the `{screenshot}` section points at is saved
+    #[cfg(feature = "std")]
+    screenshot: Option<screenshot::ScreenshotSource>,
+    /// Round-robin position of each team
+    rotation: teams::Rotation,
+    /// Interned index of all registered templates
+    registry: registry::TemplateRegistry,
 }
 
-/// Controls how hyperlinks are displayed in terminal output.
-///
-/// Modern terminals support clickable hyperlinks using ANSI escape sequences.
-/// This enum allows you to control when to use them.
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::HyperlinkMode;
-/// 
-/// // Automatically detect terminal support
-/// let auto_mode = HyperlinkMode::Auto;
-/// 
-/// // Always show hyperlinks (good for known compatible terminals)
-/// let always_mode = HyperlinkMode::Always;
-/// 
-/// // Never show hyperlinks (good for logs or unknown terminals)
-/// let never_mode = HyperlinkMode::Never;
-/// ```
-#[derive(Debug, Clone)]
-pub enum HyperlinkMode {
-    /// Automatically detect terminal hyperlink support based on environment variables
-    Auto,
-    /// Always use hyperlinks regardless of terminal detection
-    Always,
-    /// Never use hyperlinks, always show full URLs
-    Never,
-}
+/// Error returned by `build` when a template label is not in the allowed labels.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const DISALLOWED_LABEL_ERROR: &str = "A template uses a label that is not in the allowed labels";
 
-/// A GitHub issue template with title, body, and labels.
-///
-/// Issue templates define the structure of bug reports that will be submitted to GitHub.
-/// They support placeholder substitution using `{placeholder}` syntax.
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::IssueTemplate;
-/// 
-/// let template = IssueTemplate::new(
-///     "Bug: {component} not working",
-///     "## Problem\n{description}\n\n## Expected Behavior\n{expected}\n\n## Actual Behavior\n{actual}"
-/// ).with_labels(vec!["bug".to_string(), "needs-investigation".to_string()]);
-/// 
-/// assert_eq!(template.title, "Bug: {component} not working");
-/// assert_eq!(template.labels.len(), 2);
-/// ```
-#[derive(Debug, Clone)]
-pub struct IssueTemplate {
-    /// The title template for the GitHub issue
-    pub title: String,
-    /// The body template for the GitHub issue
-    pub body: String,
-    /// Labels to apply to the GitHub issue
-    pub labels: Vec<String>,
+/// Error returned by `build` when a template assigns a team that isn't configured.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const UNKNOWN_TEAM_ERROR: &str = "A template assigns a team that is not configured";
+
+/// Error returned by `build` when a template has more assignees than GitHub allows.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const TOO_MANY_ASSIGNEES_ERROR: &str = "A template has more assignees than GitHub allows";
+
+/// Error returned by `build` when a label contains a comma under `LabelEncoding::Joined`.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const COMMA_LABEL_ERROR: &str = "A template label contains a comma, which joined labels would split";
+
+/// The assignees a template names directly.
+fn slot_assignees(slot: &registry::Slot) -> &[String] {
+    match &slot.source {
+        registry::SlotSource::Inline(template) => &template.assignees,
+        registry::SlotSource::File(template_file) => &template_file.assignees,
+    }
 }
 
-/// A template loaded from a static string (typically from `include_str!`).
-///
-/// Template files allow you to store issue templates in separate files and embed
-/// them at compile time. The first line becomes the title, and the rest becomes the body.
-/// 
-/// # File Format
-/// 
-/// ```text
-/// Issue Title Here
-/// Issue body content starts here.
-/// It can span multiple lines.
-/// 
-/// Placeholders like {param} are supported.
-/// ```
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::TemplateFile;
-/// 
-/// let template_file = TemplateFile::new("Bug Report\nFound a bug: {description}")
-///     .with_labels(vec!["bug".to_string()]);
-/// 
-/// let parsed = template_file.parse().unwrap();
-/// assert_eq!(parsed.title, "Bug Report");
-/// assert_eq!(parsed.body, "Found a bug: {description}");
-/// ```
-#[derive(Debug, Clone)]
-pub struct TemplateFile {
-    /// The raw template content (first line is title, rest is body)
-    pub content: &'static str,
-    /// Labels to apply to issues created from this template
-    pub labels: Vec<String>,
+/// The teams a template assigns.
+fn slot_teams(slot: &registry::Slot) -> &[String] {
+    match &slot.source {
+        registry::SlotSource::Inline(template) => &template.teams,
+        registry::SlotSource::File(template_file) => &template_file.teams,
+    }
 }
 
-impl TemplateFile {
-    /// Create a new template file with the given content.
+/// Error returned by `build` when a template file no longer has its pinned digest.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const PINNED_DIGEST_ERROR: &str = "A template file does not match its pinned SHA-256 digest";
+
+/// Error returned by `build` when the base URL or a template target's host is malformed.
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+const BASE_URL_ERROR: &str = "The base URL is not a well-formed http or https URL";
+
+/// Placeholder filled with the error code by `bug_code!` and the `*_for_code` methods.
+pub const ERROR_CODE_PARAM: &str = "error_code";
+
+/// Placeholder filled with the parent report's URL by `create_follow_up`.
+pub const PARENT_URL_PARAM: &str = "parent_url";
+
+/// Placeholder filled with `file:line:column` of the reporting call site.
+pub const SOURCE_LOCATION_PARAM: &str = "source_location";
+
+/// Placeholder filled with the module path of the reporting call site, when known.
+pub const MODULE_PARAM: &str = "module";
+
+/// Placeholder filled with the template's sample rate, for sampled templates.
+pub const SAMPLE_RATE_PARAM: &str = "sample_rate";
+
+/// Placeholder filled with the configured clock's time, written as `T+12345.678s`.
+pub const TIMESTAMP_PARAM: &str = "timestamp";
+
+/// Placeholder filled with the source lines around the reporting call site.
+pub const SOURCE_EXCERPT_PARAM: &str = "source_excerpt";
+
+/// Placeholder filled with a marker identifying the template and parameters, see `fingerprint`.
+pub const FINGERPRINT_PARAM: &str = "fingerprint";
+
+/// Placeholder filled with the configuration's digest in hex, see `digest`.
+pub const CONFIG_DIGEST_PARAM: &str = "config_digest";
+
+/// Start of every fingerprint marker.
+const FINGERPRINT_PREFIX: &str = "bug-fingerprint-";
+
+/// Lines of source shown before and after the call site in `{source_excerpt}`.
+#[cfg(feature = "std")]
+const SOURCE_EXCERPT_CONTEXT: u32 = 3;
+
+impl BugReportConfig {
+    /// Create an empty configuration for a GitHub repository.
     /// 
     /// # Arguments
     /// 
-    /// * `content` - The template content where first line is the title
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use bug::TemplateFile;
-    /// 
-    /// let template = TemplateFile::new("Bug Title\nBug description with {param}");
-    /// assert_eq!(template.content, "Bug Title\nBug description with {param}");
-    /// assert!(template.labels.is_empty());
-    /// ```
-    pub fn new(content: &'static str) -> Self {
+    /// * `github_owner` - GitHub username or organization name
+    /// * `github_repo` - GitHub repository name
+    pub fn new(github_owner: impl Into<String>, github_repo: impl Into<String>) -> Self {
         Self {
-            content,
-            labels: Vec::new(),
+            github_owner: github_owner.into(),
+            github_repo: github_repo.into(),
+            base_url: None,
+            namespace: None,
+            use_hyperlinks: HyperlinkMode::Auto,
+            error_codes: FxHashMap::default(),
+            allowed_labels: None,
+            allowed_label_prefixes: Vec::new(),
+            warn_on_disallowed_labels: false,
+            teams: FxHashMap::default(),
+            team_assignment: TeamAssignment::All,
+            #[cfg(feature = "codeowners")]
+            codeowners: None,
+            sample_rates: FxHashMap::default(),
+            template_targets: FxHashMap::default(),
+            sampler: sampling::Sampler::default(),
+            suppressions: suppress::Suppressions::default(),
+            middleware: middleware::Pipeline::default(),
+            sinks: sink::Sinks::default(),
+            time: time::TimeSource::default(),
+            include_source_excerpt: false,
+            summarize: false,
+            show_warnings: false,
+            show_config_digest: false,
+            deny_unfilled_placeholders: false,
+            strict_params: false,
+            minify_body: false,
+            body_space_encoding: url_encode::SpaceEncoding::Percent,
+            label_encoding: url_encode::LabelEncoding::Joined,
+            on_missing_template: OnMissingTemplate::Error,
+            messages: messages::Messages::default(),
+            require_consent: false,
+            param_policies: param::ParamPolicies::default(),
+            backoff: None,
+            occurrences: backoff::Occurrences::new(),
+            quiet_period: None,
+            quiet: backoff::QuietState::new(),
+            #[cfg(feature = "api")]
+            github_api: None,
+            #[cfg(feature = "remote")]
+            remote_source: None,
+            #[cfg(feature = "wasm")]
+            browser: None,
+            #[cfg(any(feature = "android", feature = "ios"))]
+            device: None,
+            #[cfg(feature = "std")]
+            auto_context: None,
+            #[cfg(feature = "std")]
+            screenshot: None,
+            rotation: teams::Rotation::new(),
+            registry: registry::TemplateRegistry::default(),
         }
     }
 
-    /// Add labels to this template file.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `labels` - Vector of label strings to apply to issues
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use bug::TemplateFile;
-    /// 
-    /// let template = TemplateFile::new("Title\nBody")
-    ///     .with_labels(vec!["bug".to_string(), "urgent".to_string()]);
-    /// assert_eq!(template.labels.len(), 2);
-    /// ```
-    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
-        self.labels = labels;
-        self
+    /// Register copies of `other`'s templates, named `namespace/name` if it has a namespace.
+    /// 
+    /// Fallbacks to `other`'s own templates are renamed along with them. A
+    /// template replacing one already registered under the same name is
+    /// warned about on stderr (std only).
+    pub(crate) fn merge_templates(&mut self, other: &BugReportConfig) {
+        let merged_name = |name: &str| match &other.namespace {
+            Some(namespace) => format!("{}/{}", namespace, name),
+            None => String::from(name),
+        };
+        for slot in other.registry.slots() {
+            let name = merged_name(&slot.name);
+            #[cfg(feature = "std")]
+            if self.registry.contains(&name) {
+                Output::write_fmt(
+                    &mut std::io::stderr(),
+                    format_args!("warning: merged template '{}' replaces the template registered under that name\n", name),
+                );
+            }
+            let fallback = slot
+                .fallback()
+                .map(|fallback| match other.registry.get_by_name(fallback) {
+                    Some(target) => merged_name(&target.name),
+                    None => String::from(fallback),
+                });
+            match &slot.source {
+                registry::SlotSource::Inline(template) => {
+                    let mut template = template.clone();
+                    template.fallback = fallback;
+                    self.register_template(name, template);
+                }
+                registry::SlotSource::File(template_file) => {
+                    let mut template_file = template_file.clone();
+                    template_file.fallback = fallback;
+                    self.register_template_file(name, template_file);
+                }
+            }
+        }
     }
 
-    /// Parse the template file content into an IssueTemplate.
+    /// Register an issue template, replacing any template with the same name.
+    pub(crate) fn register_template(&mut self, name: String, template: IssueTemplate) -> TemplateId {
+        self.registry.register(name, registry::SlotSource::Inline(template))
+    }
+
+    /// Register a template file, replacing any template with the same name.
+    pub(crate) fn register_template_file(&mut self, name: String, template_file: TemplateFile) -> TemplateId {
+        self.registry.register(name, registry::SlotSource::File(template_file))
+    }
+
+    /// Replace the template named `name` with one downloaded from the remote source.
+    /// 
+    /// The downloaded title and body take the place of the registered
+    /// template's, which keeps its labels, assignees, teams, and fallback.
+    /// Nothing changes if the download or parsing fails.
+    #[cfg(feature = "remote")]
+    pub(crate) fn add_remote_template(&mut self, name: String, template: &remote::RemoteTemplate) -> Result<TemplateId, String> {
+        let source = self
+            .remote_source
+            .as_ref()
+            .ok_or_else(|| "No remote source is set; call remote_source first".to_string())?;
+        let content = source.fetch(template)?;
+        let (title, body) = TemplateFile::split(&content).map_err(|e| format!("{}: {}", template.url(), e))?;
+        let mut fetched = match self.registry.get_exact(&name).map(|slot| &slot.source) {
+            Some(registry::SlotSource::Inline(existing)) => existing.clone(),
+            Some(registry::SlotSource::File(existing)) => IssueTemplate {
+                title: String::new(),
+                body: String::new(),
+                labels: existing.labels.clone(),
+                assignees: existing.assignees.clone(),
+                teams: existing.teams.clone(),
+                milestone: existing.milestone.clone(),
+                projects: existing.projects.clone(),
+                fallback: existing.fallback.clone(),
+                description: existing.description.clone(),
+                param_docs: existing.param_docs.clone(),
+            },
+            None => IssueTemplate::new("", ""),
+        };
+        fetched.title = title;
+        fetched.body = body;
+        Ok(self.register_template(name, fetched))
+    }
+
+    /// Check every template's pinned digest, teams, assignees, and labels.
     /// 
-    /// The first line of the content becomes the title, and the remaining
-    /// lines become the body. Empty templates are rejected.
+    /// Template files pinned with `TemplateFile::with_sha256` must still
+    /// have that digest. Teams must be registered, and no template may have more than
+    /// `MAX_ASSIGNEES` assignees once its teams are expanded. Labels must be
+    /// in the allowed labels, if configured, and can't contain a comma
+    /// unless `LabelEncoding::Repeated` is used. Labels containing placeholders
+    /// are only known once filled, so they are skipped here and checked when
+    /// a report is generated, where disallowed labels are dropped from the
+    /// issue.
     /// 
     /// # Returns
     /// 
-    /// * `Ok(IssueTemplate)` - Successfully parsed template
-    /// * `Err(String)` - Error message if parsing fails
+    /// * `Ok(())` - Every template passes
+    /// * `Err(String)` - The first problem found
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::TemplateFile;
-    /// 
-    /// let template_file = TemplateFile::new("Bug Report\nSomething is broken: {issue}");
-    /// let parsed = template_file.parse().unwrap();
-    /// assert_eq!(parsed.title, "Bug Report");
-    /// assert_eq!(parsed.body, "Something is broken: {issue}");
+    /// use bug::{init_handle, IssueTemplate};
     /// 
-    /// // Empty templates fail
-    /// let empty_template = TemplateFile::new("");
-    /// assert!(empty_template.parse().is_err());
+    /// let handle = init_handle("owner", "repo")
+    ///     .allowed_labels(["bug", "crash", "perf"])
+    ///     .allow_label_prefix("area:")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "")
+    ///         .with_labels(vec!["crash".to_string(), "area:{component}".to_string()]))
+    ///     .add_template("slow", IssueTemplate::new("Slow", "")
+    ///         .with_labels(vec!["preformance".to_string()]));
+    /// 
+    /// assert_eq!(
+    ///     handle.config().validate().unwrap_err(),
+    ///     "Template 'slow' uses label 'preformance', which is not in the allowed labels"
+    /// );
     /// ```
-    pub fn parse(&self) -> Result<IssueTemplate, String> {
-        let lines: Vec<&str> = self.content.lines().collect();
-        
-        if lines.is_empty() {
-            return Err("Template file is empty".to_string());
-        }
+    pub fn validate(&self) -> Result<(), String> {
+        self.check_pins()?;
+        self.check_base_urls()?;
+        self.check_teams()?;
+        self.check_assignee_counts()?;
+        self.check_label_encoding()?;
+        self.check_allowed_labels()
+    }
 
-        let title = lines[0].trim();
-        if title.is_empty() {
-            return Err("Template must have a title on the first line".to_string());
+    /// Check that every team a template assigns is configured.
+    fn check_teams(&self) -> Result<(), String> {
+        for slot in self.registry.slots() {
+            for team_name in slot_teams(slot) {
+                if !self.teams.contains_key(team_name) {
+                    return Err(format!("Template '{}' assigns unknown team '{}'", slot.name, team_name));
+                }
+            }
         }
+        Ok(())
+    }
 
-        let body = if lines.len() > 1 {
-            lines[1..].join("\n").trim().to_string()
-        } else {
-            String::new()
-        };
+    /// Check that no template assigns more people than GitHub allows.
+    fn check_assignee_counts(&self) -> Result<(), String> {
+        for slot in self.registry.slots() {
+            let mut assignee_count = slot_assignees(slot).len();
+            for team_name in slot_teams(slot) {
+                assignee_count += match self.team_assignment {
+                    TeamAssignment::All => self.teams.get(team_name).map_or(0, |members| members.len()),
+                    TeamAssignment::RoundRobin => 1,
+                };
+            }
+            if assignee_count > teams::MAX_ASSIGNEES {
+                return Err(format!(
+                    "Template '{}' has {} assignees; GitHub allows at most {}",
+                    slot.name,
+                    assignee_count,
+                    teams::MAX_ASSIGNEES
+                ));
+            }
+        }
+        Ok(())
+    }
 
-        Ok(IssueTemplate {
-            title: title.to_string(),
-            body,
-            labels: self.labels.clone(),
-        })
+    /// Check that no label would be split by `LabelEncoding::Joined`.
+    fn check_label_encoding(&self) -> Result<(), String> {
+        if self.label_encoding != url_encode::LabelEncoding::Joined {
+            return Ok(());
+        }
+        for slot in self.registry.slots() {
+            for label in &slot.labels {
+                if extract_placeholders(label).is_empty() && label.contains(',') {
+                    return Err(format!(
+                        "Template '{}' uses label '{}', which GitHub would split at the comma",
+                        slot.name, label
+                    ));
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// Validate that all required parameters are provided and no extra parameters exist.
-    /// 
-    /// This method extracts all placeholders from the template content and ensures
-    /// that the provided parameters match exactly.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `params` - Map of parameter names to values
-    /// 
-    /// # Returns
-    /// 
-    /// * `Ok(())` - All parameters are valid
-    /// * `Err(String)` - Error describing missing or unused parameters
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use bug::{TemplateFile, FxHashMap};
-    /// 
-    /// let template = TemplateFile::new("Bug: {component}\nError: {message}");
-    /// let mut params = FxHashMap::default();
-    /// params.insert("component".to_string(), "UI".to_string());
-    /// params.insert("message".to_string(), "Button broken".to_string());
-    /// 
-    /// assert!(template.validate_params(&params).is_ok());
-    /// 
-    /// // Missing parameter
-    /// let mut incomplete_params = FxHashMap::default();
-    /// incomplete_params.insert("component".to_string(), "UI".to_string());
-    /// assert!(template.validate_params(&incomplete_params).is_err());
-    /// ```
-    pub fn validate_params(&self, params: &FxHashMap<String, String>) -> Result<(), String> {
-        let placeholders = extract_placeholders(self.content);
-        
-        for placeholder in &placeholders {
-            if !params.contains_key(placeholder) {
-                return Err(format!("Missing required parameter: {}", placeholder));
+    /// Check that every fixed label is in the allowed labels.
+    fn check_allowed_labels(&self) -> Result<(), String> {
+        for slot in self.registry.slots() {
+            for label in &slot.labels {
+                if extract_placeholders(label).is_empty() && !self.label_allowed(label) {
+                    return Err(format!(
+                        "Template '{}' uses label '{}', which is not in the allowed labels",
+                        slot.name, label
+                    ));
+                }
             }
         }
+        Ok(())
+    }
 
-        for param_key in params.keys() {
-            if !placeholders.contains(param_key) {
-                return Err(format!("Unused parameter: {}", param_key));
+    /// Check the base URL and the hosts of template targets.
+    fn check_base_urls(&self) -> Result<(), String> {
+        if let Some(base_url) = &self.base_url {
+            target::check_base_url(base_url)?;
+        }
+        for (template_name, template_target) in &self.template_targets {
+            if let Some(host) = &template_target.host {
+                target::check_base_url(host).map_err(|e| format!("Template '{}' target: {}", template_name, e))?;
             }
         }
+        Ok(())
+    }
 
+    /// Check every pinned template file against its digest.
+    fn check_pins(&self) -> Result<(), String> {
+        for slot in self.registry.slots() {
+            if let registry::SlotSource::File(template_file) = &slot.source {
+                template_file
+                    .verify_sha256()
+                    .map_err(|e| format!("Template '{}' changed: {}", slot.name, e))?;
+            }
+        }
         Ok(())
     }
-}
 
-impl IssueTemplate {
-    /// Create a new issue template with title and body.
+    /// Whether a report may carry `label`.
     /// 
     /// # Arguments
     /// 
-    /// * `title` - The issue title template (supports placeholders)
-    /// * `body` - The issue body template (supports placeholders)
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use bug::IssueTemplate;
-    /// 
-    /// let template = IssueTemplate::new("Bug in {module}", "Error: {error_msg}");
-    /// assert_eq!(template.title, "Bug in {module}");
-    /// assert_eq!(template.body, "Error: {error_msg}");
-    /// assert!(template.labels.is_empty());
-    /// ```
-    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
-        Self {
-            title: title.into(),
-            body: body.into(),
-            labels: Vec::new(),
+    /// * `label` - A filled label
+    pub fn label_allowed(&self, label: &str) -> bool {
+        match &self.allowed_labels {
+            None => true,
+            Some(allowed) => {
+                allowed.iter().any(|allowed| allowed == label)
+                    || self.allowed_label_prefixes.iter().any(|prefix| label.starts_with(prefix.as_str()))
+            }
         }
     }
 
-    /// Create an issue template from a template file with parameter substitution.
+    /// Look up the id of a registered template.
     /// 
     /// # Arguments
     /// 
-    /// * `template_file` - The template file to parse
-    /// * `params` - Parameters to substitute in the template
+    /// * `template_name` - Name of the template
     /// 
     /// # Returns
     /// 
-    /// * `Ok(IssueTemplate)` - Successfully created template with parameters filled
-    /// * `Err(String)` - Error from validation or parsing
+    /// The template's id, or `None` if no template has that name.
+    pub fn template_id(&self, template_name: &str) -> Option<TemplateId> {
+        self.registry.id(template_name)
+    }
+
+    /// Names of all registered templates, in registration order.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{TemplateFile, IssueTemplate, FxHashMap};
+    /// use bug::{init_handle, IssueTemplate, TemplateFile};
     /// 
-    /// let template_file = TemplateFile::new("Bug: {type}\nDescription: {desc}");
-    /// let mut params = FxHashMap::default();
-    /// params.insert("type".to_string(), "Crash".to_string());
-    /// params.insert("desc".to_string(), "App crashes on startup".to_string());
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""))
+    ///     .add_template_file("perf", TemplateFile::new("Slow"));
     /// 
-    /// let issue = IssueTemplate::from_template_file(&template_file, &params).unwrap();
-    /// assert_eq!(issue.title, "Bug: Crash");
-    /// assert_eq!(issue.body, "Description: App crashes on startup");
+    /// let names: Vec<&str> = handle.config().template_names().collect();
+    /// assert_eq!(names, vec!["crash", "perf"]);
     /// ```
-    pub fn from_template_file(template_file: &TemplateFile, params: &FxHashMap<String, String>) -> Result<Self, String> {
-        template_file.validate_params(params)?;
-        let parsed_template = template_file.parse()?;
-        Ok(parsed_template.fill_params(params))
+    pub fn template_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.registry.slots().iter().map(|slot| &*slot.name)
     }
 
-    /// Add labels to this issue template.
+    /// The inline template registered as `template_name`.
+    /// 
+    /// Templates are stored once, in the configuration's registry, and can
+    /// only be changed through the handle or builder methods that add them.
     /// 
     /// # Arguments
     /// 
-    /// * `labels` - Vector of label strings to apply to the GitHub issue
+    /// * `template_name` - Exact name the template was registered under
+    /// 
+    /// # Returns
+    /// 
+    /// The template, or `None` if no inline template has that name.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::IssueTemplate;
+    /// use bug::{init_handle, IssueTemplate, TemplateFile};
     /// 
-    /// let template = IssueTemplate::new("Bug Title", "Bug description")
-    ///     .with_labels(vec!["bug".to_string(), "high-priority".to_string()]);
-    /// assert_eq!(template.labels.len(), 2);
-    /// assert!(template.labels.contains(&"bug".to_string()));
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "It crashed"))
+    ///     .add_template_file("perf", TemplateFile::new("Slow"));
+    /// 
+    /// assert_eq!(handle.config().template("crash").unwrap().body, "It crashed");
+    /// assert!(handle.config().template("perf").is_none());
+    /// assert!(handle.config().template_file("perf").is_some());
     /// ```
-    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
-        self.labels = labels;
-        self
+    pub fn template(&self, template_name: &str) -> Option<&IssueTemplate> {
+        match &self.registry.get_exact(template_name)?.source {
+            registry::SlotSource::Inline(template) => Some(template),
+            registry::SlotSource::File(_) => None,
+        }
     }
 
-    /// Fill template placeholders with provided parameters.
-    /// 
-    /// This method replaces all `{placeholder}` patterns in the title and body
-    /// with the corresponding values from the params map.
+    /// The template file registered as `template_name`.
     /// 
     /// # Arguments
     /// 
-    /// * `params` - Map of parameter names to replacement values
+    /// * `template_name` - Exact name the template file was registered under
     /// 
     /// # Returns
     /// 
-    /// A new `IssueTemplate` with placeholders replaced by parameter values.
-    /// 
+    /// The template file, or `None` if no template file has that name.
+    pub fn template_file(&self, template_name: &str) -> Option<&TemplateFile> {
+        match &self.registry.get_exact(template_name)?.source {
+            registry::SlotSource::File(template_file) => Some(template_file),
+            registry::SlotSource::Inline(_) => None,
+        }
+    }
+
+    /// List the placeholders used by a registered template.
+    ///
+    /// Both inline templates and template files are searched. Placeholders
+    /// are returned in order of first appearance: title, then body, then labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template to inspect
+    ///
+    /// # Returns
+    ///
+    /// The template's placeholder names, or `None` if no template has that name.
+    ///
     /// # Examples
-    /// 
+    ///
     /// ```
-    /// use bug::{IssueTemplate, FxHashMap};
-    /// 
-    /// let template = IssueTemplate::new("Error in {component}", "Details: {message}");
-    /// let mut params = FxHashMap::default();
-    /// params.insert("component".to_string(), "parser".to_string());
-    /// params.insert("message".to_string(), "Invalid syntax".to_string());
+    /// use bug::{init_handle, IssueTemplate};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {module}", "{module}: {message}"));
+    ///
+    /// let placeholders = handle.config().placeholders("crash").unwrap();
+    /// assert_eq!(placeholders, vec!["module".to_string(), "message".to_string()]);
+    /// assert!(handle.config().placeholders("missing").is_none());
+    /// ```
+    pub fn placeholders(&self, template_name: &str) -> Option<Vec<String>> {
+        let slot = self.registry.get_by_name(template_name)?;
+        Some(match &slot.source {
+            registry::SlotSource::Inline(template) => template.placeholders(),
+            registry::SlotSource::File(template_file) => template_file.placeholders(),
+        })
+    }
+
+    /// The parameters among `available` that the template uses.
     /// 
-    /// let filled = template.fill_params(&params);
-    /// assert_eq!(filled.title, "Error in parser");
-    /// assert_eq!(filled.body, "Details: Invalid syntax");
+    /// Integrations offer the same placeholders to every template, and
+    /// strict templates reject parameters they don't use.
+    #[cfg(feature = "std")]
+    pub(crate) fn used_params<V: Into<String>>(
+        &self,
+        template_name: &str,
+        available: impl IntoIterator<Item = (&'static str, V)>,
+    ) -> Params {
+        let used = self.placeholders(template_name).unwrap_or_default();
+        available
+            .into_iter()
+            .filter(|(key, _)| used.iter().any(|placeholder| placeholder == key))
+            .collect()
+    }
+
+    /// The description of a template, see `IssueTemplate::with_description`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template to inspect
+    ///
+    /// # Returns
+    ///
+    /// The description, or `None` if the template has none or no template has that name.
+    pub fn description(&self, template_name: &str) -> Option<&str> {
+        let slot = self.registry.get_by_name(template_name)?;
+        match &slot.source {
+            registry::SlotSource::Inline(template) => template.description.as_deref(),
+            registry::SlotSource::File(template_file) => template_file.description.as_deref(),
+        }
+    }
+
+    /// What a placeholder of a template stands for, see `IssueTemplate::document_param`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template to inspect
+    /// * `placeholder` - Name of the placeholder, without braces
+    ///
+    /// # Returns
+    ///
+    /// The explanation, or `None` if the placeholder is undocumented or no template has that name.
+    pub fn param_doc(&self, template_name: &str, placeholder: &str) -> Option<&str> {
+        let slot = self.registry.get_by_name(template_name)?;
+        let param_docs = match &slot.source {
+            registry::SlotSource::Inline(template) => &template.param_docs,
+            registry::SlotSource::File(template_file) => &template_file.param_docs,
+        };
+        param_docs
+            .iter()
+            .find(|(name, _)| name == placeholder)
+            .map(|(_, doc)| doc.as_str())
+    }
+
+    /// Render parameter values for a template.
+    ///
+    /// Only values the template has a placeholder for are rendered; other
+    /// keys are kept with empty text, so a template file still rejects them
+    /// as unused.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template the parameters are for
+    /// * `values` - Parameter values, possibly lazy or binary
+    ///
+    /// # Examples
+    ///
     /// ```
-    pub fn fill_params(&self, params: &FxHashMap<String, String>) -> IssueTemplate {
-        let mut filled_title = self.title.clone();
-        let mut filled_body = self.body.clone();
+    /// use bug::{init_handle, IssueTemplate};
+    /// use bug::param::{ParamValue, ParamValues};
+    /// use std::sync::Arc;
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "{summary}"));
+    ///
+    /// let mut values = ParamValues::default();
+    /// values.insert("summary".to_string(), ParamValue::from("out of memory"));
+    /// values.insert("state".to_string(), ParamValue::Lazy(Arc::new(|| unreachable!("never rendered"))));
+    ///
+    /// let params = handle.config().materialize("crash", &values);
+    /// assert_eq!(&params["summary"], "out of memory");
+    /// assert_eq!(&params["state"], "");
+    /// ```
+    pub fn materialize(&self, template_name: &str, values: &param::ParamValues<'_>) -> Params {
+        let placeholders = self.placeholders(template_name).unwrap_or_default();
+        param::materialize(values, &placeholders)
+    }
 
-        for (key, value) in params {
-            let placeholder = format!("{{{}}}", key);
-            filled_title = filled_title.replace(&placeholder, value);
-            filled_body = filled_body.replace(&placeholder, value);
+    /// Fill the named template and build its GitHub issue URL.
+    pub(crate) fn create_report(&self, template_name: &str, params: &Params) -> Result<BugReport, String> {
+        match self.registry.get_by_name(template_name) {
+            Some(slot) => self.create_report_from_slot(slot, params),
+            None => self.missing_template(template_name),
         }
+    }
 
-        IssueTemplate {
-            title: filled_title,
-            body: filled_body,
-            labels: self.labels.clone(),
+    /// The report for a template name that matches no template, as `on_missing_template` says.
+    fn missing_template(&self, template_name: &str) -> Result<BugReport, String> {
+        match self.on_missing_template {
+            OnMissingTemplate::Error => Err(self.registry.not_found(template_name)),
+            OnMissingTemplate::LinkChooser => {
+                let mut warnings = Warnings::new();
+                warnings.push(Warning::LinkedChooser(template_name.to_string()));
+                Ok(BugReport {
+                    template_name: template_name.to_string(),
+                    title: String::new(),
+                    body: String::new(),
+                    labels: Vec::new(),
+                    assignees: Vec::new(),
+                    milestone: None,
+                    projects: Vec::new(),
+                    url: self.chooser_url(),
+                    warnings,
+                })
+            }
         }
     }
-}
 
-/// Extract placeholder names from template content.
-/// 
-/// This function scans the content for `{placeholder}` patterns and returns
-/// a vector of unique placeholder names. Only valid identifiers (alphanumeric
-/// characters and underscores) are recognized as placeholders.
-/// 
-/// # Arguments
-/// 
-/// * `content` - The template content to scan
-/// 
-/// # Returns
-/// 
-/// A vector of unique placeholder names found in the content.
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::extract_placeholders;
-/// 
-/// let content = "Error in {module}: {message}. See {module} docs.";
-/// let placeholders = extract_placeholders(content);
-/// assert_eq!(placeholders.len(), 2);
-/// assert!(placeholders.contains(&"module".to_string()));
-/// assert!(placeholders.contains(&"message".to_string()));
-/// 
-/// // Invalid placeholders with spaces are ignored
-/// let invalid_content = "Invalid: {123} {with space} {valid_name}";
-/// let valid_placeholders = extract_placeholders(invalid_content);
-/// assert_eq!(valid_placeholders, vec!["123".to_string(), "valid_name".to_string()]);
-/// ```
-pub fn extract_placeholders(content: &str) -> Vec<String> {
-    let mut placeholders = Vec::new();
-    let mut chars = content.chars().peekable();
-    
-    while let Some(ch) = chars.next() {
-        if ch == '{' {
-            let mut placeholder = String::new();
-            let mut found_end = false;
-            
-            while let Some(inner_ch) = chars.next() {
-                if inner_ch == '}' {
-                    found_end = true;
-                    break;
-                } else if inner_ch.is_alphanumeric() || inner_ch == '_' {
-                    placeholder.push(inner_ch);
-                } else {
-                    placeholder.clear();
-                    break;
-                }
+    /// The URL of the repository's issue template chooser.
+    pub(crate) fn chooser_url(&self) -> String {
+        format!("{}/{}/{}/issues/new/choose", self.host(), self.github_owner, self.github_repo)
+    }
+
+    /// The URL opening `form` in the handle's repository with `params` prefilled.
+    pub(crate) fn form_url(&self, form: &issue_form::IssueForm, params: &Params) -> Result<String, String> {
+        let mut url = format!("{}/{}/{}/issues/new", self.host(), self.github_owner, self.github_repo);
+        form.write_query(params, self.body_space_encoding, &mut url)?;
+        Ok(url)
+    }
+
+    /// Fill the template with the given id and build its GitHub issue URL.
+    pub(crate) fn create_report_by_id(&self, id: TemplateId, params: &Params) -> Result<BugReport, String> {
+        let slot = self
+            .registry
+            .get(id)
+            .ok_or_else(|| format!("Template id {} not found", id.index()))?;
+        self.create_report_from_slot(slot, params)
+    }
+
+    /// Fill the template mapped to an error code, adding the code as a label.
+    pub(crate) fn create_report_for_code(&self, code: &str, params: &Params) -> Result<BugReport, String> {
+        let template_name = self
+            .error_codes
+            .get(code)
+            .ok_or_else(|| format!("Error code '{}' is not mapped to a template", code))?;
+        let Some(slot) = self.registry.get_by_name(template_name) else {
+            return self
+                .missing_template(template_name)
+                .map_err(|e| format!("{} (mapped from error code '{}')", e, code));
+        };
+
+        let extended = self.with_implicit_param(template_name, params, ERROR_CODE_PARAM, code);
+        let (mut filled_template, warnings) = self.fill_slot(slot, extended.as_ref().unwrap_or(params))?;
+
+        if !filled_template.labels.iter().any(|label| label == code) {
+            filled_template.labels.push(code.to_string());
+        }
+        Ok(self.finish_report(&slot.name, filled_template, warnings))
+    }
+
+    /// Fill a template as a follow-up to `parent`, providing `{parent_url}`.
+    #[cfg(feature = "std")]
+    pub(crate) fn create_follow_up(&self, parent: &BugReport, template_name: &str, params: &Params) -> Result<BugReport, String> {
+        let extended = self.with_implicit_param(template_name, params, PARENT_URL_PARAM, &parent.url);
+        self.create_report(template_name, extended.as_ref().unwrap_or(params))
+    }
+
+    /// Fill the template mapped to an error code, also providing the location placeholders.
+    pub(crate) fn create_report_for_code_at(&self, code: &str, params: &Params, loc: &Location) -> Result<BugReport, String> {
+        let extended = self
+            .error_codes
+            .get(code)
+            .and_then(|template_name| self.with_location_params(template_name, params, loc));
+        self.create_report_for_code(code, extended.as_ref().unwrap_or(params))
+            .map(|report| self.annotate(report, loc))
+    }
+
+    /// Whether a report is skipped by a suppression rule or the template's sample rate.
+    pub(crate) fn skipped(&self, template_name: &str, params: &Params) -> bool {
+        self.suppressions.suppresses(template_name, params)
+            || self
+                .sample_rates
+                .get(template_name)
+                .is_some_and(|rate| !self.sampler.sample(*rate))
+    }
+
+    /// Whether a report for `code` is skipped, as with `skipped`.
+    pub(crate) fn code_skipped(&self, code: &str, params: &Params) -> bool {
+        self.error_codes
+            .get(code)
+            .is_some_and(|template_name| self.skipped(template_name, params))
+    }
+
+    /// Finish a report made at `loc`: note its sample rate in the body when
+    /// the template has no `{sample_rate}`, and add the code owners of `loc`
+    /// (with the `codeowners` feature).
+    pub(crate) fn annotate(&self, report: BugReport, loc: &Location) -> BugReport {
+        let sample_note = self.sample_rates.get(&report.template_name).filter(|rate| {
+            **rate < 1.0
+                && !self
+                .placeholders(&report.template_name)
+                .is_some_and(|placeholders| placeholders.iter().any(|p| p == SAMPLE_RATE_PARAM))
+        });
+
+        #[cfg(feature = "codeowners")]
+        let (owner_assignees, owner_labels) = match &self.codeowners {
+            Some(owners) => codeowners::assignees_and_labels(owners.owners_of(loc.file)),
+            None => (Vec::new(), Vec::new()),
+        };
+        #[cfg(not(feature = "codeowners"))]
+        let (owner_assignees, owner_labels): (Vec<String>, Vec<String>) = {
+            let _ = loc;
+            (Vec::new(), Vec::new())
+        };
+
+        if sample_note.is_none() && owner_assignees.is_empty() && owner_labels.is_empty() {
+            return report;
+        }
+
+        let mut template = IssueTemplate {
+            title: report.title,
+            body: report.body,
+            labels: report.labels,
+            assignees: report.assignees,
+            teams: Vec::new(),
+            milestone: report.milestone,
+            projects: report.projects,
+            fallback: None,
+            description: None,
+            param_docs: Vec::new(),
+        };
+        if let Some(rate) = sample_note {
+            template.body = sampling::note(&template.body, &self.messages.sampled_note, *rate);
+        }
+        for label in owner_labels {
+            if !template.labels.contains(&label) {
+                template.labels.push(label);
             }
-            
-            if found_end && !placeholder.is_empty() && !placeholders.contains(&placeholder) {
-                placeholders.push(placeholder);
+        }
+        for assignee in owner_assignees {
+            if !template.assignees.contains(&assignee) {
+                template.assignees.push(assignee);
             }
         }
+        self.finish_report(&report.template_name, template, report.warnings)
     }
-    
-    placeholders
-}
 
-/// Macro to create a `TemplateFile` from a file path at compile time.
-/// 
-/// This macro uses `include_str!` to embed the template content directly into
-/// the binary at compile time. It supports an optional `labels` parameter to
-/// add GitHub issue labels.
-/// 
-/// # Syntax
-/// 
-/// - `template_file!("path/to/template.txt")` - Basic usage
-/// - `template_file!("path/to/template.txt", labels: ["bug", "urgent"])` - With labels
+    /// Whether report details must be withheld because consent is required but not given.
+    fn consent_withheld(&self) -> bool {
+        self.require_consent && !consent::has_consent()
+    }
+
+    /// A marker identifying a report by its template and parameters.
+    /// 
+    /// Equal templates and parameters give equal markers on every machine,
+    /// so a template whose body contains `{fingerprint}` lets duplicates of
+    /// an issue be found by searching for the marker. Reports with a
+    /// location fill the placeholder automatically.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// * `params` - Parameters the report is generated with
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Location, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "<!-- {fingerprint} -->"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("screen", "home");
+    /// let marker = handle.config().fingerprint("crash", &params);
+    /// assert!(marker.starts_with("bug-fingerprint-"));
+    /// 
+    /// let url = handle.report_bug_at("crash", &params, Location::new(file!(), line!()));
+    /// assert!(url.contains(&marker));
+    /// ```
+    pub fn fingerprint(&self, template_name: &str, params: &Params) -> String {
+        format!("{}{:016x}", FINGERPRINT_PREFIX, cache::stable_fingerprint(template_name, params))
+    }
+
+    /// A stable hash of the repository and the registered templates.
+    /// 
+    /// The digest covers the owner, the repository, the host, and every
+    /// template's name, text, labels, assignees, teams, fallback, and target,
+    /// so it changes whenever the templates reports are generated from do. It is the same
+    /// on every platform and doesn't depend on the order templates were
+    /// registered in. Printed in banners with `show_config_digest`, or put
+    /// in a body's metadata with the `{config_digest}` placeholder, it tells
+    /// maintainers which template set produced a report.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Location, Params};
+    /// 
+    /// let v1 = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "<!-- templates {config_digest} -->"));
+    /// let v2 = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {screen}", "<!-- templates {config_digest} -->"));
+    /// assert_ne!(v1.config().digest(), v2.config().digest());
+    /// // The same value on every platform
+    /// assert_eq!(format!("{:016x}", v1.config().digest()), "e0752ad08c28f948");
+    /// 
+    /// let url = v1.report_bug_at("crash", &Params::new(), Location::new(file!(), line!()));
+    /// assert!(url.contains(&format!("{:016x}", v1.config().digest())));
+    /// ```
+    pub fn digest(&self) -> u64 {
+        fn part(hasher: &mut siphash::SipHasher13, bytes: &[u8]) {
+            hasher.write(bytes);
+            hasher.write_u8(0xff);
+        }
+        fn list(hasher: &mut siphash::SipHasher13, items: &[String]) {
+            hasher.write(&(items.len() as u64).to_le_bytes());
+            for item in items {
+                part(hasher, item.as_bytes());
+            }
+        }
+
+        // Summed so registration order doesn't matter
+        let combined = self.registry.slots().iter().fold(0u64, |combined, slot| {
+            let mut hasher = siphash::SipHasher13::new_with_keys(0, 0);
+            part(&mut hasher, slot.name.as_bytes());
+            let (assignees, teams, milestone, projects, fallback) = match &slot.source {
+                registry::SlotSource::Inline(template) => {
+                    hasher.write_u8(0);
+                    part(&mut hasher, template.title.as_bytes());
+                    part(&mut hasher, template.body.as_bytes());
+                    (&template.assignees, &template.teams, &template.milestone, &template.projects, &template.fallback)
+                }
+                registry::SlotSource::File(template_file) => {
+                    hasher.write_u8(1);
+                    part(&mut hasher, template_file.content.as_bytes());
+                    (
+                        &template_file.assignees,
+                        &template_file.teams,
+                        &template_file.milestone,
+                        &template_file.projects,
+                        &template_file.fallback,
+                    )
+                }
+            };
+            hasher.write(&(slot.labels.len() as u64).to_le_bytes());
+            for label in &slot.labels {
+                part(&mut hasher, label.as_bytes());
+            }
+            list(&mut hasher, assignees);
+            list(&mut hasher, teams);
+            part(&mut hasher, milestone.as_deref().unwrap_or("").as_bytes());
+            list(&mut hasher, projects);
+            part(&mut hasher, fallback.as_deref().unwrap_or("").as_bytes());
+            let target = self.template_targets.get(&*slot.name);
+            let (owner, repo) = target.and_then(|target| target.repository.as_ref()).map_or(("", ""), |(owner, repo)| (owner, repo));
+            part(&mut hasher, owner.as_bytes());
+            part(&mut hasher, repo.as_bytes());
+            part(&mut hasher, target.and_then(|target| target.host.as_deref()).unwrap_or("").as_bytes());
+            combined.wrapping_add(hasher.finish())
+        });
+
+        let mut hasher = siphash::SipHasher13::new_with_keys(0, 0);
+        part(&mut hasher, self.github_owner.as_bytes());
+        part(&mut hasher, self.github_repo.as_bytes());
+        part(&mut hasher, self.host().as_bytes());
+        hasher.write(&combined.to_le_bytes());
+        hasher.finish()
+    }
+
+    /// Find an open issue whose title or body contains `query` (feature `api`).
+    /// 
+    /// Returns `None` without a GitHub API configured, when nothing
+    /// matches, or when the request fails.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - A fingerprint marker or issue title
+    #[cfg(feature = "api")]
+    pub fn find_existing(&self, query: &str) -> Option<api::IssueRef> {
+        self.github_api
+            .as_ref()?
+            .find_open_issue(&self.github_owner, &self.github_repo, query)
+    }
+
+    /// The open issue a report duplicates, matched on its fingerprint or title.
+    #[cfg(feature = "api")]
+    fn existing_issue(&self, report: &BugReport, params: &Params) -> Option<api::IssueRef> {
+        let github_api = self.github_api.as_ref()?;
+        let uses_fingerprint = self
+            .placeholders(&report.template_name)
+            .is_some_and(|placeholders| placeholders.iter().any(|p| p == FINGERPRINT_PARAM));
+        let query = if uses_fingerprint {
+            self.fingerprint(&report.template_name, params)
+        } else {
+            report.title.clone()
+        };
+        // Searched where the template files its reports
+        let (owner, repo, _) = self.target_of(&report.template_name);
+        github_api.find_open_issue(owner, repo, &query)
+    }
+
+    /// The owner, repository, and host reports from `template_name` are filed at.
+    /// 
+    /// The template's `TemplateTarget` wins over the handle's repository
+    /// and `base_url`; the host defaults to `github::HOST`.
+    pub(crate) fn target_of(&self, template_name: &str) -> (&str, &str, &str) {
+        let target = self.template_targets.get(template_name);
+        let (owner, repo) = target
+            .and_then(|target| target.repository.as_ref())
+            .map_or((self.github_owner.as_str(), self.github_repo.as_str()), |(owner, repo)| (owner, repo));
+        let host = target
+            .and_then(|target| target.host.as_deref())
+            .unwrap_or_else(|| self.host());
+        (owner, repo, host)
+    }
+
+    /// The host of the handle's issue URLs, `base_url` or `github::HOST`.
+    fn host(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(github::HOST)
+    }
+
+    /// How banners show the URLs of reports from `template_name`.
+    pub(crate) fn hyperlinks_for(&self, template_name: &str) -> HyperlinkMode {
+        self.template_targets
+            .get(template_name)
+            .and_then(|target| target.hyperlinks)
+            .unwrap_or(self.use_hyperlinks)
+    }
+
+    /// Copy `params` with `{source_location}`, `{module}`, `{sample_rate}`,
+    /// `{timestamp}`, `{fingerprint}`, `{config_digest}`, `{panic_count}`,
+    /// `{last_panic}`, and `{source_excerpt}` added where the template uses them.
+    pub(crate) fn with_location_params(&self, template_name: &str, params: &Params, loc: &Location) -> Option<Params> {
+        let placeholders = self.placeholders(template_name)?;
+        let implicit = [
+            Some((SOURCE_LOCATION_PARAM, loc.to_string())),
+            Some((MODULE_PARAM, loc.module_path.unwrap_or("unknown").to_string())),
+            self.sample_rates
+                .get(template_name)
+                .map(|rate| (SAMPLE_RATE_PARAM, rate.to_string())),
+            self.time.now().map(|now| (TIMESTAMP_PARAM, now.to_string())),
+            placeholders
+                .iter()
+                .any(|p| p == FINGERPRINT_PARAM)
+                .then(|| (FINGERPRINT_PARAM, self.fingerprint(template_name, params))),
+            placeholders
+                .iter()
+                .any(|p| p == CONFIG_DIGEST_PARAM)
+                .then(|| (CONFIG_DIGEST_PARAM, format!("{:016x}", self.digest()))),
+            #[cfg(feature = "std")]
+            Some((panic_hook::PANIC_COUNT_PARAM, panic_hook::panic_count().to_string())),
+            #[cfg(feature = "std")]
+            placeholders
+                .iter()
+                .any(|p| p == panic_hook::LAST_PANIC_PARAM)
+                .then(|| (panic_hook::LAST_PANIC_PARAM, panic_hook::last_panic().unwrap_or_else(|| "none".to_string()))),
+        ];
+
+        let mut extended: Option<Params> = None;
+        for (key, value) in implicit.into_iter().flatten() {
+            if placeholders.iter().any(|p| p == key) && !params.contains_key(key) {
+                extended.get_or_insert_with(|| params.clone()).insert(key.to_string(), value);
+            }
+        }
+
+        // Reading the file is only worth it for templates that show the excerpt.
+        if self.include_source_excerpt
+            && placeholders.iter().any(|p| p == SOURCE_EXCERPT_PARAM)
+            && !params.contains_key(SOURCE_EXCERPT_PARAM)
+        {
+            extended
+                .get_or_insert_with(|| params.clone())
+                .insert(SOURCE_EXCERPT_PARAM, source_excerpt(loc, &self.messages));
+        }
+        extended
+    }
+
+    /// Copy `params` with the environment placeholders the template uses and `params` lacks.
+    #[cfg(any(feature = "std", feature = "wasm"))]
+    fn with_environment_params(&self, placeholders: &[String], params: &Params) -> Option<Params> {
+        let mut extended = None;
+        #[cfg(feature = "std")]
+        if let Some(context) = &self.auto_context {
+            context.fill(placeholders, params, &mut extended);
+        }
+        #[cfg(feature = "std")]
+        if let Some(screenshot) = &self.screenshot {
+            screenshot.fill(placeholders, params, &self.messages, &mut extended);
+        }
+        #[cfg(feature = "wasm")]
+        if let Some(browser) = &self.browser {
+            browser.fill(placeholders, params, &mut extended);
+        }
+        #[cfg(any(feature = "android", feature = "ios"))]
+        {
+            let device = match &self.device {
+                Some(device) => Some(device),
+                None => device::DeviceContext::current(),
+            };
+            if let Some(device) = device {
+                device.fill(placeholders, params, &mut extended);
+            }
+        }
+        extended
+    }
+
+    /// Whether a report from `template_name` with `params` may be cached.
+    /// 
+    /// Reports from a template, or one of its fallbacks, that fills a
+    /// placeholder from something that changes between reports, such as the
+    /// screenshot provider, a context provider, or the browser, or that
+    /// assigns a team in rotation, are not.
+    pub(crate) fn cacheable(&self, template_name: &str, params: &Params) -> bool {
+        let mut slot = self.registry.get_by_name(template_name);
+        // A chain can't be longer than the registry without a cycle
+        for _ in 0..self.registry.slots().len() {
+            let Some(current) = slot else {
+                break;
+            };
+            if self.placeholders(&current.name).is_some_and(|placeholders| self.fills_volatile(&placeholders, params)) {
+                return false;
+            }
+            // Each report takes the next member of a rotating team
+            if self.team_assignment == TeamAssignment::RoundRobin && !slot_teams(current).is_empty() {
+                return false;
+            }
+            slot = current.fallback().and_then(|name| self.registry.get_by_name(name));
+        }
+        true
+    }
+
+    /// Whether `with_environment_params` fills one of `placeholders` with a
+    /// value that can change between reports.
+    #[cfg_attr(not(any(feature = "std", feature = "wasm")), allow(unused_variables))]
+    fn fills_volatile(&self, placeholders: &[String], params: &Params) -> bool {
+        let missing = |key: &str| placeholders.iter().any(|p| p == key) && !params.contains_key(key);
+        #[cfg(feature = "std")]
+        if self.screenshot.is_some() && missing(screenshot::SCREENSHOT_PARAM) {
+            return true;
+        }
+        #[cfg(feature = "std")]
+        if self.auto_context.as_ref().is_some_and(|context| context.has_providers())
+            && placeholders.iter().any(|p| missing(p))
+        {
+            return true;
+        }
+        #[cfg(feature = "wasm")]
+        if self.browser.is_some() && [wasm::USER_AGENT_PARAM, wasm::PLATFORM_PARAM, wasm::VIEWPORT_PARAM].into_iter().any(missing) {
+            return true;
+        }
+        false
+    }
+
+    /// Copy `params` with `key` added, if the template uses `key` and `params` lacks it.
+    /// 
+    /// Strict templates reject unused parameters, so implicit values are only
+    /// passed to templates that ask for them.
+    fn with_implicit_param(&self, template_name: &str, params: &Params, key: &str, value: &str) -> Option<Params> {
+        let uses_key = self
+            .placeholders(template_name)
+            .is_some_and(|placeholders| placeholders.iter().any(|p| p == key));
+        if !uses_key || params.contains_key(key) {
+            return None;
+        }
+
+        let mut params = params.clone();
+        params.insert(key.to_string(), value.to_string());
+        Some(params)
+    }
+
+    /// Fill a registered template, following its fallbacks if it can't be filled.
+    fn create_report_from_slot(&self, slot: &registry::Slot, params: &Params) -> Result<BugReport, String> {
+        let (name, filled_template, warnings) = self.fill_with_fallbacks(slot, params)?;
+        Ok(self.finish_report(name, filled_template, warnings))
+    }
+
+    /// Fill a registered template or, if it can't be filled, its fallbacks,
+    /// returning the name of the template that was filled.
+    fn fill_with_fallbacks<'a>(
+        &'a self,
+        slot: &'a registry::Slot,
+        params: &Params,
+    ) -> Result<(&'a str, IssueTemplate, Warnings), String> {
+        let mut slot = slot;
+        let mut fell_back = Vec::new();
+        loop {
+            match self.fill_slot(slot, params) {
+                Ok((filled_template, mut warnings)) => {
+                    for (template, error) in fell_back {
+                        warnings.push(Warning::UsedFallback { template, error });
+                    }
+                    return Ok((&slot.name, filled_template, warnings));
+                }
+                Err(error) => {
+                    // A chain can't be longer than the registry without a cycle
+                    let next = slot
+                        .fallback()
+                        .and_then(|name| self.registry.get_by_name(name))
+                        .filter(|_| fell_back.len() < self.registry.slots().len());
+                    let Some(next) = next else {
+                        return Err(error);
+                    };
+                    fell_back.push((slot.name.to_string(), error));
+                    slot = next;
+                }
+            }
+        }
+    }
+
+    /// Fill the named template and write its GitHub issue URL to `output`.
+    /// 
+    /// The URL is the one `create_report` builds, but it is encoded into
+    /// `output` field by field and never held in memory as a whole.
+    pub(crate) fn write_report_url(&self, template_name: &str, params: &Params, output: &mut dyn Output) -> Result<(), String> {
+        let Some(slot) = self.registry.get_by_name(template_name) else {
+            return match self.on_missing_template {
+                OnMissingTemplate::Error => Err(self.registry.not_found(template_name)),
+                OnMissingTemplate::LinkChooser => {
+                    output.write_str(&self.chooser_url());
+                    Ok(())
+                }
+            };
+        };
+        let (name, filled_template, warnings) = self.fill_with_fallbacks(slot, params)?;
+        let (filled_template, assignees, _) = self.finish_fields(name, filled_template, warnings);
+        self.write_url(name, &filled_template, &assignees, output);
+        Ok(())
+    }
+
+    /// Fill the named template as `create_report` does, without building the URL.
+    pub(crate) fn fill(&self, template_name: &str, params: &Params) -> Result<IssueTemplate, String> {
+        let slot = self
+            .registry
+            .get_by_name(template_name)
+            .ok_or_else(|| self.registry.not_found(template_name))?;
+        let (name, filled_template, warnings) = self.fill_with_fallbacks(slot, params)?;
+        let (mut filled_template, assignees, _) = self.finish_fields(name, filled_template, warnings);
+        // The teams have been expanded into the assignees
+        filled_template.assignees = assignees;
+        filled_template.teams.clear();
+        Ok(filled_template)
+    }
+
+    /// Fill a registered template, with the warnings a lenient fill leaves behind.
+    fn fill_slot(&self, slot: &registry::Slot, params: &Params) -> Result<(IssueTemplate, Warnings), String> {
+        let applied = self.param_policies.apply(&slot.name, params);
+        let params = applied.as_ref().unwrap_or(params);
+
+        // Template files are always validated, inline templates with `strict_params`
+        let parsed;
+        let (template, policy) = match &slot.source {
+            registry::SlotSource::Inline(template) if self.strict_params => (template, fill::Policy::Strict),
+            registry::SlotSource::Inline(template) => (template, fill::Policy::Lenient),
+            registry::SlotSource::File(template_file) => {
+                parsed = template_file.parse()?;
+                (&parsed, fill::Policy::Strict)
+            }
+        };
+        let fields = fill::Fields {
+            title: &template.title,
+            body: &template.body,
+            labels: &slot.labels,
+            assignees: &template.assignees,
+            teams: &template.teams,
+            milestone: template.milestone.as_deref(),
+            projects: &template.projects,
+        };
+        let placeholders = fields.placeholders();
+        #[cfg(any(feature = "std", feature = "wasm"))]
+        let environment = self.with_environment_params(&placeholders, params);
+        #[cfg(any(feature = "std", feature = "wasm"))]
+        let params = environment.as_ref().unwrap_or(params);
+        let filled = fields.fill_with(params, policy)?;
+
+        let mut warnings = Warnings::new();
+        for key in params.keys() {
+            if !placeholders.iter().any(|p| p == key) {
+                warnings.push(Warning::UnusedParameter(key.to_string()));
+            }
+        }
+        let texts = [filled.title.as_str(), filled.body.as_str()]
+            .into_iter()
+            .chain(filled.labels.iter().map(String::as_str))
+            .chain(filled.assignees.iter().map(String::as_str))
+            .chain(filled.milestone.as_deref())
+            .chain(filled.projects.iter().map(String::as_str));
+        let unfilled = fill::unfilled(&placeholders, texts);
+        if self.deny_unfilled_placeholders && !unfilled.is_empty() {
+            let list: Vec<String> = unfilled.iter().map(|p| format!("{{{}}}", p)).collect();
+            return Err(format!(
+                "Template '{}' has placeholders without a value: {}",
+                slot.name,
+                list.join(", ")
+            ));
+        }
+        for placeholder in unfilled {
+            warnings.push(Warning::UnfilledPlaceholder(placeholder.to_string()));
+        }
+        Ok((filled, warnings))
+    }
+
+    /// Build the GitHub issue URL for a filled template.
+    /// 
+    /// `warnings` are those found while filling; dropped labels and
+    /// assignees are added to them.
+    pub(crate) fn finish_report(&self, template_name: &str, filled_template: IssueTemplate, warnings: Warnings) -> BugReport {
+        let (filled_template, assignees, warnings) = self.finish_fields(template_name, filled_template, warnings);
+        let mut url = String::new();
+        self.write_url(template_name, &filled_template, &assignees, &mut url);
+        BugReport {
+            template_name: template_name.to_string(),
+            title: filled_template.title,
+            body: filled_template.body,
+            labels: filled_template.labels,
+            assignees,
+            milestone: filled_template.milestone,
+            projects: filled_template.projects,
+            url,
+            warnings,
+        }
+    }
+
+    /// Apply the report settings to a filled template: minify the body,
+    /// drop disallowed labels, and expand teams into the returned assignees.
+    /// 
+    /// Every successful report passes through here once, so this is where
+    /// the template's use is recorded.
+    fn finish_fields(
+        &self,
+        template_name: &str,
+        mut filled_template: IssueTemplate,
+        mut warnings: Warnings,
+    ) -> (IssueTemplate, Vec<String>, Warnings) {
+        #[cfg(feature = "usage-stats")]
+        usage::record(template_name);
+
+        if self.minify_body {
+            filled_template.body = minify::minify(&filled_template.body);
+        }
+        if self.allowed_labels.is_some() {
+            filled_template.labels.retain(|label| {
+                let allowed = self.label_allowed(label);
+                if !allowed {
+                    #[cfg(feature = "std")]
+                    if self.warn_on_disallowed_labels {
+                        Output::write_fmt(
+                            &mut std::io::stderr(),
+                            format_args!(
+                                "warning: dropping label '{}' from '{}' report: not in the allowed labels\n",
+                                label, template_name
+                            ),
+                        );
+                    }
+                    warnings.push(Warning::DroppedLabel(label.clone()));
+                }
+                allowed
+            });
+        }
+
+        if !filled_template.labels.is_empty() && self.label_encoding == url_encode::LabelEncoding::Joined {
+            for label in filled_template.labels.iter().filter(|label| label.contains(',')) {
+                warnings.push(Warning::CommaInLabel(label.clone()));
+            }
+        }
+
+        let (_, _, host) = self.target_of(template_name);
+        if let Err(error) = target::check_base_url(host) {
+            warnings.push(Warning::MalformedBaseUrl(error));
+        }
+
+        let mut assignees = core::mem::take(&mut filled_template.assignees);
+        let dropped = teams::expand(&self.teams, &filled_template.teams, self.team_assignment, &self.rotation, &mut assignees);
+        if dropped > 0 {
+            warnings.push(Warning::AssigneesTruncated { dropped });
+        }
+        (filled_template, assignees, warnings)
+    }
+
+    /// Write the new-issue URL for a finished template to `output`.
+    /// 
+    /// Each field is encoded straight into `output`, so the URL is never
+    /// held in memory as a whole.
+    fn write_url(&self, template_name: &str, template: &IssueTemplate, assignees: &[String], output: &mut dyn Output) {
+        let (owner, repo, host) = self.target_of(template_name);
+        output.write_fmt(format_args!("{}/{}/{}/issues/new", host, owner, repo));
+        let mut separator = '?';
+        let mut start = |output: &mut dyn Output, name: &str| {
+            output.write_fmt(format_args!("{}{}=", separator, name));
+            separator = '&';
+        };
+
+        if !template.title.is_empty() {
+            start(output, "title");
+            url_encode::encode_to(&template.title, url_encode::SpaceEncoding::Plus, output);
+        }
+        if !template.body.is_empty() {
+            start(output, "body");
+            url_encode::encode_to(&template.body, self.body_space_encoding, output);
+        }
+        match self.label_encoding {
+            url_encode::LabelEncoding::Joined if !template.labels.is_empty() => {
+                start(output, "labels");
+                for (index, label) in template.labels.iter().enumerate() {
+                    if index > 0 {
+                        output.write_str(",");
+                    }
+                    url_encode::encode_to(label, url_encode::SpaceEncoding::Plus, output);
+                }
+            }
+            url_encode::LabelEncoding::Joined => {}
+            url_encode::LabelEncoding::Repeated => {
+                for label in &template.labels {
+                    start(output, "labels[]");
+                    url_encode::encode_to(label, url_encode::SpaceEncoding::Plus, output);
+                }
+            }
+        }
+        if !assignees.is_empty() {
+            start(output, "assignees");
+            for (index, assignee) in assignees.iter().enumerate() {
+                if index > 0 {
+                    // The list is encoded as one value, comma included
+                    output.write_str("%2C");
+                }
+                url_encode::encode_to(assignee, url_encode::SpaceEncoding::Plus, output);
+            }
+        }
+        if let Some(milestone) = &template.milestone {
+            start(output, "milestone");
+            url_encode::encode_to(milestone, url_encode::SpaceEncoding::Plus, output);
+        }
+        if !template.projects.is_empty() {
+            start(output, "projects");
+            for (index, project) in template.projects.iter().enumerate() {
+                if index > 0 {
+                    output.write_str(",");
+                }
+                url_encode::encode_to(project, url_encode::SpaceEncoding::Plus, output);
+            }
+        }
+    }
+}
+
+/// Controls how hyperlinks are displayed in terminal output.
+///
+/// Modern terminals support clickable hyperlinks using ANSI escape sequences.
+/// This enum allows you to control when to use them.
 /// 
 /// # Examples
 /// 
-/// ```ignore
-/// use bug::template_file;
-/// 
-/// // Basic usage (assumes you have a template.txt file)
-/// let template = template_file!("templates/bug_report.txt");
-/// 
-/// // With labels
-/// let labeled_template = template_file!(
-///     "templates/crash_report.txt", 
-///     labels: ["bug", "crash", "high-priority"]
-/// );
 /// ```
+/// use bug::HyperlinkMode;
 /// 
-/// # Template File Format
+/// // Automatically detect terminal support
+/// let auto_mode = HyperlinkMode::Auto;
 /// 
-/// Template files should have the title on the first line and the body on subsequent lines:
+/// // Always show hyperlinks (good for known compatible terminals)
+/// let always_mode = HyperlinkMode::Always;
 /// 
-/// ```text
-/// Bug Report: {component}
-/// ## Description
-/// {description}
+/// // Never show hyperlinks (good for logs or unknown terminals)
+/// let never_mode = HyperlinkMode::Never;
 /// 
-/// ## Steps to Reproduce
-/// {steps}
+/// assert_eq!(HyperlinkMode::default(), auto_mode);
 /// ```
-#[macro_export]
-macro_rules! template_file {
-    ($path:expr) => {
-        $crate::TemplateFile::new(include_str!($path))
-    };
-    ($path:expr, labels: [$($label:expr),* $(,)?]) => {
-        $crate::TemplateFile::new(include_str!($path))
-            .with_labels(vec![$($label.to_string()),*])
-    };
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HyperlinkMode {
+    /// Automatically detect terminal hyperlink support based on environment variables
+    #[default]
+    Auto,
+    /// Always use hyperlinks regardless of terminal detection
+    Always,
+    /// Never use hyperlinks, always show full URLs
+    Never,
 }
 
-/// Initialize a bug report configuration builder (std only).
-/// 
-/// This function creates a new configuration builder that allows you to set up
-/// templates and options before building the global configuration. This is only
-/// available with the "std" feature.
+impl HyperlinkMode {
+    /// Whether output should use hyperlinks, detecting terminal support for `Auto`.
+    pub(crate) fn enabled(&self) -> bool {
+        match self {
+            HyperlinkMode::Auto => supports_hyperlinks(),
+            HyperlinkMode::Always => true,
+            HyperlinkMode::Never => false,
+        }
+    }
+}
+
+/// A GitHub issue template with title, body, labels, and assignees.
+///
+/// Issue templates define the structure of bug reports that will be submitted to GitHub.
+/// They support placeholder substitution using `{placeholder}` syntax.
 /// 
-/// # Arguments
+/// # Examples
 /// 
-/// * `github_owner` - GitHub username or organization name
-/// * `github_repo` - GitHub repository name
+/// ```
+/// use bug::IssueTemplate;
 /// 
-/// # Returns
+/// let template = IssueTemplate::new(
+///     "Bug: {component} not working",
+///     "## Problem\n{description}\n\n## Expected Behavior\n{expected}\n\n## Actual Behavior\n{actual}"
+/// ).with_labels(vec!["bug".to_string(), "needs-investigation".to_string()]);
 /// 
-/// A `BugReportConfigBuilder` that can be used to configure templates and options.
+/// assert_eq!(template.title, "Bug: {component} not working");
+/// assert_eq!(template.labels.len(), 2);
+/// ```
 /// 
-/// # Examples
+/// Displaying a template previews it as the issue would read, with the
+/// title first, then the labels and assignees, then the body:
 /// 
 /// ```
-/// use bug::{init, IssueTemplate};
+/// use bug::IssueTemplate;
 /// 
-/// # #[cfg(feature = "std")] {
-/// let result = init("octocat", "Hello-World")
-///     .add_template("bug", IssueTemplate::new("Bug Report", "Something is broken"))
-///     .hyperlinks(bug::HyperlinkMode::Always)
-///     .build();
-/// # }
+/// let template = IssueTemplate::new("Crash in {module}", "Details: {details}")
+///     .with_labels(vec!["bug".to_string(), "crash".to_string()]);
+/// assert_eq!(template.to_string(), "Crash in {module}\nLabels: bug, crash\n\nDetails: {details}");
 /// ```
-pub fn init(github_owner: impl Into<String>, github_repo: impl Into<String>) -> BugReportConfigBuilder {
-    BugReportConfigBuilder::new(github_owner.into(), github_repo.into())
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueTemplate {
+    /// The title template for the GitHub issue
+    pub title: String,
+    /// The body template for the GitHub issue
+    pub body: String,
+    /// Labels to apply to the GitHub issue
+    pub labels: Vec<String>,
+    /// GitHub users to assign the issue to
+    pub assignees: Vec<String>,
+    /// Teams whose members are assigned, see `BugReportConfigBuilder::team`
+    pub teams: Vec<String>,
+    /// Milestone the issue is added to, by title, see `with_milestone`
+    pub milestone: Option<String>,
+    /// Projects the issue is added to, as `owner/number`, see `with_projects`
+    pub projects: Vec<String>,
+    /// Template to use instead when this one can't be filled, see `with_fallback`
+    pub fallback: Option<String>,
+    /// What the template reports, for listings and prompts
+    pub description: Option<String>,
+    /// What each placeholder means, in the order documented, see `document_param`
+    pub param_docs: Vec<(String, String)>,
 }
 
-/// Initialize a bug report handle (works in both std and no_std).
+impl core::fmt::Display for IssueTemplate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        report::write_preview(f, &self.title, &self.body, &self.labels, &self.assignees)
+    }
+}
+
+/// A template loaded from a static string (typically from `include_str!`).
+///
+/// Template files allow you to store issue templates in separate files and embed
+/// them at compile time. The first line becomes the title, and the rest becomes the body.
 /// 
-/// This function creates a handle-based configuration that doesn't rely on
-/// global state. It can be used in both std and no_std environments.
+/// # File Format
 /// 
-/// # Arguments
-/// 
-/// * `github_owner` - GitHub username or organization name
-/// * `github_repo` - GitHub repository name
-/// 
-/// # Returns
+/// ```text
+/// Issue Title Here
+/// Issue body content starts here.
+/// It can span multiple lines.
 /// 
-/// A `BugReportHandle` that can be used to generate bug reports.
+/// Placeholders like {param} are supported.
+/// ```
 /// 
 /// # Examples
 /// 
 /// ```
-/// use bug::{init_handle, IssueTemplate};
+/// use bug::TemplateFile;
 /// 
-/// let handle = init_handle("octocat", "Hello-World")
-///     .add_template("crash", IssueTemplate::new("Crash Report", "App crashed: {reason}"))
-///     .hyperlinks(bug::HyperlinkMode::Never);
+/// let template_file = TemplateFile::new("Bug Report\nFound a bug: {description}")
+///     .with_labels(vec!["bug".to_string()]);
 /// 
-/// // Use with bug_with_handle! macro
+/// let parsed = template_file.parse().unwrap();
+/// assert_eq!(parsed.title, "Bug Report");
+/// assert_eq!(parsed.body, "Found a bug: {description}");
 /// ```
-pub fn init_handle(github_owner: impl Into<String>, github_repo: impl Into<String>) -> BugReportHandle {
-    BugReportHandle::new(github_owner.into(), github_repo.into())
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TemplateFile {
+    /// The raw template content (first line is title, rest is body)
+    pub content: &'static str,
+    /// Labels to apply to issues created from this template
+    pub labels: Vec<String>,
+    /// GitHub users to assign issues created from this template to
+    pub assignees: Vec<String>,
+    /// Teams whose members are assigned, see `BugReportConfigBuilder::team`
+    pub teams: Vec<String>,
+    /// Milestone the issue is added to, by title, see `with_milestone`
+    pub milestone: Option<String>,
+    /// Projects the issue is added to, as `owner/number`, see `with_projects`
+    pub projects: Vec<String>,
+    /// Template to use instead when this one can't be filled, see `with_fallback`
+    pub fallback: Option<String>,
+    /// What the template reports, for listings and prompts
+    pub description: Option<String>,
+    /// What each placeholder means, in the order documented, see `document_param`
+    pub param_docs: Vec<(String, String)>,
+    /// SHA-256 digest the content must have, see `with_sha256`
+    pub sha256: Option<String>,
 }
 
-/// Builder for configuring the global bug reporting system (std only).
-/// 
-/// This builder allows you to add templates, configure hyperlink behavior,
-/// and build the global configuration. Once built, the configuration is
-/// stored globally and used by the `bug!` macro.
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::{init, IssueTemplate, HyperlinkMode};
-/// 
-/// # #[cfg(feature = "std")] {
-/// let builder = init("owner", "repo")
-///     .add_template("error", IssueTemplate::new("Error Report", "An error occurred"))
-///     .hyperlinks(HyperlinkMode::Auto);
-/// # }
-/// ```
-pub struct BugReportConfigBuilder {
-    config: BugReportConfig,
-}
+impl TemplateFile {
+    /// Create a new template file with the given content.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - The template content where first line is the title
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("Bug Title\nBug description with {param}");
+    /// assert_eq!(template.content, "Bug Title\nBug description with {param}");
+    /// assert!(template.labels.is_empty());
+    /// ```
+    pub fn new(content: &'static str) -> Self {
+        Self {
+            content,
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            teams: Vec::new(),
+            milestone: None,
+            projects: Vec::new(),
+            fallback: None,
+            description: None,
+            param_docs: Vec::new(),
+            sha256: None,
+        }
+    }
 
-impl BugReportConfigBuilder {
-    /// Create a new configuration builder.
+    /// Add labels to this template file.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - Vector of label strings to apply to issues
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("Title\nBody")
+    ///     .with_labels(vec!["bug".to_string(), "urgent".to_string()]);
+    /// assert_eq!(template.labels.len(), 2);
+    /// ```
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Assign issues created from this template to GitHub users.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `assignees` - GitHub usernames; placeholders are filled like other fields
+    pub fn with_assignees(mut self, assignees: Vec<String>) -> Self {
+        self.assignees = assignees;
+        self
+    }
+
+    /// Assign issues created from this template to a team.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `team` - Name of a team registered with `team`
+    pub fn assign_team(mut self, team: impl Into<String>) -> Self {
+        self.teams.push(team.into());
+        self
+    }
+
+    /// Add issues created from this template to a milestone.
+    /// 
+    /// See `IssueTemplate::with_milestone`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `milestone` - Title of the milestone
+    pub fn with_milestone(mut self, milestone: impl Into<String>) -> Self {
+        self.milestone = Some(milestone.into());
+        self
+    }
+
+    /// Add issues created from this template to projects.
+    /// 
+    /// See `IssueTemplate::with_projects`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `projects` - Projects as `owner/number`, such as `octo-org/1`
+    pub fn with_projects(mut self, projects: Vec<String>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    /// Use another template when this one can't be filled.
+    /// 
+    /// See `IssueTemplate::with_fallback`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to fall back to
+    pub fn with_fallback(mut self, template_name: impl Into<String>) -> Self {
+        self.fallback = Some(template_name.into());
+        self
+    }
+
+    /// Describe what the template reports.
+    /// 
+    /// See `IssueTemplate::with_description`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `description` - A sentence on when to use the template
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Explain what a placeholder stands for.
+    /// 
+    /// See `IssueTemplate::document_param`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `placeholder` - Name of the placeholder, without braces
+    /// * `doc` - What the value should be
+    pub fn document_param(mut self, placeholder: impl Into<String>, doc: impl Into<String>) -> Self {
+        set_param_doc(&mut self.param_docs, placeholder.into(), doc.into());
+        self
+    }
+
+    /// Pin the content to a SHA-256 digest.
+    /// 
+    /// `BugReportConfig::validate` and `build` fail when the content no
+    /// longer has this digest, so a template edited without updating the
+    /// code that fills it is caught in CI. Get the digest to pin with
+    /// `content_hash`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `hex` - The expected digest, as 64 hex digits
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("Crash\n{details}")
+    ///     .with_sha256("0000000000000000000000000000000000000000000000000000000000000000");
+    /// assert_eq!(
+    ///     template.verify_sha256().unwrap_err(),
+    ///     format!(
+    ///         "SHA-256 mismatch: expected 0000000000000000000000000000000000000000000000000000000000000000, got {}",
+    ///         template.content_hash()
+    ///     )
+    /// );
+    /// ```
+    pub fn with_sha256(mut self, hex: impl Into<String>) -> Self {
+        self.sha256 = Some(hex.into());
+        self
+    }
+
+    /// The SHA-256 digest of the content, as 64 lowercase hex digits.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// assert_eq!(
+    ///     TemplateFile::new("hello").content_hash(),
+    ///     "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    /// );
+    /// ```
+    pub fn content_hash(&self) -> String {
+        sha256::hex_digest(self.content.as_bytes())
+    }
+
+    /// Check the content against the digest pinned with `with_sha256`.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - No digest is pinned, or the content has it
+    /// * `Err(String)` - The pinned digest is malformed or doesn't match
+    pub fn verify_sha256(&self) -> Result<(), String> {
+        match &self.sha256 {
+            Some(expected) => sha256::verify(self.content.as_bytes(), expected),
+            None => Ok(()),
+        }
+    }
+
+    /// Parse the template file content into an IssueTemplate.
+    /// 
+    /// The first line of the content becomes the title, and the remaining
+    /// lines become the body. Empty templates are rejected, with the error
+    /// naming the line and column of the problem. Use `lint` to find
+    /// malformed placeholders as well.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(IssueTemplate)` - Successfully parsed template
+    /// * `Err(String)` - Error message if parsing fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template_file = TemplateFile::new("Bug Report\nSomething is broken: {issue}");
+    /// let parsed = template_file.parse().unwrap();
+    /// assert_eq!(parsed.title, "Bug Report");
+    /// assert_eq!(parsed.body, "Something is broken: {issue}");
+    /// 
+    /// // Empty templates fail
+    /// let empty_template = TemplateFile::new("");
+    /// assert!(empty_template.parse().is_err());
+    /// 
+    /// let untitled = TemplateFile::new("\nBody without a title");
+    /// assert_eq!(untitled.parse().unwrap_err(), "line 1, column 1: Template must have a title on the first line");
+    /// ```
+    pub fn parse(&self) -> Result<IssueTemplate, String> {
+        let (title, body) = Self::split(self.content)?;
+        Ok(IssueTemplate {
+            title,
+            body,
+            labels: self.labels.clone(),
+            assignees: self.assignees.clone(),
+            teams: self.teams.clone(),
+            milestone: self.milestone.clone(),
+            projects: self.projects.clone(),
+            fallback: self.fallback.clone(),
+            description: self.description.clone(),
+            param_docs: self.param_docs.clone(),
+        })
+    }
+
+    /// Split template content into its title line and body.
+    pub(crate) fn split(content: &str) -> Result<(String, String), String> {
+        let lines: Vec<&str> = content.lines().collect();
+        
+        if lines.is_empty() {
+            return Err(lint::Diagnostic::new(1, 1, lint::DiagnosticKind::EmptyTemplate).to_string());
+        }
+
+        let title = lines[0].trim();
+        if title.is_empty() {
+            return Err(lint::Diagnostic::new(1, 1, lint::DiagnosticKind::MissingTitle).to_string());
+        }
+
+        let body = if lines.len() > 1 {
+            lines[1..].join("\n").trim().to_string()
+        } else {
+            String::new()
+        };
+        Ok((title.to_string(), body))
+    }
+
+    /// Check the template for every problem at once.
+    /// 
+    /// Reports the structural errors `parse` would fail with, plus each
+    /// malformed placeholder (an unclosed `{`, an empty `{}`, or an invalid
+    /// character in a placeholder name), in order of position. Malformed
+    /// placeholders don't make parsing fail; they are just left unfilled,
+    /// so this is the way to catch them before a report goes out.
+    /// 
+    /// # Returns
+    /// 
+    /// The diagnostics found, or an empty vector for a clean template.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("Bug in {module}\n\nError: {error message}");
+    /// let diagnostics = template.lint();
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(
+    ///     diagnostics[0].to_string(),
+    ///     "line 3, column 14: Invalid character ' ' in placeholder; names may only contain letters, digits, and '_'"
+    /// );
+    /// 
+    /// assert!(TemplateFile::new("Title\nBody with {param}").lint().is_empty());
+    /// ```
+    /// 
+    /// Braces that don't start with a name character, such as JSON, and
+    /// everything in fenced code blocks are not placeholders:
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("\
+    /// Crash in {module}
+    /// Sent { \"retry\": true } to the server.
+    /// 
+    /// ~~~rust
+    /// fn main() { let point = Point {x, y}; }
+    /// ~~~
+    /// 
+    /// Fields: {a, b}");
+    /// let diagnostics: Vec<String> = template.lint().iter().map(ToString::to_string).collect();
+    /// assert_eq!(diagnostics, [
+    ///     "line 8, column 11: Invalid character ',' in placeholder; names may only contain letters, digits, and '_'",
+    /// ]);
+    /// ```
+    pub fn lint(&self) -> Vec<lint::Diagnostic> {
+        let mut diagnostics = Vec::new();
+        match self.content.lines().next() {
+            None => diagnostics.push(lint::Diagnostic::new(1, 1, lint::DiagnosticKind::EmptyTemplate)),
+            Some(title) if title.trim().is_empty() => {
+                diagnostics.push(lint::Diagnostic::new(1, 1, lint::DiagnosticKind::MissingTitle))
+            }
+            Some(_) => {}
+        }
+        lint::check_placeholders(self.content, &mut diagnostics);
+        diagnostics
+    }
+
+    /// Validate that all required parameters are provided and no extra parameters exist.
+    /// 
+    /// This method extracts all placeholders from the template content and labels
+    /// and ensures that the provided parameters match exactly.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - Map of parameter names to values
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - All parameters are valid
+    /// * `Err(String)` - Error describing missing or unused parameters
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{TemplateFile, Params};
+    /// 
+    /// let template = TemplateFile::new("Bug: {component}\nError: {message}");
+    /// let mut params = Params::new();
+    /// params.insert("component".to_string(), "UI".to_string());
+    /// params.insert("message".to_string(), "Button broken".to_string());
+    /// 
+    /// assert!(template.validate_params(&params).is_ok());
+    /// 
+    /// // Missing parameter
+    /// let mut incomplete_params = Params::new();
+    /// incomplete_params.insert("component".to_string(), "UI".to_string());
+    /// assert!(template.validate_params(&incomplete_params).is_err());
+    /// 
+    /// // Placeholders in labels are required as well
+    /// let labeled = TemplateFile::new("Bug: {component}\nError: {message}")
+    ///     .with_labels(vec!["severity:{severity}".to_string()]);
+    /// assert_eq!(labeled.validate_params(&params).unwrap_err(), "Missing required parameter: severity");
+    /// ```
+    pub fn validate_params(&self, params: &Params) -> Result<(), String> {
+        fill::check_strict(&self.placeholders(), params)
+    }
+
+    /// Placeholders in the content followed by those only found in labels,
+    /// assignees, the milestone, or projects.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::TemplateFile;
+    /// 
+    /// let template = TemplateFile::new("Bug: {component}\nError: {message}")
+    ///     .with_labels(vec!["severity:{severity}".to_string()]);
+    /// assert_eq!(template.placeholders(), vec!["component", "message", "severity"]);
+    /// ```
+    pub fn placeholders(&self) -> Vec<String> {
+        let mut placeholders = extract_placeholders(self.content);
+        for field in self.labels.iter().chain(&self.assignees).chain(&self.milestone).chain(&self.projects) {
+            fill::collect_placeholders(field, &mut placeholders);
+        }
+        placeholders
+    }
+}
+
+impl IssueTemplate {
+    /// Create a new issue template with title and body.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `title` - The issue title template (supports placeholders)
+    /// * `body` - The issue body template (supports placeholders)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::IssueTemplate;
+    /// 
+    /// let template = IssueTemplate::new("Bug in {module}", "Error: {error_msg}");
+    /// assert_eq!(template.title, "Bug in {module}");
+    /// assert_eq!(template.body, "Error: {error_msg}");
+    /// assert!(template.labels.is_empty());
+    /// ```
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            teams: Vec::new(),
+            milestone: None,
+            projects: Vec::new(),
+            fallback: None,
+            description: None,
+            param_docs: Vec::new(),
+        }
+    }
+
+    /// Create an issue template from a template file with parameter substitution.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_file` - The template file to parse
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(IssueTemplate)` - Successfully created template with parameters filled
+    /// * `Err(String)` - Error from validation or parsing
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{TemplateFile, IssueTemplate, Params};
+    /// 
+    /// let template_file = TemplateFile::new("Bug: {type}\nDescription: {desc}");
+    /// let mut params = Params::new();
+    /// params.insert("type".to_string(), "Crash".to_string());
+    /// params.insert("desc".to_string(), "App crashes on startup".to_string());
+    /// 
+    /// let issue = IssueTemplate::from_template_file(&template_file, &params).unwrap();
+    /// assert_eq!(issue.title, "Bug: Crash");
+    /// assert_eq!(issue.body, "Description: App crashes on startup");
+    /// ```
+    pub fn from_template_file(template_file: &TemplateFile, params: &Params) -> Result<Self, String> {
+        template_file.validate_params(params)?;
+        let parsed_template = template_file.parse()?;
+        Ok(parsed_template.fields().fill(params))
+    }
+
+    /// Add labels to this issue template.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - Vector of label strings to apply to the GitHub issue
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::IssueTemplate;
+    /// 
+    /// let template = IssueTemplate::new("Bug Title", "Bug description")
+    ///     .with_labels(vec!["bug".to_string(), "high-priority".to_string()]);
+    /// assert_eq!(template.labels.len(), 2);
+    /// assert!(template.labels.contains(&"bug".to_string()));
+    /// ```
+    pub fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Assign issues created from this template to GitHub users.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `assignees` - GitHub usernames; placeholders are filled like other fields
+    pub fn with_assignees(mut self, assignees: Vec<String>) -> Self {
+        self.assignees = assignees;
+        self
+    }
+
+    /// Assign issues created from this template to a team.
+    /// 
+    /// The team is expanded into assignees when a report is generated, so
+    /// routing can change without touching templates.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `team` - Name of a team registered with `team`
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .team("ui-team", ["alice", "bob"])
+    ///     .add_template("ui_glitch", IssueTemplate::new("UI glitch", "").assign_team("ui-team"));
+    /// 
+    /// let report = handle.create_report("ui_glitch", &Params::new()).unwrap();
+    /// assert_eq!(report.assignees, vec!["alice".to_string(), "bob".to_string()]);
+    /// assert!(report.url.ends_with("&assignees=alice%2Cbob"));
+    /// ```
+    pub fn assign_team(mut self, team: impl Into<String>) -> Self {
+        self.teams.push(team.into());
+        self
+    }
+
+    /// Add issues created from this template to a milestone.
+    /// 
+    /// Placeholders in the milestone are filled like the title's, and the
+    /// result is written to the URL's `milestone` parameter; GitHub ignores
+    /// it unless the user can set milestones in the repository.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `milestone` - Title of the milestone
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "")
+    ///         .with_assignees(vec!["octocat".to_string()])
+    ///         .with_milestone("v2.0 release")
+    ///         .with_projects(vec!["octo-org/1".to_string(), "octo-org/44".to_string()]));
+    /// 
+    /// let report = handle.create_report("crash", &Params::new()).unwrap();
+    /// assert_eq!(report.milestone.as_deref(), Some("v2.0 release"));
+    /// assert!(report.url.ends_with("&assignees=octocat&milestone=v2.0+release&projects=octo-org%2F1,octo-org%2F44"));
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("regression", IssueTemplate::new("Regression", "")
+    ///         .with_milestone("v{version}")
+    ///         .with_projects(vec!["octo-org/{board}".to_string()]))
+    ///     .deny_unfilled_placeholders(true);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("version", "2.1");
+    /// assert_eq!(handle.create_report("regression", &params).unwrap_err(), "Template 'regression' has placeholders without a value: {board}");
+    /// params.insert("board", "7");
+    /// let report = handle.create_report("regression", &params).unwrap();
+    /// assert_eq!(report.milestone.as_deref(), Some("v2.1"));
+    /// assert_eq!(report.projects, vec!["octo-org/7".to_string()]);
+    /// ```
+    pub fn with_milestone(mut self, milestone: impl Into<String>) -> Self {
+        self.milestone = Some(milestone.into());
+        self
+    }
+
+    /// Add issues created from this template to projects.
+    /// 
+    /// Placeholders in the projects are filled like the title's, and the
+    /// results are written to the URL's `projects` parameter; GitHub ignores
+    /// them unless the user can add issues to the projects.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `projects` - Projects as `owner/number`, such as `octo-org/1`
+    pub fn with_projects(mut self, projects: Vec<String>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    /// Use another template when this one can't be filled.
+    /// 
+    /// If filling fails, for example because a strict template file is
+    /// missing a parameter, the report is made from the fallback template
+    /// instead, which may have a fallback of its own. The report's
+    /// `template_name` is the template actually used, and its warnings
+    /// include a `Warning::UsedFallback`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to fall back to
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, TemplateFile};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("generic", IssueTemplate::new("Something went wrong", "{details}"))
+    ///     .add_template_file("crash", TemplateFile::new("Crash in {screen}\n{details}").with_fallback("generic"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("details", "null pointer");
+    /// let report = handle.create_report("crash", &params).unwrap();
+    /// assert_eq!(report.template_name, "generic");
+    /// assert_eq!(report.body, "null pointer");
+    /// ```
+    pub fn with_fallback(mut self, template_name: impl Into<String>) -> Self {
+        self.fallback = Some(template_name.into());
+        self
+    }
+
+    /// Describe what the template reports.
+    /// 
+    /// The description is for people choosing a template: the debug menu,
+    /// `bug-cli --list-templates`, and anything else listing templates
+    /// through `BugReportConfig::description` show it. It is not part of
+    /// the issue.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `description` - A sentence on when to use the template
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash: {error_type}", "")
+    ///         .with_description("The application panicked or aborted"));
+    /// assert_eq!(handle.config().description("crash"), Some("The application panicked or aborted"));
+    /// ```
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Explain what a placeholder stands for.
+    /// 
+    /// Prompts show the explanation when asking for the value, so whoever
+    /// fills in the template knows what is wanted. Documenting a
+    /// placeholder again replaces its explanation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `placeholder` - Name of the placeholder, without braces
+    /// * `doc` - What the value should be
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash: {error_type}", "")
+    ///         .document_param("error_type", "The Rust error type name"));
+    /// assert_eq!(handle.config().param_doc("crash", "error_type"), Some("The Rust error type name"));
+    /// assert_eq!(handle.config().param_doc("crash", "module"), None);
+    /// ```
+    pub fn document_param(mut self, placeholder: impl Into<String>, doc: impl Into<String>) -> Self {
+        set_param_doc(&mut self.param_docs, placeholder.into(), doc.into());
+        self
+    }
+
+    /// Fill template placeholders with provided parameters.
+    /// 
+    /// This method replaces all `{placeholder}` patterns in the title, body,
+    /// and labels with the corresponding values from the params map, in a
+    /// single pass: values are inserted verbatim and placeholders appearing
+    /// inside a value are not substituted again. Placeholders without a
+    /// value are left in place.
+    /// 
+    /// A placeholder name may only contain letters, digits, and `_`; a
+    /// parameter whose key has other characters is never substituted. A `{`
+    /// that doesn't start a placeholder is kept as written, so `{{name}}`
+    /// fills the inner `{name}`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - Map of parameter names to replacement values
+    /// 
+    /// # Returns
+    /// 
+    /// A new `IssueTemplate` with placeholders replaced by parameter values.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{IssueTemplate, Params};
+    /// 
+    /// let template = IssueTemplate::new("Error in {component}", "Details: {message}");
+    /// let mut params = Params::new();
+    /// params.insert("component".to_string(), "parser".to_string());
+    /// params.insert("message".to_string(), "Invalid syntax".to_string());
+    /// 
+    /// let filled = template.fill_params(&params);
+    /// assert_eq!(filled.title, "Error in parser");
+    /// assert_eq!(filled.body, "Details: Invalid syntax");
+    /// 
+    /// // Labels are filled too, and values are never re-scanned
+    /// let template = IssueTemplate::new("{title}", "{missing}")
+    ///     .with_labels(vec!["area:{component}".to_string()]);
+    /// let mut params = Params::new();
+    /// params.insert("title".to_string(), "Literal {component}".to_string());
+    /// params.insert("component".to_string(), "parser".to_string());
+    /// 
+    /// let filled = template.fill_params(&params);
+    /// assert_eq!(filled.title, "Literal {component}");
+    /// assert_eq!(filled.body, "{missing}");
+    /// assert_eq!(filled.labels, vec!["area:parser".to_string()]);
+    /// 
+    /// // Only letters, digits, and `_` make a name; extra braces are kept
+    /// let template = IssueTemplate::new("{{name}} and {a{name}", "{my-key} {name}");
+    /// let mut params = Params::new();
+    /// params.insert("name".to_string(), "value".to_string());
+    /// params.insert("my-key".to_string(), "ignored".to_string());
+    /// 
+    /// let filled = template.fill_params(&params);
+    /// assert_eq!(filled.title, "{value} and {avalue");
+    /// assert_eq!(filled.body, "{my-key} value");
+    /// ```
+    pub fn fill_params(&self, params: &Params) -> IssueTemplate {
+        self.fields().fill(params)
+    }
+
+    /// Placeholders in the title, body, labels, and assignees, in order of appearance.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::IssueTemplate;
+    /// 
+    /// let template = IssueTemplate::new("Bug in {module}", "Error: {message}")
+    ///     .with_labels(vec!["area:{module}".to_string()]);
+    /// assert_eq!(template.placeholders(), vec!["module", "message"]);
+    /// ```
+    pub fn placeholders(&self) -> Vec<String> {
+        self.fields().placeholders()
+    }
+
+    /// Validate that the provided parameters match the template's placeholders.
+    /// 
+    /// This is the check template files are filled under: every placeholder
+    /// needs a parameter, and every parameter a placeholder. `fill_params`
+    /// doesn't validate, so call this first, or set `strict_params` on the
+    /// configuration to validate every report.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `params` - Map of parameter names to values
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - All parameters are valid
+    /// * `Err(String)` - Error describing missing or unused parameters
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{IssueTemplate, Params};
+    /// 
+    /// let template = IssueTemplate::new("Bug: {component}", "Error: {message}");
+    /// let mut params = Params::new();
+    /// params.insert("component", "UI");
+    /// assert_eq!(template.validate_params(&params).unwrap_err(), "Missing required parameter: message");
+    /// 
+    /// params.insert("message", "Button broken");
+    /// assert!(template.validate_params(&params).is_ok());
+    /// ```
+    pub fn validate_params(&self, params: &Params) -> Result<(), String> {
+        fill::check_strict(&self.placeholders(), params)
+    }
+
+    fn fields(&self) -> fill::Fields<'_, String> {
+        fill::Fields {
+            title: &self.title,
+            body: &self.body,
+            labels: &self.labels,
+            assignees: &self.assignees,
+            teams: &self.teams,
+            milestone: self.milestone.as_deref(),
+            projects: &self.projects,
+        }
+    }
+}
+
+/// Extract placeholder names from template content.
+/// 
+/// This function scans the content for `{placeholder}` patterns and returns
+/// a vector of unique placeholder names. Only valid identifiers (alphanumeric
+/// characters and underscores) are recognized as placeholders.
+/// 
+/// # Arguments
+/// 
+/// * `content` - The template content to scan
+/// 
+/// # Returns
+/// 
+/// A vector of unique placeholder names found in the content.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::extract_placeholders;
+/// 
+/// let content = "Error in {module}: {message}. See {module} docs.";
+/// let placeholders = extract_placeholders(content);
+/// assert_eq!(placeholders.len(), 2);
+/// assert!(placeholders.contains(&"module".to_string()));
+/// assert!(placeholders.contains(&"message".to_string()));
+/// 
+/// // Invalid placeholders with spaces are ignored
+/// let invalid_content = "Invalid: {123} {with space} {valid_name}";
+/// let valid_placeholders = extract_placeholders(invalid_content);
+/// assert_eq!(valid_placeholders, vec!["123".to_string(), "valid_name".to_string()]);
+/// ```
+pub fn extract_placeholders(content: &str) -> Vec<String> {
+    let mut placeholders = Vec::new();
+    fill::collect_placeholders(content, &mut placeholders);
+    placeholders
+}
+
+/// Macro to create a `TemplateFile` from a file path at compile time.
+/// 
+/// This macro uses `include_str!` to embed the template content directly into
+/// the binary at compile time. It supports an optional `labels` parameter to
+/// add GitHub issue labels.
+/// 
+/// # Syntax
+/// 
+/// - `template_file!("path/to/template.txt")` - Basic usage
+/// - `template_file!("path/to/template.txt", labels: ["bug", "urgent"])` - With labels
+/// - `template_file!("path/to/template.txt", sha256 = "9f86…")` - Pinned to a digest, see `TemplateFile::with_sha256`
+/// - `template_file!("path/to/template.txt", labels: ["bug"], sha256 = "9f86…")` - Both
+/// 
+/// # Examples
+/// 
+/// ```ignore
+/// use bug::template_file;
+/// 
+/// // Basic usage (assumes you have a template.txt file)
+/// let template = template_file!("templates/bug_report.txt");
+/// 
+/// // With labels
+/// let labeled_template = template_file!(
+///     "templates/crash_report.txt", 
+///     labels: ["bug", "crash", "high-priority"]
+/// );
+/// ```
+/// 
+/// # Template File Format
+/// 
+/// Template files should have the title on the first line and the body on subsequent lines:
+/// 
+/// ```text
+/// Bug Report: {component}
+/// ## Description
+/// {description}
+/// 
+/// ## Steps to Reproduce
+/// {steps}
+/// ```
+#[macro_export]
+macro_rules! template_file {
+    ($path:expr) => {
+        $crate::TemplateFile::new(include_str!($path))
+    };
+    ($path:expr, labels: [$($label:expr),* $(,)?]) => {
+        $crate::TemplateFile::new(include_str!($path))
+            .with_labels(vec![$($label.to_string()),*])
+    };
+    ($path:expr, sha256 = $hex:expr) => {
+        $crate::TemplateFile::new(include_str!($path)).with_sha256($hex)
+    };
+    ($path:expr, labels: [$($label:expr),* $(,)?], sha256 = $hex:expr) => {
+        $crate::TemplateFile::new(include_str!($path))
+            .with_labels(vec![$($label.to_string()),*])
+            .with_sha256($hex)
+    };
+}
+
+/// Initialize a bug report configuration builder (std only).
+/// 
+/// This function creates a new configuration builder that allows you to set up
+/// templates and options before building the global configuration. This is only
+/// available with the "std" feature.
+/// 
+/// # Arguments
+/// 
+/// * `github_owner` - GitHub username or organization name
+/// * `github_repo` - GitHub repository name
+/// 
+/// # Returns
+/// 
+/// A `BugReportConfigBuilder` that can be used to configure templates and options.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init, IssueTemplate};
+/// 
+/// # #[cfg(feature = "std")] {
+/// let result = init("octocat", "Hello-World")
+///     .add_template("bug", IssueTemplate::new("Bug Report", "Something is broken"))
+///     .hyperlinks(bug::HyperlinkMode::Always)
+///     .build();
+/// # }
+/// ```
+pub fn init(github_owner: impl Into<String>, github_repo: impl Into<String>) -> BugReportConfigBuilder {
+    BugReportConfigBuilder::new(github_owner.into(), github_repo.into())
+}
+
+/// Initialize a bug report handle (works in both std and no_std).
+/// 
+/// This function creates a handle-based configuration that doesn't rely on
+/// global state. It can be used in both std and no_std environments.
+/// 
+/// # Arguments
+/// 
+/// * `github_owner` - GitHub username or organization name
+/// * `github_repo` - GitHub repository name
+/// 
+/// # Returns
+/// 
+/// A `BugReportHandle` that can be used to generate bug reports.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init_handle, IssueTemplate};
+/// 
+/// let handle = init_handle("octocat", "Hello-World")
+///     .add_template("crash", IssueTemplate::new("Crash Report", "App crashed: {reason}"))
+///     .hyperlinks(bug::HyperlinkMode::Never);
+/// 
+/// // Use with bug_with_handle! macro
+/// ```
+pub fn init_handle(github_owner: impl Into<String>, github_repo: impl Into<String>) -> BugReportHandle {
+    BugReportHandle::new(github_owner.into(), github_repo.into())
+}
+
+/// Builder for configuring the global bug reporting system (std only).
+/// 
+/// This builder allows you to add templates, configure hyperlink behavior,
+/// and build the global configuration. Once built, the configuration is
+/// stored globally and used by the `bug!` macro.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init, IssueTemplate, HyperlinkMode};
+/// 
+/// # #[cfg(feature = "std")] {
+/// let builder = init("owner", "repo")
+///     .add_template("error", IssueTemplate::new("Error Report", "An error occurred"))
+///     .hyperlinks(HyperlinkMode::Auto);
+/// # }
+/// ```
+pub struct BugReportConfigBuilder {
+    config: BugReportConfig,
+}
+
+impl BugReportConfigBuilder {
+    /// Create a new configuration builder.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `github_owner` - GitHub username or organization
+    /// * `github_repo` - GitHub repository name
+    fn new(github_owner: String, github_repo: String) -> Self {
+        Self {
+            config: BugReportConfig::new(github_owner, github_repo),
+        }
+    }
+
+    /// Add an issue template to the configuration.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template
+    /// * `template` - The issue template to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate};
+    /// 
+    /// # #[cfg(feature = "std")] {
+    /// let builder = init("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug Report", "Found a bug"));
+    /// # }
+    /// ```
+    pub fn add_template(mut self, name: impl Into<String>, template: IssueTemplate) -> Self {
+        self.config.register_template(name.into(), template);
+        self
+    }
+
+    /// Add a template file to the configuration.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template file
+    /// * `template_file` - The template file to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, TemplateFile};
+    /// 
+    /// # #[cfg(feature = "std")] {
+    /// let builder = init("owner", "repo")
+    ///     .add_template_file("crash", TemplateFile::new("Crash Report\nApp crashed"));
+    /// # }
+    /// ```
+    pub fn add_template_file(mut self, name: impl Into<String>, template_file: TemplateFile) -> Self {
+        self.config.register_template_file(name.into(), template_file);
+        self
+    }
+
+    /// Register an issue template and return its id.
+    /// 
+    /// This is the non-chaining form of `add_template`. The returned id can
+    /// be passed to `generate_github_url_by_id` once the configuration is built.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template
+    /// * `template` - The issue template to add
+    pub fn register_template(&mut self, name: impl Into<String>, template: IssueTemplate) -> TemplateId {
+        self.config.register_template(name.into(), template)
+    }
+
+    /// Register a template file and return its id.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template file
+    /// * `template_file` - The template file to add
+    pub fn register_template_file(&mut self, name: impl Into<String>, template_file: TemplateFile) -> TemplateId {
+        self.config.register_template_file(name.into(), template_file)
+    }
+
+    /// Add every template file from an embedded directory.
+    /// 
+    /// Templates are named after their path relative to the directory,
+    /// without the extension; see the `template_dir` module for details.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dir` - A directory embedded with `include_dir::include_dir!`
+    #[cfg(feature = "include_dir")]
+    pub fn add_template_dir(mut self, dir: &'static include_dir::Dir<'static>) -> Self {
+        for (name, template_file) in template_dir::templates(dir) {
+            self.config.register_template_file(name, template_file);
+        }
+        self
+    }
+
+    /// See `BugReportHandle::namespace`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `namespace` - Prefix for this configuration's template names, such as the crate name
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.config.namespace = Some(namespace.into());
+        self
+    }
+
+    /// See `BugReportHandle::merge`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The handle whose templates to add
+    pub fn merge(mut self, other: &BugReportHandle) -> Self {
+        self.config.merge_templates(&other.config);
+        self
+    }
+
+    /// Map an error code to the template used to report it.
+    /// 
+    /// Reports created for the code carry it as a label, and templates with
+    /// an `{error_code}` placeholder receive it automatically. With
+    /// `allowed_labels`, allow the codes too (for example with
+    /// `allow_label_prefix("E")`) or the code label is dropped.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - Stable error code, such as `"E0420"`
+    /// * `template_name` - Name of the template to report the code with
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate};
+    /// 
+    /// let builder = init("owner", "repo")
+    ///     .add_template("parser_crash", IssueTemplate::new("Parser crash {error_code}", ""))
+    ///     .map_error_code("E0420", "parser_crash");
+    /// ```
+    pub fn map_error_code(mut self, code: impl Into<String>, template_name: impl Into<String>) -> Self {
+        self.config.error_codes.insert(code.into(), template_name.into());
+        self
+    }
+
+    /// Register a team of GitHub users.
+    /// 
+    /// Templates that call `assign_team` with this name are assigned to the
+    /// team's members, see `team_assignment`. Registering a name again
+    /// replaces the team.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Team name used by templates
+    /// * `members` - GitHub usernames in the team
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate, TeamAssignment};
+    /// 
+    /// let builder = init("owner", "repo")
+    ///     .team("ui-team", ["alice", "bob"])
+    ///     .team_assignment(TeamAssignment::RoundRobin)
+    ///     .add_template("ui_glitch", IssueTemplate::new("UI glitch", "").assign_team("ui-team"));
+    /// assert!(builder.validate().is_ok());
+    /// ```
+    pub fn team<I, S>(mut self, name: impl Into<String>, members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.teams.insert(name.into(), members.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Choose how teams are expanded into assignees.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - Assign whole teams, or one member per report in rotation
+    pub fn team_assignment(mut self, mode: TeamAssignment) -> Self {
+        self.config.team_assignment = mode;
+        self
+    }
+
+    /// Assign reports to the owners of the file they were made from.
+    /// 
+    /// Requires the `codeowners` feature. Reports that carry a location
+    /// (`bug!`, `report_bug_at`, and friends) get the file's user owners as
+    /// assignees and its team owners as labels; see the `codeowners` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - Contents of a CODEOWNERS file, usually from `include_str!`
+    #[cfg(feature = "codeowners")]
+    pub fn codeowners(mut self, content: &str) -> Self {
+        self.config.codeowners = Some(codeowners::CodeOwners::parse(content));
+        self
+    }
+
+    /// Report only a fraction of a template's occurrences.
+    /// 
+    /// Each time the template is reported, the occurrence is emitted with
+    /// probability `rate` and skipped otherwise. Emitted reports carry the
+    /// rate in `{sample_rate}`, or in a note at the end of the body if the
+    /// template has no such placeholder. See the `sampling` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to sample
+    /// * `rate` - Fraction of occurrences to report, from 0.0 to 1.0
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("cache_miss", IssueTemplate::new("Cache miss", "Key not found"))
+    ///     .sample_rate("cache_miss", 0.01)
+    ///     .sample_rng(|| 0);
+    /// 
+    /// let url = handle.report_bug_at("cache_miss", &Params::new(), Location::new(file!(), line!()));
+    /// assert!(url.contains("sample%20rate%200.01"));
+    /// ```
+    pub fn sample_rate(mut self, template_name: impl Into<String>, rate: f64) -> Self {
+        self.config.sample_rates.insert(template_name.into(), rate.clamp(0.0, 1.0));
+        self
+    }
+
+    /// See `BugReportHandle::template_target`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// * `target` - Where its reports are filed
+    pub fn template_target(mut self, template_name: impl Into<String>, target: target::TemplateTarget) -> Self {
+        self.config.template_targets.insert(template_name.into(), target);
+        self
+    }
+
+    /// Set the RNG that sample rates roll against.
+    /// 
+    /// With `std` this replaces `sampling::DefaultRng`. In no_std builds
+    /// sample rates have no effect until an RNG is set.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rng` - Source of random numbers, such as a closure returning `u64`
+    pub fn sample_rng(mut self, rng: impl SampleRng + 'static) -> Self {
+        self.config.sampler.set_rng(rng);
+        self
+    }
+
+    /// Set the clock that fills `{timestamp}`.
+    /// 
+    /// Without a clock, `{timestamp}` is left unfilled.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `clock` - Source of timestamps, such as a closure reading a tick counter
+    pub fn clock(mut self, clock: impl time::Clock + 'static) -> Self {
+        self.config.time.set(clock);
+        self
+    }
+
+    /// Fill `{source_excerpt}` with the source lines around the call site.
+    /// 
+    /// The excerpt shows three lines before and after the reporting line,
+    /// read from the source file when the report is made. It is only
+    /// available in std debug builds run where the sources are on disk;
+    /// elsewhere the placeholder says the source is not available.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `include` - Whether to read source excerpts
+    pub fn include_source_excerpt(mut self, include: bool) -> Self {
+        self.config.include_source_excerpt = include;
+        self
+    }
+
+    /// Never report when `predicate` returns true.
+    /// 
+    /// The predicate gets the template name and the parameters given at the
+    /// call site. See the `suppress` module for where suppression applies.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `predicate` - Returns true for reports to suppress
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("io_error", IssueTemplate::new("I/O error", "{kind}"))
+    ///     .suppress_if(|_template, params| params.get("kind").is_some_and(|kind| kind == "Interrupted"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("kind".to_string(), "Interrupted".to_string());
+    /// assert!(handle.report_bug_at("io_error", &params, Location::new(file!(), line!())).is_empty());
+    /// ```
+    pub fn suppress_if(mut self, predicate: impl Fn(&str, &Params) -> bool + Send + Sync + 'static) -> Self {
+        self.config.suppressions.add_predicate(predicate);
+        self
+    }
+
+    /// Never report when `rule` matches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rule` - A template glob and parameter globs, see `SuppressionRule`
+    pub fn suppress(mut self, rule: suppress::SuppressionRule) -> Self {
+        self.config.suppressions.rules.push(rule);
+        self
+    }
+
+    /// Pass every reported bug through `middleware`, after the middleware added before.
+    /// 
+    /// See the `middleware` module for what middleware can do and where
+    /// it runs.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `middleware` - A `ReportMiddleware` or a closure taking the report and the rest of the pipeline
+    pub fn middleware(mut self, middleware: impl middleware::ReportMiddleware + 'static) -> Self {
+        self.config.middleware.push(middleware);
+        self
+    }
+
+    /// Also write every reported bug to `sink`, after the sinks added before.
+    /// 
+    /// See the `sink` module for what sinks receive and when.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sink` - Where to write, in which format, and at what verbosity
+    pub fn add_sink(mut self, sink: sink::Sink) -> Self {
+        self.config.sinks.push(sink);
+        self
+    }
+
+    /// Restrict the labels reports may carry.
+    /// 
+    /// `build` fails if a template has a label outside this list, and labels
+    /// produced at runtime (from placeholders or error codes) that are not
+    /// allowed are dropped from the issue, so a typo can't create a new
+    /// label on GitHub. Use `allow_label_prefix` for families of dynamic
+    /// labels.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - Every label reports may use
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate};
+    /// 
+    /// let builder = init("owner", "repo")
+    ///     .allowed_labels(["bug", "crash", "perf"])
+    ///     .add_template("crash", IssueTemplate::new("Crash", "").with_labels(vec!["crahs".to_string()]));
+    /// assert!(builder.validate().is_err());
+    /// ```
+    pub fn allowed_labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.allowed_labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Allow every label starting with `prefix`, in addition to `allowed_labels`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - Prefix of allowed dynamic labels, such as `"area:"`
+    pub fn allow_label_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config.allowed_label_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Print a warning to stderr whenever a disallowed label is dropped (std only).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `warn` - Whether to warn
+    pub fn warn_on_disallowed_labels(mut self, warn: bool) -> Self {
+        self.config.warn_on_disallowed_labels = warn;
+        self
+    }
+
+    /// Check the configuration without installing it.
+    /// 
+    /// See `BugReportConfig::validate`.
+    pub fn validate(&self) -> Result<(), String> {
+        self.config.validate()
+    }
+
+    /// Configure hyperlink behavior for terminal output.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - How to handle hyperlinks in output
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, HyperlinkMode};
+    /// 
+    /// # #[cfg(feature = "std")] {
+    /// let builder = init("owner", "repo")
+    ///     .hyperlinks(HyperlinkMode::Always);
+    /// # }
+    /// ```
+    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
+        self.config.use_hyperlinks = mode;
+        self
+    }
+
+    /// See `BugReportHandle::base_url`.
+    /// 
+    /// A malformed URL makes `build` fail; `validate` says what is wrong.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `base_url` - Scheme and host of the GitHub server, such as `https://github.example.com`
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url: String = base_url.into();
+        self.config.base_url = Some(String::from(base_url.trim_end_matches('/')));
+        self
+    }
+
+    /// Collect reported bugs into one summary instead of printing each one.
+    /// 
+    /// Reports are recorded in `summary::global()` and printed by
+    /// `flush_summary`, typically at the end of a batch job.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `summarize` - Whether to summarize
+    pub fn summarize(mut self, summarize: bool) -> Self {
+        self.config.summarize = summarize;
+        self
+    }
+
+    /// List the warnings found while generating a report in the banner.
+    /// 
+    /// Warnings are always attached to the generated `BugReport`; this only
+    /// controls whether the banner prints them.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `show` - Whether to print warnings
+    pub fn show_warnings(mut self, show: bool) -> Self {
+        self.config.show_warnings = show;
+        self
+    }
+
+    /// Print the configuration's `digest` in the banner.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `show` - Whether to print the digest
+    pub fn show_config_digest(mut self, show: bool) -> Self {
+        self.config.show_config_digest = show;
+        self
+    }
+
+    /// Fail to generate reports that would leave placeholders unfilled.
+    /// 
+    /// Plain `IssueTemplate`s are filled leniently: a placeholder without a
+    /// parameter stays in the issue as literal `{name}` text and is only
+    /// reported as a `Warning::UnfilledPlaceholder`. With this set, such a
+    /// report fails instead, which catches templates and call sites that
+    /// have drifted apart. Template files always fail.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `deny` - Whether unfilled placeholders are an error
+    pub fn deny_unfilled_placeholders(mut self, deny: bool) -> Self {
+        self.config.deny_unfilled_placeholders = deny;
+        self
+    }
+
+    /// Validate the parameters of every template, as template files are.
+    /// 
+    /// By default an `IssueTemplate` is filled leniently: unused parameters
+    /// and missing placeholders become warnings. With this set, they fail
+    /// the report with the error `validate_params` gives, whichever kind of
+    /// template is used.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strict` - Whether inline templates are validated
+    pub fn strict_params(mut self, strict: bool) -> Self {
+        self.config.strict_params = strict;
+        self
+    }
+
+    /// Minify issue bodies before encoding them into the URL.
+    /// 
+    /// HTML comments are stripped, trailing whitespace is trimmed, and runs
+    /// of blank lines left by empty parameters collapse into one. Fenced
+    /// code blocks keep their blank lines and comments, and comments holding
+    /// a `{fingerprint}` marker are kept. Markdown hard line breaks made
+    /// with trailing spaces are lost; use a trailing `\` instead.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `minify` - Whether to minify bodies
+    pub fn minify_body(mut self, minify: bool) -> Self {
+        self.config.minify_body = minify;
+        self
+    }
+
+    /// Choose how spaces in the issue body are encoded into the URL.
+    /// 
+    /// Bodies encode spaces as `%20` by default, which every target reads
+    /// as a space. `SpaceEncoding::Plus` restores the `+` of earlier
+    /// versions, for tooling that compares URLs byte for byte. Titles,
+    /// labels, and assignees always use `+`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spaces` - How to encode spaces in the body
+    pub fn body_space_encoding(mut self, spaces: url_encode::SpaceEncoding) -> Self {
+        self.config.body_space_encoding = spaces;
+        self
+    }
+
+    /// Choose how labels are written into the URL.
+    /// 
+    /// GitHub takes one comma-separated `labels` parameter, the default.
+    /// `LabelEncoding::Repeated` writes a `labels[]` parameter per label,
+    /// for trackers that prefer it and for labels containing commas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `encoding` - How labels are written
+    pub fn label_encoding(mut self, encoding: url_encode::LabelEncoding) -> Self {
+        self.config.label_encoding = encoding;
+        self
+    }
+
+    /// Choose what reporting a template name that matches no template does.
+    /// 
+    /// By default it fails with an error. With
+    /// `OnMissingTemplate::LinkChooser` the report links to the
+    /// repository's issue template chooser instead, so a misspelled or
+    /// removed template still leaves the user somewhere to file the bug.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - What to do about unknown template names
+    pub fn on_missing_template(mut self, policy: OnMissingTemplate) -> Self {
+        self.config.on_missing_template = policy;
+        self
+    }
+
+    /// Localize the text the crate writes into issue bodies.
+    /// 
+    /// Templates are written in whatever language the maintainers use, but
+    /// the note on sampled reports and the stand-in for a missing source
+    /// excerpt are English unless replaced here. See `messages::Messages`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `messages` - The text to use
+    pub fn body_messages(mut self, messages: messages::Messages) -> Self {
+        self.config.messages = messages;
+        self
+    }
+
+    /// Match requested template names loosely, such as ignoring case.
+    /// 
+    /// Whatever the matching, a name that matches no template fails with
+    /// an error suggesting the closest registered name, unless
+    /// `on_missing_template` says otherwise.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `matching` - How names are matched
+    pub fn name_matching(mut self, matching: NameMatching) -> Self {
+        self.config.registry.set_matching(matching);
+        self
+    }
+
+    /// Withhold report details until the user consents with `bug::set_consent(true)`.
+    /// 
+    /// Without consent, reports are still counted, summarized, and backed
+    /// off, but the banner prints neither parameters nor the issue URL and
+    /// asks the user to enable bug reporting instead, and the URL is not
+    /// returned. See the `consent` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `require` - Whether consent is required
+    pub fn require_consent(mut self, require: bool) -> Self {
+        self.config.require_consent = require;
+        self
+    }
+
+    /// Set how a placeholder's value is substituted in every template.
+    /// 
+    /// See `param::ParamPolicy`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `placeholder` - Name of the placeholder
+    /// * `policy` - How its value is substituted
+    pub fn param_policy(mut self, placeholder: impl Into<String>, policy: param::ParamPolicy) -> Self {
+        self.config.param_policies.set(placeholder.into(), policy);
+        self
+    }
+
+    /// Set how a placeholder's value is substituted in one template.
+    /// 
+    /// Overrides `param_policy` for that template.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// * `placeholder` - Name of the placeholder
+    /// * `policy` - How its value is substituted
+    pub fn template_param_policy(
+        mut self,
+        template_name: impl Into<String>,
+        placeholder: impl Into<String>,
+        policy: param::ParamPolicy,
+    ) -> Self {
+        self.config
+            .param_policies
+            .set_for_template(template_name.into(), placeholder.into(), policy);
+        self
+    }
+
+    /// Key the hashes of `ParamPolicy::HashValue` with a secret.
+    /// 
+    /// Without a key, anyone can hash candidate values and compare them
+    /// with a report. Keep the key the same across releases to keep hashes
+    /// comparable.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `k0` - First half of the key
+    /// * `k1` - Second half of the key
+    pub fn param_hash_key(mut self, k0: u64, k1: u64) -> Self {
+        self.config.param_policies.set_key(k0, k1);
+        self
+    }
+
+    /// Search GitHub for an open duplicate before suggesting a new issue (feature `api`).
+    /// 
+    /// When a report matches an open issue, the banner prints that issue's
+    /// URL instead of a new-issue link, and it is returned in place of the
+    /// new-issue URL. See the `api` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `api` - The GitHub API to search
+    #[cfg(feature = "api")]
+    pub fn github_api(mut self, api: api::GitHubApi) -> Self {
+        self.config.github_api = Some(api);
+        self
+    }
+
+    /// Download templates from `source` in `add_remote_template` (feature `remote`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `source` - The HTTP client and timeout to fetch with
+    #[cfg(feature = "remote")]
+    pub fn remote_source(mut self, source: remote::RemoteSource) -> Self {
+        self.config.remote_source = Some(source);
+        self
+    }
+
+    /// Fill `{user_agent}`, `{platform}`, and `{viewport}` from `environment` (feature `wasm`).
+    /// 
+    /// See the `wasm` module for reading them with `web-sys`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `environment` - A `BrowserEnvironment` or a closure returning a `BrowserContext`
+    #[cfg(feature = "wasm")]
+    pub fn browser_environment(mut self, environment: impl wasm::BrowserEnvironment + 'static) -> Self {
+        self.config.browser = Some(wasm::BrowserSource::new(environment));
+        self
+    }
+
+    /// Fill `{device}` and the other device placeholders from `context` (features `android` and `ios`).
+    /// 
+    /// Without this they are filled from the device the program runs on;
+    /// see the `device` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - The device to describe
+    #[cfg(any(feature = "android", feature = "ios"))]
+    pub fn device_context(mut self, context: device::DeviceContext) -> Self {
+        self.config.device = Some(context);
+        self
+    }
+
+    /// Fill `{os}`, `{arch}`, `{rust_version}`, `{crate_version}`, `{hostname}`, and the placeholders of `context`'s providers (std only).
+    /// 
+    /// See the `context` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - The environment placeholders to fill
+    #[cfg(feature = "std")]
+    pub fn with_auto_context(mut self, context: context::AutoContext) -> Self {
+        self.config.auto_context = Some(context);
+        self
+    }
+
+    /// Fill `{screenshot}` with a section pointing at the screenshot `provider` saved (std only).
+    /// 
+    /// The section asks the user to attach a screenshot themselves when
+    /// `provider` has none. See the `screenshot` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `provider` - A `ScreenshotProvider` or a closure returning the screenshot's path
+    #[cfg(feature = "std")]
+    pub fn screenshot_provider(mut self, provider: impl screenshot::ScreenshotProvider + 'static) -> Self {
+        self.config.screenshot = Some(screenshot::ScreenshotSource::new(provider));
+        self
+    }
+
+    /// Replace a template with one downloaded now (feature `remote`).
+    /// 
+    /// The content is fetched from the source set with `remote_source`,
+    /// checked against its pinned digest if it has one, and parsed like a
+    /// template file. If any step fails, a warning is printed and the
+    /// template already registered under `name` stays in place, so add
+    /// the embedded default first. See the `remote` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name of the template to replace
+    /// * `template` - A URL or `RemoteTemplate` to download
+    #[cfg(feature = "remote")]
+    pub fn add_remote_template(mut self, name: impl Into<String>, template: impl Into<remote::RemoteTemplate>) -> Self {
+        let name = name.into();
+        if let Err(e) = self.config.add_remote_template(name.clone(), &template.into()) {
+            Output::write_fmt(
+                &mut std::io::stderr(),
+                format_args!("warning: not using remote template '{}': {}\n", name, e),
+            );
+        }
+        self
+    }
+
+    /// Print repeated occurrences of the same report less and less often.
+    /// 
+    /// Occurrences are counted per template and parameter values. See
+    /// `backoff::BackoffPolicy` for when each occurrence is printed.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - When occurrences are printed
+    pub fn backoff(mut self, policy: backoff::BackoffPolicy) -> Self {
+        self.config.backoff = Some(policy);
+        self
+    }
+
+    /// Print at most one full banner per `period`, whatever the report.
+    /// 
+    /// See `BugReportHandle::quiet_period`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `period` - The least time between two full banners
+    pub fn quiet_period(mut self, period: core::time::Duration) -> Self {
+        self.config.quiet_period = Some(period);
+        self
+    }
+
+    /// Build and install the global configuration (std only).
+    /// 
+    /// This method finalizes the configuration and stores it globally.
+    /// After calling this, the `bug!` macro can be used throughout the application.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - Configuration was successfully installed
+    /// * `Err(&'static str)` - Configuration was already initialized, or
+    ///   `validate` failed: a template file no longer has its pinned digest,
+    ///   the base URL is malformed, a template assigns an unknown team or
+    ///   more assignees than GitHub allows, a label contains a comma under
+    ///   `LabelEncoding::Joined`, or a label is not allowed (call `validate`
+    ///   for details)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate};
+    /// 
+    /// # #[cfg(feature = "std")] {
+    /// let result = init("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug", "Description"))
+    ///     .build();
+    /// assert!(result.is_ok() || result == Err("Bug reporting already initialized"));
+    ///
+    /// let result = init("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug", "Description").assign_team("core"))
+    ///     .build();
+    /// assert_eq!(result, Err("A template assigns a team that is not configured"));
+    /// # }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn build(self) -> Result<(), &'static str> {
+        self.config.check_pins().map_err(|_| PINNED_DIGEST_ERROR)?;
+        self.config.check_base_urls().map_err(|_| BASE_URL_ERROR)?;
+        self.config.check_teams().map_err(|_| UNKNOWN_TEAM_ERROR)?;
+        self.config.check_assignee_counts().map_err(|_| TOO_MANY_ASSIGNEES_ERROR)?;
+        self.config.check_label_encoding().map_err(|_| COMMA_LABEL_ERROR)?;
+        self.config.check_allowed_labels().map_err(|_| DISALLOWED_LABEL_ERROR)?;
+        CONFIG.set(Arc::new(self.config)).map_err(|_| "Bug reporting already initialized")
+    }
+    
+    /// Build and install the global configuration (no_std only).
+    /// 
+    /// This method finalizes the configuration and stores it globally.
+    /// Installing runs in a critical section, so calling this concurrently
+    /// with reports or another `build` is sound, and only the first call
+    /// installs its configuration.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(())` - Configuration was successfully installed
+    /// * `Err(&'static str)` - Configuration was already initialized, or
+    ///   `validate` failed: a template file no longer has its pinned digest,
+    ///   the base URL is malformed, a template assigns an unknown team or
+    ///   more assignees than GitHub allows, a label contains a comma under
+    ///   `LabelEncoding::Joined`, or a label is not allowed (call `validate`
+    ///   for details)
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init, IssueTemplate};
+    /// 
+    /// # #[cfg(all(not(feature = "std"), feature = "global-nostd"))] {
+    /// let result = init("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug", "Description"))
+    ///     .build();
+    /// assert!(result.is_ok() || result == Err("Bug reporting already initialized"));
+    /// # }
+    /// ```
+    #[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+    pub fn build(self) -> Result<(), &'static str> {
+        self.config.check_pins().map_err(|_| PINNED_DIGEST_ERROR)?;
+        self.config.check_base_urls().map_err(|_| BASE_URL_ERROR)?;
+        self.config.check_teams().map_err(|_| UNKNOWN_TEAM_ERROR)?;
+        self.config.check_assignee_counts().map_err(|_| TOO_MANY_ASSIGNEES_ERROR)?;
+        self.config.check_label_encoding().map_err(|_| COMMA_LABEL_ERROR)?;
+        self.config.check_allowed_labels().map_err(|_| DISALLOWED_LABEL_ERROR)?;
+        CONFIG.set(self.config).map_err(|_| "Bug reporting already initialized")
+    }
+}
+
+/// A handle for bug reporting that doesn't rely on global state.
+/// 
+/// This struct provides the same functionality as the global configuration
+/// but can be used in no_std environments and allows multiple independent
+/// configurations within the same application.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+/// 
+/// let handle = init_handle("octocat", "Hello-World")
+///     .add_template("bug", IssueTemplate::new("Bug Report", "Issue: {description}"));
+/// 
+/// let mut params = Params::new();
+/// params.insert("description".to_string(), "Button not working".to_string());
+/// 
+/// let url = handle.generate_url("bug", &params).unwrap();
+/// assert!(url.contains("github.com/octocat/Hello-World/issues/new"));
+/// ```
+/// 
+/// A handle is `Send + Sync`. Its report cache and occurrence counts are
+/// locked internally, so threads can share one handle and report at once:
+/// 
+/// ```
+/// use bug::{init_handle, IssueTemplate, Location, Params};
+/// use bug::backoff::BackoffPolicy;
+/// use std::thread;
+/// 
+/// let handle = init_handle("owner", "repo")
+///     .add_template("stress", IssueTemplate::new("Stress", "{worker}"))
+///     .cache_size(4)
+///     .backoff(BackoffPolicy::new().brief_until(10));
+/// 
+/// thread::scope(|scope| {
+///     for worker in 0..8 {
+///         let handle = &handle;
+///         scope.spawn(move || {
+///             let mut params = Params::new();
+///             params.insert("worker", (worker % 3).to_string());
+///             for _ in 0..200 {
+///                 let url = handle.report_bug_at("stress", &params, Location::new(file!(), line!()));
+///                 assert_eq!(url, handle.generate_url("stress", &params).unwrap());
+///             }
+///         });
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct BugReportHandle {
+    config: Arc<BugReportConfig>,
+    cache: cache::ReportCache,
+}
+
+impl BugReportHandle {
+    /// Create a new bug report handle.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `github_owner` - GitHub username or organization
+    /// * `github_repo` - GitHub repository name
+    fn new(github_owner: String, github_repo: String) -> Self {
+        Self {
+            config: Arc::new(BugReportConfig::new(github_owner, github_repo)),
+            cache: cache::ReportCache::new(0),
+        }
+    }
+
+    /// The configuration for changing, copied first if a snapshot shares it.
+    ///
+    /// Every change goes through here, so cached reports built from the old
+    /// configuration are dropped.
+    fn config_mut(&mut self) -> &mut BugReportConfig {
+        self.cache.clear();
+        Arc::make_mut(&mut self.config)
+    }
+
+    /// Add an issue template to this handle.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template
+    /// * `template` - The issue template to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug Report", "Found a bug"));
+    /// ```
+    pub fn add_template(mut self, name: impl Into<String>, template: IssueTemplate) -> Self {
+        self.register_template(name, template);
+        self
+    }
+
+    /// Add a template file to this handle.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template file
+    /// * `template_file` - The template file to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, TemplateFile};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template_file("crash", TemplateFile::new("Crash Report\nApp crashed"));
+    /// ```
+    pub fn add_template_file(mut self, name: impl Into<String>, template_file: TemplateFile) -> Self {
+        self.register_template_file(name, template_file);
+        self
+    }
+
+    /// Register an issue template and return its id.
+    /// 
+    /// This is the non-chaining form of `add_template`. The returned
+    /// `TemplateId` can be passed to `generate_url_by_id` to skip the name
+    /// lookup, which helps when templates number in the hundreds (for
+    /// example one per error code). Registering a name again replaces the
+    /// template but keeps its id.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template
+    /// * `template` - The issue template to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// 
+    /// let mut handle = init_handle("owner", "repo");
+    /// let first = handle.register_template("crash", IssueTemplate::new("Crash", "v1"));
+    /// let second = handle.register_template("crash", IssueTemplate::new("Crash", "v2"));
+    /// assert_eq!(first, second);
+    /// assert_eq!(handle.config().template("crash").unwrap().body, "v2");
+    /// ```
+    pub fn register_template(&mut self, name: impl Into<String>, template: IssueTemplate) -> TemplateId {
+        self.config_mut().register_template(name.into(), template)
+    }
+
+    /// Register a template file and return its id.
+    /// 
+    /// This is the non-chaining form of `add_template_file`; see
+    /// `register_template`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name to identify the template file
+    /// * `template_file` - The template file to add
+    pub fn register_template_file(&mut self, name: impl Into<String>, template_file: TemplateFile) -> TemplateId {
+        self.config_mut().register_template_file(name.into(), template_file)
+    }
+
+    /// Add every template file from an embedded directory to this handle.
+    /// 
+    /// Templates are named after their path relative to the directory,
+    /// without the extension; see the `template_dir` module for details.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `dir` - A directory embedded with `include_dir::include_dir!`
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use include_dir::{include_dir, Dir};
+    /// 
+    /// static TEMPLATES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+    /// 
+    /// let handle = bug::init_handle("owner", "repo").add_template_dir(&TEMPLATES);
+    /// assert!(handle.template_id("crash_report").is_some());
+    /// assert!(handle.template_id("performance_issue").is_some());
+    /// ```
+    #[cfg(feature = "include_dir")]
+    pub fn add_template_dir(mut self, dir: &'static include_dir::Dir<'static>) -> Self {
+        for (name, template_file) in template_dir::templates(dir) {
+            self.config_mut().register_template_file(name, template_file);
+        }
+        self
+    }
+
+    /// Set the prefix this handle's templates get when another handle merges it.
+    /// 
+    /// A library that ships its own templates sets its crate name, so an
+    /// application merging several libraries gets `serde_thing/crash` and
+    /// `db_thing/crash` instead of one `crash` replacing the other.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `namespace` - Prefix for this handle's template names, usually `env!("CARGO_CRATE_NAME")`
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.config_mut().namespace = Some(namespace.into());
+        self
+    }
+
+    /// Add every template of another handle, such as one a library exports.
+    /// 
+    /// The templates are named `namespace/name` after `other`'s `namespace`,
+    /// or keep their names if it has none. Only templates are merged: the
+    /// other handle's label rules, sinks, and other settings are not.
+    /// 
+    /// Lookup takes either form. The bare name `crash` finds
+    /// `serde_thing/crash` when no template is named `crash` and no other
+    /// namespace has one; if several do, reporting `crash` fails with an
+    /// error listing them. A merged template replacing one of the same name
+    /// is warned about on stderr (std only).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `other` - The handle whose templates to add
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// // What `serde_thing::bug_templates()` and `db_thing::bug_templates()` return
+    /// let serde_thing = init_handle("serde-org", "serde_thing")
+    ///     .namespace("serde_thing")
+    ///     .add_template("crash", IssueTemplate::new("serde_thing crashed", ""))
+    ///     .add_template("bad_input", IssueTemplate::new("Bad input", ""));
+    /// let db_thing = init_handle("db-org", "db_thing")
+    ///     .namespace("db_thing")
+    ///     .add_template("crash", IssueTemplate::new("db_thing crashed", ""));
+    /// 
+    /// let handle = init_handle("owner", "app").merge(&serde_thing).merge(&db_thing);
+    /// let names: Vec<&str> = handle.config().template_names().collect();
+    /// assert_eq!(names, vec!["serde_thing/crash", "serde_thing/bad_input", "db_thing/crash"]);
+    /// 
+    /// let report = handle.create_report("bad_input", &Params::new()).unwrap();
+    /// assert_eq!(report.template_name, "serde_thing/bad_input");
+    /// let report = handle.create_report("db_thing/crash", &Params::new()).unwrap();
+    /// assert_eq!(report.title, "db_thing crashed");
+    /// let error = handle.create_report("crash", &Params::new()).unwrap_err();
+    /// assert_eq!(error, "Template 'crash' is ambiguous; name one of 'serde_thing/crash', 'db_thing/crash'");
+    /// ```
+    pub fn merge(mut self, other: &BugReportHandle) -> Self {
+        self.config_mut().merge_templates(&other.config);
+        self
+    }
+
+    /// Look up the id of a registered template.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// 
+    /// # Returns
+    /// 
+    /// The template's id, or `None` if no template has that name.
+    pub fn template_id(&self, template_name: &str) -> Option<TemplateId> {
+        self.config.template_id(template_name)
+    }
+
+    /// Cache up to `capacity` generated reports on this handle.
+    /// 
+    /// Reports are keyed by template name and parameters, so generating the
+    /// same report again returns the cached URL without re-filling or
+    /// re-encoding the template. When the cache is full, the least recently
+    /// used report is evicted. A capacity of zero (the default) disables
+    /// caching. Cloned handles start with an empty cache of the same size.
+    /// 
+    /// Implicit placeholders such as `{timestamp}` and `{panic_count}` are
+    /// part of the key, so a report with new values is filled again. Reports
+    /// whose template fills a placeholder from the screenshot provider, a
+    /// context provider, or the browser, or assigns a team with
+    /// `TeamAssignment::RoundRobin`, are never cached, as those can change
+    /// between reports with the same parameters. Changing the handle's
+    /// configuration afterwards drops every cached report.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `capacity` - Maximum number of cached reports
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("timeout", IssueTemplate::new("Timeout in {service}", "Request timed out"))
+    ///     .cache_size(64);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("service".to_string(), "billing".to_string());
+    /// 
+    /// let first = handle.generate_url("timeout", &params).unwrap();
+    /// let second = handle.generate_url("timeout", &params).unwrap();
+    /// assert_eq!(first, second);
+    /// assert_eq!(handle.cached_reports(), 1);
+    /// ```
+    /// 
+    /// Each report asks the screenshot provider again:
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use std::path::PathBuf;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// 
+    /// static SHOTS: AtomicUsize = AtomicUsize::new(0);
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("glitch", IssueTemplate::new("Rendering glitch", "{screenshot}"))
+    ///     .screenshot_provider(|| Some(PathBuf::from(format!("shot-{}.png", SHOTS.fetch_add(1, Ordering::Relaxed)))))
+    ///     .cache_size(64);
+    /// 
+    /// let first = handle.create_report("glitch", &Params::new()).unwrap();
+    /// let second = handle.create_report("glitch", &Params::new()).unwrap();
+    /// assert!(first.body.contains("`shot-0.png`"));
+    /// assert!(second.body.contains("`shot-1.png`"));
+    /// assert_eq!(handle.cached_reports(), 0);
+    /// ```
+    /// 
+    /// Reports cached before a setting changes are built again:
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::url_encode::SpaceEncoding;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "a b"))
+    ///     .cache_size(8);
+    /// assert!(handle.generate_url("crash", &Params::new()).unwrap().ends_with("&body=a%20b"));
+    /// assert_eq!(handle.cached_reports(), 1);
+    /// 
+    /// let handle = handle.body_space_encoding(SpaceEncoding::Plus);
+    /// assert_eq!(handle.cached_reports(), 0);
+    /// assert!(handle.generate_url("crash", &Params::new()).unwrap().ends_with("&body=a+b"));
+    /// ```
+    pub fn cache_size(mut self, capacity: usize) -> Self {
+        self.cache = cache::ReportCache::new(capacity);
+        self
+    }
+
+    /// Number of reports currently held in the cache.
+    /// 
+    /// Always zero unless caching was enabled with `cache_size`.
+    pub fn cached_reports(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Find an open issue whose title or body contains `query` (feature `api`).
+    /// 
+    /// `query` is usually a fingerprint marker from `config().fingerprint`,
+    /// or an issue title. Returns `None` unless `github_api` was set and an
+    /// open issue matches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `query` - A fingerprint marker or issue title
+    #[cfg(feature = "api")]
+    pub fn find_existing(&self, query: &str) -> Option<api::IssueRef> {
+        self.config.find_existing(query)
+    }
+
+    /// Encode this handle for reuse in another process.
+    /// 
+    /// The repository and base URL, templates with their docs, error codes, label
+    /// rules, teams, sample rates, template targets, body messages, output settings,
+    /// backoff, quiet period, namespace, and cache size are encoded; `suppress_if`
+    /// conditions, middleware, sinks, the clock, and CODEOWNERS rules are not, and
+    /// must be set again on the decoded handle. Decode the bytes with `from_bytes` on
+    /// the same version of this crate.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, BugReportHandle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {worker}", "It crashed")
+    ///         .document_param("worker", "Index of the worker process"))
+    ///     .map_error_code("E0001", "crash");
+    /// let bytes = handle.to_bytes();
+    /// 
+    /// // In the worker process:
+    /// let worker = BugReportHandle::from_bytes(&bytes).unwrap();
+    /// let mut params = Params::new();
+    /// params.insert("worker", "3");
+    /// assert_eq!(worker.generate_url("crash", &params), handle.generate_url("crash", &params));
+    /// assert_eq!(worker.config().param_doc("crash", "worker"), Some("Index of the worker process"));
+    /// assert_eq!(worker.to_bytes(), bytes);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        wire::encode(&self.config, self.cache.capacity())
+    }
+
+    /// Rebuild a handle encoded with `to_bytes`.
+    /// 
+    /// Template files borrow their contents for `'static`, so the contents
+    /// of each decoded template file are leaked; decode a handle once per
+    /// process rather than per report.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `bytes` - Bytes returned by `to_bytes`
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(BugReportHandle)` - The decoded handle
+    /// * `Err(String)` - The bytes are not an encoded handle, are truncated,
+    ///   or were encoded by an incompatible version of this crate
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::BugReportHandle;
+    /// 
+    /// assert!(BugReportHandle::from_bytes(b"not a handle").is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let (config, cache_size) = wire::decode(bytes)?;
+        Ok(Self {
+            config: Arc::new(config),
+            cache: cache::ReportCache::new(cache_size),
+        })
+    }
+
+    /// Map an error code to the template used to report it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - Stable error code, such as `"E0420"`
+    /// * `template_name` - Name of the template to report the code with
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("parser_crash", IssueTemplate::new("Parser crash {error_code}", "").with_labels(vec!["parser".to_string()]))
+    ///     .map_error_code("E0420", "parser_crash");
+    /// 
+    /// let report = handle.create_report_for_code("E0420", &Params::new()).unwrap();
+    /// assert_eq!(report.title, "Parser crash E0420");
+    /// assert_eq!(report.labels, vec!["parser".to_string(), "E0420".to_string()]);
+    /// ```
+    pub fn map_error_code(mut self, code: impl Into<String>, template_name: impl Into<String>) -> Self {
+        self.config_mut().error_codes.insert(code.into(), template_name.into());
+        self
+    }
+
+    /// Register a team of GitHub users.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Team name used by templates
+    /// * `members` - GitHub usernames in the team
+    pub fn team<I, S>(mut self, name: impl Into<String>, members: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config_mut().teams.insert(name.into(), members.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Choose how teams are expanded into assignees.
+    /// 
+    /// With `TeamAssignment::RoundRobin`, reports from templates that
+    /// assign a team are never cached, so each one takes the next member.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - Assign whole teams, or one member per report in rotation
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, TeamAssignment, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .team("ui-team", ["alice", "bob"])
+    ///     .team_assignment(TeamAssignment::RoundRobin)
+    ///     .add_template("ui_glitch", IssueTemplate::new("UI glitch", "").assign_team("ui-team"));
+    /// 
+    /// let params = Params::new();
+    /// let first = handle.create_report("ui_glitch", &params).unwrap();
+    /// let second = handle.create_report("ui_glitch", &params).unwrap();
+    /// let third = handle.create_report("ui_glitch", &params).unwrap();
+    /// assert_eq!(first.assignees, vec!["alice".to_string()]);
+    /// assert_eq!(second.assignees, vec!["bob".to_string()]);
+    /// assert_eq!(third.assignees, vec!["alice".to_string()]);
+    /// 
+    /// let handle = handle.cache_size(16);
+    /// assert_eq!(handle.create_report("ui_glitch", &params).unwrap().assignees, vec!["bob".to_string()]);
+    /// assert_eq!(handle.create_report("ui_glitch", &params).unwrap().assignees, vec!["alice".to_string()]);
+    /// ```
+    pub fn team_assignment(mut self, mode: TeamAssignment) -> Self {
+        self.config_mut().team_assignment = mode;
+        self
+    }
+
+    /// Assign reports to the owners of the file they were made from.
+    /// 
+    /// Requires the `codeowners` feature. See the `codeowners` module for how
+    /// owners become assignees and labels.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `content` - Contents of a CODEOWNERS file, usually from `include_str!`
+    #[cfg(feature = "codeowners")]
+    pub fn codeowners(mut self, content: &str) -> Self {
+        self.config_mut().codeowners = Some(codeowners::CodeOwners::parse(content));
+        self
+    }
+
+    /// Report only a fraction of a template's occurrences.
+    /// 
+    /// Sampling applies to the `report_bug_*` methods and `Reporter::report`;
+    /// `create_report` and `generate_url` always produce a report.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to sample
+    /// * `rate` - Fraction of occurrences to report, from 0.0 to 1.0
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("cache_miss", IssueTemplate::new("Cache miss", "Sampled at {sample_rate}"))
+    ///     .sample_rate("cache_miss", 0.25)
+    ///     .sample_rng(|| 0);
+    /// 
+    /// let url = handle.report_bug_at("cache_miss", &Params::new(), Location::new(file!(), line!()));
+    /// assert!(url.contains("body=Sampled%20at%200.25"));
+    /// 
+    /// let handle = handle.sample_rate("cache_miss", 0.0);
+    /// assert!(handle.report_bug_at("cache_miss", &Params::new(), Location::new(file!(), line!())).is_empty());
+    /// ```
+    pub fn sample_rate(mut self, template_name: impl Into<String>, rate: f64) -> Self {
+        self.config_mut().sample_rates.insert(template_name.into(), rate.clamp(0.0, 1.0));
+        self
+    }
+
+    /// File one template's reports in another repository or on another host.
+    /// 
+    /// The target's settings override the handle's for reports made from
+    /// that template; see the `target` module for the precedence. Setting a
+    /// target again replaces it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// * `target` - Where its reports are filed
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, TemplateTarget};
+    /// 
+    /// let handle = init_handle("acme", "app")
+    ///     .add_template("docs_typo", IssueTemplate::new("Typo in the docs", ""))
+    ///     .template_target("docs_typo", TemplateTarget::new().repository("acme", "docs"));
+    /// 
+    /// let report = handle.create_report("docs_typo", &Params::new()).unwrap();
+    /// assert!(report.url.starts_with("https://github.com/acme/docs/issues/new?"));
+    /// ```
+    pub fn template_target(mut self, template_name: impl Into<String>, target: target::TemplateTarget) -> Self {
+        self.config_mut().template_targets.insert(template_name.into(), target);
+        self
+    }
+
+    /// Set the RNG that sample rates roll against.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rng` - Source of random numbers, such as a closure returning `u64`
+    pub fn sample_rng(mut self, rng: impl SampleRng + 'static) -> Self {
+        self.config_mut().sampler.set_rng(rng);
+        self
+    }
+
+    /// Set the clock that fills `{timestamp}`.
+    /// 
+    /// Without a clock, `{timestamp}` is left unfilled.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `clock` - Source of timestamps, such as a closure reading a tick counter
+    pub fn clock(mut self, clock: impl time::Clock + 'static) -> Self {
+        self.config_mut().time.set(clock);
+        self
+    }
+
+    /// Fill `{source_excerpt}` with the source lines around the call site.
+    /// 
+    /// The excerpt shows three lines before and after the reporting line,
+    /// read from the source file when the report is made. It is only
+    /// available in std debug builds run where the sources are on disk;
+    /// elsewhere the placeholder says the source is not available.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `include` - Whether to read source excerpts
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "{source_excerpt}"))
+    ///     .include_source_excerpt(true);
+    /// 
+    /// let report_line = line!() + 1;
+    /// let url = handle.report_bug_at("crash", &Params::new(), Location::new(file!(), report_line));
+    /// // Debug builds show the reporting line; release builds say the source is not available.
+    /// let shown = url.contains(&bug::url_encode::encode_with("let url = handle.report_bug_at", bug::url_encode::SpaceEncoding::Percent));
+    /// assert!(shown || url.contains("Source%20not%20available"));
+    /// ```
+    pub fn include_source_excerpt(mut self, include: bool) -> Self {
+        self.config_mut().include_source_excerpt = include;
+        self
+    }
+
+    /// Never report when `predicate` returns true.
+    /// 
+    /// The predicate gets the template name and the parameters given at the
+    /// call site. See the `suppress` module for where suppression applies.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `predicate` - Returns true for reports to suppress
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("io_error", IssueTemplate::new("I/O error", "{kind}"))
+    ///     .suppress_if(|_template, params| params.get("kind").is_some_and(|kind| kind == "Interrupted"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("kind".to_string(), "Interrupted".to_string());
+    /// assert!(handle.report_bug_at("io_error", &params, Location::new(file!(), line!())).is_empty());
+    /// ```
+    pub fn suppress_if(mut self, predicate: impl Fn(&str, &Params) -> bool + Send + Sync + 'static) -> Self {
+        self.config_mut().suppressions.add_predicate(predicate);
+        self
+    }
+
+    /// Never report when `rule` matches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `rule` - A template glob and parameter globs, see `SuppressionRule`
+    pub fn suppress(mut self, rule: suppress::SuppressionRule) -> Self {
+        self.config_mut().suppressions.rules.push(rule);
+        self
+    }
+
+    /// Pass every reported bug through `middleware`, after the middleware added before.
+    /// 
+    /// See the `middleware` module for what middleware can do and where
+    /// it runs.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `middleware` - A `ReportMiddleware` or a closure taking the report and the rest of the pipeline
+    pub fn middleware(mut self, middleware: impl middleware::ReportMiddleware + 'static) -> Self {
+        self.config_mut().middleware.push(middleware);
+        self
+    }
+
+    /// Also write every reported bug to `sink`, after the sinks added before.
+    /// 
+    /// See the `sink` module for what sinks receive and when.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sink` - Where to write, in which format, and at what verbosity
+    pub fn add_sink(mut self, sink: sink::Sink) -> Self {
+        self.config_mut().sinks.push(sink);
+        self
+    }
+
+    /// Restrict the labels reports may carry.
+    /// 
+    /// Check templates with `config().validate()`. Disallowed labels are
+    /// dropped from generated reports.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `labels` - Every label reports may use
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .allowed_labels(["bug"])
+    ///     .add_template("crash", IssueTemplate::new("Crash", "")
+    ///         .with_labels(vec!["bug".to_string(), "sev:{severity}".to_string()]));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("severity".to_string(), "high".to_string());
+    /// let report = handle.create_report("crash", &params).unwrap();
+    /// assert_eq!(report.labels, vec!["bug".to_string()]);
+    /// ```
+    pub fn allowed_labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config_mut().allowed_labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Allow every label starting with `prefix`, in addition to `allowed_labels`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - Prefix of allowed dynamic labels, such as `"area:"`
+    pub fn allow_label_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.config_mut().allowed_label_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Print a warning to stderr whenever a disallowed label is dropped (std only).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `warn` - Whether to warn
+    pub fn warn_on_disallowed_labels(mut self, warn: bool) -> Self {
+        self.config_mut().warn_on_disallowed_labels = warn;
+        self
+    }
+
+    /// Configure hyperlink behavior for this handle.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - How to handle hyperlinks in output
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, HyperlinkMode};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .hyperlinks(HyperlinkMode::Always);
+    /// ```
+    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
+        self.config_mut().use_hyperlinks = mode;
+        self
+    }
+
+    /// Point issue URLs at a GitHub Enterprise server instead of `github.com`.
+    /// 
+    /// Every link the handle builds starts with `base_url` instead of
+    /// `github::HOST`, unless a template's `TemplateTarget` sets its own
+    /// host. The URL must be `https://` or `http://` followed by a host
+    /// name or a bracketed IPv6 address, optionally with a port and a path,
+    /// and nothing else; `config().validate()` reports a malformed one, and
+    /// every report built with it carries a `Warning::MalformedBaseUrl`.
+    /// Searching for existing issues goes through the `GitHubApi`, whose
+    /// own `base_url` is set separately, usually to `<base_url>/api/v3`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `base_url` - Scheme and host of the GitHub server, such as `https://github.example.com`; a trailing `/` is ignored
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("platform", "tools")
+    ///     .base_url("https://github.corp.example/")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""));
+    /// assert!(handle.config().validate().is_ok());
+    /// 
+    /// let report = handle.create_report("crash", &Params::new()).unwrap();
+    /// assert!(report.url.starts_with("https://github.corp.example/platform/tools/issues/new?"));
+    /// 
+    /// let handle = handle.base_url("github.corp.example");
+    /// assert_eq!(
+    ///     handle.config().validate().unwrap_err(),
+    ///     "Base URL 'github.corp.example' must start with https:// or http://"
+    /// );
+    /// let report = handle.create_report("crash", &Params::new()).unwrap();
+    /// assert_eq!(
+    ///     report.warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    ///     ["Base URL 'github.corp.example' must start with https:// or http://"]
+    /// );
+    /// 
+    /// let handle = handle.base_url("http://[fd00::17]:8080/github");
+    /// assert!(handle.config().validate().is_ok());
+    /// assert!(handle.generate_url("crash", &Params::new()).unwrap().starts_with("http://[fd00::17]:8080/github/platform/tools/issues/new?"));
+    /// ```
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url: String = base_url.into();
+        self.config_mut().base_url = Some(String::from(base_url.trim_end_matches('/')));
+        self
+    }
+
+    /// Collect reported bugs into one summary instead of printing each one.
+    /// 
+    /// The `report_bug_*` methods still return the URL of each report.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `summarize` - Whether to summarize
+    /// 
+    /// # Examples
+    /// 
+    /// ```standalone_crate
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("summarized_crash", IssueTemplate::new("Crash", ""))
+    ///     .summarize(true);
+    /// 
+    /// for _ in 0..3 {
+    ///     handle.report_bug_at("summarized_crash", &Params::new(), Location::new(file!(), line!()));
+    /// }
+    /// 
+    /// let summary = bug::summary::global().summary();
+    /// let crashes = summary.iter().find(|s| s.template_name == "summarized_crash").unwrap();
+    /// assert_eq!(crashes.count, 3);
+    /// ```
+    pub fn summarize(mut self, summarize: bool) -> Self {
+        self.config_mut().summarize = summarize;
+        self
+    }
+
+    /// List the warnings found while generating a report in the banner.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `show` - Whether to print warnings
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location, Output};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""))
+    ///     .show_warnings(true);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("stage", "parse");
+    /// let mut output = String::new();
+    /// handle.report_bug_with_output_at("crash", &params, Location::new("main.rs", 3), &mut output);
+    /// assert!(output.contains("   Warnings:\n     parameter 'stage' is not used by the template\n"));
+    /// ```
+    pub fn show_warnings(mut self, show: bool) -> Self {
+        self.config_mut().show_warnings = show;
+        self
+    }
+
+    /// Print the configuration's `digest` in the banner.
+    /// 
+    /// The digest identifies the template set a build reports with, so a
+    /// pasted banner can be matched with the templates that produced it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `show` - Whether to print the digest
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""))
+    ///     .show_config_digest(true);
+    /// 
+    /// let mut output = String::new();
+    /// handle.report_bug_with_output_at("crash", &Params::new(), Location::new("main.rs", 3), &mut output);
+    /// let digest = format!("   Config digest: {:016x}\n", handle.config().digest());
+    /// assert!(output.contains(&digest));
+    /// ```
+    pub fn show_config_digest(mut self, show: bool) -> Self {
+        self.config_mut().show_config_digest = show;
+        self
+    }
+
+    /// Fail to generate reports that would leave placeholders unfilled.
+    /// 
+    /// Plain `IssueTemplate`s are filled leniently: a placeholder without a
+    /// parameter stays in the issue as literal `{name}` text and is only
+    /// reported as a `Warning::UnfilledPlaceholder`. With this set, such a
+    /// report fails instead, which catches templates and call sites that
+    /// have drifted apart. Template files always fail.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `deny` - Whether unfilled placeholders are an error
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {screen}", "{details}"))
+    ///     .deny_unfilled_placeholders(true);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("screen", "settings");
+    /// let error = handle.create_report("crash", &params).unwrap_err();
+    /// assert_eq!(error, "Template 'crash' has placeholders without a value: {details}");
+    /// ```
+    pub fn deny_unfilled_placeholders(mut self, deny: bool) -> Self {
+        self.config_mut().deny_unfilled_placeholders = deny;
+        self
+    }
+
+    /// Validate the parameters of every template, as template files are.
+    /// 
+    /// By default an `IssueTemplate` is filled leniently: unused parameters
+    /// and missing placeholders become warnings. With this set, they fail
+    /// the report with the error `validate_params` gives, whichever kind of
+    /// template is used.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strict` - Whether inline templates are validated
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {screen}", ""))
+    ///     .strict_params(true);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("screen", "settings");
+    /// params.insert("stage", "save");
+    /// assert_eq!(handle.create_report("crash", &params).unwrap_err(), "Unused parameter: stage");
+    /// ```
+    pub fn strict_params(mut self, strict: bool) -> Self {
+        self.config_mut().strict_params = strict;
+        self
+    }
+
+    /// Minify issue bodies before encoding them into the URL.
+    /// 
+    /// HTML comments are stripped, trailing whitespace is trimmed, and runs
+    /// of blank lines left by empty parameters collapse into one. Fenced
+    /// code blocks keep their blank lines and comments, and comments holding
+    /// a `{fingerprint}` marker are kept. Markdown hard line breaks made
+    /// with trailing spaces are lost; use a trailing `\` instead.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `minify` - Whether to minify bodies
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let body = "<!-- Keep this template short -->\n## Error\n{error}   \n\n\n\n## Notes\n{notes}\n";
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", body))
+    ///     .minify_body(true);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("error", "out of memory");
+    /// params.insert("notes", "");
+    /// let report = handle.create_report("crash", &params).unwrap();
+    /// assert_eq!(report.body, "## Error\nout of memory\n\n## Notes");
+    /// ```
+    pub fn minify_body(mut self, minify: bool) -> Self {
+        self.config_mut().minify_body = minify;
+        self
+    }
+
+    /// Choose how spaces in the issue body are encoded into the URL.
+    /// 
+    /// Bodies encode spaces as `%20` by default, which every target reads
+    /// as a space. `SpaceEncoding::Plus` restores the `+` of earlier
+    /// versions, for tooling that compares URLs byte for byte. Titles,
+    /// labels, and assignees always use `+`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `spaces` - How to encode spaces in the body
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::url_encode::SpaceEncoding;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "a + b"));
+    /// assert!(handle.generate_url("crash", &Params::new()).unwrap().ends_with("&body=a%20%2B%20b"));
+    /// 
+    /// let handle = handle.body_space_encoding(SpaceEncoding::Plus);
+    /// assert!(handle.generate_url("crash", &Params::new()).unwrap().ends_with("&body=a+%2B+b"));
+    /// ```
+    pub fn body_space_encoding(mut self, spaces: url_encode::SpaceEncoding) -> Self {
+        self.config_mut().body_space_encoding = spaces;
+        self
+    }
+
+    /// Choose how labels are written into the URL.
+    /// 
+    /// GitHub takes one comma-separated `labels` parameter, the default.
+    /// `LabelEncoding::Repeated` writes a `labels[]` parameter per label,
+    /// for trackers that prefer it and for labels containing commas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `encoding` - How labels are written
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::url_encode::LabelEncoding;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "")
+    ///         .with_labels(vec!["bug".to_string(), "needs-triage,p1".to_string()]))
+    ///     .label_encoding(LabelEncoding::Repeated);
+    /// 
+    /// let url = handle.generate_url("crash", &Params::new()).unwrap();
+    /// assert!(url.ends_with("labels[]=bug&labels[]=needs-triage%2Cp1"));
+    /// ```
+    pub fn label_encoding(mut self, encoding: url_encode::LabelEncoding) -> Self {
+        self.config_mut().label_encoding = encoding;
+        self
+    }
+
+    /// Choose what reporting a template name that matches no template does.
+    /// 
+    /// By default it fails with an error. With
+    /// `OnMissingTemplate::LinkChooser` the report links to the
+    /// repository's issue template chooser instead, so a misspelled or
+    /// removed template still leaves the user somewhere to file the bug.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - What to do about unknown template names
+    pub fn on_missing_template(mut self, policy: OnMissingTemplate) -> Self {
+        self.config_mut().on_missing_template = policy;
+        self
+    }
+
+    /// Localize the text the crate writes into issue bodies.
+    /// 
+    /// Templates are written in whatever language the maintainers use, but
+    /// the note on sampled reports and the stand-in for a missing source
+    /// excerpt are English unless replaced here. See `messages::Messages`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `messages` - The text to use
+    pub fn body_messages(mut self, messages: messages::Messages) -> Self {
+        self.config_mut().messages = messages;
+        self
+    }
+
+    /// Match requested template names loosely, such as ignoring case.
+    /// 
+    /// Whatever the matching, a name that matches no template fails with
+    /// an error suggesting the closest registered name, unless
+    /// `on_missing_template` says otherwise.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `matching` - How names are matched
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, NameMatching, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .name_matching(NameMatching::CaseInsensitive)
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""));
+    /// 
+    /// assert!(handle.create_report("CRASH", &Params::new()).is_ok());
+    /// assert_eq!(
+    ///     handle.create_report("crsh", &Params::new()).unwrap_err(),
+    ///     "Template 'crsh' not found; did you mean 'crash'?"
+    /// );
+    /// ```
+    pub fn name_matching(mut self, matching: NameMatching) -> Self {
+        self.config_mut().registry.set_matching(matching);
+        self
+    }
+
+    /// Withhold report details until the user consents with `bug::set_consent(true)`.
+    /// 
+    /// Without consent, reports are still counted, summarized, and backed
+    /// off, but the banner prints neither parameters nor the issue URL and
+    /// asks the user to enable bug reporting instead, and the URL is not
+    /// returned. See the `consent` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `require` - Whether consent is required
+    pub fn require_consent(mut self, require: bool) -> Self {
+        self.config_mut().require_consent = require;
+        self
+    }
+
+    /// Set how a placeholder's value is substituted in every template.
+    /// 
+    /// See `param::ParamPolicy`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `placeholder` - Name of the placeholder
+    /// * `policy` - How its value is substituted
+    pub fn param_policy(mut self, placeholder: impl Into<String>, policy: param::ParamPolicy) -> Self {
+        self.config_mut().param_policies.set(placeholder.into(), policy);
+        self
+    }
+
+    /// Set how a placeholder's value is substituted in one template.
+    /// 
+    /// Overrides `param_policy` for that template.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template
+    /// * `placeholder` - Name of the placeholder
+    /// * `policy` - How its value is substituted
+    pub fn template_param_policy(
+        mut self,
+        template_name: impl Into<String>,
+        placeholder: impl Into<String>,
+        policy: param::ParamPolicy,
+    ) -> Self {
+        self.config_mut()
+            .param_policies
+            .set_for_template(template_name.into(), placeholder.into(), policy);
+        self
+    }
+
+    /// Key the hashes of `ParamPolicy::HashValue` with a secret.
+    /// 
+    /// Without a key, anyone can hash candidate values and compare them
+    /// with a report. Keep the key the same across releases to keep hashes
+    /// comparable.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `k0` - First half of the key
+    /// * `k1` - Second half of the key
+    pub fn param_hash_key(mut self, k0: u64, k1: u64) -> Self {
+        self.config_mut().param_policies.set_key(k0, k1);
+        self
+    }
+
+    /// Search GitHub for an open duplicate before suggesting a new issue (feature `api`).
+    /// 
+    /// When a report matches an open issue, the banner prints that issue's
+    /// URL instead of a new-issue link, and it is returned in place of the
+    /// new-issue URL. See the `api` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `api` - The GitHub API to search
+    #[cfg(feature = "api")]
+    pub fn github_api(mut self, api: api::GitHubApi) -> Self {
+        self.config_mut().github_api = Some(api);
+        self
+    }
+
+    /// Download templates from `source` in `add_remote_template` (feature `remote`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `source` - The HTTP client and timeout to fetch with
+    #[cfg(feature = "remote")]
+    pub fn remote_source(mut self, source: remote::RemoteSource) -> Self {
+        self.config_mut().remote_source = Some(source);
+        self
+    }
+
+    /// Fill `{user_agent}`, `{platform}`, and `{viewport}` from `environment` (feature `wasm`).
+    /// 
+    /// See the `wasm` module for reading them with `web-sys`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `environment` - A `BrowserEnvironment` or a closure returning a `BrowserContext`
+    #[cfg(feature = "wasm")]
+    pub fn browser_environment(mut self, environment: impl wasm::BrowserEnvironment + 'static) -> Self {
+        self.config_mut().browser = Some(wasm::BrowserSource::new(environment));
+        self
+    }
+
+    /// Fill `{device}` and the other device placeholders from `context` (features `android` and `ios`).
+    /// 
+    /// Without this they are filled from the device the program runs on;
+    /// see the `device` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - The device to describe
+    #[cfg(any(feature = "android", feature = "ios"))]
+    pub fn device_context(mut self, context: device::DeviceContext) -> Self {
+        self.config_mut().device = Some(context);
+        self
+    }
+
+    /// Fill `{os}`, `{arch}`, `{rust_version}`, `{crate_version}`, `{hostname}`, and the placeholders of `context`'s providers (std only).
+    /// 
+    /// See the `context` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - The environment placeholders to fill
+    #[cfg(feature = "std")]
+    pub fn with_auto_context(mut self, context: context::AutoContext) -> Self {
+        self.config_mut().auto_context = Some(context);
+        self
+    }
+
+    /// Fill `{screenshot}` with a section pointing at the screenshot `provider` saved (std only).
+    /// 
+    /// The section asks the user to attach a screenshot themselves when
+    /// `provider` has none. See the `screenshot` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `provider` - A `ScreenshotProvider` or a closure returning the screenshot's path
+    #[cfg(feature = "std")]
+    pub fn screenshot_provider(mut self, provider: impl screenshot::ScreenshotProvider + 'static) -> Self {
+        self.config_mut().screenshot = Some(screenshot::ScreenshotSource::new(provider));
+        self
+    }
+
+    /// Replace a template with one downloaded now (feature `remote`).
+    /// 
+    /// The content is fetched from the source set with `remote_source`,
+    /// checked against its pinned digest if it has one, and parsed like a
+    /// template file. If any step fails, a warning is printed and the
+    /// template already registered under `name` stays in place, so add
+    /// the embedded default first. See the `remote` module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - Name of the template to replace
+    /// * `template` - A URL or `RemoteTemplate` to download
+    #[cfg(feature = "remote")]
+    pub fn add_remote_template(mut self, name: impl Into<String>, template: impl Into<remote::RemoteTemplate>) -> Self {
+        let name = name.into();
+        if let Err(e) = self.config_mut().add_remote_template(name.clone(), &template.into()) {
+            Output::write_fmt(
+                &mut std::io::stderr(),
+                format_args!("warning: not using remote template '{}': {}\n", name, e),
+            );
+        }
+        self
+    }
+
+    /// Print repeated occurrences of the same report less and less often.
+    /// 
+    /// Every occurrence still returns its URL; only the banner backs off.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `policy` - When occurrences are printed
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location, Output};
+    /// use bug::backoff::BackoffPolicy;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("retry_failed", IssueTemplate::new("Retry failed", ""))
+    ///     .backoff(BackoffPolicy::new().brief_until(2));
+    /// 
+    /// let mut output = String::new();
+    /// for _ in 0..3 {
+    ///     let url = handle.report_bug_with_output_at("retry_failed", &Params::new(), Location::new("net.rs", 7), &mut output);
+    ///     assert!(!url.is_empty());
+    /// }
+    /// 
+    /// assert_eq!(output.matches("File a bug report").count(), 1);
+    /// assert!(output.ends_with("🐛 BUG ENCOUNTERED again in net.rs:7: retry_failed (2 occurrences)\n"));
+    /// ```
+    pub fn backoff(mut self, policy: backoff::BackoffPolicy) -> Self {
+        self.config_mut().backoff = Some(policy);
+        self
+    }
+
+    /// Print at most one full banner per `period`, whatever the report.
+    /// 
+    /// Unlike `backoff`, which quiets repeats of the same report, this
+    /// keeps an error storm of many different reports from filling the
+    /// console. A banner due less than `period` after the last full one
+    /// isn't printed; the report is added to the summary `flush_summary`
+    /// prints instead, and the next full banner starts with a
+    /// `(+3 more reports, see summary)` line. Every report still returns
+    /// its URL and is written to sinks.
+    /// 
+    /// Time is read from the `clock` if one is set, otherwise (std only)
+    /// from a monotonic clock. In no_std builds without a clock the period
+    /// has no effect.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `period` - The least time between two full banners
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// use std::time::Duration;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("parse_failed", IssueTemplate::new("Parse failed", ""))
+    ///     .add_template("save_failed", IssueTemplate::new("Save failed", ""))
+    ///     .quiet_period(Duration::from_secs(3600));
+    /// 
+    /// let mut output = String::new();
+    /// let loc = Location::new("storm.rs", 7);
+    /// for template in ["parse_failed", "save_failed", "parse_failed"] {
+    ///     let url = handle.report_bug_with_output_at(template, &Params::new(), loc, &mut output);
+    ///     assert!(!url.is_empty());
+    /// }
+    /// assert_eq!(output.matches("🐛 BUG ENCOUNTERED").count(), 1);
+    /// assert_eq!(bug::summary::global().summary().iter().map(|entry| entry.count).sum::<u64>(), 2);
+    /// ```
+    pub fn quiet_period(mut self, period: core::time::Duration) -> Self {
+        self.config_mut().quiet_period = Some(period);
+        self
+    }
+
+    /// Generate a GitHub issue URL from a template and parameters.
+    /// 
+    /// This method fills the specified template with the provided parameters
+    /// and generates a complete GitHub issue URL with query parameters for
+    /// title, body, and labels.
     /// 
     /// # Arguments
     /// 
-    /// * `github_owner` - GitHub username or organization
-    /// * `github_repo` - GitHub repository name
-    fn new(github_owner: String, github_repo: String) -> Self {
-        Self {
-            config: BugReportConfig {
-                github_owner,
-                github_repo,
-                templates: FxHashMap::default(),
-                template_files: FxHashMap::default(),
-                use_hyperlinks: HyperlinkMode::Auto,
-            },
-        }
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The generated GitHub issue URL
+    /// * `Err(String)` - Error message if template not found or validation fails
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template("bug", IssueTemplate::new("Bug: {component}", "Error: {message}"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("component".to_string(), "UI".to_string());
+    /// params.insert("message".to_string(), "Button not working".to_string());
+    /// 
+    /// let url = handle.generate_url("bug", &params).unwrap();
+    /// assert!(url.contains("github.com/octocat/Hello-World/issues/new"));
+    /// assert!(url.contains("title=Bug%3A+UI"));
+    /// ```
+    pub fn generate_url(&self, template_name: &str, params: &Params) -> Result<String, String> {
+        self.create_report(template_name, params).map(|report| report.url)
     }
 
-    /// Add an issue template to the configuration.
+    /// Generate a GitHub issue URL, writing it to `output` in chunks.
+    /// 
+    /// The URL is the same as `generate_url`'s, but each field is encoded
+    /// straight into `output` through a small buffer, so the full URL, which
+    /// for a large body is about three times its size, is never built as a
+    /// `String`. The filled title, body, and labels are still held while
+    /// writing. This suits memory-constrained targets writing long reports
+    /// to a serial port, a display, or a file. The report cache isn't used.
     /// 
     /// # Arguments
     /// 
-    /// * `name` - Name to identify the template
-    /// * `template` - The issue template to add
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `output` - Where the URL is written
+    /// 
+    /// # Returns
+    /// 
+    /// An error if the template is not found or can't be filled, in which
+    /// case nothing has been written.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init, IssueTemplate};
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::url_encode::LabelEncoding;
     /// 
-    /// # #[cfg(feature = "std")] {
-    /// let builder = init("owner", "repo")
-    ///     .add_template("bug", IssueTemplate::new("Bug Report", "Found a bug"));
-    /// # }
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template(
+    ///         "crash",
+    ///         IssueTemplate::new("Crash in {component}", "Log:\n{log}")
+    ///             .with_labels(vec!["bug".to_string(), "needs triage".to_string()])
+    ///             .with_assignees(vec!["octocat".to_string(), "hubot".to_string()]),
+    ///     )
+    ///     .label_encoding(LabelEncoding::Repeated);
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("component", "storage");
+    /// params.insert("log", "write failed: disk full\n".repeat(200));
+    /// 
+    /// let mut output = Vec::new();
+    /// handle.generate_url_streaming("crash", &params, &mut output).unwrap();
+    /// assert_eq!(String::from_utf8(output).unwrap(), handle.generate_url("crash", &params).unwrap());
     /// ```
-    pub fn add_template(mut self, name: impl Into<String>, template: IssueTemplate) -> Self {
-        self.config.templates.insert(name.into(), template);
-        self
+    pub fn generate_url_streaming(&self, template_name: &str, params: &Params, output: &mut dyn Output) -> Result<(), String> {
+        self.config.write_report_url(template_name, params, output)
     }
 
-    /// Add a template file to the configuration.
-    /// 
-    /// # Arguments
+    /// The URL of the repository's issue template chooser.
     /// 
-    /// * `name` - Name to identify the template file
-    /// * `template_file` - The template file to add
+    /// The chooser lists the issue forms and templates configured in the
+    /// repository's `.github/ISSUE_TEMPLATE` directory, for users who should
+    /// pick one themselves.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init, TemplateFile};
+    /// use bug::init_handle;
     /// 
-    /// # #[cfg(feature = "std")] {
-    /// let builder = init("owner", "repo")
-    ///     .add_template_file("crash", TemplateFile::new("Crash Report\nApp crashed"));
-    /// # }
+    /// let handle = init_handle("octocat", "Hello-World");
+    /// assert_eq!(handle.chooser_url(), "https://github.com/octocat/Hello-World/issues/new/choose");
     /// ```
-    pub fn add_template_file(mut self, name: impl Into<String>, template_file: TemplateFile) -> Self {
-        self.config.template_files.insert(name.into(), template_file);
-        self
+    pub fn chooser_url(&self) -> String {
+        self.config.chooser_url()
     }
 
-    /// Configure hyperlink behavior for terminal output.
+    /// The URL opening an issue form with some of its fields prefilled.
+    /// 
+    /// GitHub fills each field from the parameter named after its `id`;
+    /// the `title` parameter replaces the form's title. Field values are
+    /// encoded like bodies, following `body_space_encoding`. See the
+    /// `issue_form` module.
     /// 
     /// # Arguments
     /// 
-    /// * `mode` - How to handle hyperlinks in output
+    /// * `form` - The form, parsed from the repository's `.github/ISSUE_TEMPLATE` directory
+    /// * `params` - Field values keyed by field id
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The new-issue URL for the form
+    /// * `Err(String)` - A parameter names no field of the form
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init, HyperlinkMode};
-    /// 
-    /// # #[cfg(feature = "std")] {
-    /// let builder = init("owner", "repo")
-    ///     .hyperlinks(HyperlinkMode::Always);
-    /// # }
+    /// use bug::{init_handle, Params};
+    /// use bug::issue_form::IssueForm;
+    /// 
+    /// let form = IssueForm::parse("crash.yml", "name: Crash\nbody:\n- type: textarea\n  id: log\n  attributes:\n    label: Log\n").unwrap();
+    /// let handle = init_handle("octocat", "Hello-World");
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("title", "Crash on start");
+    /// params.insert("log", "exit 101");
+    /// assert_eq!(
+    ///     handle.form_url(&form, &params).unwrap(),
+    ///     "https://github.com/octocat/Hello-World/issues/new?template=crash.yml&title=Crash+on+start&log=exit%20101"
+    /// );
+    /// 
+    /// params.insert("stack", "...");
+    /// assert_eq!(handle.form_url(&form, &params).unwrap_err(), "Issue form 'crash.yml' has no field with id 'stack'");
     /// ```
-    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
-        self.config.use_hyperlinks = mode;
-        self
+    pub fn form_url(&self, form: &issue_form::IssueForm, params: &Params) -> Result<String, String> {
+        self.config.form_url(form, params)
     }
 
-    /// Build and install the global configuration (std only).
+    /// Create a structured bug report from a template and parameters.
     /// 
-    /// This method finalizes the configuration and stores it globally.
-    /// After calling this, the `bug!` macro can be used throughout the application.
+    /// This does the same work as `generate_url` but keeps the filled title,
+    /// body, and labels alongside the URL, so UIs and other integrations can
+    /// present the report without re-deriving its pieces from the URL.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
     /// 
     /// # Returns
     /// 
-    /// * `Ok(())` - Configuration was successfully installed
-    /// * `Err(&'static str)` - Configuration was already initialized
+    /// * `Ok(BugReport)` - The filled report and its GitHub issue URL
+    /// * `Err(String)` - Error message if template not found or validation fails
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init, IssueTemplate};
+    /// use bug::{init_handle, IssueTemplate, Params};
     /// 
-    /// # #[cfg(feature = "std")] {
-    /// let result = init("owner", "repo")
-    ///     .add_template("bug", IssueTemplate::new("Bug", "Description"))
-    ///     .build();
-    /// assert!(result.is_ok() || result == Err("Bug reporting already initialized"));
-    /// # }
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template("bug", IssueTemplate::new("Bug: {component}", "Error: {message}"));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("component".to_string(), "UI".to_string());
+    /// params.insert("message".to_string(), "Button not working".to_string());
+    /// 
+    /// let report = handle.create_report("bug", &params).unwrap();
+    /// assert_eq!(report.title, "Bug: UI");
+    /// assert_eq!(report.body, "Error: Button not working");
+    /// assert!(report.url.contains("github.com/octocat/Hello-World/issues/new"));
     /// ```
-    #[cfg(feature = "std")]
-    pub fn build(self) -> Result<(), &'static str> {
-        CONFIG.set(self.config).map_err(|_| "Bug reporting already initialized")
+    pub fn create_report(&self, template_name: &str, params: &Params) -> Result<BugReport, String> {
+        if self.cache.capacity() == 0 || !self.config.cacheable(template_name, params) {
+            return self.config.create_report(template_name, params);
+        }
+        if let Some(report) = self.cache.get(template_name, params) {
+            #[cfg(feature = "usage-stats")]
+            usage::record(template_name);
+            return Ok(report);
+        }
+
+        let report = self.config.create_report(template_name, params)?;
+        self.cache.insert(template_name, params, &report);
+        Ok(report)
     }
-    
-    /// Build and install the global configuration (no_std only).
+
+    /// Fill a template without generating its URL.
     /// 
-    /// This method finalizes the configuration and stores it globally.
-    /// In no_std environments, this uses unsafe code to manage static state.
+    /// The template is validated and filled exactly as for `create_report`,
+    /// fallbacks, parameter policies, label rules, and teams included, so
+    /// the rendered title and body can be sent somewhere other than GitHub,
+    /// such as an email or a chat webhook. The returned template's
+    /// assignees are the report's, with teams already expanded.
     /// 
-    /// # Safety
+    /// # Arguments
     /// 
-    /// This function is unsafe because it modifies global mutable static state.
-    /// It should only be called once during application initialization.
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
     /// 
     /// # Returns
     /// 
-    /// * `Ok(())` - Configuration was successfully installed
-    /// * `Err(&'static str)` - Configuration was already initialized
+    /// * `Ok(IssueTemplate)` - The filled title, body, labels, and assignees
+    /// * `Err(String)` - Error message if template not found or validation fails
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init, IssueTemplate};
+    /// use bug::{init_handle, IssueTemplate, Params};
     /// 
-    /// # #[cfg(not(feature = "std"))] {
-    /// unsafe {
-    ///     let result = init("owner", "repo")
-    ///         .add_template("bug", IssueTemplate::new("Bug", "Description"))
-    ///         .build();
-    ///     assert!(result.is_ok() || result == Err("Bug reporting already initialized"));
-    /// }
-    /// # }
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template(
+    ///         "bug",
+    ///         IssueTemplate::new("Bug: {component}", "Error: {message}").with_labels(vec!["bug".to_string()]),
+    ///     );
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("component", "UI");
+    /// params.insert("message", "Button not working");
+    /// 
+    /// let filled = handle.fill("bug", &params).unwrap();
+    /// assert_eq!(filled.title, "Bug: UI");
+    /// assert_eq!(filled.body, "Error: Button not working");
+    /// assert_eq!(filled.labels, ["bug"]);
+    /// assert!(handle.fill("missing", &params).is_err());
     /// ```
-    #[cfg(not(feature = "std"))]
-    pub unsafe fn build(self) -> Result<(), &'static str> {
-        unsafe {
-            match CONFIG {
-                Some(_) => return Err("Bug reporting already initialized"),
-                None => CONFIG = Some(self.config),
-            }
-        }
-        Ok(())
+    pub fn fill(&self, template_name: &str, params: &Params) -> Result<IssueTemplate, String> {
+        self.config.fill(template_name, params)
     }
-}
 
-/// A handle for bug reporting that doesn't rely on global state.
-/// 
-/// This struct provides the same functionality as the global configuration
-/// but can be used in no_std environments and allows multiple independent
-/// configurations within the same application.
-/// 
-/// # Examples
-/// 
-/// ```
-/// use bug::{init_handle, IssueTemplate, FxHashMap};
-/// 
-/// let handle = init_handle("octocat", "Hello-World")
-///     .add_template("bug", IssueTemplate::new("Bug Report", "Issue: {description}"));
-/// 
-/// let mut params = FxHashMap::default();
-/// params.insert("description".to_string(), "Button not working".to_string());
-/// 
-/// let url = handle.generate_url("bug", &params).unwrap();
-/// assert!(url.contains("github.com/octocat/Hello-World/issues/new"));
-/// ```
-#[derive(Debug, Clone)]
-pub struct BugReportHandle {
-    config: BugReportConfig,
-}
+    /// Generate a GitHub issue URL from the first template that can be used.
+    /// 
+    /// See `create_report_with_fallback`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_names` - Templates to try, most specific first
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""))
+    ///     .add_template("generic", IssueTemplate::new("Something went wrong", ""));
+    /// 
+    /// // "crash_v2" is registered by a plugin that hasn't loaded yet.
+    /// let url = handle.generate_url_with_fallback(["crash_v2", "crash", "generic"], &Params::new()).unwrap();
+    /// assert!(url.contains("title=Crash"));
+    /// ```
+    pub fn generate_url_with_fallback<I, S>(&self, template_names: I, params: &Params) -> Result<String, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.create_report_with_fallback(template_names, params).map(|report| report.url)
+    }
 
-impl BugReportHandle {
-    /// Create a new bug report handle.
+    /// Create a report from the first template that can be used.
+    /// 
+    /// Each template is tried in turn, skipping those that aren't
+    /// registered or can't be filled, so a report can name a specific
+    /// template that is registered late and still degrade to a generic
+    /// one. A template's own `with_fallback` is followed before moving on.
+    /// The report's `template_name` is the template actually used, and its
+    /// warnings include a `Warning::UsedFallback` for each one skipped.
+    /// 
+    /// With `OnMissingTemplate::LinkChooser`, unregistered names are skipped
+    /// like the rest, and the report links to the issue template chooser
+    /// only if no template could be used and one of them isn't registered.
     /// 
     /// # Arguments
     /// 
-    /// * `github_owner` - GitHub username or organization
-    /// * `github_repo` - GitHub repository name
-    fn new(github_owner: String, github_repo: String) -> Self {
-        Self {
-            config: BugReportConfig {
-                github_owner,
-                github_repo,
-                templates: FxHashMap::default(),
-                template_files: FxHashMap::default(),
-                use_hyperlinks: HyperlinkMode::Auto,
-            },
+    /// * `template_names` - Templates to try, most specific first
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(BugReport)` - The report from the first usable template
+    /// * `Err(String)` - Why each template could not be used
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::warning::Warning;
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("generic", IssueTemplate::new("Something went wrong", ""));
+    /// 
+    /// let report = handle.create_report_with_fallback(["crash_v2", "generic"], &Params::new()).unwrap();
+    /// assert_eq!(report.template_name, "generic");
+    /// assert!(report.warnings.iter().any(|warning| matches!(warning, Warning::UsedFallback { template, .. } if template == "crash_v2")));
+    /// 
+    /// let error = handle.create_report_with_fallback(["crash_v2", "crash"], &Params::new()).unwrap_err();
+    /// assert_eq!(error, "No template could be used: Template 'crash_v2' not found; Template 'crash' not found");
+    /// 
+    /// let handle = handle.on_missing_template(bug::OnMissingTemplate::LinkChooser);
+    /// let report = handle.create_report_with_fallback(["crash_v2", "generic"], &Params::new()).unwrap();
+    /// assert_eq!(report.template_name, "generic");
+    /// let report = handle.create_report_with_fallback(["crash_v2", "crash"], &Params::new()).unwrap();
+    /// assert_eq!(report.url, handle.chooser_url());
+    /// ```
+    pub fn create_report_with_fallback<I, S>(&self, template_names: I, params: &Params) -> Result<BugReport, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut skipped: Vec<(String, String)> = Vec::new();
+        let mut missing = None;
+        for template_name in template_names {
+            let template_name = template_name.as_ref();
+            let result = if self.config.registry.get_by_name(template_name).is_some() {
+                self.create_report(template_name, params)
+            } else {
+                missing.get_or_insert_with(|| template_name.to_string());
+                Err(self.config.registry.not_found(template_name))
+            };
+            match result {
+                Ok(mut report) => {
+                    for (template, error) in skipped {
+                        report.warnings.push(Warning::UsedFallback { template, error });
+                    }
+                    return Ok(report);
+                }
+                Err(error) => skipped.push((template_name.to_string(), error)),
+            }
+        }
+        if skipped.is_empty() {
+            return Err("No templates were given".to_string());
+        }
+        if let Some(template_name) = missing
+            && self.config.on_missing_template == OnMissingTemplate::LinkChooser
+        {
+            let mut report = self.config.missing_template(&template_name)?;
+            for (template, error) in skipped {
+                report.warnings.push(Warning::UsedFallback { template, error });
+            }
+            return Ok(report);
         }
+        let errors: Vec<String> = skipped.into_iter().map(|(_, error)| error).collect();
+        Err(format!("No template could be used: {}", errors.join("; ")))
     }
 
-    /// Add an issue template to this handle.
+    /// Create a report from parameter values that are rendered only if used.
+    /// 
+    /// See `BugReportConfig::materialize` and the `param` module.
     /// 
     /// # Arguments
     /// 
-    /// * `name` - Name to identify the template
-    /// * `template` - The issue template to add
+    /// * `template_name` - Name of the template to use
+    /// * `values` - Parameter values, possibly lazy or binary
     /// 
     /// # Examples
     /// 
     /// ```
     /// use bug::{init_handle, IssueTemplate};
+    /// use bug::param::{Encoding, ParamValue, ParamValues};
     /// 
     /// let handle = init_handle("owner", "repo")
-    ///     .add_template("bug", IssueTemplate::new("Bug Report", "Found a bug"));
+    ///     .add_template("bad_packet", IssueTemplate::new("Bad packet", "```\n{packet}\n```"));
+    /// 
+    /// let packet = [0x01, 0x02, 0xff];
+    /// let mut values = ParamValues::default();
+    /// values.insert("packet".to_string(), ParamValue::Bytes(&packet, Encoding::Hex));
+    /// 
+    /// let report = handle.create_report_with_values("bad_packet", &values).unwrap();
+    /// assert_eq!(report.body, "```\n01 02 ff\n```");
     /// ```
-    pub fn add_template(mut self, name: impl Into<String>, template: IssueTemplate) -> Self {
-        self.config.templates.insert(name.into(), template);
-        self
+    pub fn create_report_with_values(&self, template_name: &str, values: &param::ParamValues<'_>) -> Result<BugReport, String> {
+        self.create_report(template_name, &self.config.materialize(template_name, values))
     }
 
-    /// Add a template file to this handle.
+    /// Render a report's banner ahead of time, for handlers that can't allocate.
+    /// 
+    /// The banner is the one `report_bug_with_output_at` prints, located at
+    /// "emergency handler" since the bug hasn't happened yet. It is not
+    /// summarized or backed off, and no suppression rule applies.
     /// 
     /// # Arguments
     /// 
-    /// * `name` - Name to identify the template file
-    /// * `template_file` - The template file to add
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(PrerenderedReport)` - The rendered banner and URL
+    /// * `Err(String)` - Error message if the template is missing or fails to render
+    pub fn prerender(&self, template_name: &str, params: &Params) -> Result<PrerenderedReport, String> {
+        let report = self.create_report(template_name, params)?;
+        let url = report.url.clone();
+        let mut buffer = String::new();
+        write_banner(&mut buffer, Ok(report), template_name, params, &"emergency handler", None, None);
+        Ok(PrerenderedReport::new(buffer, &url))
+    }
+
+    /// Create a report proposing a fix, with the edits as a unified diff.
+    /// 
+    /// The template's `{diff}` placeholder is filled with
+    /// `suggestion::render_diff(edits)`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template, besides `{diff}`
+    /// * `edits` - The proposed changes
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(BugReport)` - The report and its GitHub issue URL
+    /// * `Err(String)` - Error message if the template is missing or fails to render
+    pub fn create_suggestion(&self, template_name: &str, params: &Params, edits: &[suggestion::SuggestionEdit]) -> Result<BugReport, String> {
+        let mut params = params.clone();
+        params.insert(suggestion::DIFF_PARAM, suggestion::render_diff(edits));
+        self.create_report(template_name, &params)
+    }
+
+    /// Generate a GitHub issue URL from a template id and parameters.
+    /// 
+    /// Like `generate_url`, but the template is found by indexing with its
+    /// `TemplateId` rather than hashing its name.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `id` - Id returned by `register_template` or `template_id`
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The generated GitHub issue URL
+    /// * `Err(String)` - Error message if the id is unknown or validation fails
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init_handle, TemplateFile};
+    /// use bug::{init_handle, IssueTemplate, Params};
     /// 
-    /// let handle = init_handle("owner", "repo")
-    ///     .add_template_file("crash", TemplateFile::new("Crash Report\nApp crashed"));
+    /// let mut handle = init_handle("owner", "repo");
+    /// let id = handle.register_template("parse", IssueTemplate::new("Parse error {code}", ""));
+    /// 
+    /// let mut params = Params::new();
+    /// params.insert("code".to_string(), "E0420".to_string());
+    /// let url = handle.generate_url_by_id(id, &params).unwrap();
+    /// assert!(url.contains("title=Parse+error+E0420"));
     /// ```
-    pub fn add_template_file(mut self, name: impl Into<String>, template_file: TemplateFile) -> Self {
-        self.config.template_files.insert(name.into(), template_file);
-        self
+    pub fn generate_url_by_id(&self, id: TemplateId, params: &Params) -> Result<String, String> {
+        self.create_report_by_id(id, params).map(|report| report.url)
     }
 
-    /// Configure hyperlink behavior for this handle.
+    /// Create a structured bug report from a template id and parameters.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `id` - Id returned by `register_template` or `template_id`
+    /// * `params` - Parameters to substitute in the template
+    pub fn create_report_by_id(&self, id: TemplateId, params: &Params) -> Result<BugReport, String> {
+        self.config.create_report_by_id(id, params)
+    }
+
+    /// Generate a GitHub issue URL for an error code.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - An error code registered with `map_error_code`
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` - The generated GitHub issue URL
+    /// * `Err(String)` - Error message if the code is unmapped or validation fails
+    pub fn generate_url_for_code(&self, code: &str, params: &Params) -> Result<String, String> {
+        self.create_report_for_code(code, params).map(|report| report.url)
+    }
+
+    /// Create a structured bug report for an error code.
+    /// 
+    /// The code's template is filled with `params`, plus the code itself as
+    /// `error_code` when the template uses that placeholder, and the code is
+    /// appended to the report's labels.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - An error code registered with `map_error_code`
+    /// * `params` - Parameters to substitute in the template
+    pub fn create_report_for_code(&self, code: &str, params: &Params) -> Result<BugReport, String> {
+        self.config.create_report_for_code(code, params)
+    }
+
+    /// Report a bug for an error code, writing to the given output.
+    /// 
+    /// This is what `bug_code!` calls when given a handle.
     /// 
     /// # Arguments
     /// 
-    /// * `mode` - How to handle hyperlinks in output
-    /// 
-    /// # Examples
+    /// * `code` - An error code registered with `map_error_code`
+    /// * `params` - Parameters to substitute in the template
+    /// * `loc` - Where the bug occurred, usually `Location::caller()`
+    /// * `output` - Output implementation for displaying bug information
     /// 
-    /// ```
-    /// use bug::{init_handle, HyperlinkMode};
+    /// # Returns
     /// 
-    /// let handle = init_handle("owner", "repo")
-    ///     .hyperlinks(HyperlinkMode::Always);
-    /// ```
-    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
-        self.config.use_hyperlinks = mode;
-        self
+    /// The generated GitHub issue URL, or empty string on error.
+    pub fn report_code_with_output(&self, code: &str, params: &Params, loc: Location, output: &mut dyn Output) -> String {
+        if self.config.code_skipped(code, params) {
+            return String::new();
+        }
+        let label = code_label(&self.config, code);
+        let result = self.config.create_report_for_code_at(code, params, &loc);
+        write_banner(output, result, &label, params, &loc, loc.module_path, Some(&self.config))
     }
 
-    /// Generate a GitHub issue URL from a template and parameters.
+    /// Create a report that references a previously generated one.
     /// 
-    /// This method fills the specified template with the provided parameters
-    /// and generates a complete GitHub issue URL with query parameters for
-    /// title, body, and labels.
+    /// If the template has a `{parent_url}` placeholder and `params` doesn't
+    /// set it, it is filled with the URL of `parent`.
     /// 
     /// # Arguments
     /// 
+    /// * `parent` - The earlier report this one follows up on
     /// * `template_name` - Name of the template to use
     /// * `params` - Parameters to substitute in the template
     /// 
     /// # Returns
     /// 
-    /// * `Ok(String)` - The generated GitHub issue URL
+    /// * `Ok(BugReport)` - The filled follow-up report and its GitHub issue URL
     /// * `Err(String)` - Error message if template not found or validation fails
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init_handle, IssueTemplate, FxHashMap};
+    /// use bug::{init_handle, IssueTemplate, Params};
     /// 
-    /// let handle = init_handle("octocat", "Hello-World")
-    ///     .add_template("bug", IssueTemplate::new("Bug: {component}", "Error: {message}"));
-    /// 
-    /// let mut params = FxHashMap::default();
-    /// params.insert("component".to_string(), "UI".to_string());
-    /// params.insert("message".to_string(), "Button not working".to_string());
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "The app crashed"))
+    ///     .add_template("data_loss", IssueTemplate::new("Unsaved data lost", "See also: {parent_url}"));
     /// 
-    /// let url = handle.generate_url("bug", &params).unwrap();
-    /// assert!(url.contains("github.com/octocat/Hello-World/issues/new"));
-    /// assert!(url.contains("title=Bug%3A+UI"));
+    /// let crash = handle.create_report("crash", &Params::new()).unwrap();
+    /// let follow_up = handle.create_follow_up(&crash, "data_loss", &Params::new()).unwrap();
+    /// assert_eq!(follow_up.body, format!("See also: {}", crash.url));
     /// ```
-    pub fn generate_url(&self, template_name: &str, params: &FxHashMap<String, String>) -> Result<String, String> {
-        let filled_template = if let Some(template) = self.config.templates.get(template_name) {
-            template.fill_params(params)
-        } else if let Some(template_file) = self.config.template_files.get(template_name) {
-            IssueTemplate::from_template_file(template_file, params)?
-        } else {
-            return Err(format!("Template '{}' not found", template_name));
-        };
-        
-        let mut url = format!(
-            "https://github.com/{}/{}/issues/new",
-            self.config.github_owner, self.config.github_repo
-        );
-
-        let mut query_params = Vec::new();
-        
-        if !filled_template.title.is_empty() {
-            query_params.push(format!("title={}", url_encode::encode(&filled_template.title)));
-        }
-        
-        if !filled_template.body.is_empty() {
-            query_params.push(format!("body={}", url_encode::encode(&filled_template.body)));
-        }
-        
-        if !filled_template.labels.is_empty() {
-            let labels_str = filled_template.labels.join(",");
-            query_params.push(format!("labels={}", url_encode::encode(&labels_str)));
-        }
-
-        if !query_params.is_empty() {
-            url.push('?');
-            url.push_str(&query_params.join("&"));
-        }
+    pub fn create_follow_up(&self, parent: &BugReport, template_name: &str, params: &Params) -> Result<BugReport, String> {
+        let extended = self.config.with_implicit_param(template_name, params, PARENT_URL_PARAM, &parent.url);
+        self.create_report(template_name, extended.as_ref().unwrap_or(params))
+    }
 
-        Ok(url)
+    /// Render a template as a comment on an issue that is already tracked.
+    /// 
+    /// For known issues, further occurrences are better appended to the
+    /// existing issue than filed as new ones. GitHub can't prefill a
+    /// comment, so the result holds the issue's URL and the comment for the
+    /// user to paste, which `CommentReport::copy_to_clipboard` can put on
+    /// the clipboard.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `issue_number` - Number of the issue to comment on
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(CommentReport)` - The issue URL and the comment to paste
+    /// * `Err(String)` - Error message if template not found or validation fails
+    pub fn comment_url(&self, issue_number: u64, template_name: &str, params: &Params) -> Result<CommentReport, String> {
+        let report = self.create_report(template_name, params)?;
+        let (owner, repo, host) = self.config.target_of(&report.template_name);
+        Ok(CommentReport::new(host, owner, repo, issue_number, &report))
     }
 
     /// Report a bug with no output (silent mode).
@@ -1089,8 +5721,7 @@ impl BugReportHandle {
     /// 
     /// * `template_name` - Name of the template to use
     /// * `params` - Parameters to substitute in the template
-    /// * `file` - Source file name where the bug occurred
-    /// * `line` - Line number where the bug occurred
+    /// * `loc` - Where the bug occurred, usually `Location::caller()`
     /// 
     /// # Returns
     /// 
@@ -1099,19 +5730,33 @@ impl BugReportHandle {
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init_handle, IssueTemplate, FxHashMap};
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
     /// 
     /// let handle = init_handle("owner", "repo")
     ///     .add_template("error", IssueTemplate::new("Error", "Something broke"));
     /// 
-    /// let params = FxHashMap::default();
-    /// let url = handle.report_bug("error", &params, "main.rs", 42);
+    /// let params = Params::new();
+    /// let url = handle.report_bug_at("error", &params, Location::caller());
     /// assert!(url.contains("github.com"));
     /// ```
-    pub fn report_bug(&self, template_name: &str, params: &FxHashMap<String, String>, file: &str, line: u32) -> String {
-        self.report_bug_with_output(template_name, params, file, line, &mut NoOutput)
+    pub fn report_bug_at(&self, template_name: &str, params: &Params, loc: Location) -> String {
+        self.report_bug_with_output_at(template_name, params, loc, &mut NoOutput)
     }
-    
+
+    /// Report a bug with no output, located at the caller.
+    /// 
+    /// The file and line are those of the code calling this method, captured
+    /// with `#[track_caller]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    #[track_caller]
+    pub fn report_bug_here(&self, template_name: &str, params: &Params) -> String {
+        self.report_bug_at(template_name, params, Location::caller())
+    }
+
     /// Report a bug with output to stderr (std only).
     /// 
     /// This method generates a bug report URL and prints formatted bug
@@ -1121,8 +5766,7 @@ impl BugReportHandle {
     /// 
     /// * `template_name` - Name of the template to use
     /// * `params` - Parameters to substitute in the template
-    /// * `file` - Source file name where the bug occurred
-    /// * `line` - Line number where the bug occurred
+    /// * `loc` - Where the bug occurred, usually `Location::caller()`
     /// 
     /// # Returns
     /// 
@@ -1131,22 +5775,79 @@ impl BugReportHandle {
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init_handle, IssueTemplate, FxHashMap};
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
     /// 
     /// # #[cfg(feature = "std")] {
     /// let handle = init_handle("owner", "repo")
     ///     .add_template("crash", IssueTemplate::new("Crash", "App crashed"));
     /// 
-    /// let params = FxHashMap::default();
-    /// let url = handle.report_bug_stderr("crash", &params, "main.rs", 42);
+    /// let params = Params::new();
+    /// let url = handle.report_bug_stderr_at("crash", &params, Location::caller());
     /// // This will print to stderr and return the URL
     /// # }
     /// ```
     #[cfg(feature = "std")]
-    pub fn report_bug_stderr(&self, template_name: &str, params: &FxHashMap<String, String>, file: &str, line: u32) -> String {
-        self.report_bug_with_output(template_name, params, file, line, &mut std::io::stderr())
+    pub fn report_bug_stderr_at(&self, template_name: &str, params: &Params, loc: Location) -> String {
+        self.report_bug_with_output_at(template_name, params, loc, &mut std::io::stderr())
     }
-    
+
+    /// Report a bug to stderr, or to a temporary file if stderr is broken (std only).
+    /// 
+    /// Like `report_bug_stderr_at`, but when writing to stderr fails, as it
+    /// does for a CLI tool piped into `head` once `head` exits, the banner
+    /// is written to a file in the temporary directory instead. See
+    /// `output::StderrOrFile`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `loc` - Where the bug occurred, usually `Location::caller()`
+    /// 
+    /// # Returns
+    /// 
+    /// The generated GitHub issue URL, or an empty string on error, and
+    /// the path of the file the banner was written to if stderr failed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params, Location};
+    /// 
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "App crashed"));
+    /// 
+    /// let (url, file) = handle.report_bug_stderr_or_file_at("crash", &Params::new(), Location::caller());
+    /// assert!(url.contains("title=Crash"));
+    /// if let Some(file) = file {
+    ///     // stderr was a broken pipe
+    ///     assert!(file.exists());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn report_bug_stderr_or_file_at(
+        &self,
+        template_name: &str,
+        params: &Params,
+        loc: Location,
+    ) -> (String, Option<std::path::PathBuf>) {
+        let mut output = output::StderrOrFile::new();
+        let url = self.report_bug_with_output_at(template_name, params, loc, &mut output);
+        (url, output.fallback_path().map(std::path::Path::to_path_buf))
+    }
+
+    /// Report a bug to stderr, located at the caller (std only).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn report_bug_stderr_here(&self, template_name: &str, params: &Params) -> String {
+        self.report_bug_stderr_at(template_name, params, Location::caller())
+    }
+
     /// Report a bug with custom output destination.
     /// 
     /// This is the most flexible bug reporting method, allowing you to specify
@@ -1157,8 +5858,7 @@ impl BugReportHandle {
     /// 
     /// * `template_name` - Name of the template to use
     /// * `params` - Parameters to substitute in the template
-    /// * `file` - Source file name where the bug occurred
-    /// * `line` - Line number where the bug occurred
+    /// * `loc` - Where the bug occurred, usually `Location::caller()`
     /// * `output` - Custom output destination implementing `Output` trait
     /// 
     /// # Returns
@@ -1168,161 +5868,622 @@ impl BugReportHandle {
     /// # Examples
     /// 
     /// ```
-    /// use bug::{init_handle, IssueTemplate, FxHashMap, Output};
-    /// 
-    /// struct MockOutput(String);
-    /// 
-    /// impl Output for MockOutput {
-    ///     fn write_str(&mut self, s: &str) {
-    ///         self.0.push_str(s);
-    ///     }
-    ///     fn write_fmt(&mut self, args: core::fmt::Arguments) {
-    ///         self.0.push_str(&format!("{}", args));
-    ///     }
-    /// }
+    /// use bug::{init_handle, IssueTemplate, Params, Location, Output};
     /// 
     /// let handle = init_handle("owner", "repo")
     ///     .add_template("test", IssueTemplate::new("Test", "Test bug"));
     /// 
-    /// let params = FxHashMap::default();
-    /// let mut output = MockOutput(String::new());
-    /// let url = handle.report_bug_with_output("test", &params, "test.rs", 10, &mut output);
+    /// let params = Params::new();
+    /// let mut output = String::new();
+    /// let url = handle.report_bug_with_output_at("test", &params, Location::new("test.rs", 10), &mut output);
     /// 
     /// assert!(url.contains("github.com"));
-    /// assert!(output.0.contains("BUG ENCOUNTERED"));
-    /// ```
-    pub fn report_bug_with_output(&self, template_name: &str, params: &FxHashMap<String, String>, file: &str, line: u32, output: &mut dyn Output) -> String {
-        match self.generate_url(template_name, params) {
-            Ok(url) => {
-                output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}:{}\n", file, line));
-                output.write_fmt(format_args!("   Template: {}\n", template_name));
-                if !params.is_empty() {
-                    output.write_str("   Parameters:\n");
-                    for (key, value) in params {
-                        output.write_fmt(format_args!("     {}: {}\n", key, value));
+    /// assert!(output.contains("BUG ENCOUNTERED in test.rs:10"));
+    /// 
+    /// // Parameters are listed in the order the template uses them, and
+    /// // placeholders left without a value are named.
+    /// let handle = handle.add_template("slow", IssueTemplate::new("Slow {stage}", "Took {elapsed} of {budget}"));
+    /// let mut params = Params::new();
+    /// params.insert("elapsed", "9s");
+    /// params.insert("stage", "fetch");
+    /// let mut output = String::new();
+    /// handle.report_bug_with_output_at("slow", &params, Location::new("test.rs", 20), &mut output);
+    /// 
+    /// assert!(output.contains("   Parameters:\n     stage: fetch\n     elapsed: 9s\n   Unfilled placeholders: budget\n"));
+    /// ```
+    pub fn report_bug_with_output_at(&self, template_name: &str, params: &Params, loc: Location, output: &mut dyn Output) -> String {
+        if self.config.skipped(template_name, params) {
+            return String::new();
+        }
+        let extended = self.config.with_location_params(template_name, params, &loc);
+        let result = self
+            .create_report(template_name, extended.as_ref().unwrap_or(params))
+            .map(|report| self.config.annotate(report, &loc));
+        write_banner(output, result, template_name, params, &loc, loc.module_path, Some(&self.config))
+    }
+
+    /// Report a bug with no output (silent mode).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `file` - Source file name where the bug occurred
+    /// * `line` - Line number where the bug occurred
+    #[deprecated(note = "use `report_bug_at` with `Location::caller()`, or `report_bug_here`")]
+    pub fn report_bug(&self, template_name: &str, params: &Params, file: &str, line: u32) -> String {
+        self.write_report(template_name, params, file, line, &mut NoOutput)
+    }
+
+    /// Report a bug with output to stderr (std only).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `file` - Source file name where the bug occurred
+    /// * `line` - Line number where the bug occurred
+    #[cfg(feature = "std")]
+    #[deprecated(note = "use `report_bug_stderr_at` with `Location::caller()`, or `report_bug_stderr_here`")]
+    pub fn report_bug_stderr(&self, template_name: &str, params: &Params, file: &str, line: u32) -> String {
+        self.write_report(template_name, params, file, line, &mut std::io::stderr())
+    }
+
+    /// Report a bug with custom output destination.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template_name` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `file` - Source file name where the bug occurred
+    /// * `line` - Line number where the bug occurred
+    /// * `output` - Custom output destination implementing `Output` trait
+    #[deprecated(note = "use `report_bug_with_output_at` with `Location::caller()`")]
+    pub fn report_bug_with_output(&self, template_name: &str, params: &Params, file: &str, line: u32, output: &mut dyn Output) -> String {
+        self.write_report(template_name, params, file, line, output)
+    }
+
+    /// Report for the deprecated file/line methods.
+    fn write_report(&self, template_name: &str, params: &Params, file: &str, line: u32, output: &mut dyn Output) -> String {
+        let result = self.create_report(template_name, params);
+        write_banner(output, result, template_name, params, &format_args!("{}:{}", file, line), None, Some(&self.config))
+    }
+
+    /// Get a reference to the internal configuration.
+    /// 
+    /// This method provides read-only access to the handle's configuration,
+    /// allowing you to inspect templates, repository information, and settings.
+    /// The configuration is shared, so cloning the `Arc` takes a cheap
+    /// snapshot; changing the handle afterwards copies the configuration
+    /// first and leaves the snapshot as it was.
+    /// 
+    /// # Returns
+    /// 
+    /// The shared `BugReportConfig` used by this handle.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// 
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template("test", IssueTemplate::new("Test", "Test issue"));
+    /// 
+    /// let config = handle.config();
+    /// assert_eq!(config.github_owner, "octocat");
+    /// assert_eq!(config.github_repo, "Hello-World");
+    /// assert_eq!(config.template_names().count(), 1);
+    /// 
+    /// let snapshot = std::sync::Arc::clone(handle.config());
+    /// let handle = handle.add_template("other", IssueTemplate::new("Other", ""));
+    /// assert_eq!(snapshot.template_names().count(), 1);
+    /// assert_eq!(handle.config().template_names().count(), 2);
+    /// ```
+    pub fn config(&self) -> &Arc<BugReportConfig> {
+        &self.config
+    }
+}
+
+/// Generate a GitHub issue URL using the global configuration (std only).
+/// 
+/// This function generates a bug report URL using the global configuration
+/// set up with `init().build()`. It's a convenience function for when you
+/// don't want to use the `bug!` macro but still want to use global config.
+/// 
+/// # Arguments
+/// 
+/// * `template_name` - Name of the template to use
+/// * `params` - Parameters to substitute in the template
+/// 
+/// # Returns
+/// 
+/// * `Ok(String)` - The generated GitHub issue URL
+/// * `Err(String)` - Error if not initialized or template not found
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init, generate_github_url, IssueTemplate, Params};
+/// 
+/// # #[cfg(feature = "std")] {
+/// // First initialize the global config
+/// init("owner", "repo")
+///     .add_template("error", IssueTemplate::new("Error", "An error occurred"))
+///     .build()
+///     .expect("Failed to initialize");
+/// 
+/// let mut params = Params::new();
+/// let url = generate_github_url("error", &params).unwrap();
+/// assert!(url.contains("github.com/owner/repo"));
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_github_url(template_name: &str, params: &Params) -> Result<String, String> {
+    generate_github_report(template_name, params).map(|report| report.url)
+}
+
+/// Create a structured bug report using the global configuration (std only).
+/// 
+/// This is the global-configuration counterpart of
+/// `BugReportHandle::create_report`.
+/// 
+/// # Arguments
+/// 
+/// * `template_name` - Name of the template to use
+/// * `params` - Parameters to substitute in the template
+/// 
+/// # Returns
+/// 
+/// * `Ok(BugReport)` - The filled report and its GitHub issue URL
+/// * `Err(String)` - Error if not initialized or template not found
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init, generate_github_report, IssueTemplate, Params};
+/// 
+/// # #[cfg(feature = "std")] {
+/// init("owner", "repo")
+///     .add_template("error", IssueTemplate::new("Error", "An error occurred"))
+///     .build()
+///     .expect("Failed to initialize");
+/// 
+/// let report = generate_github_report("error", &Params::new()).unwrap();
+/// assert_eq!(report.title, "Error");
+/// assert!(report.url.contains("github.com/owner/repo"));
+/// # }
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_github_report(template_name: &str, params: &Params) -> Result<BugReport, String> {
+    let config = CONFIG.get().ok_or("Bug reporting not initialized. Call bug_rs::init() first.")?;
+    config.create_report(template_name, params)
+}
+
+/// Generate a GitHub issue URL from a template id using the global configuration (std only).
+/// 
+/// # Arguments
+/// 
+/// * `id` - Id returned by `BugReportConfigBuilder::register_template`
+/// * `params` - Parameters to substitute in the template
+/// 
+/// # Returns
+/// 
+/// * `Ok(String)` - The generated GitHub issue URL
+/// * `Err(String)` - Error if not initialized or the id is unknown
+#[cfg(feature = "std")]
+pub fn generate_github_url_by_id(id: TemplateId, params: &Params) -> Result<String, String> {
+    let config = CONFIG.get().ok_or("Bug reporting not initialized. Call bug_rs::init() first.")?;
+    config.create_report_by_id(id, params).map(|report| report.url)
+}
+
+/// Create a structured bug report for an error code using the global configuration (std only).
+/// 
+/// # Arguments
+/// 
+/// * `code` - An error code registered with `BugReportConfigBuilder::map_error_code`
+/// * `params` - Parameters to substitute in the template
+/// 
+/// # Returns
+/// 
+/// * `Ok(BugReport)` - The filled report and its GitHub issue URL
+/// * `Err(String)` - Error if not initialized, the code is unmapped, or validation fails
+#[cfg(feature = "std")]
+pub fn generate_github_report_for_code(code: &str, params: &Params) -> Result<BugReport, String> {
+    let config = CONFIG.get().ok_or("Bug reporting not initialized. Call bug_rs::init() first.")?;
+    config.create_report_for_code(code, params)
+}
+
+/// Generate a GitHub issue URL for an error code using the global configuration (std only).
+/// 
+/// # Arguments
+/// 
+/// * `code` - An error code registered with `BugReportConfigBuilder::map_error_code`
+/// * `params` - Parameters to substitute in the template
+#[cfg(feature = "std")]
+pub fn generate_github_url_for_code(code: &str, params: &Params) -> Result<String, String> {
+    generate_github_report_for_code(code, params).map(|report| report.url)
+}
+
+/// Create a report that references a previously generated one, using the global configuration (std only).
+/// 
+/// This is the global-configuration counterpart of
+/// `BugReportHandle::create_follow_up`.
+/// 
+/// # Arguments
+/// 
+/// * `parent` - The earlier report this one follows up on
+/// * `template_name` - Name of the template to use
+/// * `params` - Parameters to substitute in the template
+#[cfg(feature = "std")]
+pub fn generate_github_follow_up(parent: &BugReport, template_name: &str, params: &Params) -> Result<BugReport, String> {
+    let config = CONFIG.get().ok_or("Bug reporting not initialized. Call bug_rs::init() first.")?;
+    config.create_follow_up(parent, template_name, params)
+}
+
+/// Describe an error code for the bug banner, naming its template when mapped.
+fn code_label(config: &BugReportConfig, code: &str) -> String {
+    match config.error_codes.get(code) {
+        Some(template_name) => format!("{} (error code {})", template_name, code),
+        None => format!("error code {}", code),
+    }
+}
+
+/// Set the explanation of `placeholder` in `param_docs`, replacing any it has.
+fn set_param_doc(param_docs: &mut Vec<(String, String)>, placeholder: String, doc: String) {
+    match param_docs.iter_mut().find(|(name, _)| *name == placeholder) {
+        Some(entry) => entry.1 = doc,
+        None => param_docs.push((placeholder, doc)),
+    }
+}
+
+/// The `{source_excerpt}` of a call site.
+fn source_excerpt(loc: &Location, messages: &messages::Messages) -> String {
+    #[cfg(feature = "std")]
+    if cfg!(debug_assertions)
+        && let Some(excerpt) = format::source_excerpt(loc.file, loc.line, SOURCE_EXCERPT_CONTEXT)
+    {
+        return excerpt;
+    }
+    let _ = loc;
+    messages.source_unavailable.clone()
+}
+
+/// Write the bug banner for a report, or for the error that prevented one.
+/// 
+/// Returns the report URL, or an empty string on error.
+fn write_banner(
+    output: &mut dyn Output,
+    result: Result<BugReport, String>,
+    template_label: &str,
+    params: &Params,
+    site: &dyn core::fmt::Display,
+    module_path: Option<&str>,
+    config: Option<&BugReportConfig>,
+) -> String {
+    let result = match (result, config) {
+        (Ok(report), Some(config)) => match config.middleware.run(report) {
+            ReportOutcome::Reported(report) => Ok(report),
+            ReportOutcome::Suppressed => return String::new(),
+            ReportOutcome::Failed(e) => Err(e),
+        },
+        (result, _) => result,
+    };
+    let withheld = config.is_some_and(BugReportConfig::consent_withheld);
+    let summarize = config.is_some_and(|config| config.summarize);
+    let backoff = config.and_then(|config| config.backoff.as_ref().map(|policy| (config, policy)));
+    let (occurrence, verbosity) = match (&result, backoff) {
+        (Ok(report), Some((config, policy))) if !summarize => {
+            let occurrence = config.occurrences.record(&report.template_name, params);
+            (Some(occurrence), policy.verbosity(occurrence))
+        }
+        _ => (None, backoff::Verbosity::Full),
+    };
+    if let Some(config) = config
+        && !config.sinks.is_empty()
+    {
+        let url = match &result {
+            Ok(report) if !withheld => report.url.as_str(),
+            _ => "",
+        };
+        config.sinks.write(&sink::SinkRecord {
+            result: result.as_ref().map_err(String::as_str),
+            template: template_label,
+            params,
+            site,
+            module_path,
+            url,
+            occurrence,
+            verbosity: if withheld && verbosity == backoff::Verbosity::Full { backoff::Verbosity::Brief } else { verbosity },
+        });
+    }
+    match result {
+        Ok(mut report) if summarize => {
+            if withheld {
+                report.url.clear();
+            }
+            summary::global().record(&report);
+            report.url
+        }
+        Ok(report) => {
+            let url = if withheld { String::new() } else { report.url.clone() };
+            match (verbosity, occurrence) {
+                (backoff::Verbosity::Full, _) => {}
+                (backoff::Verbosity::Brief, Some(occurrence)) => {
+                    output.write_fmt(format_args!(
+                        "🐛 BUG ENCOUNTERED again in {}: {} ({} occurrences)\n",
+                        site, template_label, occurrence
+                    ));
+                    return url;
+                }
+                (_, _) => return url,
+            }
+
+            let quiet = config.and_then(|config| config.quiet_period.map(|period| (config, period)));
+            if let Some((config, period)) = quiet {
+                match config.quiet.admit(period, config.time.now()) {
+                    Some(0) => {}
+                    Some(held) => output.write_fmt(format_args!(
+                        "(+{} more {}, see summary)\n",
+                        held,
+                        if held == 1 { "report" } else { "reports" }
+                    )),
+                    None => {
+                        let mut report = report;
+                        if withheld {
+                            report.url.clear();
+                        }
+                        summary::global().record(&report);
+                        return url;
                     }
                 }
-                let should_use_hyperlinks = match self.config.use_hyperlinks {
-                    HyperlinkMode::Auto => supports_hyperlinks(),
-                    HyperlinkMode::Always => true,
-                    HyperlinkMode::Never => false,
-                };
-                
-                if should_use_hyperlinks {
-                    output.write_fmt(format_args!("   {}\n", create_terminal_hyperlink(&url, "File a bug report")));
+            }
+
+            if withheld {
+                output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}\n", site));
+                output.write_fmt(format_args!("   Template: {}\n", template_label));
+                output.write_fmt(format_args!("   {}\n", consent::CONSENT_REQUIRED_MESSAGE));
+                output.write_str("\n");
+                return url;
+            }
+
+            let placeholders = config
+                .and_then(|config| config.placeholders(&report.template_name))
+                .unwrap_or_default();
+
+            output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}\n", site));
+            output.write_fmt(format_args!("   Template: {}\n", template_label));
+            if let Some(module_path) = module_path {
+                output.write_fmt(format_args!("   Module: {}\n", module_path));
+            }
+            if let Some(config) = config.filter(|config| config.show_config_digest) {
+                output.write_fmt(format_args!("   Config digest: {:016x}\n", config.digest()));
+            }
+            if !params.is_empty() {
+                output.write_str("   Parameters:\n");
+                for (key, value) in fill::in_template_order(params, &placeholders) {
+                    output.write_fmt(format_args!("     {}: {}\n", key, value));
+                }
+            }
+            let unfilled: Vec<&str> = report
+                .warnings
+                .iter()
+                .filter_map(|warning| match warning {
+                    Warning::UnfilledPlaceholder(name) => Some(name.as_str()),
+                    _ => None,
+                })
+                .collect();
+            if !unfilled.is_empty() {
+                output.write_fmt(format_args!("   Unfilled placeholders: {}\n", unfilled.join(", ")));
+            }
+            if config.is_some_and(|config| config.show_warnings) {
+                let mut others = report
+                    .warnings
+                    .iter()
+                    .filter(|warning| !matches!(warning, Warning::UnfilledPlaceholder(_)))
+                    .peekable();
+                if others.peek().is_some() {
+                    output.write_str("   Warnings:\n");
+                    for warning in others {
+                        output.write_fmt(format_args!("     {}\n", warning));
+                    }
+                }
+            }
+
+            #[cfg(feature = "api")]
+            if let Some(issue) = config.and_then(|config| config.existing_issue(&report, params)) {
+                if config.is_some_and(|config| config.hyperlinks_for(&report.template_name).enabled()) {
+                    let text = format!("Already reported as #{}", issue.number);
+                    output.write_fmt(format_args!("   {}\n", create_terminal_hyperlink(&issue.url, &text)));
                 } else {
-                    output.write_fmt(format_args!("   File a bug report: {}\n", url));
+                    output.write_fmt(format_args!("   Already reported as #{}: {}\n", issue.number, issue.url));
                 }
                 output.write_str("\n");
-                url
+                return issue.url;
             }
-            Err(e) => {
-                output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}:{}\n", file, line));
-                output.write_fmt(format_args!("   Error generating bug report: {}\n", e));
-                output.write_str("\n");
-                String::new()
+
+            if config.is_some_and(|config| config.hyperlinks_for(&report.template_name).enabled()) {
+                output.write_fmt(format_args!("   {}\n", create_terminal_hyperlink(&report.url, "File a bug report")));
+            } else {
+                output.write_fmt(format_args!("   File a bug report: {}\n", report.url));
             }
+            output.write_str("\n");
+            report.url
+        }
+        Err(e) => {
+            output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}\n", site));
+            output.write_fmt(format_args!("   Error generating bug report: {}\n", e));
+            output.write_str("\n");
+            String::new()
         }
-    }
-
-    /// Get a reference to the internal configuration.
-    /// 
-    /// This method provides read-only access to the handle's configuration,
-    /// allowing you to inspect templates, repository information, and settings.
-    /// 
-    /// # Returns
-    /// 
-    /// A reference to the `BugReportConfig` used by this handle.
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use bug::{init_handle, IssueTemplate};
-    /// 
-    /// let handle = init_handle("octocat", "Hello-World")
-    ///     .add_template("test", IssueTemplate::new("Test", "Test issue"));
-    /// 
-    /// let config = handle.config();
-    /// assert_eq!(config.github_owner, "octocat");
-    /// assert_eq!(config.github_repo, "Hello-World");
-    /// assert_eq!(config.templates.len(), 1);
-    /// ```
-    pub fn config(&self) -> &BugReportConfig {
-        &self.config
     }
 }
 
-/// Generate a GitHub issue URL using the global configuration (std only).
-/// 
-/// This function generates a bug report URL using the global configuration
-/// set up with `init().build()`. It's a convenience function for when you
-/// don't want to use the `bug!` macro but still want to use global config.
-/// 
-/// # Arguments
+/// Print the summary of reports collected by `summarize` to stderr (std only).
 /// 
-/// * `template_name` - Name of the template to use
-/// * `params` - Parameters to substitute in the template
+/// Clears the collected reports, so each report is summarized once. Call
+/// this before the process exits; nothing is printed if no reports were
+/// collected.
 /// 
 /// # Returns
 /// 
-/// * `Ok(String)` - The generated GitHub issue URL
-/// * `Err(String)` - Error if not initialized or template not found
+/// The number of reports summarized.
+#[cfg(feature = "std")]
+pub fn flush_summary() -> u64 {
+    summary::global().flush(&mut std::io::stderr())
+}
+
+/// Flush collected reports when the returned guard is dropped (std only).
+/// 
+/// Create the guard at the top of `main` so that everything collected by
+/// `summarize` is printed when the program returns, including when it
+/// unwinds from a panic. `std::process::exit` skips destructors, so call
+/// `flush_summary` before exiting that way.
 /// 
 /// # Examples
 /// 
-/// ```
-/// use bug::{init, generate_github_url, IssueTemplate, FxHashMap};
+/// ```standalone_crate
+/// use bug::{init_handle, IssueTemplate, Params, Location};
 /// 
-/// # #[cfg(feature = "std")] {
-/// // First initialize the global config
-/// init("owner", "repo")
-///     .add_template("error", IssueTemplate::new("Error", "An error occurred"))
-///     .build()
-///     .expect("Failed to initialize");
+/// let handle = init_handle("owner", "repo")
+///     .add_template("guarded_job", IssueTemplate::new("Job failed", ""))
+///     .summarize(true);
 /// 
-/// let mut params = FxHashMap::default();
-/// let url = generate_github_url("error", &params).unwrap();
-/// assert!(url.contains("github.com/owner/repo"));
-/// # }
+/// {
+///     let _guard = bug::flush_on_exit();
+///     handle.report_bug_at("guarded_job", &Params::new(), Location::new(file!(), line!()));
+/// } // the summary is printed here
+/// 
+/// assert!(bug::summary::global().summary().iter().all(|s| s.template_name != "guarded_job"));
 /// ```
 #[cfg(feature = "std")]
-pub fn generate_github_url(template_name: &str, params: &FxHashMap<String, String>) -> Result<String, String> {
-    let config = CONFIG.get().ok_or("Bug reporting not initialized. Call bug_rs::init() first.")?;
-    
-    let filled_template = if let Some(template) = config.templates.get(template_name) {
-        template.fill_params(params)
-    } else if let Some(template_file) = config.template_files.get(template_name) {
-        IssueTemplate::from_template_file(template_file, params)?
-    } else {
-        return Err(format!("Template '{}' not found", template_name));
-    };
-    
-    let mut url = format!(
-        "https://github.com/{}/{}/issues/new",
-        config.github_owner, config.github_repo
-    );
+pub fn flush_on_exit() -> summary::FlushGuard {
+    summary::FlushGuard::new()
+}
 
-    let mut query_params = Vec::new();
-    
-    if !filled_template.title.is_empty() {
-        query_params.push(format!("title={}", url_encode::encode(&filled_template.title)));
-    }
-    
-    if !filled_template.body.is_empty() {
-        query_params.push(format!("body={}", url_encode::encode(&filled_template.body)));
+/// Write the summary of reports collected by `summarize` to `output`.
+/// 
+/// Like `flush_summary`, for no_std builds or custom destinations.
+/// 
+/// # Arguments
+/// 
+/// * `output` - Where to write the summary
+pub fn flush_summary_to(output: &mut dyn Output) -> u64 {
+    summary::global().flush(output)
+}
+
+/// Report a bug through the global configuration, printing to stderr.
+/// 
+/// This is the implementation of the `bug!` macro and not part of the
+/// public API. With `global-nostd` and without `std` the report is only
+/// generated, not printed.
+#[doc(hidden)]
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+pub fn __report_global(template_name: &str, params: &Params, loc: Location) -> String {
+    let config = global_config();
+    if config.is_some_and(|config| config.skipped(template_name, params)) {
+        return String::new();
     }
-    
-    if !filled_template.labels.is_empty() {
-        let labels_str = filled_template.labels.join(",");
-        query_params.push(format!("labels={}", url_encode::encode(&labels_str)));
+    let result = config
+        .ok_or_else(|| "Bug reporting not initialized. Call bug_rs::init() first.".to_string())
+        .and_then(|config| {
+            let extended = config.with_location_params(template_name, params, &loc);
+            config
+                .create_report(template_name, extended.as_ref().unwrap_or(params))
+                .map(|report| config.annotate(report, &loc))
+        });
+    write_banner(&mut global_output(), result, template_name, params, &loc, loc.module_path, config)
+}
+
+/// Report a bug for an error code through the global configuration, printing to stderr.
+/// 
+/// This is the implementation of the `bug_code!` macro and not part of the
+/// public API. With `global-nostd` and without `std` the report is only
+/// generated, not printed.
+#[doc(hidden)]
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+pub fn __report_code_global(code: &str, params: &Params, loc: Location) -> String {
+    let config = global_config();
+    if config.is_some_and(|config| config.code_skipped(code, params)) {
+        return String::new();
     }
+    let (result, label) = match config {
+        Some(config) => (config.create_report_for_code_at(code, params, &loc), code_label(config, code)),
+        None => (
+            Err("Bug reporting not initialized. Call bug_rs::init() first.".to_string()),
+            format!("error code {}", code),
+        ),
+    };
+    write_banner(&mut global_output(), result, &label, params, &loc, loc.module_path, config)
+}
+
+/// Where reports through the global configuration are printed.
+#[cfg(feature = "std")]
+fn global_output() -> std::io::Stderr {
+    std::io::stderr()
+}
+
+/// Where reports through the global configuration are printed.
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+fn global_output() -> NoOutput {
+    NoOutput
+}
+
+/// Expand a global report, or fail to compile without a global backend.
+/// 
+/// This is used by `bug!` and `bug_code!` and not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(any(feature = "std", feature = "global-nostd"))]
+macro_rules! __report_through_global {
+    ($macro_name:literal, $report:ident, $name:expr, $params:expr) => {
+        $crate::$report($name, &$params, $crate::__location!())
+    };
+}
+
+/// Expand a global report, or fail to compile without a global backend.
+/// 
+/// This is used by `bug!` and `bug_code!` and not part of the public API.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(any(feature = "std", feature = "global-nostd")))]
+macro_rules! __report_through_global {
+    ($macro_name:literal, $report:ident, $name:expr, $params:expr) => {
+        ::core::compile_error!(::core::concat!(
+            "`", $macro_name, "!` needs a global configuration, but `bug` was built without one: ",
+            "enable its `std` or `global-nostd` feature, or report through a handle with `bug_with_handle!`"
+        ))
+    };
+}
 
-    if !query_params.is_empty() {
-        url.push('?');
-        url.push_str(&query_params.join("&"));
+/// Report a bug through this thread's scoped handle, or the global
+/// configuration outside a scope, printing to stderr.
+/// 
+/// This is the implementation of the `bug_scoped!` macro and not part of
+/// the public API.
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub fn __report_scoped(template_name: &str, params: &Params, loc: Location) -> String {
+    match scoped::current() {
+        Some(handle) => handle.report_bug_stderr_at(template_name, params, loc),
+        None => __report_global(template_name, params, loc),
     }
+}
 
-    Ok(url)
+/// Merge `extra` into macro parameters without overriding literal ones.
+/// 
+/// This is the implementation of the `..extra` macro syntax and not part of
+/// the public API.
+#[doc(hidden)]
+pub fn __merge_params<K, V>(params: &mut Params, extra: impl IntoIterator<Item = (K, V)>)
+where
+    K: AsRef<str> + Into<String>,
+    V: Into<String>,
+{
+    for (key, value) in extra {
+        if !params.contains_key(key.as_ref()) {
+            params.insert(key, value);
+        }
+    }
 }
 
 /// Create a clickable terminal hyperlink using ANSI escape sequences.
@@ -1368,6 +6529,35 @@ pub fn create_terminal_hyperlink(url: &str, text: &str) -> String {
     format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
 }
 
+/// A shared snapshot of the global configuration (std only).
+/// 
+/// The global configuration can't change once built, so the snapshot is
+/// just another reference to it, for code such as monitoring endpoints that
+/// wants to keep it around without cloning every template.
+/// 
+/// # Returns
+/// 
+/// The global configuration, or `None` if `build` hasn't been called.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{config_snapshot, init, IssueTemplate};
+/// 
+/// init("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""))
+///     .build()
+///     .ok();
+/// 
+/// // Another report may have initialized the configuration first
+/// let config = config_snapshot().unwrap();
+/// assert_eq!(config.github_owner, "owner");
+/// ```
+#[cfg(feature = "std")]
+pub fn config_snapshot() -> Option<Arc<BugReportConfig>> {
+    CONFIG.get().cloned()
+}
+
 /// Get the hyperlink mode from the global configuration (std only).
 /// 
 /// This function retrieves the hyperlink mode setting from the global
@@ -1400,20 +6590,15 @@ pub fn create_terminal_hyperlink(url: &str, text: &str) -> String {
 #[cfg(feature = "std")]
 pub fn get_hyperlink_mode() -> HyperlinkMode {
     CONFIG.get()
-        .map(|config| config.use_hyperlinks.clone())
+        .map(|config| config.use_hyperlinks)
         .unwrap_or(HyperlinkMode::Never)
 }
 
 /// Get the hyperlink mode from the global configuration (no_std version).
 /// 
 /// This function retrieves the hyperlink mode setting from the global
-/// configuration in no_std environments. It uses unsafe code to access
-/// the static configuration.
-/// 
-/// # Safety
-/// 
-/// This function is unsafe because it reads from global mutable static state.
-/// It should only be called after the configuration has been properly initialized.
+/// configuration in no_std environments. Reading the configuration is
+/// synchronized with `build`, so this can be called at any time.
 /// 
 /// # Returns
 /// 
@@ -1424,22 +6609,17 @@ pub fn get_hyperlink_mode() -> HyperlinkMode {
 /// ```
 /// use bug::{init, HyperlinkMode};
 /// 
-/// # #[cfg(not(feature = "std"))] {
-/// unsafe {
-///     // After initialization with unsafe build()
-///     let mode = bug::get_hyperlink_mode();
-///     // Returns the configured hyperlink mode
-/// }
+/// # #[cfg(all(not(feature = "std"), feature = "global-nostd"))] {
+/// // After initialization with build()
+/// let mode = bug::get_hyperlink_mode();
+/// // Returns the configured hyperlink mode
 /// # }
 /// ```
-#[cfg(not(feature = "std"))]
-pub unsafe fn get_hyperlink_mode() -> HyperlinkMode {
-    unsafe {
-        match core::ptr::addr_of!(CONFIG).read() {
-            Some(config) => config.use_hyperlinks.clone(),
-            None => HyperlinkMode::Never,
-        }
-    }
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+pub fn get_hyperlink_mode() -> HyperlinkMode {
+    global_config()
+        .map(|config| config.use_hyperlinks)
+        .unwrap_or(HyperlinkMode::Never)
 }
 
 /// Detect if the current terminal supports clickable hyperlinks (std only).
@@ -1493,10 +6673,10 @@ pub unsafe fn get_hyperlink_mode() -> HyperlinkMode {
 #[cfg(feature = "std")]
 pub fn supports_hyperlinks() -> bool {
     // Check for common terminal emulators that support hyperlinks
-    if let Ok(term) = std::env::var("TERM") {
-        if term.contains("xterm") || term.contains("screen") || term.contains("tmux") {
-            return true;
-        }
+    if let Ok(term) = std::env::var("TERM")
+        && (term.contains("xterm") || term.contains("screen") || term.contains("tmux"))
+    {
+        return true;
     }
     
     // Check for specific terminal programs
@@ -1552,7 +6732,7 @@ pub fn supports_hyperlinks() -> bool {
     false
 }
 
-/// Report a bug using the global configuration (std only).
+/// Report a bug using the global configuration.
 /// 
 /// This macro generates a GitHub issue URL using a predefined template and
 /// parameters, then prints bug report information to stderr. It uses the
@@ -1562,11 +6742,13 @@ pub fn supports_hyperlinks() -> bool {
 /// 
 /// - `bug!("template_name")` - Use template without parameters
 /// - `bug!("template_name", { param1 = value1, param2 = value2 })` - With parameters
+/// - `bug!("template_name", { param1 = value1 }, ..context)` - Also merge existing `Params` or a map of strings;
+///   parameters written in the braces take precedence over entries of `context`
 /// 
 /// # Returns
 /// 
 /// Returns the generated GitHub issue URL as a `String`, or an empty string if
-/// an error occurs or in no_std mode.
+/// an error occurs.
 /// 
 /// # Examples
 /// 
@@ -1597,8 +6779,9 @@ pub fn supports_hyperlinks() -> bool {
 /// 
 /// The macro prints to stderr in this format:
 /// ```text
-/// 🐛 BUG ENCOUNTERED in src/main.rs:42
+/// 🐛 BUG ENCOUNTERED in src/main.rs:42:15
 ///    Template: crash
+///    Module: my_app
 ///    Parameters:
 ///      error_type: NullPointerException
 ///      error_message: Attempted to access null pointer
@@ -1608,60 +6791,66 @@ pub fn supports_hyperlinks() -> bool {
 /// # Platform Support
 /// 
 /// - **std**: Full functionality with terminal output
-/// - **no_std**: Returns empty string, no output (use `bug_with_handle!` instead)
+/// - **no_std with `global-nostd`**: Reports through the configuration
+///   installed with `build`, without printing
+/// - **no_std without `global-nostd`**: Fails to compile; use
+///   `bug_with_handle!` instead
+/// 
+/// With neither feature there is no global configuration to report
+/// through, so a `bug!` call is rejected rather than silently doing nothing:
+/// 
+#[cfg_attr(not(any(feature = "std", feature = "global-nostd")), doc = "```compile_fail")]
+#[cfg_attr(any(feature = "std", feature = "global-nostd"), doc = "```")]
+/// let _url: String = bug::bug!("crash", { reason = "lost" });
+/// ```
+/// 
+/// # Concurrency
+/// 
+/// The global configuration is installed once and then only read, so any
+/// number of threads may report at the same time, including while another
+/// thread is still calling `build`:
+/// 
+/// ```
+/// # #[cfg(feature = "std")] {
+/// use bug::{bug, init, IssueTemplate};
+/// use std::thread;
+/// 
+/// let threads: Vec<_> = (0..8)
+///     .map(|worker| {
+///         thread::spawn(move || {
+///             if worker == 0 {
+///                 // Only the first `build` anywhere in the process installs
+///                 let _ = init("owner", "repo")
+///                     .add_template("stress", IssueTemplate::new("Stress {worker}", "{iteration}"))
+///                     .summarize(true)
+///                     .build();
+///             }
+///             for iteration in 0..200 {
+///                 let _ = bug!("stress", { worker = worker, iteration = iteration });
+///             }
+///         })
+///     })
+///     .collect();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+/// # }
+/// ```
 #[macro_export]
 macro_rules! bug {
     ($template:expr) => {
         $crate::bug!($template, {})
     };
-    ($template:expr, { $($key:ident = $value:expr),* $(,)? }) => {{
-        use $crate::FxHashMap;
-        
-        let mut params = FxHashMap::default();
+    ($template:expr, { $($key:ident = $value:expr),* $(,)? } $(, ..$extra:expr)?) => {{
+        let mut params = $crate::Params::new();
         $(
-            params.insert(stringify!($key).to_string(), $value.to_string());
+            params.insert(stringify!($key), $value.to_string());
         )*
+        $(
+            $crate::__merge_params(&mut params, &$extra);
+        )?
 
-        #[cfg(feature = "std")]
-        {
-            match $crate::generate_github_url($template, &params) {
-                Ok(url) => {
-                    eprintln!("🐛 BUG ENCOUNTERED in {}:{}", file!(), line!());
-                    eprintln!("   Template: {}", $template);
-                    if !params.is_empty() {
-                        eprintln!("   Parameters:");
-                        for (key, value) in &params {
-                            eprintln!("     {}: {}", key, value);
-                        }
-                    }
-                    let should_use_hyperlinks = match $crate::get_hyperlink_mode() {
-                        $crate::HyperlinkMode::Auto => $crate::supports_hyperlinks(),
-                        $crate::HyperlinkMode::Always => true,
-                        $crate::HyperlinkMode::Never => false,
-                    };
-                    
-                    if should_use_hyperlinks {
-                        eprintln!("   {}", $crate::create_terminal_hyperlink(&url, "File a bug report"));
-                    } else {
-                        eprintln!("   File a bug report: {}", url);
-                    }
-                    eprintln!();
-                    url
-                }
-                Err(e) => {
-                    eprintln!("🐛 BUG ENCOUNTERED in {}:{}", file!(), line!());
-                    eprintln!("   Error generating bug report: {}", e);
-                    eprintln!();
-                    String::new()
-                }
-            }
-        }
-        #[cfg(not(feature = "std"))]
-        {
-            // In no_std mode, we can't use the global config, so just return empty string
-            // User should use bug_with_handle! instead
-            String::new()
-        }
+        $crate::__report_through_global!("bug", __report_global, $template, params)
     }};
 }
 
@@ -1671,10 +6860,16 @@ macro_rules! bug {
 /// and prints bug report information. Unlike `bug!`, this works in both std
 /// and no_std environments since it doesn't rely on global state.
 /// 
+/// Templates can use `{source_location}` (`file:line:column` of the macro
+/// call) and `{module}` (its module path); both are filled automatically
+/// unless passed explicitly. The same applies to `bug!` and `bug_code!`.
+/// 
 /// # Syntax
 /// 
 /// - `bug_with_handle!(handle, "template_name")` - Use template without parameters
 /// - `bug_with_handle!(handle, "template_name", { param1 = value1, param2 = value2 })` - With parameters
+/// - `bug_with_handle!(handle, "template_name", { param1 = value1 }, ..context)` - Also merge
+///   `Params` or a map of strings; parameters written in the braces take precedence
 /// 
 /// # Returns
 /// 
@@ -1701,14 +6896,27 @@ macro_rules! bug {
 /// let simple_handle = init_handle("owner", "repo")
 ///     .add_template("simple", IssueTemplate::new("Simple Bug", "Something broke"));
 /// let url = bug_with_handle!(simple_handle, "simple");
+/// 
+/// // Merge context captured elsewhere
+/// let mut context = bug::Params::new();
+/// context.insert("message".to_string(), "Request body was empty".to_string());
+/// let url = bug_with_handle!(handle, "error", { type = "ValidationError" }, ..context);
+/// assert!(url.contains("Request%20body%20was%20empty"));
+/// 
+/// // Call-site placeholders
+/// let located = init_handle("owner", "repo")
+///     .add_template("located", IssueTemplate::new("Bug in {module}", "At {source_location}"));
+/// let url = bug_with_handle!(located, "located");
+/// assert!(!url.contains("%7B"), "placeholders left unfilled: {}", url);
 /// ```
 /// 
 /// # Output (when using stderr output)
 /// 
-/// With `report_bug_stderr()`, the macro prints to stderr:
+/// With `report_bug_stderr_at()`, the macro prints to stderr:
 /// ```text
-/// 🐛 BUG ENCOUNTERED in src/main.rs:42
+/// 🐛 BUG ENCOUNTERED in src/main.rs:42:15
 ///    Template: error
+///    Module: my_app
 ///    Parameters:
 ///      type: ValidationError
 ///      message: Invalid input provided
@@ -1724,15 +6932,135 @@ macro_rules! bug_with_handle {
     ($handle:expr, $template:expr) => {
         $crate::bug_with_handle!($handle, $template, {})
     };
-    ($handle:expr, $template:expr, { $($key:ident = $value:expr),* $(,)? }) => {{
-        use $crate::FxHashMap;
-        
-        let mut params = FxHashMap::default();
+    ($handle:expr, $template:expr, { $($key:ident = $value:expr),* $(,)? } $(, ..$extra:expr)?) => {{
+        let mut params = $crate::Params::new();
+        $(
+            params.insert(stringify!($key), $value.to_string());
+        )*
+        $(
+            $crate::__merge_params(&mut params, &$extra);
+        )?
+
+        $handle.report_bug_at($template, &params, $crate::__location!())
+    }};
+}
+
+/// Report a bug by error code, through the global configuration or a handle.
+/// 
+/// The code is looked up in the error codes registered with
+/// `map_error_code`, so call sites name a stable code instead of a template.
+/// The code is added to the issue's labels and filled into an
+/// `{error_code}` placeholder if the template has one.
+/// 
+/// # Syntax
+/// 
+/// - `bug_code!("E0420")` - Report the code without parameters
+/// - `bug_code!("E0420", { param1 = value1, param2 = value2 })` - With parameters
+/// - `bug_code!("E0420", { param1 = value1 }, ..context)` - Also merge existing `Params` or a map of strings
+/// - `bug_code!(handle, "E0420", { .. })` - Use a `BugReportHandle` rather than the global configuration
+/// 
+/// # Returns
+/// 
+/// Returns the generated GitHub issue URL as a `String`, or an empty string if
+/// the code is unmapped or an error occurs. Without a handle it needs the
+/// global configuration, so like `bug!` it fails to compile when neither
+/// `std` nor `global-nostd` is enabled.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{init_handle, bug_code, IssueTemplate};
+/// 
+/// let handle = init_handle("owner", "repo")
+///     .add_template("parser_crash", IssueTemplate::new("Parser crash {error_code}", "At {offset}"))
+///     .map_error_code("E0420", "parser_crash");
+/// 
+/// let url = bug_code!(handle, "E0420", { offset = 17 });
+/// assert!(url.contains("title=Parser+crash+E0420"));
+/// assert!(url.contains("labels=E0420"));
+/// ```
+#[macro_export]
+macro_rules! bug_code {
+    ($code:literal) => {
+        $crate::bug_code!($code, {})
+    };
+    ($code:literal, { $($key:ident = $value:expr),* $(,)? } $(, ..$extra:expr)?) => {{
+        let mut params = $crate::Params::new();
         $(
-            params.insert(stringify!($key).to_string(), $value.to_string());
+            params.insert(stringify!($key), $value.to_string());
         )*
+        $(
+            $crate::__merge_params(&mut params, &$extra);
+        )?
+
+        $crate::__report_through_global!("bug_code", __report_code_global, $code, params)
+    }};
+    ($handle:expr, $code:expr) => {
+        $crate::bug_code!($handle, $code, {})
+    };
+    ($handle:expr, $code:expr, { $($key:ident = $value:expr),* $(,)? } $(, ..$extra:expr)?) => {{
+        let mut params = $crate::Params::new();
+        $(
+            params.insert(stringify!($key), $value.to_string());
+        )*
+        $(
+            $crate::__merge_params(&mut params, &$extra);
+        )?
+
+        $handle.report_code_with_output($code, &params, $crate::__location!(), &mut $crate::NoOutput)
+    }};
+}
+
+/// Report a bug through the current scoped handle (std only).
+/// 
+/// Inside `bug::scoped::with_handle`, this reports through that handle;
+/// elsewhere it reports through the global configuration, exactly like
+/// `bug!`. Either way the report is printed to stderr.
+/// 
+/// # Syntax
+/// 
+/// Same as `bug!`:
+/// - `bug_scoped!("template_name")` - Report with no parameters
+/// - `bug_scoped!("template_name", { key = value, ... })` - Report with parameters
+/// - `bug_scoped!("template_name", { key = value, ... }, ..context)` - Also take parameters from a map
+/// 
+/// # Examples
+/// 
+/// ```
+/// use bug::{bug_scoped, init_handle, IssueTemplate};
+/// 
+/// let test_handle = init_handle("owner", "test-repo")
+///     .add_template("parse_error", IssueTemplate::new("Parse error at {offset}", ""));
+/// 
+/// let url = bug::scoped::with_handle(test_handle, || bug_scoped!("parse_error", { offset = 17 }));
+/// assert!(url.starts_with("https://github.com/owner/test-repo/issues/new"));
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! bug_scoped {
+    ($template:expr) => {
+        $crate::bug_scoped!($template, {})
+    };
+    ($template:expr, { $($key:ident = $value:expr),* $(,)? } $(, ..$extra:expr)?) => {{
+        let mut params = $crate::Params::new();
+        $(
+            params.insert(stringify!($key), $value.to_string());
+        )*
+        $(
+            $crate::__merge_params(&mut params, &$extra);
+        )?
 
-        $handle.report_bug($template, &params, file!(), line!())
+        $crate::__report_scoped($template, &params, $crate::__location!())
     }};
 }
 
+/// The full `Location` of the macro call site, including column and module.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __location {
+    () => {
+        $crate::Location::new(file!(), line!())
+            .with_column(column!())
+            .with_module_path(module_path!())
+    };
+}
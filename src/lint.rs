@@ -0,0 +1,173 @@
+//! Positioned diagnostics for template source.
+//!
+//! [`TemplateFile::lint`](crate::TemplateFile::lint) reports every problem in
+//! a template at once, each with the line and column where it occurs, so a
+//! broken placeholder in a long template can be found without bisecting it.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// What is wrong at a diagnostic's position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// The template has no content at all.
+    EmptyTemplate,
+    /// The first line, which becomes the issue title, is blank.
+    MissingTitle,
+    /// A `{` is not closed before the end of its line.
+    UnclosedBrace,
+    /// `{}` has no placeholder name.
+    EmptyPlaceholder,
+    /// A placeholder name contains a character that can't appear in one.
+    InvalidCharacter(char),
+    /// `{name|}` has no filter name after `|`.
+    EmptyFilter,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::EmptyTemplate => write!(f, "Template file is empty"),
+            DiagnosticKind::MissingTitle => write!(f, "Template must have a title on the first line"),
+            DiagnosticKind::UnclosedBrace => write!(f, "Unclosed '{{' in placeholder"),
+            DiagnosticKind::EmptyPlaceholder => write!(f, "Empty placeholder '{{}}'"),
+            DiagnosticKind::InvalidCharacter(ch) => write!(
+                f,
+                "Invalid character {:?} in placeholder; names may only contain letters, digits, and '_'",
+                ch
+            ),
+            DiagnosticKind::EmptyFilter => write!(f, "Empty filter after '|' in placeholder"),
+        }
+    }
+}
+
+/// A problem in a template, located by 1-based line and column.
+///
+/// Columns count characters, not bytes.
+///
+/// # Examples
+///
+/// ```
+/// use bug::TemplateFile;
+/// use bug::lint::DiagnosticKind;
+///
+/// let template = TemplateFile::new("Crash in {module}\nError: {message\nSee {}");
+/// let diagnostics = template.lint();
+///
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 8));
+/// assert_eq!(diagnostics[0].kind, DiagnosticKind::UnclosedBrace);
+/// assert_eq!(diagnostics[1].to_string(), "line 3, column 5: Empty placeholder '{}'");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Line of the problem, starting at 1
+    pub line: usize,
+    /// Column of the problem, starting at 1
+    pub column: usize,
+    /// What the problem is
+    pub kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(line: usize, column: usize, kind: DiagnosticKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+/// Append a diagnostic for every malformed placeholder in `text`.
+///
+/// Only a `{` followed by a name character starts a placeholder, so code
+/// and JSON in the body aren't flagged; such a run is malformed if a
+/// character other than a name character or one `|` comes before its `}`,
+/// or no `}` closes it on its line. An empty `{}` is flagged too. Fenced
+/// code blocks are skipped entirely.
+pub(crate) fn check_placeholders(text: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut fence: Option<&str> = None;
+    for (index, line) in text.split('\n').enumerate() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) => {
+                if trimmed.starts_with(marker) {
+                    fence = None;
+                }
+            }
+            None => match ["```", "~~~"].into_iter().find(|marker| trimmed.starts_with(marker)) {
+                Some(marker) => fence = Some(marker),
+                None => check_line(index + 1, line, diagnostics),
+            },
+        }
+    }
+}
+
+/// Append a diagnostic for every malformed placeholder on one line.
+fn check_line(line: usize, text: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let is_name_char = |ch: char| ch.is_alphanumeric() || ch == '_';
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '{' {
+            i += 1;
+            continue;
+        }
+        // Columns are 1-based
+        let open_column = i + 1;
+        match chars.get(i + 1) {
+            Some('}') => {
+                diagnostics.push(Diagnostic::new(line, open_column, DiagnosticKind::EmptyPlaceholder));
+                i += 2;
+                continue;
+            }
+            Some(&ch) if is_name_char(ch) => {}
+            // Not a placeholder: code, JSON, or a literal brace
+            _ => {
+                i += 1;
+                continue;
+            }
+        }
+        let Some(close) = chars[i + 1..].iter().position(|&ch| ch == '}').map(|offset| i + 1 + offset) else {
+            diagnostics.push(Diagnostic::new(line, open_column, DiagnosticKind::UnclosedBrace));
+            return;
+        };
+
+        // Characters of the filter name, once a `|` has been seen.
+        let mut filter_len: Option<usize> = None;
+        let mut invalid = None;
+        for (offset, &ch) in chars[i + 1..close].iter().enumerate() {
+            if ch == '|' && filter_len.is_none() {
+                filter_len = Some(0);
+            } else if is_name_char(ch) {
+                if let Some(len) = &mut filter_len {
+                    *len += 1;
+                }
+            } else {
+                invalid = Some((i + 1 + offset, ch));
+                break;
+            }
+        }
+        match invalid {
+            Some((position, ch)) => {
+                diagnostics.push(Diagnostic::new(line, position + 1, DiagnosticKind::InvalidCharacter(ch)));
+                // A `{` here may start the next placeholder
+                i = position;
+            }
+            None => {
+                if filter_len == Some(0) {
+                    diagnostics.push(Diagnostic::new(line, close + 1, DiagnosticKind::EmptyFilter));
+                }
+                i = close + 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! The text the crate writes into issue bodies, for localizing them.
+//!
+//! Most of a body comes from its template, but a few parts are written by
+//! the crate itself: the note on sampled reports, the stand-in for a
+//! source excerpt that can't be read, the headings of
+//! [`ReproSteps`](crate::repro::ReproSteps), and the screenshot section
+//! (std only). [`Messages`] holds that text,
+//! English by default. A project whose maintainers work in another
+//! language gives its handle translated messages with `body_messages`, so
+//! the whole body reads in that language and not only the templates.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, BugReport, IssueTemplate, Location, Params};
+//! use bug::messages::Messages;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("slow", IssueTemplate::new("Image lente", "Le rendu a pris {ms} ms."))
+//!     .sample_rate("slow", 0.5)
+//!     .sample_rng(|| 0)
+//!     .body_messages(Messages {
+//!         sampled_note: "_Rapport échantillonné (taux {sample_rate})._".to_string(),
+//!         ..Messages::default()
+//!     });
+//!
+//! let mut params = Params::new();
+//! params.insert("ms", "120");
+//! let mut output = String::new();
+//! let url = handle.report_bug_with_output_at("slow", &params, Location::new("render.rs", 42), &mut output);
+//! let report = BugReport::parse_url(&url).unwrap();
+//! assert_eq!(report.body, "Le rendu a pris 120 ms.\n\n_Rapport échantillonné (taux 0.5)._");
+//! ```
+
+#[cfg(feature = "std")]
+use std::string::{String, ToString};
+
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+/// The text written into issue bodies by the crate rather than by templates.
+///
+/// `Default` gives the English text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    /// Appended to sampled reports whose template has no `{sample_rate}`;
+    /// `{sample_rate}` in it is replaced with the rate
+    pub sampled_note: String,
+    /// `{source_excerpt}` when the source file can't be read
+    pub source_unavailable: String,
+    /// The heading of `ReproSteps`' numbered steps
+    pub steps_heading: String,
+    /// The heading of `ReproSteps`' expected behavior
+    pub expected_heading: String,
+    /// The heading of `ReproSteps`' actual behavior
+    pub actual_heading: String,
+    /// The heading of the `{screenshot}` section
+    pub screenshot_heading: String,
+    /// The `{screenshot}` section when the app saved no screenshot
+    pub screenshot_prompt: String,
+    /// The `{screenshot}` section when the app saved one; `{path}` in it
+    /// is replaced with where
+    pub screenshot_saved_prompt: String,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            sampled_note: "_Sampled report: only a fraction of occurrences are reported (sample rate {sample_rate})._"
+                .to_string(),
+            source_unavailable: "_Source not available._".to_string(),
+            steps_heading: "Steps to reproduce".to_string(),
+            expected_heading: "Expected behavior".to_string(),
+            actual_heading: "Actual behavior".to_string(),
+            screenshot_heading: "Screenshot".to_string(),
+            screenshot_prompt: "_If you can, drag and drop a screenshot here to attach it._".to_string(),
+            screenshot_saved_prompt: "A screenshot was saved to `{path}`. Drag and drop it here to attach it.".to_string(),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Reporting bugs in this crate itself.
+//!
+//! When the library detects that one of its own invariants doesn't hold,
+//! the problem is in `bug`, not in the application using it. Such errors
+//! are reported with [`report_internal_error`], which files against this
+//! crate's repository with the crate version and enabled features filled
+//! in, so users can forward them upstream in one click.
+
+use crate::{init_handle, BugReportHandle, IssueTemplate, Location, Params};
+use core::fmt::Display;
+
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Owner of this crate's repository.
+pub const REPOSITORY_OWNER: &str = "tristanpoland";
+
+/// Name of this crate's repository.
+pub const REPOSITORY_NAME: &str = "bug";
+
+/// Name of the template internal errors are reported with.
+pub const INTERNAL_ERROR_TEMPLATE: &str = "internal_error";
+
+/// A handle that files issues against this crate's repository.
+///
+/// # Examples
+///
+/// ```
+/// use bug::meta;
+///
+/// let report = meta::handle()
+///     .create_report(meta::INTERNAL_ERROR_TEMPLATE, &meta::internal_error_params("encoder overflow", "src/lib.rs:1"))
+///     .unwrap();
+/// assert!(report.url.starts_with("https://github.com/tristanpoland/bug/issues/new?"));
+/// assert_eq!(report.title, "Internal error: encoder overflow");
+/// ```
+pub fn handle() -> BugReportHandle {
+    init_handle(REPOSITORY_OWNER, REPOSITORY_NAME).add_template(
+        INTERNAL_ERROR_TEMPLATE,
+        IssueTemplate::new(
+            "Internal error: {error}",
+            "The `bug` crate hit an internal error. This is a bug in `bug`, \
+             not in the application using it.\n\n\
+             **Error:** {error}\n\
+             **Location:** {source_location}\n\
+             **Version:** {version}\n\
+             **Features:** {features}\n",
+        )
+        .with_labels(vec!["internal-error".to_string()]),
+    )
+}
+
+/// Parameters for the internal error template.
+///
+/// # Arguments
+///
+/// * `error` - What went wrong
+/// * `location` - Where in this crate it went wrong
+pub fn internal_error_params(error: impl Display, location: impl Display) -> Params {
+    let mut params = Params::new();
+    params.insert("error", error.to_string());
+    params.insert("source_location", location.to_string());
+    params.insert("version", env!("CARGO_PKG_VERSION"));
+    params.insert("features", enabled_features());
+    params
+}
+
+/// Report a bug in this crate, located at the caller.
+///
+/// With `std` the banner is printed to stderr; without it the report is
+/// only generated.
+///
+/// # Arguments
+///
+/// * `error` - What went wrong
+///
+/// # Returns
+///
+/// The issue URL for this crate's repository.
+#[track_caller]
+pub fn report_internal_error(error: impl Display) -> String {
+    let loc = Location::caller();
+    let params = internal_error_params(error, loc);
+    let handle = handle();
+    #[cfg(feature = "std")]
+    {
+        handle.report_bug_with_output_at(INTERNAL_ERROR_TEMPLATE, &params, loc, &mut std::io::stderr())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        handle.report_bug_at(INTERNAL_ERROR_TEMPLATE, &params, loc)
+    }
+}
+
+/// The enabled cargo features, comma separated.
+fn enabled_features() -> String {
+    let features = [
+        ("std", cfg!(feature = "std")),
+        ("global-nostd", cfg!(feature = "global-nostd")),
+        ("egui", cfg!(feature = "egui")),
+        ("tauri", cfg!(feature = "tauri")),
+        ("web", cfg!(feature = "web")),
+        ("grpc", cfg!(feature = "grpc")),
+        ("include_dir", cfg!(feature = "include_dir")),
+        ("usage-stats", cfg!(feature = "usage-stats")),
+        ("codeowners", cfg!(feature = "codeowners")),
+        ("capi", cfg!(feature = "capi")),
+        ("api", cfg!(feature = "api")),
+        ("remote", cfg!(feature = "remote")),
+        ("webhook", cfg!(feature = "webhook")),
+        ("wasm", cfg!(feature = "wasm")),
+        ("android", cfg!(feature = "android")),
+        ("ios", cfg!(feature = "ios")),
+        ("cli", cfg!(feature = "cli")),
+    ];
+    let enabled: Vec<&str> = features
+        .iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(name, _)| *name)
+        .collect();
+    if enabled.is_empty() {
+        "none".to_string()
+    } else {
+        enabled.join(", ")
+    }
+}
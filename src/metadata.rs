@@ -0,0 +1,157 @@
+//! Setting up from human-panic style metadata (std only).
+//!
+//! Projects moving from `human-panic` already describe themselves with a
+//! `Metadata` of name, version, authors, and homepage. [`from_metadata`]
+//! takes the same shape and returns a handle filing issues in the
+//! repository the homepage points to, with a `panic` template ready for
+//! [`PanicHook`](crate::panic_hook::PanicHook). Crashes then link to a
+//! prefilled issue instead of leaving a report file to attach by hand.
+//!
+//! The homepage must be a repository URL such as
+//! `https://github.com/owner/repo`; any other host is used as a GitHub
+//! Enterprise `base_url`.
+//!
+//! # Examples
+//!
+//! ```standalone_crate
+//! use bug::{from_metadata, metadata, Params};
+//! use bug::panic_hook::{PanicHook, PANIC_TEMPLATE};
+//!
+//! let handle = from_metadata(metadata!()).unwrap();
+//! let url = handle.generate_url(PANIC_TEMPLATE, &Params::new()).unwrap();
+//! assert!(url.starts_with("https://github.com/tristanpoland/bug/issues/new?"));
+//!
+//! PanicHook::new(PANIC_TEMPLATE).handle(handle).install();
+//! ```
+
+use crate::context::AutoContext;
+use crate::panic_hook::PANIC_TEMPLATE;
+use crate::{github, init_handle, BugReportHandle, IssueTemplate};
+use std::borrow::Cow;
+use std::format;
+use std::string::String;
+use std::vec::Vec;
+
+/// What a program says about itself, as human-panic's `Metadata` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// The program's name
+    pub name: Cow<'static, str>,
+    /// The program's version
+    pub version: Cow<'static, str>,
+    /// Who wrote it, as in `CARGO_PKG_AUTHORS`
+    pub authors: Cow<'static, str>,
+    /// URL of the program's repository
+    pub homepage: Cow<'static, str>,
+}
+
+/// `Metadata` from the calling crate's `Cargo.toml`.
+///
+/// The homepage is the `repository` field, or `homepage` when there is no
+/// repository.
+///
+/// # Examples
+///
+/// ```
+/// let metadata = bug::metadata!();
+/// assert_eq!(metadata.name, "bug");
+/// assert_eq!(metadata.homepage, "https://github.com/tristanpoland/bug");
+/// ```
+#[macro_export]
+macro_rules! metadata {
+    () => {
+        $crate::metadata::Metadata {
+            name: env!("CARGO_PKG_NAME").into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            authors: env!("CARGO_PKG_AUTHORS").replace(':', ", ").into(),
+            homepage: match env!("CARGO_PKG_REPOSITORY") {
+                "" => env!("CARGO_PKG_HOMEPAGE"),
+                repository => repository,
+            }
+            .into(),
+        }
+    };
+}
+
+/// A handle filing issues in the repository `metadata`'s homepage points to.
+///
+/// The handle has a `panic` template that fills the panic placeholders
+/// along with the program's name and version, `{os}`, and `{arch}`;
+/// `{crate_version}` is the metadata's version in every template.
+///
+/// # Arguments
+///
+/// * `metadata` - The program's name, version, authors, and repository URL
+///
+/// # Returns
+///
+/// * `Ok(BugReportHandle)` - A handle for the repository
+/// * `Err(String)` - The homepage is not a repository URL
+///
+/// # Examples
+///
+/// ```
+/// use bug::{from_metadata, BugReport, Params};
+/// use bug::metadata::Metadata;
+///
+/// let handle = from_metadata(Metadata {
+///     name: "mytool".into(),
+///     version: "1.2.0".into(),
+///     authors: "Jane Doe <jane@example.com>".into(),
+///     homepage: "https://github.example.com/tools/mytool.git".into(),
+/// })
+/// .unwrap();
+///
+/// let mut params = Params::new();
+/// params.insert("panic_message", "disk full");
+/// let report = handle.create_report("panic", &params).unwrap();
+/// assert!(report.url.starts_with("https://github.example.com/tools/mytool/issues/new?"));
+/// assert_eq!(report.title, "mytool 1.2.0 crashed: disk full");
+///
+/// let error = from_metadata(Metadata { homepage: "https://mytool.example".into(), ..bug::metadata!() });
+/// assert_eq!(error.unwrap_err(), "Homepage 'https://mytool.example' is not a repository URL such as https://github.com/owner/repo");
+/// ```
+pub fn from_metadata(metadata: Metadata) -> Result<BugReportHandle, String> {
+    let (base_url, owner, repo) = parse_homepage(&metadata.homepage)
+        .ok_or_else(|| format!(
+            "Homepage '{}' is not a repository URL such as https://{}/owner/repo",
+            metadata.homepage,
+            github::HOST.trim_start_matches("https://")
+        ))?;
+
+    let mut handle = init_handle(owner, repo)
+        .add_template(PANIC_TEMPLATE, IssueTemplate::new(
+            format!("{} {{crate_version}} crashed: {{panic_message}}", metadata.name),
+            format!(
+                "`{}` {{crate_version}} panicked.\n\n\
+                 **Message:** {{panic_message}}\n\
+                 **Location:** {{source_location}}\n\
+                 **Thread:** {{thread}}\n\
+                 **OS:** {{os}} ({{arch}})\n",
+                metadata.name
+            ),
+        ))
+        .with_auto_context(AutoContext::new().crate_version(metadata.version.clone()));
+    if let Some(base_url) = base_url {
+        handle = handle.base_url(base_url);
+    }
+    Ok(handle)
+}
+
+/// Split a repository URL into its GitHub Enterprise base URL, owner, and name.
+///
+/// The base URL is `None` for github.com.
+fn parse_homepage(homepage: &str) -> Option<(Option<&str>, &str, &str)> {
+    let path_start = homepage.find("://")? + 3;
+    let trimmed = homepage.trim_end_matches('/');
+    let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed);
+    let segments: Vec<&str> = trimmed[path_start..].split('/').collect();
+    let [host @ .., owner, repo] = segments.as_slice() else {
+        return None;
+    };
+    if host.is_empty() || owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    let base_url = &trimmed[..trimmed.len() - owner.len() - repo.len() - 2];
+    Some(((base_url != github::HOST).then_some(base_url), owner, repo))
+}
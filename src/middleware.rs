@@ -0,0 +1,126 @@
+//! Processing reports through an ordered pipeline.
+//!
+//! Redaction, deduplication, throttling, persistence, and extra sinks all
+//! want to see a report after it is generated and before it is shown.
+//! Instead of a configuration flag for each, they can be written as
+//! [`ReportMiddleware`] and added to the configuration with `middleware`.
+//! Each middleware receives the report and the rest of the pipeline as
+//! [`Next`]: it can change the report before passing it on, act on what
+//! the rest of the pipeline returns, or return early to stop the report.
+//! Middleware runs in the order it was added.
+//!
+//! Like suppression, middleware applies where bugs are reported (`bug!`,
+//! `report_bug_at`, `Reporter::report`, and friends), after suppression
+//! and sampling. A `Suppressed` outcome prints no banner and returns an
+//! empty URL, and a `Failed` one prints the error banner. `create_report`
+//! and `generate_url` don't run the pipeline.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, BugReport, IssueTemplate, Location, Params, Reporter, ReportOutcome};
+//! use bug::middleware::Next;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("login_failed", IssueTemplate::new("Login failed for {user}", "{details}"))
+//!     .add_template("noisy", IssueTemplate::new("Noisy", ""))
+//!     // Redact, then pass the report on
+//!     .middleware(|mut report: BugReport, next: &dyn Next| {
+//!         report.body = report.body.replace("hunter2", "[redacted]");
+//!         next.run(report)
+//!     })
+//!     // Drop reports from one template entirely
+//!     .middleware(|report: BugReport, next: &dyn Next| {
+//!         if report.template_name == "noisy" {
+//!             ReportOutcome::Suppressed
+//!         } else {
+//!             next.run(report)
+//!         }
+//!     });
+//!
+//! let mut params = Params::new();
+//! params.insert("user", "alice");
+//! params.insert("details", "password was hunter2");
+//! let loc = Location::new(file!(), line!());
+//! let outcome = handle.report("login_failed", &params, loc);
+//! match outcome {
+//!     ReportOutcome::Reported(report) => assert_eq!(report.body, "password was [redacted]"),
+//!     other => panic!("unexpected outcome {:?}", other),
+//! }
+//! assert!(handle.report_bug_at("noisy", &Params::new(), loc).is_empty());
+//! ```
+
+use crate::{BugReport, ReportOutcome};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{sync::Arc, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{sync::Arc, vec::Vec};
+
+/// The rest of a pipeline, as seen by one middleware.
+pub trait Next {
+    /// Pass `report` to the remaining middleware.
+    ///
+    /// After the last middleware this returns `ReportOutcome::Reported`
+    /// with the report as it was passed in.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The report, possibly changed
+    fn run(&self, report: BugReport) -> ReportOutcome;
+}
+
+/// One step of the report pipeline.
+///
+/// Implemented for closures taking the report and the rest of the pipeline.
+pub trait ReportMiddleware: Send + Sync {
+    /// Process a report, usually by calling `next.run` with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The generated report
+    /// * `next` - The rest of the pipeline
+    fn process(&self, report: BugReport, next: &dyn Next) -> ReportOutcome;
+}
+
+impl<F: Fn(BugReport, &dyn Next) -> ReportOutcome + Send + Sync> ReportMiddleware for F {
+    fn process(&self, report: BugReport, next: &dyn Next) -> ReportOutcome {
+        self(report, next)
+    }
+}
+
+/// The middleware of a configuration, in the order it was added.
+#[derive(Clone, Default)]
+pub(crate) struct Pipeline(Vec<Arc<dyn ReportMiddleware>>);
+
+impl Pipeline {
+    /// Add `middleware` after the existing steps.
+    pub(crate) fn push(&mut self, middleware: impl ReportMiddleware + 'static) {
+        self.0.push(Arc::new(middleware));
+    }
+
+    /// Run `report` through every step.
+    pub(crate) fn run(&self, report: BugReport) -> ReportOutcome {
+        Chain(&self.0).run(report)
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline").field("len", &self.0.len()).finish()
+    }
+}
+
+/// The steps of a pipeline still to run.
+struct Chain<'a>(&'a [Arc<dyn ReportMiddleware>]);
+
+impl Next for Chain<'_> {
+    fn run(&self, report: BugReport) -> ReportOutcome {
+        match self.0.split_first() {
+            Some((first, rest)) => first.process(report, &Chain(rest)),
+            None => ReportOutcome::Reported(report),
+        }
+    }
+}
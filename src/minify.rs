@@ -0,0 +1,112 @@
+//! Shrinking issue bodies before they are encoded into the URL.
+//!
+//! Templates with optional sections leave runs of blank lines where a
+//! parameter was empty, and HTML comments meant as notes to the template's
+//! authors travel in every URL. Both cost URL length, which GitHub caps.
+//! [`minify`] removes them without changing how the body renders.
+
+use crate::FINGERPRINT_PREFIX;
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Strip HTML comments, trim trailing whitespace, and collapse blank lines.
+///
+/// Fenced code blocks are left as they are, apart from trailing whitespace,
+/// and comments holding a fingerprint marker are kept so issues can still
+/// be found by it.
+pub(crate) fn minify(body: &str) -> String {
+    let stripped = strip_comments(body);
+    let mut minified = String::with_capacity(stripped.len());
+    let mut in_fence = false;
+    let mut after_blank = false;
+    for line in stripped.lines() {
+        let line = line.trim_end();
+        if is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if line.is_empty() && !in_fence {
+            if after_blank || minified.is_empty() {
+                continue;
+            }
+            after_blank = true;
+        } else {
+            after_blank = false;
+        }
+        minified.push_str(line);
+        minified.push('\n');
+    }
+    while minified.ends_with('\n') {
+        minified.pop();
+    }
+    minified
+}
+
+/// Remove HTML comments outside code fences, dropping lines left empty.
+fn strip_comments(body: &str) -> String {
+    let mut stripped = String::with_capacity(body.len());
+    let mut in_fence = false;
+    let mut comment: Option<String> = None;
+    for raw in body.lines() {
+        if comment.is_none() && is_fence(raw) {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            stripped.push_str(raw);
+            stripped.push('\n');
+            continue;
+        }
+
+        let mut line = String::new();
+        let mut removed = false;
+        let mut rest = raw;
+        loop {
+            if let Some(open) = comment.as_mut() {
+                let Some(end) = rest.find("-->") else {
+                    open.push_str(rest);
+                    open.push('\n');
+                    break;
+                };
+                open.push_str(&rest[..end + 3]);
+                rest = &rest[end + 3..];
+                let closed = comment.take().unwrap_or_default();
+                if closed.contains(FINGERPRINT_PREFIX) {
+                    line.push_str(&closed);
+                } else {
+                    removed = true;
+                }
+            } else {
+                let Some(start) = rest.find("<!--") else {
+                    line.push_str(rest);
+                    break;
+                };
+                line.push_str(&rest[..start]);
+                comment = Some(String::from("<!--"));
+                rest = &rest[start + 4..];
+            }
+        }
+
+        // A line that only held comments goes away entirely
+        if (removed || comment.is_some()) && line.trim().is_empty() {
+            continue;
+        }
+        stripped.push_str(&line);
+        if comment.is_none() {
+            stripped.push('\n');
+        }
+    }
+    // An unclosed comment is kept as written rather than guessing where it ends
+    if let Some(open) = comment {
+        stripped.push_str(&open);
+    }
+    stripped
+}
+
+/// Whether a line opens or closes a fenced code block.
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
@@ -0,0 +1,273 @@
+//! Ready-made [`Output`] implementations.
+//!
+//! Banners can be collected into a `String` or a `Vec<u8>` directly, and
+//! any `core::fmt::Write` or `std::io::Write` sink works through
+//! [`FmtOutput`] or [`IoOutput`]. [`TeeOutput`] sends the same banner to
+//! two outputs, and [`StderrOrFile`] saves it to a temporary file when
+//! stderr can't be written. Like every `Output`, these drop write errors
+//! instead of panicking.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, Params};
+//! use bug::output::{FmtOutput, TeeOutput};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {module}", ""));
+//! let mut params = Params::new();
+//! params.insert("module", "parser");
+//! let loc = Location::new(file!(), line!());
+//!
+//! let mut banner = String::new();
+//! handle.report_bug_with_output_at("crash", &params, loc, &mut banner);
+//! assert!(banner.contains("issues/new?title=Crash+in+parser"));
+//!
+//! // The same banner, once as text and once as bytes
+//! let mut tee = TeeOutput(FmtOutput(String::new()), Vec::<u8>::new());
+//! handle.report_bug_with_output_at("crash", &params, loc, &mut tee);
+//! let TeeOutput(FmtOutput(text), bytes) = tee;
+//! assert_eq!(text, banner);
+//! assert_eq!(bytes, banner.as_bytes());
+//! ```
+
+use crate::Output;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// Appends to the string.
+impl Output for String {
+    fn write_str(&mut self, s: &str) {
+        self.push_str(s);
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        let _ = fmt::Write::write_fmt(self, args);
+    }
+}
+
+/// Appends the UTF-8 bytes to the vector.
+impl Output for Vec<u8> {
+    fn write_str(&mut self, s: &str) {
+        self.extend_from_slice(s.as_bytes());
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        let _ = fmt::Write::write_fmt(&mut Bytes(self), args);
+    }
+}
+
+/// Formats straight into a byte vector.
+struct Bytes<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for Bytes<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Writes to a `core::fmt::Write` sink, ignoring its errors.
+///
+/// # Examples
+///
+/// ```
+/// use bug::Output;
+/// use bug::output::FmtOutput;
+/// use core::fmt;
+///
+/// // Any fmt::Write sink, here one that counts what it is given
+/// struct Counter(usize);
+///
+/// impl fmt::Write for Counter {
+///     fn write_str(&mut self, s: &str) -> fmt::Result {
+///         self.0 += s.len();
+///         Ok(())
+///     }
+/// }
+///
+/// let mut output = FmtOutput(Counter(0));
+/// output.write_str("crash");
+/// Output::write_fmt(&mut output, format_args!(" in {}", "parser"));
+/// assert_eq!(output.0.0, "crash in parser".len());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct FmtOutput<W>(pub W);
+
+impl<W: fmt::Write> Output for FmtOutput<W> {
+    fn write_str(&mut self, s: &str) {
+        let _ = self.0.write_str(s);
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        let _ = self.0.write_fmt(args);
+    }
+}
+
+/// Writes to a `std::io::Write` sink, ignoring its errors.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Location, Params};
+/// use bug::output::IoOutput;
+/// use std::io::Cursor;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""));
+/// let mut output = IoOutput(Cursor::new(Vec::new()));
+/// handle.report_bug_with_output_at("crash", &Params::new(), Location::new(file!(), line!()), &mut output);
+/// assert!(!output.0.into_inner().is_empty());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone)]
+pub struct IoOutput<W>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Output for IoOutput<W> {
+    fn write_str(&mut self, s: &str) {
+        let _ = self.0.write_all(s.as_bytes());
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        let _ = self.0.write_fmt(args);
+    }
+}
+
+/// Writes to stderr, or to a temporary file once stderr fails.
+///
+/// A CLI tool piped into `head` loses whatever it writes to stderr once
+/// the reader has gone. When a write to stderr fails, the whole banner
+/// written so far, and everything after it, goes to a new file in
+/// `std::env::temp_dir()` instead, and [`fallback_path`](Self::fallback_path)
+/// says where. If the file can't be written either, the output is dropped.
+///
+/// Until stderr fails, a copy of what was written is kept for the file, so
+/// use a new `StderrOrFile` for each report.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Location, Params};
+/// use bug::output::StderrOrFile;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""));
+/// let mut output = StderrOrFile::new();
+/// let url = handle.report_bug_with_output_at("crash", &Params::new(), Location::new(file!(), line!()), &mut output);
+/// if let Some(path) = output.fallback_path() {
+///     // stderr was a broken pipe; the banner and URL are in the file
+///     assert!(std::fs::read_to_string(path).unwrap().contains(&url));
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct StderrOrFile {
+    /// What was written to stderr, kept until a write fails
+    written: String,
+    file: Option<(std::path::PathBuf, std::fs::File)>,
+    /// Whether the fallback file couldn't be created
+    failed: bool,
+}
+
+#[cfg(feature = "std")]
+impl StderrOrFile {
+    /// An output that writes to stderr until it fails.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The file the output went to after stderr failed, if it did.
+    pub fn fallback_path(&self) -> Option<&std::path::Path> {
+        self.file.as_ref().map(|(path, _)| path.as_path())
+    }
+
+    /// A new file to write to, named after the process and not yet used.
+    fn create_file() -> std::io::Result<(std::path::PathBuf, std::fs::File)> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        loop {
+            let name = std::format!(
+                "bug-report-{}-{}.txt",
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            );
+            let path = std::env::temp_dir().join(name);
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(file) => return Ok((path, file)),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Output for StderrOrFile {
+    fn write_str(&mut self, s: &str) {
+        use std::io::Write;
+        if self.failed {
+            return;
+        }
+        if let Some((_, file)) = &mut self.file {
+            let _ = file.write_all(s.as_bytes());
+            return;
+        }
+        if std::io::stderr().write_all(s.as_bytes()).is_ok() {
+            self.written.push_str(s);
+            return;
+        }
+        match Self::create_file() {
+            Ok((path, mut file)) => {
+                let _ = file.write_all(self.written.as_bytes());
+                let _ = file.write_all(s.as_bytes());
+                self.file = Some((path, file));
+            }
+            Err(_) => self.failed = true,
+        }
+        self.written = String::new();
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        match args.as_str() {
+            Some(s) => self.write_str(s),
+            None => self.write_str(&std::fmt::format(args)),
+        }
+    }
+}
+
+/// Writes everything to both outputs, first `A` and then `B`.
+///
+/// A failing output doesn't stop the other one from being written. Tees
+/// nest to reach more than two outputs.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{NoOutput, Output};
+/// use bug::output::TeeOutput;
+///
+/// let mut output = TeeOutput(String::new(), TeeOutput(NoOutput, String::new()));
+/// output.write_str("crash");
+/// assert_eq!(output.0, "crash");
+/// assert_eq!(output.1.1, "crash");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TeeOutput<A, B>(pub A, pub B);
+
+impl<A: Output, B: Output> Output for TeeOutput<A, B> {
+    fn write_str(&mut self, s: &str) {
+        self.0.write_str(s);
+        self.1.write_str(s);
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        self.0.write_fmt(args);
+        self.1.write_fmt(args);
+    }
+}
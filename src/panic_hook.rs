@@ -0,0 +1,316 @@
+//! Reporting panics as bugs (std only).
+//!
+//! A [`PanicHook`] replaces the process panic hook with one that fills a
+//! template from the panic and prints the issue URL through the usual
+//! banner. Different kinds of panics can go to different templates, each
+//! with its own labels, by registering routes: the first route that picks
+//! a template for the panic wins, and panics no route picks use the
+//! default template.
+//!
+//! The template may use any of these placeholders:
+//!
+//! - `{panic_message}` - The panic message, or `Box<dyn Any>` for payloads
+//!   that aren't strings
+//! - `{thread}` - Name of the panicking thread, or `unnamed`
+//! - `{source_location}` - Where the panic happened, as `file:line:column`
+//!
+//! Placeholders the template doesn't use are not passed to it, so template
+//! files with strict validation work as well.
+//!
+//! Tools that only need one template for every panic can call
+//! [`install_panic_hook`], which reports with the global configuration's
+//! `panic` template.
+//!
+//! Installed hooks also count the panics they see. Any report, not only a
+//! panic report, can mention earlier panics with two more placeholders:
+//!
+//! - `{panic_count}` - How many panics the hook has seen so far
+//! - `{last_panic}` - The message of the most recent panic, or `none`
+//!
+//! # Examples
+//!
+//! ```standalone_crate
+//! use bug::{init_handle, IssueTemplate};
+//! use bug::panic_hook::PanicHook;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("generic_panic", IssueTemplate::new("Panic: {panic_message}", ""))
+//!     .add_template("bounds_bug", IssueTemplate::new("Out of bounds at {source_location}", ""))
+//!     .summarize(true);
+//!
+//! PanicHook::new("generic_panic")
+//!     .handle(handle)
+//!     .route(|panic| panic.message.contains("index out of bounds").then_some("bounds_bug"))
+//!     .install();
+//!
+//! let _ = std::thread::spawn(|| {
+//!     let empty: Vec<u8> = Vec::new();
+//!     empty[3]
+//! })
+//! .join();
+//!
+//! let summary = bug::summary::global().summary();
+//! assert_eq!(summary[0].template_name, "bounds_bug");
+//! ```
+
+use crate::{BugReportHandle, Location};
+use std::any::Any;
+use std::panic::PanicHookInfo;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Placeholder filled with the panic message.
+pub const PANIC_MESSAGE_PARAM: &str = "panic_message";
+
+/// Placeholder filled with the name of the panicking thread.
+pub const THREAD_PARAM: &str = "thread";
+
+/// Placeholder filled with the number of panics installed hooks have seen.
+pub const PANIC_COUNT_PARAM: &str = "panic_count";
+
+/// Placeholder filled with the message of the most recent panic, or `none`.
+pub const LAST_PANIC_PARAM: &str = "last_panic";
+
+/// Template `install_panic_hook` reports panics with.
+pub const PANIC_TEMPLATE: &str = "panic";
+
+static PANIC_COUNT: AtomicU64 = AtomicU64::new(0);
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// How many panics installed hooks have seen in this process.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// use bug::{init_handle, BugReport, IssueTemplate, Location, Params};
+/// use bug::panic_hook::{self, PanicHook};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("panic", IssueTemplate::new("Panic: {panic_message}", ""))
+///     .add_template("slow", IssueTemplate::new("Slow render", "Earlier panics: {panic_count}, last: {last_panic}"));
+/// let handle = std::sync::Arc::new(handle);
+/// PanicHook::new("panic").handle(handle.clone()).install();
+/// assert_eq!(panic_hook::panic_count(), 0);
+///
+/// let _ = std::thread::spawn(|| panic!("socket closed")).join();
+/// assert_eq!(panic_hook::panic_count(), 1);
+/// assert_eq!(panic_hook::last_panic().as_deref(), Some("socket closed"));
+///
+/// let mut output = String::new();
+/// let url = handle.report_bug_with_output_at("slow", &Params::new(), Location::new("render.rs", 7), &mut output);
+/// let report = BugReport::parse_url(&url).unwrap();
+/// assert_eq!(report.body, "Earlier panics: 1, last: socket closed");
+/// ```
+pub fn panic_count() -> u64 {
+    PANIC_COUNT.load(Ordering::Relaxed)
+}
+
+/// The message of the most recent panic an installed hook saw, if any.
+pub fn last_panic() -> Option<String> {
+    LAST_PANIC
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Count a panic and remember its message.
+fn record_panic(message: &str) {
+    PANIC_COUNT.fetch_add(1, Ordering::Relaxed);
+    *LAST_PANIC.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(message.to_string());
+}
+
+/// Report every panic with the global configuration's `panic` template.
+///
+/// The banner with the issue URL is printed to stderr after the standard
+/// "thread panicked at" message. Register the template before the first
+/// panic; use [`PanicHook`] to pick another template, route panics to
+/// several, or report through a handle.
+///
+/// # Examples
+///
+/// ```standalone_crate
+/// use bug::{init, install_panic_hook, IssueTemplate};
+///
+/// init("owner", "repo")
+///     .add_template("panic", IssueTemplate::new(
+///         "Crash: {panic_message}",
+///         "Thread `{thread}` panicked at {source_location}.",
+///     ))
+///     .summarize(true)
+///     .build()
+///     .unwrap();
+/// install_panic_hook();
+///
+/// let _ = std::thread::Builder::new()
+///     .name("worker".to_string())
+///     .spawn(|| panic!("disk full"))
+///     .unwrap()
+///     .join();
+///
+/// let summary = bug::summary::global().summary();
+/// assert_eq!(summary[0].template_name, "panic");
+/// assert!(summary[0].sample_urls[0].contains("title=Crash%3A+disk+full"));
+/// ```
+pub fn install_panic_hook() {
+    PanicHook::new(PANIC_TEMPLATE).install();
+}
+
+/// What a route sees of a panic.
+#[derive(Debug)]
+pub struct PanicDetails<'a> {
+    /// The panic message, or `Box<dyn Any>` for payloads that aren't strings
+    pub message: &'a str,
+    /// The panic payload, for routing on its type
+    pub payload: &'a (dyn Any + Send),
+    /// Where the panic happened, if known
+    pub location: Option<Location>,
+    /// Name of the panicking thread, if it has one
+    pub thread: Option<&'a str>,
+}
+
+type Route = dyn Fn(&PanicDetails) -> Option<&'static str> + Send + Sync;
+
+/// Builder for a panic hook that reports panics.
+pub struct PanicHook {
+    default_template: String,
+    handle: Option<Arc<BugReportHandle>>,
+    routes: Vec<Box<Route>>,
+    keep_previous: bool,
+}
+
+impl PanicHook {
+    /// Report panics with `default_template` unless a route picks another.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_template` - Name of the template for panics no route picks
+    pub fn new(default_template: impl Into<String>) -> Self {
+        Self {
+            default_template: default_template.into(),
+            handle: None,
+            routes: Vec::new(),
+            keep_previous: true,
+        }
+    }
+
+    /// Report through `handle` instead of the global configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle holding the panic templates
+    pub fn handle(mut self, handle: impl Into<Arc<BugReportHandle>>) -> Self {
+        self.handle = Some(handle.into());
+        self
+    }
+
+    /// Add a route that picks a template for some panics.
+    ///
+    /// Routes are tried in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - Returns the template for panics it recognizes, `None` otherwise
+    pub fn route(mut self, route: impl Fn(&PanicDetails) -> Option<&'static str> + Send + Sync + 'static) -> Self {
+        self.routes.push(Box::new(route));
+        self
+    }
+
+    /// Send panics whose message contains `needle` to `template`.
+    ///
+    /// # Arguments
+    ///
+    /// * `needle` - Text to look for in the panic message
+    /// * `template` - Name of the template for matching panics
+    pub fn route_message(self, needle: &'static str, template: &'static str) -> Self {
+        self.route(move |panic| panic.message.contains(needle).then_some(template))
+    }
+
+    /// Whether the previously installed hook still runs after reporting.
+    ///
+    /// On by default, so the standard "thread panicked at" message is
+    /// still printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Whether to run the previous hook
+    pub fn keep_previous(mut self, keep: bool) -> Self {
+        self.keep_previous = keep;
+        self
+    }
+
+    /// Install the hook, replacing the current panic hook.
+    pub fn install(self) {
+        let previous = self.keep_previous.then(std::panic::take_hook);
+        std::panic::set_hook(Box::new(move |info| {
+            if let Some(previous) = &previous {
+                previous(info);
+            }
+            self.report(info);
+        }));
+    }
+
+    /// The template a panic is reported with.
+    fn template_for(&self, panic: &PanicDetails) -> &str {
+        self.routes
+            .iter()
+            .find_map(|route| route(panic))
+            .unwrap_or(&self.default_template)
+    }
+
+    fn report(&self, info: &PanicHookInfo) {
+        let payload = info.payload();
+        let message = match payload.downcast_ref::<&str>() {
+            Some(message) => *message,
+            None => payload.downcast_ref::<String>().map_or("Box<dyn Any>", String::as_str),
+        };
+        let current = std::thread::current();
+        let location = info
+            .location()
+            .map(|loc| Location::new(intern(loc.file()), loc.line()).with_column(loc.column()));
+        let panic = PanicDetails {
+            message,
+            payload,
+            location,
+            thread: current.name(),
+        };
+        record_panic(panic.message);
+
+        let template = self.template_for(&panic);
+        let loc = panic.location.unwrap_or(Location::new("<unknown>", 0));
+        let available = [
+            (PANIC_MESSAGE_PARAM, panic.message),
+            (THREAD_PARAM, panic.thread.unwrap_or("unnamed")),
+        ];
+        let params = match &self.handle {
+            Some(handle) => handle.config().used_params(template, available),
+            None => crate::CONFIG
+                .get()
+                .map(|config| config.used_params(template, available))
+                .unwrap_or_default(),
+        };
+
+        match &self.handle {
+            Some(handle) => {
+                handle.report_bug_with_output_at(template, &params, loc, &mut std::io::stderr());
+            }
+            None => {
+                crate::__report_global(template, &params, loc);
+            }
+        }
+    }
+}
+
+/// Leak a source file name once so it can be used in a `Location`.
+///
+/// Panic locations borrow their file name only for the duration of the
+/// hook; the set of source files is small, so each is leaked at most once.
+fn intern(file: &str) -> &'static str {
+    static FILES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+    let mut files = FILES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(interned) = files.iter().find(|interned| **interned == file) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(file.to_string().into_boxed_str());
+    files.push(interned);
+    interned
+}
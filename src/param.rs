@@ -0,0 +1,709 @@
+//! Template parameters.
+//!
+//! [`Params`] maps placeholder names to the text substituted for them and
+//! is what every reporting method takes.
+//!
+//! Plain parameters are `String`s, so a large payload such as serialized
+//! state or a binary buffer has to be formatted before reporting even if
+//! the template never shows it. A [`ParamValue`] defers that work:
+//! [`ParamValue::Lazy`] runs its closure and [`ParamValue::Bytes`] encodes
+//! its bytes only when the template being filled has a placeholder for
+//! the parameter.
+//!
+//! Use `BugReportConfig::materialize` to turn [`ParamValues`] into the
+//! `Params` every reporting method takes, or `create_report_with_values`
+//! on a handle to do both at once.
+//!
+//! A [`ParamPolicy`] set on the configuration controls how a parameter is
+//! substituted, such as hashing sensitive values.
+
+use crate::base64;
+use crate::siphash::SipHasher13;
+use crate::{format as formatting, FxHashMap};
+use core::fmt::{self, Write};
+use core::hash::{BuildHasher, Hasher};
+use core::ops::Index;
+use rustc_hash::FxHasher;
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+};
+
+/// Template parameters: placeholder names mapped to their values.
+///
+/// # Examples
+///
+/// ```
+/// use bug::Params;
+///
+/// let mut params = Params::new();
+/// params.insert("module", "parser");
+/// let params = params.with("line", 42.to_string());
+///
+/// assert_eq!(params.get("module"), Some("parser"));
+/// assert_eq!(&params["line"], "42");
+/// assert_eq!(params.len(), 2);
+///
+/// let same: Params = [("module", "parser"), ("line", "42")].into_iter().collect();
+/// assert_eq!(params, same);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    map: hashbrown::HashMap<String, String, ParamHasher>,
+}
+
+impl Params {
+    /// Create empty parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create empty parameters that hash their names with `hasher`.
+    ///
+    /// The default FxHash is fast but collisions are easy to construct, so
+    /// parameters whose names come from untrusted input, such as a request
+    /// body, should use a keyed hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `hasher` - The hash function for parameter names
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::Params;
+    /// use bug::param::ParamHasher;
+    ///
+    /// let mut params = Params::with_hasher(ParamHasher::random());
+    /// params.insert("user_supplied_key", "value");
+    /// assert_eq!(params.get("user_supplied_key"), Some("value"));
+    ///
+    /// let keyed = Params::with_hasher(ParamHasher::Keyed(0x0123, 0x4567));
+    /// assert!(keyed.is_empty());
+    /// ```
+    pub fn with_hasher(hasher: ParamHasher) -> Self {
+        Self {
+            map: hashbrown::HashMap::with_hasher(hasher),
+        }
+    }
+
+    /// Parse `key=value` arguments, such as the values of repeated
+    /// `--bug-param` flags, into parameters.
+    ///
+    /// Each argument is split at its first unescaped `=`. A backslash
+    /// escapes the next character: `\=` and `\\` stand for themselves, and
+    /// `\n` and `\t` for a newline and a tab, so keys and values can hold
+    /// what a shell argument can't. Keys must be placeholder names, made of
+    /// letters, digits, and `_`, and may only be given once.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The arguments, in the order they were given
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Params)` - The parsed parameters
+    /// * `Err(String)` - The first malformed argument, numbered from 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::Params;
+    ///
+    /// let args = ["module=parser", "query=a\\=b", "details=line one\\nline two"];
+    /// let params = Params::from_kv_args(args.iter().map(|arg| arg.to_string())).unwrap();
+    /// assert_eq!(params.get("module"), Some("parser"));
+    /// assert_eq!(params.get("query"), Some("a=b"));
+    /// assert_eq!(params.get("details"), Some("line one\nline two"));
+    ///
+    /// assert_eq!(
+    ///     Params::from_kv_args(["module parser"]).unwrap_err(),
+    ///     "Argument 1 'module parser' is not a key=value pair"
+    /// );
+    /// assert_eq!(
+    ///     Params::from_kv_args(["error code=E1"]).unwrap_err(),
+    ///     "Argument 1 has invalid key 'error code'; names may only contain letters, digits, and '_'"
+    /// );
+    /// assert_eq!(
+    ///     Params::from_kv_args(["a=1", "a=2"]).unwrap_err(),
+    ///     "Argument 2 sets 'a', which argument 1 already set"
+    /// );
+    /// assert_eq!(
+    ///     Params::from_kv_args(["path=C:\\dir"]).unwrap_err(),
+    ///     "Argument 1 has an unknown escape '\\d'; write '\\\\' for a backslash"
+    /// );
+    /// ```
+    pub fn from_kv_args<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut params = Params::new();
+        let mut first_set: FxHashMap<String, usize> = FxHashMap::default();
+        for (index, arg) in args.into_iter().enumerate() {
+            let number = index + 1;
+            let arg = arg.as_ref();
+            let (key, value) = split_kv_arg(arg).map_err(|e| format!("Argument {} {}", number, e))?;
+            let Some(value) = value else {
+                return Err(format!("Argument {} '{}' is not a key=value pair", number, arg));
+            };
+            if key.is_empty() {
+                return Err(format!("Argument {} '{}' has an empty key", number, arg));
+            }
+            if !key.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+                return Err(format!(
+                    "Argument {} has invalid key '{}'; names may only contain letters, digits, and '_'",
+                    number, key
+                ));
+            }
+            if let Some(first) = first_set.get(&key) {
+                return Err(format!("Argument {} sets '{}', which argument {} already set", number, key, first));
+            }
+            first_set.insert(key.clone(), number);
+            params.insert(key, value);
+        }
+        Ok(params)
+    }
+
+    /// Set a parameter, returning its previous value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Name of the placeholder
+    /// * `value` - Text substituted for it
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.map.insert(key.into(), value.into())
+    }
+
+    /// Set a parameter and return the parameters, for chaining.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Name of the placeholder
+    /// * `value` - Text substituted for it
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Get the value of a parameter.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.map.get(key).map(String::as_str)
+    }
+
+    /// Whether a parameter is set.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Remove a parameter, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.map.remove(key)
+    }
+
+    /// Number of parameters.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Whether there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterate over parameter names and values, in no particular order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.map.iter())
+    }
+
+    /// Iterate over parameter names, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.map.keys().map(String::as_str)
+    }
+}
+
+/// Unescape a `key=value` argument, splitting it at the first unescaped `=`.
+///
+/// The value is `None` when there is no unescaped `=`.
+fn split_kv_arg(arg: &str) -> Result<(String, Option<String>), String> {
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut chars = arg.chars();
+    while let Some(ch) = chars.next() {
+        let unescaped = match ch {
+            '\\' => match chars.next() {
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some(escaped @ ('\\' | '=')) => escaped,
+                Some(other) => {
+                    return Err(format!("has an unknown escape '\\{}'; write '\\\\' for a backslash", other));
+                }
+                None => return Err("ends with an unfinished escape '\\'".to_string()),
+            },
+            '=' if value.is_none() => {
+                value = Some(String::new());
+                continue;
+            }
+            ch => ch,
+        };
+        match &mut value {
+            Some(value) => value.push(unescaped),
+            None => key.push(unescaped),
+        }
+    }
+    Ok((key, value))
+}
+
+impl Index<&str> for Params {
+    type Output = str;
+
+    /// Get the value of a parameter.
+    ///
+    /// # Panics
+    ///
+    /// If the parameter is not set.
+    fn index(&self, key: &str) -> &str {
+        &self.map[key]
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Params {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut params = Params::new();
+        params.extend(iter);
+        params
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for Params {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl From<FxHashMap<String, String>> for Params {
+    fn from(map: FxHashMap<String, String>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a Params {
+    type Item = (&'a str, &'a str);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Params {
+    type Item = (String, String);
+    type IntoIter = hashbrown::hash_map::IntoIter<String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
+}
+
+/// Iterator over the names and values of `Params`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a>(hashbrown::hash_map::Iter<'a, String, String>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// The hash function `Params` uses for parameter names.
+#[derive(Debug, Clone, Default)]
+pub enum ParamHasher {
+    /// FxHash: fast, but trivially collidable. The default.
+    #[default]
+    Fx,
+    /// SipHash-1-3 with keys drawn from the OS, like std's `HashMap` (std only)
+    #[cfg(feature = "std")]
+    Random(std::hash::RandomState),
+    /// SipHash-1-3 with the given keys, for targets with their own entropy source
+    Keyed(u64, u64),
+}
+
+impl ParamHasher {
+    /// SipHash with fresh random keys (std only).
+    #[cfg(feature = "std")]
+    pub fn random() -> Self {
+        ParamHasher::Random(std::hash::RandomState::new())
+    }
+}
+
+impl BuildHasher for ParamHasher {
+    type Hasher = ParamHash;
+
+    fn build_hasher(&self) -> ParamHash {
+        ParamHash(match self {
+            ParamHasher::Fx => HashState::Fx(FxHasher::default()),
+            #[cfg(feature = "std")]
+            ParamHasher::Random(state) => HashState::Sip(state.build_hasher()),
+            ParamHasher::Keyed(k0, k1) => HashState::Keyed(SipHasher13::new_with_keys(*k0, *k1)),
+        })
+    }
+}
+
+/// Hasher built by [`ParamHasher`].
+pub struct ParamHash(HashState);
+
+enum HashState {
+    Fx(FxHasher),
+    #[cfg(feature = "std")]
+    Sip(std::hash::DefaultHasher),
+    Keyed(SipHasher13),
+}
+
+impl Hasher for ParamHash {
+    fn write(&mut self, bytes: &[u8]) {
+        match &mut self.0 {
+            HashState::Fx(hasher) => hasher.write(bytes),
+            #[cfg(feature = "std")]
+            HashState::Sip(hasher) => hasher.write(bytes),
+            HashState::Keyed(hasher) => hasher.write(bytes),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        match &self.0 {
+            HashState::Fx(hasher) => hasher.finish(),
+            #[cfg(feature = "std")]
+            HashState::Sip(hasher) => hasher.finish(),
+            HashState::Keyed(hasher) => hasher.finish(),
+        }
+    }
+}
+
+impl fmt::Debug for ParamHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParamHash").finish_non_exhaustive()
+    }
+}
+
+/// Parameters whose values may be lazy or binary.
+pub type ParamValues<'a> = FxHashMap<String, ParamValue<'a>>;
+
+/// Bytes per line of `Encoding::Hex` output.
+const HEX_BYTES_PER_LINE: usize = 16;
+
+/// Characters per line of `Encoding::Base64` output, as in MIME.
+const BASE64_LINE_LEN: usize = 76;
+
+/// How `ParamValue::Bytes` is rendered as text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Space-separated lowercase hex pairs, 16 bytes per line
+    Hex,
+    /// Standard padded base64, wrapped at 76 characters per line
+    Base64,
+}
+
+/// A parameter value, rendered to text when it is substituted.
+///
+/// # Examples
+///
+/// ```
+/// use bug::param::{Encoding, ParamValue};
+/// use std::sync::Arc;
+///
+/// assert_eq!(ParamValue::from("plain").render(), "plain");
+/// assert_eq!(ParamValue::Lazy(Arc::new(|| "computed".to_string())).render(), "computed");
+/// assert_eq!(ParamValue::Bytes(b"\xde\xad\xbe\xef", Encoding::Hex).render(), "de ad be ef");
+/// assert_eq!(ParamValue::Bytes(b"hello", Encoding::Base64).render(), "aGVsbG8=");
+/// assert_eq!(ParamValue::Bytes(b"hi", Encoding::Base64).render(), "aGk=");
+/// assert_eq!(ParamValue::Bytes(&[0; 60], Encoding::Base64).render().lines().map(str::len).collect::<Vec<_>>(), [76, 4]);
+/// ```
+#[derive(Clone)]
+pub enum ParamValue<'a> {
+    /// Text used as it is
+    Text(String),
+    /// Text computed by a closure, called only if the parameter is used
+    Lazy(Arc<dyn Fn() -> String + 'a>),
+    /// Bytes encoded as text, only if the parameter is used
+    Bytes(&'a [u8], Encoding),
+}
+
+impl ParamValue<'_> {
+    /// Render the value as the text substituted for its placeholder.
+    pub fn render(&self) -> String {
+        match self {
+            ParamValue::Text(text) => text.clone(),
+            ParamValue::Lazy(compute) => compute(),
+            ParamValue::Bytes(bytes, Encoding::Hex) => hex(bytes),
+            ParamValue::Bytes(bytes, Encoding::Base64) => base64::encode(bytes, Some(BASE64_LINE_LEN)),
+        }
+    }
+}
+
+impl From<String> for ParamValue<'_> {
+    fn from(text: String) -> Self {
+        ParamValue::Text(text)
+    }
+}
+
+impl From<&str> for ParamValue<'_> {
+    fn from(text: &str) -> Self {
+        ParamValue::Text(text.to_string())
+    }
+}
+
+impl fmt::Debug for ParamValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Text(text) => f.debug_tuple("Text").field(text).finish(),
+            ParamValue::Lazy(_) => f.write_str("Lazy(..)"),
+            ParamValue::Bytes(bytes, encoding) => f
+                .debug_struct("Bytes")
+                .field("len", &bytes.len())
+                .field("encoding", encoding)
+                .finish(),
+        }
+    }
+}
+
+/// Builds `Params` with formatting helpers for common kinds of values.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate};
+/// use bug::param::ParamsBuilder;
+/// use std::time::Duration;
+///
+/// let params = ParamsBuilder::new()
+///     .param("stage", "compaction")
+///     .bytes("heap", 1_503_238_554)
+///     .duration("elapsed", Duration::from_millis(2300))
+///     .build();
+/// assert_eq!(&params["heap"], "1.4 GiB");
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("slow", IssueTemplate::new("Slow {stage}", "{heap} / {elapsed}"));
+/// let report = handle.create_report("slow", &params).unwrap();
+/// assert_eq!(report.body, "1.4 GiB / 2.3 s");
+///
+/// // Raw numbers can be formatted by the template instead.
+/// let handle = init_handle("owner", "repo")
+///     .add_template("slow", IssueTemplate::new("Slow", "{heap|bytes} / {elapsed|duration}"));
+/// let params = ParamsBuilder::new().param("heap", 2048).param("elapsed", 0.25).build();
+/// assert_eq!(handle.create_report("slow", &params).unwrap().body, "2.0 KiB / 250.0 ms");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParamsBuilder {
+    params: Params,
+}
+
+impl ParamsBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a parameter to a value's `Display` text.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Name of the placeholder
+    /// * `value` - The value
+    pub fn param(mut self, key: impl Into<String>, value: impl fmt::Display) -> Self {
+        self.params.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Set a parameter to a byte count, like `1.4 GiB`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Name of the placeholder
+    /// * `bytes` - Number of bytes
+    pub fn bytes(mut self, key: impl Into<String>, bytes: u64) -> Self {
+        self.params.insert(key.into(), formatting::humanize_bytes(bytes));
+        self
+    }
+
+    /// Set a parameter to a duration, like `2.3 s`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Name of the placeholder
+    /// * `duration` - The duration
+    pub fn duration(mut self, key: impl Into<String>, duration: Duration) -> Self {
+        self.params.insert(key.into(), formatting::humanize_duration(duration));
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> Params {
+        self.params
+    }
+}
+
+/// Render the values for `placeholders` and keep the other keys with empty text.
+///
+/// Unused keys are kept so that strict templates still reject them as
+/// unused parameters, without paying to render them.
+pub(crate) fn materialize(values: &ParamValues<'_>, placeholders: &[String]) -> Params {
+    values
+        .iter()
+        .map(|(key, value)| {
+            let text = if placeholders.contains(key) {
+                value.render()
+            } else {
+                String::new()
+            };
+            (key.as_str(), text)
+        })
+        .collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 3);
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            output.push(if index % HEX_BYTES_PER_LINE == 0 { '\n' } else { ' ' });
+        }
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+/// How a parameter's value is substituted into the issue.
+///
+/// Some values are sensitive but still useful for triage: seeing that two
+/// reports came from the same user or the same file helps, even without
+/// knowing which one. [`ParamPolicy::HashValue`] replaces such a value with
+/// a short hash that is the same for the same value, so reports can be
+/// correlated without exposing the raw data. Set policies with
+/// `param_policy` for every template or `template_param_policy` for one.
+///
+/// The hash is not encryption: a short list of candidate values, such as
+/// the numbers of a small user table, can be tried one by one. Set a
+/// secret key with `param_hash_key` to make that impractical for anyone
+/// without the key.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+/// use bug::param::ParamPolicy;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("sync_failed", IssueTemplate::new("Sync failed", "User: {user_id}\nPath: {path}"))
+///     .param_policy("user_id", ParamPolicy::HashValue)
+///     .template_param_policy("sync_failed", "path", ParamPolicy::HashValue);
+///
+/// let mut params = Params::new();
+/// params.insert("user_id", "48213");
+/// params.insert("path", "/home/alice/taxes.pdf");
+///
+/// let first = handle.create_report("sync_failed", &params).unwrap();
+/// assert!(!first.body.contains("48213"));
+/// assert!(first.body.starts_with("User: hash:"));
+///
+/// // The same value always gives the same hash.
+/// let second = handle.create_report("sync_failed", &params).unwrap();
+/// assert_eq!(first.body, second.body);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ParamPolicy {
+    /// Substitute the value as given.
+    #[default]
+    Plain,
+    /// Substitute `hash:` and 8 hex digits of a keyed hash of the value.
+    HashValue,
+}
+
+/// Policies for the placeholders of every template and of single templates.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParamPolicies {
+    global: FxHashMap<String, ParamPolicy>,
+    per_template: FxHashMap<String, FxHashMap<String, ParamPolicy>>,
+    key: (u64, u64),
+}
+
+impl ParamPolicies {
+    pub(crate) fn set(&mut self, placeholder: String, policy: ParamPolicy) {
+        self.global.insert(placeholder, policy);
+    }
+
+    pub(crate) fn set_for_template(&mut self, template_name: String, placeholder: String, policy: ParamPolicy) {
+        self.per_template.entry(template_name).or_default().insert(placeholder, policy);
+    }
+
+    pub(crate) fn set_key(&mut self, k0: u64, k1: u64) {
+        self.key = (k0, k1);
+    }
+
+    /// Policies set for every template, by placeholder.
+    pub(crate) fn global(&self) -> &FxHashMap<String, ParamPolicy> {
+        &self.global
+    }
+
+    /// Policies set for single templates, by template and placeholder.
+    pub(crate) fn per_template(&self) -> &FxHashMap<String, FxHashMap<String, ParamPolicy>> {
+        &self.per_template
+    }
+
+    /// The key of `ParamPolicy::HashValue` hashes.
+    pub(crate) fn key(&self) -> (u64, u64) {
+        self.key
+    }
+
+    /// The policy of a placeholder in a template; per-template policies win.
+    fn get(&self, template_name: &str, placeholder: &str) -> ParamPolicy {
+        self.per_template
+            .get(template_name)
+            .and_then(|policies| policies.get(placeholder))
+            .or_else(|| self.global.get(placeholder))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Copy `params` with the policies for `template_name` applied, if any changes a value.
+    pub(crate) fn apply(&self, template_name: &str, params: &Params) -> Option<Params> {
+        if self.global.is_empty() && self.per_template.is_empty() {
+            return None;
+        }
+        let mut applied: Option<Params> = None;
+        for (key, value) in params {
+            if self.get(template_name, key) == ParamPolicy::HashValue {
+                let hashed = self.hash(value);
+                applied.get_or_insert_with(|| params.clone()).insert(key.to_string(), hashed);
+            }
+        }
+        applied
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut hasher = SipHasher13::new_with_keys(self.key.0, self.key.1);
+        hasher.write(value.as_bytes());
+        format!("hash:{:08x}", hasher.finish() >> 32)
+    }
+}
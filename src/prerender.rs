@@ -0,0 +1,85 @@
+//! Reports rendered ahead of time for handlers that can't allocate.
+//!
+//! An out-of-memory handler, a signal handler, or a hard-fault handler on
+//! a microcontroller is exactly where a bug report is most wanted and where
+//! filling a template is impossible: there is no heap to allocate from and
+//! no locks may be taken. `BugReportHandle::prerender` does all of that
+//! work up front, during startup, and returns a [`PrerenderedReport`]
+//! holding the finished banner. The handler then only needs to write its
+//! bytes, with a single `write(2)` to stderr or a loop over a UART.
+
+use crate::Output;
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use std::{boxed::Box, string::String};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String};
+
+/// A fully rendered banner and issue URL.
+///
+/// Reading it never allocates, locks, or formats, so it is safe to use from
+/// an async-signal context once created.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("oom", IssueTemplate::new("Out of memory", "Heap of {heap_size} exhausted"));
+///
+/// let mut params = Params::new();
+/// params.insert("heap_size", "64 KiB");
+/// let report = handle.prerender("oom", &params).unwrap();
+///
+/// // Later, in the handler:
+/// let bytes: &[u8] = report.as_bytes();
+/// assert!(bytes.starts_with("🐛 BUG ENCOUNTERED in emergency handler\n".as_bytes()));
+/// assert!(report.url().starts_with("https://github.com/owner/repo/issues/new?title=Out+of+memory"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrerenderedReport {
+    text: Box<str>,
+    url: Range<usize>,
+}
+
+impl PrerenderedReport {
+    /// Wrap a rendered banner containing `url`.
+    pub(crate) fn new(text: String, url: &str) -> Self {
+        let start = text.find(url).unwrap_or_else(|| {
+            crate::meta::report_internal_error("prerendered banner does not contain its report URL");
+            text.len()
+        });
+        let end = (start + url.len()).min(text.len());
+        Self {
+            text: text.into_boxed_str(),
+            url: start..end,
+        }
+    }
+
+    /// The banner as bytes, ready to be written in one call.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /// The banner as text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The issue URL inside the banner.
+    pub fn url(&self) -> &str {
+        &self.text[self.url.clone()]
+    }
+
+    /// Write the banner to `output` with a single `write_str`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Where to write the banner
+    pub fn write_to(&self, output: &mut dyn Output) {
+        output.write_str(&self.text);
+    }
+}
@@ -0,0 +1,170 @@
+//! UI-agnostic presentation of bug reports.
+//!
+//! GUI applications usually want a "Report this problem" dialog rather than a
+//! line on stderr. [`ReportPresentation`] collects the pieces such a dialog
+//! needs from a [`BugReport`], so any toolkit can render it with a heading,
+//! a few summary lines, and a button that opens the issue URL.
+
+use crate::BugReport;
+
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+    format,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+    format,
+};
+
+/// Default label for the button that opens the issue URL.
+pub const DEFAULT_BUTTON_LABEL: &str = "File a bug report";
+
+/// Default number of body lines shown in the summary.
+pub const DEFAULT_SUMMARY_LINES: usize = 5;
+
+/// Everything a dialog needs to offer the user a bug report.
+///
+/// The summary is derived from the report body: blank lines are skipped and
+/// markdown heading and list markers are stripped, so the lines read cleanly
+/// as plain text.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, ReportPresentation, Params};
+///
+/// let handle = init_handle("octocat", "Hello-World")
+///     .add_template("crash", IssueTemplate::new(
+///         "Crash: {error}",
+///         "## Description\nThe app crashed.\n\n- Error: {error}"
+///     ).with_labels(vec!["crash".to_string()]));
+///
+/// let mut params = Params::new();
+/// params.insert("error".to_string(), "OutOfMemory".to_string());
+///
+/// let report = handle.create_report("crash", &params).unwrap();
+/// let presentation = ReportPresentation::from_report(&report);
+///
+/// assert_eq!(presentation.title, "Crash: OutOfMemory");
+/// assert_eq!(presentation.summary, vec![
+///     "Description".to_string(),
+///     "The app crashed.".to_string(),
+///     "Error: OutOfMemory".to_string(),
+///     "Labels: crash".to_string(),
+/// ]);
+/// assert_eq!(presentation.url, report.url);
+/// assert_eq!(presentation.button_label, "File a bug report");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReportPresentation {
+    /// Heading for the dialog (the filled issue title)
+    pub title: String,
+    /// Short plain-text lines describing the report
+    pub summary: Vec<String>,
+    /// The issue URL the button should open
+    pub url: String,
+    /// Text for the button that opens the URL
+    pub button_label: String,
+}
+
+impl ReportPresentation {
+    /// Build a presentation from a report with the default summary length.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The report to present
+    pub fn from_report(report: &BugReport) -> Self {
+        Self::from_report_with_lines(report, DEFAULT_SUMMARY_LINES)
+    }
+
+    /// Build a presentation showing at most `max_lines` lines of the body.
+    ///
+    /// A trailing "Labels: ..." line is added when the report has labels and
+    /// does not count towards `max_lines`.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The report to present
+    /// * `max_lines` - Maximum number of body lines in the summary
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, ReportPresentation, Params};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug", "one\ntwo\nthree"));
+    /// let report = handle.create_report("bug", &Params::new()).unwrap();
+    ///
+    /// let presentation = ReportPresentation::from_report_with_lines(&report, 2);
+    /// assert_eq!(presentation.summary, vec!["one".to_string(), "two".to_string()]);
+    /// ```
+    pub fn from_report_with_lines(report: &BugReport, max_lines: usize) -> Self {
+        let mut summary: Vec<String> = report
+            .body
+            .lines()
+            .map(strip_markdown_markers)
+            .filter(|line| !line.is_empty())
+            .take(max_lines)
+            .map(|line| line.to_string())
+            .collect();
+
+        if !report.labels.is_empty() {
+            summary.push(format!("Labels: {}", report.labels.join(", ")));
+        }
+
+        Self {
+            title: report.title.clone(),
+            summary,
+            url: report.url.clone(),
+            button_label: DEFAULT_BUTTON_LABEL.to_string(),
+        }
+    }
+
+    /// Replace the button label.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Text for the button that opens the issue URL
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, ReportPresentation, Params};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("bug", IssueTemplate::new("Bug", "Broken"));
+    /// let report = handle.create_report("bug", &Params::new()).unwrap();
+    ///
+    /// let presentation = ReportPresentation::from_report(&report)
+    ///     .with_button_label("Report this problem");
+    /// assert_eq!(presentation.button_label, "Report this problem");
+    /// ```
+    pub fn with_button_label(mut self, label: impl Into<String>) -> Self {
+        self.button_label = label.into();
+        self
+    }
+}
+
+impl From<&BugReport> for ReportPresentation {
+    fn from(report: &BugReport) -> Self {
+        Self::from_report(report)
+    }
+}
+
+/// Strip leading markdown heading, quote, and list markers from a line.
+fn strip_markdown_markers(line: &str) -> &str {
+    let line = line.trim();
+    let line = line.trim_start_matches('#').trim_start_matches('>').trim_start();
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(marker) {
+            return rest.trim_start();
+        }
+    }
+    line
+}
@@ -0,0 +1,308 @@
+//! Interned template storage with O(1) lookup by [`TemplateId`].
+
+use crate::{IssueTemplate, TemplateFile, FxHashMap};
+
+#[cfg(feature = "std")]
+use std::{format, string::String, sync::Arc, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, sync::Arc, vec::Vec};
+
+/// How a requested template name is matched against registered names.
+///
+/// An exact match always wins; the looser modes only apply when no
+/// template has exactly the requested name.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, NameMatching, Params};
+///
+/// let handle = init_handle("owner", "repo")
+///     .name_matching(NameMatching::Normalized)
+///     .add_template("network_error", IssueTemplate::new("Network error", ""));
+///
+/// let report = handle.create_report("Network-Error", &Params::new()).unwrap();
+/// assert_eq!(report.template_name, "network_error");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum NameMatching {
+    /// Names must match exactly.
+    #[default]
+    Exact,
+    /// Names match regardless of ASCII case.
+    CaseInsensitive,
+    /// Names match regardless of ASCII case, with `-`, `_`, and spaces equivalent.
+    Normalized,
+}
+
+impl NameMatching {
+    /// `name` in the form names are compared in.
+    fn normalize(self, name: &str) -> String {
+        match self {
+            NameMatching::Exact => String::from(name),
+            NameMatching::CaseInsensitive => name.to_ascii_lowercase(),
+            NameMatching::Normalized => name
+                .chars()
+                .map(|c| match c {
+                    '-' | ' ' => '_',
+                    c => c.to_ascii_lowercase(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// What reporting does with a template name that matches no template.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, OnMissingTemplate, Params};
+/// use bug::warning::Warning;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""))
+///     .on_missing_template(OnMissingTemplate::LinkChooser);
+///
+/// let report = handle.create_report("crsh", &Params::new()).unwrap();
+/// assert_eq!(report.url, "https://github.com/owner/repo/issues/new/choose");
+/// assert_eq!(report.template_name, "crsh");
+/// assert_eq!(
+///     report.warnings.iter().map(Warning::to_string).collect::<Vec<_>>(),
+///     ["template 'crsh' not found, so the report links to the issue template chooser"]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum OnMissingTemplate {
+    /// Fail with an error suggesting the closest registered name.
+    #[default]
+    Error,
+    /// Report with the repository's issue template chooser as the URL,
+    /// so the user can still pick a template and file the bug.
+    LinkChooser,
+}
+
+/// A compact handle to a registered template.
+///
+/// Ids are returned by `register_template`/`register_template_file` and
+/// can be looked up with `template_id`. Looking a template up by id is a
+/// plain index into the registry, with no string hashing. An id is only
+/// meaningful for the configuration that issued it.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+///
+/// let mut handle = init_handle("owner", "repo");
+/// let crash = handle.register_template("crash", IssueTemplate::new("Crash", "It crashed"));
+///
+/// assert_eq!(handle.template_id("crash"), Some(crash));
+/// let url = handle.generate_url_by_id(crash, &Params::new()).unwrap();
+/// assert!(url.contains("title=Crash"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TemplateId(u32);
+
+impl TemplateId {
+    /// The position of this template in registration order.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A registered template of either kind.
+#[derive(Debug, Clone)]
+pub(crate) enum SlotSource {
+    Inline(IssueTemplate),
+    File(TemplateFile),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Slot {
+    pub(crate) name: Arc<str>,
+    pub(crate) source: SlotSource,
+    /// The template's labels, interned so repeated labels share one allocation.
+    pub(crate) labels: Vec<Arc<str>>,
+}
+
+impl Slot {
+    /// The template to use when this one can't be filled.
+    pub(crate) fn fallback(&self) -> Option<&str> {
+        match &self.source {
+            SlotSource::Inline(template) => template.fallback.as_deref(),
+            SlotSource::File(template_file) => template_file.fallback.as_deref(),
+        }
+    }
+}
+
+/// Templates indexed by interned name and by id.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TemplateRegistry {
+    ids: FxHashMap<Arc<str>, TemplateId>,
+    slots: Vec<Slot>,
+    strings: FxHashMap<Arc<str>, ()>,
+    matching: NameMatching,
+}
+
+impl TemplateRegistry {
+    /// Register a template, replacing any template with the same name but keeping its id.
+    pub(crate) fn register(&mut self, name: String, source: SlotSource) -> TemplateId {
+        let labels = match &source {
+            SlotSource::Inline(template) => &template.labels,
+            SlotSource::File(template_file) => &template_file.labels,
+        };
+        let labels = labels.iter().map(|label| self.intern(label)).collect();
+
+        if let Some(&id) = self.ids.get(name.as_str()) {
+            let slot = &mut self.slots[id.index()];
+            slot.source = source;
+            slot.labels = labels;
+            return id;
+        }
+
+        let name = self.intern(&name);
+        let id = TemplateId(self.slots.len() as u32);
+        self.ids.insert(name.clone(), id);
+        self.slots.push(Slot { name, source, labels });
+        id
+    }
+
+    /// The id of the template `name` matches.
+    ///
+    /// Names of merged templates carry their namespace, `serde_thing/crash`.
+    /// A bare name also finds the one namespaced template it names, if no
+    /// other namespace has a template of that name, and a namespaced name
+    /// falls back to the bare one.
+    pub(crate) fn id(&self, name: &str) -> Option<TemplateId> {
+        if let Some(id) = self.matching_id(name) {
+            return Some(id);
+        }
+        match name.split_once('/') {
+            Some((_, bare)) => self.matching_id(bare),
+            None => match self.namespaced(name).as_slice() {
+                [id] => Some(*id),
+                _ => None,
+            },
+        }
+    }
+
+    /// The id of the template named `name` under the name matching mode.
+    fn matching_id(&self, name: &str) -> Option<TemplateId> {
+        if let Some(&id) = self.ids.get(name) {
+            return Some(id);
+        }
+        if self.matching == NameMatching::Exact {
+            return None;
+        }
+        let wanted = self.matching.normalize(name);
+        self.slots
+            .iter()
+            .position(|slot| self.matching.normalize(&slot.name) == wanted)
+            .map(|index| TemplateId(index as u32))
+    }
+
+    /// Ids of the templates named `bare` in some namespace, `namespace/bare`.
+    fn namespaced(&self, bare: &str) -> Vec<TemplateId> {
+        let wanted = self.matching.normalize(bare);
+        self.slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| {
+                slot.name
+                    .split_once('/')
+                    .is_some_and(|(_, name)| self.matching.normalize(name) == wanted)
+            })
+            .map(|(index, _)| TemplateId(index as u32))
+            .collect()
+    }
+
+    /// The template registered with exactly the name `name`.
+    pub(crate) fn get_exact(&self, name: &str) -> Option<&Slot> {
+        self.ids.get(name).map(|id| &self.slots[id.index()])
+    }
+
+    /// Whether a template is registered with exactly the name `name`.
+    #[cfg(feature = "std")]
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    pub(crate) fn matching(&self) -> NameMatching {
+        self.matching
+    }
+
+    pub(crate) fn set_matching(&mut self, matching: NameMatching) {
+        self.matching = matching;
+    }
+
+    /// The error for a name no template matches, suggesting the closest registered name.
+    pub(crate) fn not_found(&self, name: &str) -> String {
+        let candidates = self.namespaced(name);
+        if !name.contains('/') && candidates.len() > 1 {
+            let names: Vec<String> = candidates
+                .iter()
+                .map(|id| format!("'{}'", self.slots[id.index()].name))
+                .collect();
+            return format!("Template '{}' is ambiguous; name one of {}", name, names.join(", "));
+        }
+        match self.suggest(name) {
+            Some(suggestion) => format!("Template '{}' not found; did you mean '{}'?", name, suggestion),
+            None => format!("Template '{}' not found", name),
+        }
+    }
+
+    /// The registered name closest to `name`, if it is close enough to be a typo.
+    fn suggest(&self, name: &str) -> Option<&str> {
+        let wanted = NameMatching::Normalized.normalize(name);
+        // About one edit per three characters
+        let limit = wanted.chars().count().max(3) / 3;
+        self.slots
+            .iter()
+            .map(|slot| (edit_distance(&wanted, &NameMatching::Normalized.normalize(&slot.name)), &*slot.name))
+            .filter(|(distance, _)| *distance <= limit)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+    }
+
+    pub(crate) fn get(&self, id: TemplateId) -> Option<&Slot> {
+        self.slots.get(id.index())
+    }
+
+    pub(crate) fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    pub(crate) fn get_by_name(&self, name: &str) -> Option<&Slot> {
+        self.id(name).and_then(|id| self.get(id))
+    }
+
+    fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some((existing, _)) = self.strings.get_key_value(s) {
+            return existing.clone();
+        }
+        let interned: Arc<str> = Arc::from(s);
+        self.strings.insert(interned.clone(), ());
+        interned
+    }
+}
+
+/// The Levenshtein distance between two strings, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = Vec::with_capacity(b.len() + 1);
+    for (i, ca) in a.chars().enumerate() {
+        current.clear();
+        current.push(i + 1);
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        core::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,194 @@
+//! Templates fetched over HTTP at startup (feature `remote`).
+//!
+//! When report wording lives in the binary, changing it takes a release.
+//! A remote template is downloaded when it is added, replacing the
+//! template registered under the same name, so the wording can be updated
+//! by editing a file on a server. The downloaded content has the layout of
+//! a [`TemplateFile`](crate::TemplateFile): the first line is the title
+//! and the rest is the body. Labels, assignees, teams, and the fallback
+//! are kept from the template it replaces.
+//!
+//! Fetching waits at most the [`RemoteSource`] timeout, and the content
+//! can be pinned to a SHA-256 digest so a compromised or truncated file
+//! is rejected. On any failure a warning is printed and the embedded
+//! template stays in place, so register one under the same name first.
+//! As with the `api` feature, the crate makes no network requests itself:
+//! requests go through an [`HttpClient`].
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::remote::{RemoteSource, RemoteTemplate};
+//!
+//! // Stands in for a real client such as `ureq` or `reqwest::blocking`.
+//! let client = |url: &str, _headers: &[(&str, &str)]| -> Result<String, String> {
+//!     match url {
+//!         "https://example.com/templates/crash.md" => Ok("Crash in {screen}\nPlease tell us what you were doing.".to_string()),
+//!         _ => Err("404 Not Found".to_string()),
+//!     }
+//! };
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {screen}", "Embedded wording"))
+//!     .add_template("hang", IssueTemplate::new("Hang", "Embedded wording"))
+//!     .remote_source(RemoteSource::new(client))
+//!     .add_remote_template("crash", "https://example.com/templates/crash.md")
+//!     // Fails, so the embedded template is kept
+//!     .add_remote_template("hang", RemoteTemplate::new("https://example.com/templates/hang.md"));
+//!
+//! let mut params = Params::new();
+//! params.insert("screen", "settings");
+//! assert_eq!(handle.create_report("crash", &params).unwrap().body, "Please tell us what you were doing.");
+//! assert_eq!(handle.create_report("hang", &Params::new()).unwrap().body, "Embedded wording");
+//! ```
+
+use crate::http::{HttpClient, USER_AGENT};
+use crate::sha256;
+use std::fmt;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+/// How long a fetch waits for the server by default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where remote templates are fetched from.
+#[derive(Clone)]
+pub struct RemoteSource {
+    client: Arc<dyn HttpClient>,
+    timeout: Duration,
+}
+
+impl RemoteSource {
+    /// Fetch templates through `client`, waiting at most 5 seconds for each.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - Sends the requests
+    pub fn new(client: impl HttpClient + 'static) -> Self {
+        Self {
+            client: Arc::new(client),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Wait at most `timeout` for each template.
+    ///
+    /// A request still running when the timeout passes is left to finish
+    /// on its own thread, and its response is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait before using the embedded template
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Download `template` and check it against its pinned digest.
+    pub(crate) fn fetch(&self, template: &RemoteTemplate) -> Result<String, String> {
+        let (sender, receiver) = mpsc::channel();
+        let client = Arc::clone(&self.client);
+        let url = template.url.clone();
+        thread::Builder::new()
+            .name("bug-remote-template".to_string())
+            .spawn(move || {
+                let _ = sender.send(client.get(&url, &[("User-Agent", USER_AGENT)]));
+            })
+            .map_err(|e| format!("Failed to start fetching {}: {}", template.url, e))?;
+
+        let content = match receiver.recv_timeout(self.timeout) {
+            Ok(response) => response.map_err(|e| format!("Failed to fetch {}: {}", template.url, e))?,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(format!(
+                    "Fetching {} timed out after {} ms",
+                    template.url,
+                    self.timeout.as_millis()
+                ));
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(format!("Failed to fetch {}: the client panicked", template.url));
+            }
+        };
+        if let Some(expected) = &template.sha256 {
+            sha256::verify(content.as_bytes(), expected).map_err(|e| format!("{}: {}", template.url, e))?;
+        }
+        Ok(content)
+    }
+}
+
+impl fmt::Debug for RemoteSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSource").field("timeout", &self.timeout).finish()
+    }
+}
+
+/// A template to download, optionally pinned to a digest.
+///
+/// A URL converts into an unpinned `RemoteTemplate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteTemplate {
+    url: String,
+    sha256: Option<String>,
+}
+
+impl RemoteTemplate {
+    /// Download the template at `url`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Where the template content is served
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            sha256: None,
+        }
+    }
+
+    /// Reject the download unless its SHA-256 digest is `hex`.
+    ///
+    /// # Arguments
+    ///
+    /// * `hex` - The expected digest, as 64 hex digits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate};
+    /// use bug::remote::{RemoteSource, RemoteTemplate};
+    ///
+    /// let client = |_url: &str, _headers: &[(&str, &str)]| -> Result<String, String> {
+    ///     Ok("Tampered\nSend your password to ...".to_string())
+    /// };
+    /// let crash = RemoteTemplate::new("https://example.com/templates/crash.md")
+    ///     .sha256("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", "Embedded wording"))
+    ///     .remote_source(RemoteSource::new(client))
+    ///     .add_remote_template("crash", crash);
+    /// assert_eq!(handle.config().template("crash").unwrap().body, "Embedded wording");
+    /// ```
+    pub fn sha256(mut self, hex: impl Into<String>) -> Self {
+        self.sha256 = Some(hex.into());
+        self
+    }
+
+    /// Where the template is downloaded from.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl From<&str> for RemoteTemplate {
+    fn from(url: &str) -> Self {
+        Self::new(url)
+    }
+}
+
+impl From<String> for RemoteTemplate {
+    fn from(url: String) -> Self {
+        Self::new(url)
+    }
+}
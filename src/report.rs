@@ -0,0 +1,365 @@
+//! Structured bug reports.
+//!
+//! A [`BugReport`] is the result of filling a template with parameters: the
+//! rendered title, body, and labels together with the GitHub issue URL built
+//! from them. Integrations that need more than the URL (dialogs, log sinks,
+//! triage tooling) work from this type instead of re-parsing the URL.
+
+use crate::warning::Warnings;
+use crate::{url_encode, IssueTemplate, Params};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// A filled bug report and the GitHub issue URL generated for it.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+///
+/// let handle = init_handle("octocat", "Hello-World")
+///     .add_template("crash", IssueTemplate::new("Crash in {module}", "Details: {details}")
+///         .with_labels(vec!["crash".to_string()]));
+///
+/// let mut params = Params::new();
+/// params.insert("module".to_string(), "parser".to_string());
+/// params.insert("details".to_string(), "stack overflow".to_string());
+///
+/// let report = handle.create_report("crash", &params).unwrap();
+/// assert_eq!(report.template_name, "crash");
+/// assert_eq!(report.title, "Crash in parser");
+/// assert_eq!(report.labels, vec!["crash".to_string()]);
+/// assert!(report.url.starts_with("https://github.com/octocat/Hello-World/issues/new?"));
+///
+/// // Displayed like its template, followed by the URL
+/// assert_eq!(
+///     report.to_string(),
+///     format!("Crash in parser\nLabels: crash\n\nDetails: stack overflow\n\n{}", report.url)
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BugReport {
+    /// Name of the template the report was generated from
+    pub template_name: String,
+    /// The filled issue title
+    pub title: String,
+    /// The filled issue body
+    pub body: String,
+    /// Labels applied to the issue
+    pub labels: Vec<String>,
+    /// GitHub users the issue is assigned to, including expanded teams
+    pub assignees: Vec<String>,
+    /// Milestone the issue is added to
+    pub milestone: Option<String>,
+    /// Projects the issue is added to, as `owner/number`
+    pub projects: Vec<String>,
+    /// The GitHub new-issue URL with all fields pre-filled
+    pub url: String,
+    /// Non-fatal issues found while generating the report
+    pub warnings: Warnings,
+}
+
+impl BugReport {
+    /// Recover the issue fields from a previously generated new-issue URL.
+    ///
+    /// Query parameters other than `title`, `body`, `labels`,
+    /// `assignees`, `milestone`, and `projects` are ignored, as are the host
+    /// and any path before the owner, so URLs for GitHub Enterprise
+    /// instances parse too, including those under a `base_url` path prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - A URL of the form `https://github.com/<owner>/<repo>/issues/new?...`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ParsedReport)` - The repository and issue fields in the URL
+    /// * `Err(String)` - If the URL isn't a new-issue URL or isn't validly encoded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, BugReport, IssueTemplate, Params};
+    ///
+    /// let handle = init_handle("octocat", "Hello-World")
+    ///     .add_template("crash", IssueTemplate::new("Crash in {module}", "Details: {details}")
+    ///         .with_labels(vec!["crash".to_string(), "p1".to_string()]));
+    ///
+    /// let mut params = Params::new();
+    /// params.insert("module".to_string(), "parser".to_string());
+    /// params.insert("details".to_string(), "100% & more".to_string());
+    /// let url = handle.generate_url("crash", &params).unwrap();
+    ///
+    /// let parsed = BugReport::parse_url(&url).unwrap();
+    /// assert_eq!(parsed.owner, "octocat");
+    /// assert_eq!(parsed.repo, "Hello-World");
+    /// assert_eq!(parsed.title, "Crash in parser");
+    /// assert_eq!(parsed.body, "Details: 100% & more");
+    /// assert_eq!(parsed.labels, vec!["crash".to_string(), "p1".to_string()]);
+    ///
+    /// assert!(BugReport::parse_url("https://github.com/octocat/Hello-World/pulls").is_err());
+    ///
+    /// let handle = handle.base_url("https://corp.example/github");
+    /// let parsed = BugReport::parse_url(&handle.generate_url("crash", &params).unwrap()).unwrap();
+    /// assert_eq!((parsed.owner.as_str(), parsed.repo.as_str()), ("octocat", "Hello-World"));
+    /// assert_eq!(parsed.title, "Crash in parser");
+    /// ```
+    pub fn parse_url(url: &str) -> Result<ParsedReport, String> {
+        let url = url.trim();
+        let url = url.split('#').next().unwrap_or(url);
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        let path = path
+            .strip_prefix("https://")
+            .or_else(|| path.strip_prefix("http://"))
+            .unwrap_or(path);
+        let segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+        // The host may be followed by the path prefix of a `base_url`
+        let (owner, repo) = match segments.as_slice() {
+            [_host, .., owner, repo, "issues", "new"] => (*owner, *repo),
+            _ => return Err(format!("Not a GitHub new-issue URL: {}", url)),
+        };
+
+        let mut parsed = ParsedReport {
+            owner: url_encode::decode(owner)?,
+            repo: url_encode::decode(repo)?,
+            title: String::new(),
+            body: String::new(),
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            milestone: None,
+            projects: Vec::new(),
+        };
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = url_encode::decode(value)?;
+            match url_encode::decode(key)?.as_str() {
+                "title" => parsed.title = value,
+                "body" => parsed.body = value,
+                "labels" => parsed.labels = split_list(&value),
+                "labels[]" => parsed.labels.push(value),
+                "assignees" => parsed.assignees = split_list(&value),
+                "milestone" => parsed.milestone = Some(value),
+                "projects" => parsed.projects = split_list(&value),
+                _ => {}
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Build a new-issue URL for a follow-up that links back to this report.
+    ///
+    /// The follow-up is titled `Follow-up: <title>`, its body references this
+    /// report's URL, and it keeps this report's labels. Entries of
+    /// `extra_params` are added as further query parameters of the new-issue
+    /// URL, replacing the defaults for `title`, `body`, or `labels`.
+    ///
+    /// To fill a full template instead, use `BugReportHandle::create_follow_up`
+    /// with a template that has a `{parent_url}` placeholder.
+    ///
+    /// # Arguments
+    ///
+    /// * `extra_params` - Additional or overriding new-issue query parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""));
+    /// let report = handle.create_report("crash", &Params::new()).unwrap();
+    ///
+    /// let mut extra = Params::new();
+    /// extra.insert("assignees".to_string(), "octocat".to_string());
+    /// let url = report.follow_up_url(&extra);
+    /// assert_eq!(
+    ///     url,
+    ///     "https://github.com/owner/repo/issues/new?title=Follow-up%3A+Crash\
+    ///      &body=Follow-up+to+https%3A%2F%2Fgithub.com%2Fowner%2Frepo%2Fissues%2Fnew%3Ftitle%3DCrash\
+    ///      &assignees=octocat"
+    /// );
+    /// ```
+    pub fn follow_up_url(&self, extra_params: &Params) -> String {
+        let base = self.url.split('?').next().unwrap_or(&self.url);
+
+        let mut fields = vec![
+            (String::from("title"), format!("Follow-up: {}", self.title)),
+            (String::from("body"), format!("Follow-up to {}", self.url)),
+        ];
+        if !self.labels.is_empty() {
+            fields.push((String::from("labels"), self.labels.join(",")));
+        }
+
+        let mut extra: Vec<(&str, &str)> = extra_params.iter().collect();
+        extra.sort();
+        for (key, value) in extra {
+            match fields.iter_mut().find(|(field, _)| field == key) {
+                Some((_, existing)) => *existing = value.to_string(),
+                None => fields.push((key.to_string(), value.to_string())),
+            }
+        }
+
+        let query: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", url_encode::encode(key), url_encode::encode(value)))
+            .collect();
+        format!("{}?{}", base, query.join("&"))
+    }
+}
+
+impl fmt::Display for BugReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_preview(f, &self.title, &self.body, &self.labels, &self.assignees)?;
+        write!(f, "\n\n{}", self.url)
+    }
+}
+
+/// Write the title, labels, assignees, and body of an issue for reading.
+pub(crate) fn write_preview(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    body: &str,
+    labels: &[String],
+    assignees: &[String],
+) -> fmt::Result {
+    f.write_str(title)?;
+    if !labels.is_empty() {
+        write!(f, "\nLabels: {}", labels.join(", "))?;
+    }
+    if !assignees.is_empty() {
+        write!(f, "\nAssignees: {}", assignees.join(", "))?;
+    }
+    if !body.is_empty() {
+        write!(f, "\n\n{}", body)?;
+    }
+    Ok(())
+}
+
+/// The repository and issue fields decoded from a new-issue URL by
+/// [`BugReport::parse_url`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedReport {
+    /// GitHub username or organization the issue is filed against
+    pub owner: String,
+    /// GitHub repository the issue is filed against
+    pub repo: String,
+    /// The issue title
+    pub title: String,
+    /// The issue body
+    pub body: String,
+    /// Labels applied to the issue
+    pub labels: Vec<String>,
+    /// GitHub users the issue is assigned to
+    pub assignees: Vec<String>,
+    /// Milestone the issue is added to
+    pub milestone: Option<String>,
+    /// Projects the issue is added to
+    pub projects: Vec<String>,
+}
+
+impl ParsedReport {
+    /// Turn the parsed fields back into a template.
+    ///
+    /// The fields are already filled, so the template has no placeholders
+    /// other than any braces that were part of the values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::BugReport;
+    ///
+    /// let parsed = BugReport::parse_url("https://github.com/owner/repo/issues/new?title=Crash&labels=bug").unwrap();
+    /// let template = parsed.into_template();
+    /// assert_eq!(template.title, "Crash");
+    /// assert_eq!(template.labels, vec!["bug".to_string()]);
+    /// ```
+    pub fn into_template(self) -> IssueTemplate {
+        let mut template = IssueTemplate::new(self.title, self.body)
+            .with_labels(self.labels)
+            .with_assignees(self.assignees)
+            .with_projects(self.projects);
+        template.milestone = self.milestone;
+        template
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// A comment to paste on an issue that is already being tracked.
+///
+/// GitHub has no query parameter that prefills a comment, so instead of a
+/// new-issue link the user gets the issue's URL, which scrolls to the
+/// comment box, and the rendered comment to paste there.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("occurrence", IssueTemplate::new("Seen again in {version}", "On {os}"));
+///
+/// let mut params = Params::new();
+/// params.insert("version", "1.4.2");
+/// params.insert("os", "linux");
+///
+/// let comment = handle.comment_url(1234, "occurrence", &params).unwrap();
+/// assert_eq!(comment.url, "https://github.com/owner/repo/issues/1234#new_comment_field");
+/// assert_eq!(comment.body, "### Seen again in 1.4.2\n\nOn linux");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentReport {
+    /// Number of the issue to comment on
+    pub issue_number: u64,
+    /// The issue's URL, anchored at the comment box
+    pub url: String,
+    /// The comment to paste: the filled title as a heading, then the body
+    pub body: String,
+}
+
+impl CommentReport {
+    /// Render a report as a comment on `issue_number`.
+    pub(crate) fn new(host: &str, owner: &str, repo: &str, issue_number: u64, report: &BugReport) -> Self {
+        let body = match (report.title.is_empty(), report.body.is_empty()) {
+            (true, _) => report.body.clone(),
+            (false, true) => format!("### {}", report.title),
+            (false, false) => format!("### {}\n\n{}", report.title, report.body),
+        };
+        Self {
+            issue_number,
+            url: format!("{}/{}/{}/issues/{}#new_comment_field", host, owner, repo, issue_number),
+            body,
+        }
+    }
+
+    /// Copy the comment to the clipboard, see `browser::copy_to_clipboard` (std only).
+    #[cfg(feature = "std")]
+    pub fn copy_to_clipboard(&self) -> std::io::Result<()> {
+        crate::browser::copy_to_clipboard(&self.body)
+    }
+}
@@ -0,0 +1,243 @@
+//! An object-safe reporting abstraction.
+//!
+//! Libraries that want to report bugs shouldn't have to name
+//! [`BugReportHandle`] in their APIs. Accepting a `&dyn Reporter` instead
+//! lets the application decide what happens to a report: a real handle
+//! generates the issue URL, [`NoopReporter`] drops it, and a test can pass
+//! [`testing::RecordingReporter`](crate::testing::RecordingReporter) to
+//! assert on what was reported without any URL generation.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, Params, Reporter, ReportOutcome};
+//!
+//! fn parse(input: &str, reporter: &dyn Reporter) -> Option<u32> {
+//!     input.parse().ok().or_else(|| {
+//!         let mut params = Params::default();
+//!         params.insert("input".to_string(), input.to_string());
+//!         reporter.report("parse_failure", &params, Location::new(file!(), line!()));
+//!         None
+//!     })
+//! }
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("parse_failure", IssueTemplate::new("Could not parse {input}", ""));
+//! assert_eq!(parse("12", &handle), Some(12));
+//! assert_eq!(parse("twelve", &handle), None);
+//! assert_eq!(parse("twelve", &bug::NoopReporter), None);
+//! ```
+
+use crate::warning::Warnings;
+use crate::{BugReport, BugReportHandle, Params};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::string::String;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+/// Where in the source a bug was reported.
+///
+/// The reporting macros capture the file, line, column, and module path of
+/// their call site. A location displays as `file:line:column`, or
+/// `file:line` when the column is unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// Source file, as given by `file!()`
+    pub file: &'static str,
+    /// Line number, as given by `line!()`
+    pub line: u32,
+    /// Column number, as given by `column!()`, or 0 if unknown
+    pub column: u32,
+    /// Module path, as given by `module_path!()`, if known
+    pub module_path: Option<&'static str>,
+}
+
+impl Location {
+    /// Create a location from a file name and line number.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Source file, usually `file!()`
+    /// * `line` - Line number, usually `line!()`
+    pub const fn new(file: &'static str, line: u32) -> Self {
+        Self {
+            file,
+            line,
+            column: 0,
+            module_path: None,
+        }
+    }
+
+    /// Set the column number.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Column number, usually `column!()`
+    pub const fn with_column(mut self, column: u32) -> Self {
+        self.column = column;
+        self
+    }
+
+    /// Set the module path.
+    ///
+    /// # Arguments
+    ///
+    /// * `module_path` - Module path, usually `module_path!()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::Location;
+    ///
+    /// let loc = Location::new("src/parser.rs", 42).with_column(9).with_module_path("app::parser");
+    /// assert_eq!(loc.to_string(), "src/parser.rs:42:9");
+    /// assert_eq!(loc.module_path, Some("app::parser"));
+    /// ```
+    pub const fn with_module_path(mut self, module_path: &'static str) -> Self {
+        self.module_path = Some(module_path);
+        self
+    }
+
+    /// The location of the code calling the current function.
+    ///
+    /// Inside a function marked `#[track_caller]` this is that function's
+    /// caller, so wrappers can report where they were called from without
+    /// taking a file and line argument. The module path is not available
+    /// this way and is left unset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::Location;
+    ///
+    /// #[track_caller]
+    /// fn where_called() -> Location {
+    ///     Location::caller()
+    /// }
+    ///
+    /// let expected_line = line!() + 1;
+    /// let loc = where_called();
+    /// assert_eq!(loc.line, expected_line);
+    /// assert!(loc.file.ends_with(".rs"));
+    /// ```
+    #[track_caller]
+    pub fn caller() -> Self {
+        let caller = core::panic::Location::caller();
+        Self::new(caller.file(), caller.line()).with_column(caller.column())
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.column == 0 {
+            write!(f, "{}:{}", self.file, self.line)
+        } else {
+            write!(f, "{}:{}:{}", self.file, self.line, self.column)
+        }
+    }
+}
+
+/// What happened to a report.
+#[derive(Debug, Clone)]
+pub enum ReportOutcome {
+    /// The report was generated.
+    Reported(BugReport),
+    /// The reporter chose not to report, for example because reporting is disabled.
+    Suppressed,
+    /// Generating the report failed.
+    Failed(String),
+}
+
+impl ReportOutcome {
+    /// The generated issue URL, if the report was generated.
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            ReportOutcome::Reported(report) => Some(&report.url),
+            _ => None,
+        }
+    }
+
+    /// Whether the report was generated.
+    pub fn is_reported(&self) -> bool {
+        matches!(self, ReportOutcome::Reported(_))
+    }
+
+    /// Non-fatal issues found while generating the report.
+    ///
+    /// Empty unless the report was generated.
+    pub fn warnings(&self) -> &Warnings {
+        static NONE: Warnings = Warnings::new();
+        match self {
+            ReportOutcome::Reported(report) => &report.warnings,
+            _ => &NONE,
+        }
+    }
+}
+
+impl From<Result<BugReport, String>> for ReportOutcome {
+    fn from(result: Result<BugReport, String>) -> Self {
+        match result {
+            Ok(report) => ReportOutcome::Reported(report),
+            Err(e) => ReportOutcome::Failed(e),
+        }
+    }
+}
+
+/// Something that bug reports can be sent to.
+///
+/// The trait is object safe, so APIs can take `&dyn Reporter`.
+pub trait Reporter {
+    /// Report a bug from a template and parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - Name of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `loc` - Where the bug was encountered
+    fn report(&self, template: &str, params: &Params, loc: Location) -> ReportOutcome;
+}
+
+impl<R: Reporter + ?Sized> Reporter for &R {
+    fn report(&self, template: &str, params: &Params, loc: Location) -> ReportOutcome {
+        (**self).report(template, params, loc)
+    }
+}
+
+/// Generates the report without writing any output, like `report_bug_at`,
+/// filling `{source_location}` and `{module}` from `loc`. Occurrences skipped
+/// by a suppression rule or the template's sample rate are `Suppressed`, and
+/// the outcome is what the configured middleware returns.
+impl Reporter for BugReportHandle {
+    fn report(&self, template: &str, params: &Params, loc: Location) -> ReportOutcome {
+        if self.config().skipped(template, params) {
+            return ReportOutcome::Suppressed;
+        }
+        let extended = self.config().with_location_params(template, params, &loc);
+        match self.create_report(template, extended.as_ref().unwrap_or(params)) {
+            Ok(report) => self.config().middleware.run(self.config().annotate(report, &loc)),
+            Err(e) => ReportOutcome::Failed(e),
+        }
+    }
+}
+
+/// A reporter that discards every report.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{Location, NoopReporter, Params, Reporter, ReportOutcome};
+///
+/// let outcome = NoopReporter.report("crash", &Params::default(), Location::new(file!(), line!()));
+/// assert!(matches!(outcome, ReportOutcome::Suppressed));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopReporter;
+
+impl Reporter for NoopReporter {
+    fn report(&self, _template: &str, _params: &Params, _loc: Location) -> ReportOutcome {
+        ReportOutcome::Suppressed
+    }
+}
@@ -0,0 +1,232 @@
+//! Reproduction steps in the conventional issue layout.
+//!
+//! Most bug templates end with the same three sections: the steps that
+//! lead to the bug, what should have happened, and what happened instead.
+//! [`ReproSteps`] collects them as the program runs and renders them as
+//! Markdown, so every template lays them out the same way and doesn't need
+//! a placeholder per step.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::repro::ReproSteps;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {screen}", "{repro}"));
+//!
+//! let repro = ReproSteps::new()
+//!     .step("Open the app")
+//!     .step("Click Export")
+//!     .expected("The file is saved")
+//!     .actual("The app crashes");
+//!
+//! let mut params = Params::new();
+//! params.insert("screen", "export");
+//! repro.insert_into(&mut params);
+//!
+//! let report = handle.create_report("crash", &params).unwrap();
+//! assert_eq!(report.body, "\
+//! ### Steps to reproduce
+//!
+//! 1. Open the app
+//! 2. Click Export
+//!
+//! ### Expected behavior
+//!
+//! The file is saved
+//!
+//! ### Actual behavior
+//!
+//! The app crashes");
+//! ```
+
+use crate::messages::Messages;
+use crate::Params;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Placeholder filled by `ReproSteps::insert_into`.
+pub const REPRO_PARAM: &str = "repro";
+
+/// Steps to reproduce a bug, with the expected and actual behavior.
+///
+/// Its `Display` text is the Markdown sections, leaving out any that are
+/// empty, under English headings; `markdown` uses the headings of other
+/// `Messages`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReproSteps {
+    steps: Vec<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+}
+
+impl ReproSteps {
+    /// Start with no steps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a step after the steps added before.
+    ///
+    /// A step spanning several lines stays one item of the numbered list.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - What the user does
+    pub fn step(mut self, step: impl Into<String>) -> Self {
+        self.steps.push(step.into());
+        self
+    }
+
+    /// Say what should have happened.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - The expected behavior
+    pub fn expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Say what happened instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `actual` - The actual behavior
+    pub fn actual(mut self, actual: impl Into<String>) -> Self {
+        self.actual = Some(actual.into());
+        self
+    }
+
+    /// The steps as a numbered Markdown list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::repro::ReproSteps;
+    ///
+    /// let repro = ReproSteps::new()
+    ///     .step("Paste:\n```\nkey = \"value\"\n```")
+    ///     .step("Press Save");
+    /// assert_eq!(repro.steps_markdown(), "1. Paste:\n   ```\n   key = \"value\"\n   ```\n2. Press Save");
+    /// ```
+    ///
+    /// Templates that lay the sections out themselves can fill a
+    /// placeholder with the steps alone:
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    /// use bug::repro::ReproSteps;
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("slow", IssueTemplate::new("Slow export", "Steps:\n{steps}\n\nTook {seconds} s"));
+    ///
+    /// let repro = ReproSteps::new().step("Open a large project").step("Export 10k rows");
+    /// let mut params = Params::new();
+    /// params.insert("steps", repro.steps_markdown());
+    /// params.insert("seconds", "40");
+    ///
+    /// let report = handle.create_report("slow", &params).unwrap();
+    /// assert_eq!(report.body, "Steps:\n1. Open a large project\n2. Export 10k rows\n\nTook 40 s");
+    /// ```
+    pub fn steps_markdown(&self) -> String {
+        let mut list = String::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            if index > 0 {
+                list.push('\n');
+            }
+            let number = (index + 1).to_string();
+            list.push_str(&number);
+            list.push_str(". ");
+            // Continuation lines line up with the text after the number
+            for (line_index, line) in step.lines().enumerate() {
+                if line_index > 0 {
+                    list.push('\n');
+                    if !line.is_empty() {
+                        list.extend(core::iter::repeat_n(' ', number.len() + 2));
+                    }
+                }
+                list.push_str(line);
+            }
+        }
+        list
+    }
+
+    /// The sections with the headings of `messages`, for bodies in other
+    /// languages.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The messages whose headings are used
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::messages::Messages;
+    /// use bug::repro::ReproSteps;
+    ///
+    /// let messages = Messages {
+    ///     steps_heading: "Schritte zum Reproduzieren".to_string(),
+    ///     expected_heading: "Erwartetes Verhalten".to_string(),
+    ///     actual_heading: "Tatsächliches Verhalten".to_string(),
+    ///     ..Messages::default()
+    /// };
+    /// let repro = ReproSteps::new().step("Export starten").actual("Absturz");
+    /// assert_eq!(
+    ///     repro.markdown(&messages),
+    ///     "## Schritte zum Reproduzieren\n\n1. Export starten\n\n## Tatsächliches Verhalten\n\nAbsturz"
+    /// );
+    /// ```
+    pub fn markdown(&self, messages: &Messages) -> String {
+        let mut text = String::new();
+        let _ = self.write_sections(&mut text, messages);
+        text
+    }
+
+    /// Write the non-empty sections under the headings of `messages`.
+    fn write_sections(&self, f: &mut dyn fmt::Write, messages: &Messages) -> fmt::Result {
+        let steps = (!self.steps.is_empty()).then(|| self.steps_markdown());
+        let sections = [
+            (&messages.steps_heading, steps.as_deref()),
+            (&messages.expected_heading, self.expected.as_deref()),
+            (&messages.actual_heading, self.actual.as_deref()),
+        ];
+        let mut first = true;
+        for (heading, text) in sections {
+            let Some(text) = text else { continue };
+            if !first {
+                f.write_str("\n\n")?;
+            }
+            first = false;
+            write!(f, "## {}\n\n{}", heading, text)?;
+        }
+        Ok(())
+    }
+
+    /// Set `{repro}` to the sections.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters to add to
+    pub fn insert_into(&self, params: &mut Params) {
+        params.insert(REPRO_PARAM, self.to_string());
+    }
+}
+
+impl fmt::Display for ReproSteps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_sections(f, &Messages::default())
+    }
+}
@@ -0,0 +1,126 @@
+//! Reporting a fraction of occurrences.
+//!
+//! Some reports fire so often that each occurrence adds nothing. Giving a
+//! template a sample rate with `sample_rate` keeps the call site in place
+//! while only emitting reports for roughly that fraction of occurrences.
+//! Reports that are emitted say so in their body, through the
+//! `{sample_rate}` placeholder when the template has one and a note at the
+//! end of the body otherwise.
+//!
+//! Sampling only applies where bugs are reported (`bug!`, `report_bug_at`,
+//! `Reporter::report`, and friends); `create_report` and `generate_url`
+//! always produce a report.
+//!
+//! The random numbers come from a [`SampleRng`]. With `std`, [`DefaultRng`]
+//! is used unless another is set with `sample_rng`. In no_std builds there
+//! is no default: sample rates have no effect until an RNG is provided.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{format, string::String, sync::Arc};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, sync::Arc};
+
+/// A source of random numbers for sampling reports.
+///
+/// Closures returning `u64` implement this trait, so a hardware RNG or any
+/// generator behind a lock can be plugged in directly.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params, Location};
+///
+/// // An RNG that always rolls the highest value never samples anything in.
+/// let handle = init_handle("owner", "repo")
+///     .add_template("hot_path", IssueTemplate::new("Slow frame", ""))
+///     .sample_rate("hot_path", 0.5)
+///     .sample_rng(|| u64::MAX);
+///
+/// let url = handle.report_bug_at("hot_path", &Params::new(), Location::new(file!(), line!()));
+/// assert!(url.is_empty());
+/// ```
+pub trait SampleRng: Send + Sync {
+    /// Return the next random number, uniformly distributed over `u64`.
+    fn next_u64(&self) -> u64;
+}
+
+impl<F: Fn() -> u64 + Send + Sync> SampleRng for F {
+    fn next_u64(&self) -> u64 {
+        self()
+    }
+}
+
+/// The RNG used for sampling when none is set (std only).
+///
+/// Numbers are hashes of a process-wide counter under randomly keyed
+/// SipHash, which is plenty for sampling but not for cryptography.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultRng;
+
+#[cfg(feature = "std")]
+impl SampleRng for DefaultRng {
+    fn next_u64(&self) -> u64 {
+        use std::hash::{BuildHasher, Hasher};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let mut hasher = std::hash::RandomState::new().build_hasher();
+        hasher.write_usize(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.finish()
+    }
+}
+
+/// The RNG a configuration samples with.
+#[derive(Clone, Default)]
+pub(crate) struct Sampler {
+    rng: Option<Arc<dyn SampleRng>>,
+}
+
+impl Sampler {
+    pub(crate) fn set_rng(&mut self, rng: impl SampleRng + 'static) {
+        self.rng = Some(Arc::new(rng));
+    }
+
+    /// Roll whether an occurrence is reported at `rate`.
+    pub(crate) fn sample(&self, rate: f64) -> bool {
+        if rate >= 1.0 {
+            return true;
+        }
+        if rate <= 0.0 {
+            return false;
+        }
+
+        let roll = match &self.rng {
+            Some(rng) => rng.next_u64(),
+            #[cfg(feature = "std")]
+            None => DefaultRng.next_u64(),
+            #[cfg(not(feature = "std"))]
+            None => return true,
+        };
+        // The top 53 bits give a uniform float in [0, 1).
+        ((roll >> 11) as f64 / (1u64 << 53) as f64) < rate
+    }
+}
+
+impl fmt::Debug for Sampler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sampler")
+            .field("custom_rng", &self.rng.is_some())
+            .finish()
+    }
+}
+
+/// Append `note`, the sampled note of `Messages`, to the body of a sampled
+/// report without `{sample_rate}`.
+pub(crate) fn note(body: &str, note: &str, rate: f64) -> String {
+    let note = note.replace("{sample_rate}", &format!("{}", rate));
+    if body.is_empty() {
+        note
+    } else {
+        format!("{}\n\n{}", body, note)
+    }
+}
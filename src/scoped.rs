@@ -0,0 +1,75 @@
+//! Per-thread reporting handles (std only).
+//!
+//! [`with_handle`] makes a handle the current one on this thread for the
+//! duration of a closure, and `bug_scoped!` reports through the current
+//! handle, falling back to the global configuration like `bug!` when no
+//! handle is in scope. Test frameworks and request handlers can use this to
+//! swap the reporting target for a scope without touching global state.
+//!
+//! Scopes nest: an inner `with_handle` shadows the outer handle and the
+//! outer one is current again once the inner closure returns or panics.
+//! Threads don't inherit the current handle automatically; pass
+//! [`current`] to the new thread and enter a scope there.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{bug_scoped, init_handle, IssueTemplate};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("request_failed", IssueTemplate::new("Request {id} failed", ""));
+//!
+//! let url = bug::scoped::with_handle(handle, || bug_scoped!("request_failed", { id = 42 }));
+//! assert!(url.contains("title=Request+42+failed"));
+//!
+//! // Hand the current handle to a worker thread.
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("worker_failed", IssueTemplate::new("Worker failed", ""));
+//! bug::scoped::with_handle(handle, || {
+//!     let current = bug::scoped::current().unwrap();
+//!     std::thread::spawn(move || {
+//!         bug::scoped::with_handle(current, || bug_scoped!("worker_failed"))
+//!     })
+//!     .join()
+//!     .unwrap()
+//! });
+//! ```
+
+use crate::BugReportHandle;
+use std::cell::RefCell;
+use std::sync::Arc;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<BugReportHandle>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `handle` as this thread's current handle.
+///
+/// # Arguments
+///
+/// * `handle` - The handle `bug_scoped!` reports through inside `f`
+/// * `f` - The scope
+///
+/// # Returns
+///
+/// Whatever `f` returns.
+pub fn with_handle<R>(handle: impl Into<Arc<BugReportHandle>>, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.with(|current| current.replace(Some(handle.into())));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// The current handle on this thread, if inside `with_handle`.
+pub fn current() -> Option<Arc<BugReportHandle>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Puts the outer handle back when a scope ends, including by panic.
+struct Restore(Option<Arc<BugReportHandle>>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
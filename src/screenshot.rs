@@ -0,0 +1,177 @@
+//! Guiding users to attach a screenshot (std only).
+//!
+//! A prefilled issue URL can't carry an image: GitHub only attaches files
+//! dropped into the issue form. GUI bugs are much easier to triage with a
+//! screenshot, so templates can end with a `{screenshot}` section that
+//! asks for one. An app that captures the window itself, for example when
+//! its error dialog opens, says where it saved the file through a
+//! [`ScreenshotProvider`], and the section points the user at that file.
+//!
+//! With `screenshot_provider` on the configuration, `{screenshot}` is
+//! filled in every report whose template uses it, under the headings and
+//! prompts of the configuration's `Messages`. [`ScreenshotSection`] renders
+//! the same section by hand.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use std::path::PathBuf;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("glitch", IssueTemplate::new("Rendering glitch", "The chart overlaps its legend.\n\n{screenshot}"))
+//!     .screenshot_provider(|| Some(PathBuf::from("/tmp/myapp/glitch.png")));
+//!
+//! let report = handle.create_report("glitch", &Params::new()).unwrap();
+//! assert_eq!(report.body, "\
+//! The chart overlaps its legend.
+//!
+//! ### Screenshot
+//!
+//! A screenshot was saved to `/tmp/myapp/glitch.png`. Drag and drop it here to attach it.");
+//! ```
+
+use crate::messages::Messages;
+use crate::Params;
+use std::fmt;
+use std::path::PathBuf;
+use std::string::String;
+use std::sync::Arc;
+
+/// Placeholder filled with the screenshot section.
+pub const SCREENSHOT_PARAM: &str = "screenshot";
+
+/// Says where the app saved a screenshot for the report being filed.
+///
+/// Implemented for closures returning the path.
+pub trait ScreenshotProvider: Send + Sync {
+    /// The screenshot saved for this report, or `None` if there is none.
+    fn screenshot_path(&self) -> Option<PathBuf>;
+}
+
+impl<F: Fn() -> Option<PathBuf> + Send + Sync> ScreenshotProvider for F {
+    fn screenshot_path(&self) -> Option<PathBuf> {
+        self()
+    }
+}
+
+/// A Markdown section asking the user to attach a screenshot.
+///
+/// Its `Display` text uses the English messages; `markdown` uses others.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScreenshotSection {
+    path: Option<PathBuf>,
+}
+
+impl ScreenshotSection {
+    /// Ask for a screenshot the user takes themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::screenshot::ScreenshotSection;
+    ///
+    /// assert_eq!(
+    ///     ScreenshotSection::new().to_string(),
+    ///     "## Screenshot\n\n_If you can, drag and drop a screenshot here to attach it._"
+    /// );
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Point the user at a screenshot the app saved.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Where the screenshot was saved
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Point the user at the screenshot `provider` saved, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - The app's screenshot provider
+    pub fn from_provider(provider: &dyn ScreenshotProvider) -> Self {
+        Self {
+            path: provider.screenshot_path(),
+        }
+    }
+
+    /// The section with the heading and prompts of `messages`.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The messages whose screenshot texts are used
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::messages::Messages;
+    /// use bug::screenshot::ScreenshotSection;
+    ///
+    /// let messages = Messages {
+    ///     screenshot_heading: "Capture d'écran".to_string(),
+    ///     screenshot_saved_prompt: "Une capture a été enregistrée dans `{path}` ; glissez-la ici.".to_string(),
+    ///     ..Messages::default()
+    /// };
+    /// assert_eq!(
+    ///     ScreenshotSection::new().path("C:\\shots\\1.png").markdown(&messages),
+    ///     "## Capture d'écran\n\nUne capture a été enregistrée dans `C:\\shots\\1.png` ; glissez-la ici."
+    /// );
+    /// ```
+    pub fn markdown(&self, messages: &Messages) -> String {
+        let prompt = match &self.path {
+            Some(path) => messages
+                .screenshot_saved_prompt
+                .replace("{path}", &path.display().to_string()),
+            None => messages.screenshot_prompt.clone(),
+        };
+        format!("## {}\n\n{}", messages.screenshot_heading, prompt)
+    }
+
+    /// Set `{screenshot}` to the section.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters to add to
+    pub fn insert_into(&self, params: &mut Params) {
+        params.insert(SCREENSHOT_PARAM, self.to_string());
+    }
+}
+
+impl fmt::Display for ScreenshotSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.markdown(&Messages::default()))
+    }
+}
+
+/// The screenshot provider of a configuration.
+#[derive(Clone)]
+pub(crate) struct ScreenshotSource(Arc<dyn ScreenshotProvider>);
+
+impl ScreenshotSource {
+    pub(crate) fn new(provider: impl ScreenshotProvider + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+
+    /// Add `{screenshot}` if the template uses it and `params` lacks it.
+    ///
+    /// The provider is only asked if the section is needed.
+    pub(crate) fn fill(&self, placeholders: &[String], params: &Params, messages: &Messages, extended: &mut Option<Params>) {
+        if !placeholders.iter().any(|p| p == SCREENSHOT_PARAM) || params.contains_key(SCREENSHOT_PARAM) {
+            return;
+        }
+        let section = ScreenshotSection::from_provider(&*self.0).markdown(messages);
+        extended.get_or_insert_with(|| params.clone()).insert(SCREENSHOT_PARAM, section);
+    }
+}
+
+impl fmt::Debug for ScreenshotSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScreenshotSource").finish_non_exhaustive()
+    }
+}
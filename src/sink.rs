@@ -0,0 +1,581 @@
+//! Sending every reported bug to more places than the console.
+//!
+//! The banner goes to one [`Output`], but a bug is often wanted in several
+//! places at once: on the console for the developer, in a JSON Lines file
+//! for support tooling, and in a callback feeding telemetry. Each
+//! [`Sink`] added with `add_sink` gets its own record of every reported
+//! bug, written in its own [`SinkFormat`] and at its own [`Verbosity`].
+//!
+//! Sinks receive bugs wherever a banner is written (`bug!`,
+//! `report_bug_at`, `report_bug_with_output_at`, and friends), after
+//! suppression, sampling, and middleware, and also while reports are
+//! summarized. `Reporter::report`, `create_report`, and `generate_url`
+//! write nothing, so they don't reach sinks either.
+//!
+//! A sink never gets more detail than its verbosity, and never more than
+//! the console: an occurrence that backoff shortens to a one-line reminder
+//! is brief in every sink, and while consent is withheld sinks only get
+//! brief records.
+//!
+//! A [`FileSink`] (std only) appends records to a file and rotates it by
+//! size, so a long-running daemon keeps a bounded archive of its reports.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, Params};
+//! use bug::backoff::Verbosity;
+//! use bug::sink::{JsonLines, Sink, SinkFormat};
+//! use std::sync::{Arc, Mutex};
+//!
+//! // Stand in for a file opened with `IoOutput(File::create(...)?)`
+//! let jsonl = Arc::new(Mutex::new(String::new()));
+//! let brief = Arc::new(Mutex::new(String::new()));
+//! let (jsonl_sink, brief_sink) = (Arc::clone(&jsonl), Arc::clone(&brief));
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {module}", "{details}"))
+//!     .add_sink(Sink::callback(move |record| JsonLines.write(record, &mut *jsonl_sink.lock().unwrap())))
+//!     .add_sink(Sink::callback(move |record| {
+//!         use std::fmt::Write;
+//!         writeln!(brief_sink.lock().unwrap(), "{} at {}", record.template, record.site).unwrap();
+//!     }).verbosity(Verbosity::Brief));
+//!
+//! let mut params = Params::new();
+//! params.insert("module", "parser");
+//! params.insert("details", "unexpected token");
+//! let mut console = String::new();
+//! handle.report_bug_with_output_at("crash", &params, Location::new("main.rs", 7), &mut console);
+//!
+//! assert!(console.starts_with("🐛 BUG ENCOUNTERED in main.rs:7\n"));
+//! assert!(jsonl.lock().unwrap().starts_with(r#"{"template":"crash","site":"main.rs:7","title":"Crash in parser","#));
+//! assert_eq!(*brief.lock().unwrap(), "crash at main.rs:7\n");
+//! ```
+
+use crate::backoff::Verbosity;
+use crate::sync::Lock;
+use crate::{BugReport, Output, Params};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
+
+/// What a sink is told about one reported bug.
+#[derive(Clone, Copy)]
+pub struct SinkRecord<'a> {
+    /// The report, or why it couldn't be generated
+    pub result: Result<&'a BugReport, &'a str>,
+    /// The template name, as shown in the banner
+    pub template: &'a str,
+    /// The parameters passed in
+    pub params: &'a Params,
+    /// Where the bug was encountered
+    pub site: &'a dyn fmt::Display,
+    /// The module the bug was encountered in, if known
+    pub module_path: Option<&'a str>,
+    /// The URL returned to the caller, empty while consent is withheld
+    pub url: &'a str,
+    /// How many times this report has occurred, when backoff counts them
+    pub occurrence: Option<u64>,
+    /// How much of the record this sink should write
+    pub verbosity: Verbosity,
+}
+
+impl fmt::Debug for SinkRecord<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SinkRecord")
+            .field("result", &self.result)
+            .field("template", &self.template)
+            .field("params", &self.params)
+            .field("site", &format_args!("{}", self.site))
+            .field("module_path", &self.module_path)
+            .field("url", &self.url)
+            .field("occurrence", &self.occurrence)
+            .field("verbosity", &self.verbosity)
+            .finish()
+    }
+}
+
+/// How a sink writes its records.
+///
+/// Implemented for closures taking the record and the sink's output.
+/// Records that are `Verbosity::Silent` never reach the format.
+pub trait SinkFormat: Send + Sync {
+    /// Write `record` to `output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The reported bug
+    /// * `output` - The sink's output
+    fn write(&self, record: &SinkRecord<'_>, output: &mut dyn Output);
+}
+
+impl<F: Fn(&SinkRecord<'_>, &mut dyn Output) + Send + Sync> SinkFormat for F {
+    fn write(&self, record: &SinkRecord<'_>, output: &mut dyn Output) {
+        self(record, output)
+    }
+}
+
+/// The console banner, without hyperlinks, followed by a blank line.
+///
+/// Brief records get a single line naming the site and template.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Banner;
+
+impl SinkFormat for Banner {
+    fn write(&self, record: &SinkRecord<'_>, output: &mut dyn Output) {
+        match (record.verbosity, record.result) {
+            (Verbosity::Full, result) => {
+                let result = result.cloned().map_err(|e| e.to_string());
+                crate::write_banner(output, result, record.template, record.params, record.site, record.module_path, None);
+            }
+            (_, _) => match record.occurrence {
+                Some(occurrence) if occurrence > 1 => output.write_fmt(format_args!(
+                    "🐛 BUG ENCOUNTERED again in {}: {} ({} occurrences)\n",
+                    record.site, record.template, occurrence
+                )),
+                _ => output.write_fmt(format_args!("🐛 BUG ENCOUNTERED in {}: {}\n", record.site, record.template)),
+            },
+        }
+    }
+}
+
+/// One JSON object per record, on its own line.
+///
+/// Every object has `template` and `site`, plus `occurrence` when backoff
+/// counts occurrences. Full records add `module`, `title`, `body`,
+/// `labels`, `assignees`, `params`, and `url`; records of reports that
+/// failed add `error` instead.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params};
+/// use bug::sink::{JsonLines, SinkFormat, SinkRecord};
+/// use bug::backoff::Verbosity;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", "Said \"no\"").with_labels(vec!["bug".to_string()]));
+/// let report = handle.create_report("crash", &Params::new()).unwrap();
+/// let record = SinkRecord {
+///     result: Ok(&report),
+///     template: "crash",
+///     params: &Params::new(),
+///     site: &"main.rs:7",
+///     module_path: None,
+///     url: &report.url,
+///     occurrence: None,
+///     verbosity: Verbosity::Full,
+/// };
+///
+/// let mut line = String::new();
+/// JsonLines.write(&record, &mut line);
+/// assert!(line.starts_with(r#"{"template":"crash","site":"main.rs:7","title":"Crash","body":"Said \"no\"","labels":["bug"],"#));
+/// assert!(line.ends_with("}\n"));
+///
+/// line.clear();
+/// JsonLines.write(&SinkRecord { verbosity: Verbosity::Brief, occurrence: Some(3), ..record }, &mut line);
+/// assert_eq!(line, "{\"template\":\"crash\",\"site\":\"main.rs:7\",\"occurrence\":3}\n");
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonLines;
+
+impl SinkFormat for JsonLines {
+    fn write(&self, record: &SinkRecord<'_>, output: &mut dyn Output) {
+        output.write_str("{\"template\":");
+        write_json_string(output, record.template);
+        output.write_str(",\"site\":");
+        write_json_string(output, &record.site.to_string());
+        if let Some(occurrence) = record.occurrence {
+            output.write_fmt(format_args!(",\"occurrence\":{}", occurrence));
+        }
+        if record.verbosity == Verbosity::Full {
+            match record.result {
+                Ok(report) => {
+                    if let Some(module_path) = record.module_path {
+                        output.write_str(",\"module\":");
+                        write_json_string(output, module_path);
+                    }
+                    output.write_str(",\"title\":");
+                    write_json_string(output, &report.title);
+                    output.write_str(",\"body\":");
+                    write_json_string(output, &report.body);
+                    output.write_str(",\"labels\":");
+                    write_json_array(output, &report.labels);
+                    output.write_str(",\"assignees\":");
+                    write_json_array(output, &report.assignees);
+                    output.write_str(",\"params\":{");
+                    for (index, (key, value)) in record.params.iter().enumerate() {
+                        if index > 0 {
+                            output.write_str(",");
+                        }
+                        write_json_string(output, key);
+                        output.write_str(":");
+                        write_json_string(output, value);
+                    }
+                    output.write_str("},\"url\":");
+                    write_json_string(output, record.url);
+                }
+                Err(e) => {
+                    output.write_str(",\"error\":");
+                    write_json_string(output, e);
+                }
+            }
+        }
+        output.write_str("}\n");
+    }
+}
+
+/// Write `value` as a JSON string literal.
+pub(crate) fn write_json_string(output: &mut dyn Output, value: &str) {
+    output.write_str("\"");
+    let mut start = 0;
+    for (index, c) in value.char_indices() {
+        let escape = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            c if (c as u32) < 0x20 => "",
+            _ => continue,
+        };
+        output.write_str(&value[start..index]);
+        if escape.is_empty() {
+            output.write_fmt(format_args!("\\u{:04x}", c as u32));
+        } else {
+            output.write_str(escape);
+        }
+        start = index + c.len_utf8();
+    }
+    output.write_str(&value[start..]);
+    output.write_str("\"");
+}
+
+/// Write `values` as a JSON array of strings.
+fn write_json_array(output: &mut dyn Output, values: &[impl AsRef<str>]) {
+    output.write_str("[");
+    for (index, value) in values.iter().enumerate() {
+        if index > 0 {
+            output.write_str(",");
+        }
+        write_json_string(output, value.as_ref());
+    }
+    output.write_str("]");
+}
+
+/// Somewhere reported bugs are written, in addition to the banner.
+///
+/// Clones share the output, so a handle and its clones write to the same
+/// file or buffer.
+#[derive(Clone)]
+pub struct Sink {
+    output: Arc<Lock<Box<dyn Output + Send>>>,
+    format: Arc<dyn SinkFormat>,
+    verbosity: Verbosity,
+}
+
+impl Sink {
+    /// Write full banners to `output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Where records are written, such as `IoOutput(file)`
+    pub fn new(output: impl Output + Send + 'static) -> Self {
+        Self {
+            output: Arc::new(Lock::new(Box::new(output))),
+            format: Arc::new(Banner),
+            verbosity: Verbosity::Full,
+        }
+    }
+
+    /// Call `callback` with every record instead of writing it.
+    ///
+    /// The callback runs while the bug is being reported, so it must not
+    /// report a bug through the same handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Receives each record at the sink's verbosity
+    pub fn callback(callback: impl Fn(&SinkRecord<'_>) + Send + Sync + 'static) -> Self {
+        Self::new(crate::NoOutput).format(move |record: &SinkRecord<'_>, _: &mut dyn Output| callback(record))
+    }
+
+    /// Write records in `format` instead of as banners.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - A `SinkFormat` such as `JsonLines`, or a closure taking the record and the output
+    pub fn format(mut self, format: impl SinkFormat + 'static) -> Self {
+        self.format = Arc::new(format);
+        self
+    }
+
+    /// Write records with at most `verbosity` detail.
+    ///
+    /// `Verbosity::Silent` turns the sink off.
+    ///
+    /// # Arguments
+    ///
+    /// * `verbosity` - The most detail this sink writes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Location, NoOutput, Params};
+    /// use bug::backoff::{BackoffPolicy, Verbosity};
+    /// use bug::sink::Sink;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let record = |seen: &Arc<Mutex<Vec<_>>>| {
+    ///     let seen = Arc::clone(seen);
+    ///     Sink::callback(move |record| seen.lock().unwrap().push(record.verbosity))
+    /// };
+    /// let brief = Arc::new(Mutex::new(Vec::new()));
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("crash", IssueTemplate::new("Crash", ""))
+    ///     .backoff(BackoffPolicy::new())
+    ///     .add_sink(record(&seen))
+    ///     .add_sink(record(&brief).verbosity(Verbosity::Brief));
+    ///
+    /// for line in 1..=3 {
+    ///     handle.report_bug_with_output_at("crash", &Params::new(), Location::new("main.rs", line), &mut NoOutput);
+    /// }
+    /// // Backoff shortens the repeats everywhere; the brief sink never gets more
+    /// assert_eq!(*seen.lock().unwrap(), [Verbosity::Full, Verbosity::Brief, Verbosity::Brief]);
+    /// assert_eq!(*brief.lock().unwrap(), [Verbosity::Brief; 3]);
+    /// ```
+    pub fn verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Write `record`, capped at this sink's verbosity.
+    fn write(&self, record: &SinkRecord<'_>) {
+        let verbosity = least(record.verbosity, self.verbosity);
+        if verbosity == Verbosity::Silent {
+            return;
+        }
+        let record = SinkRecord { verbosity, ..*record };
+        self.output.with(|output| self.format.write(&record, &mut **output));
+    }
+}
+
+impl fmt::Debug for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sink").field("verbosity", &self.verbosity).finish_non_exhaustive()
+    }
+}
+
+/// The less detailed of two verbosities.
+fn least(a: Verbosity, b: Verbosity) -> Verbosity {
+    match (a, b) {
+        (Verbosity::Silent, _) | (_, Verbosity::Silent) => Verbosity::Silent,
+        (Verbosity::Brief, _) | (_, Verbosity::Brief) => Verbosity::Brief,
+        _ => Verbosity::Full,
+    }
+}
+
+/// The sinks of a configuration, in the order they were added.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Sinks(Vec<Sink>);
+
+impl Sinks {
+    /// Add `sink` after the existing ones.
+    pub(crate) fn push(&mut self, sink: Sink) {
+        self.0.push(sink);
+    }
+
+    /// Whether there are no sinks, so no record needs to be built.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Write `record` to every sink.
+    pub(crate) fn write(&self, record: &SinkRecord<'_>) {
+        for sink in &self.0 {
+            sink.write(record);
+        }
+    }
+}
+
+/// A report archive that appends to a file and rotates it by size (std only).
+///
+/// Before a record would grow the file past `max_size`, the file is
+/// renamed to `<path>.1`, the older archives move up one number, and the
+/// one past `max_files` is deleted; then a new file is started. Records
+/// are never split across files. Write errors are dropped, as for every
+/// sink.
+///
+/// Convert it into a [`Sink`] to add it with `add_sink`, or call
+/// [`append`](Self::append) to use it on its own.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Location, NoOutput, Params};
+/// use bug::sink::{FileSink, JsonLines};
+///
+/// let dir = std::env::temp_dir().join(format!("bug-file-sink-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("reports.jsonl");
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", "{details}"))
+///     .add_sink(FileSink::new(&path).max_size(1024).max_files(2).format(JsonLines).into());
+///
+/// let params = Params::new().with("details", "x".repeat(100));
+/// for line in 1..=10 {
+///     handle.report_bug_with_output_at("crash", &params, Location::new("main.rs", line), &mut NoOutput);
+/// }
+/// // Two records fit in a file; the oldest archives were deleted
+/// let lines = |path: &std::path::Path| std::fs::read_to_string(path).unwrap().lines().count();
+/// assert_eq!(lines(&path), 2);
+/// assert_eq!(lines(&dir.join("reports.jsonl.1")), 2);
+/// assert_eq!(lines(&dir.join("reports.jsonl.2")), 2);
+/// assert!(!dir.join("reports.jsonl.3").exists());
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+#[cfg(feature = "std")]
+pub struct FileSink {
+    path: std::path::PathBuf,
+    max_size: u64,
+    max_files: usize,
+    format: Arc<dyn SinkFormat>,
+    /// The open file and its size, opened on the first write
+    file: Lock<Option<(std::fs::File, u64)>>,
+}
+
+#[cfg(feature = "std")]
+impl FileSink {
+    /// Append to the file at `path`, rotating it at 1 MiB and keeping 5 archives.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file to write; archives are named after it
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_size: 1024 * 1024,
+            max_files: 5,
+            format: Arc::new(Banner),
+            file: Lock::new(None),
+        }
+    }
+
+    /// Rotate the file before it would grow past `bytes`.
+    ///
+    /// A single record larger than this still gets a file of its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The largest size a file is allowed to grow to
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Keep `count` rotated archives besides the current file.
+    ///
+    /// With 0 the file is emptied when it is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - How many archives to keep
+    pub fn max_files(mut self, count: usize) -> Self {
+        self.max_files = count;
+        self
+    }
+
+    /// Write records in `format` instead of as banners.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - A `SinkFormat` such as `JsonLines`
+    pub fn format(mut self, format: impl SinkFormat + 'static) -> Self {
+        self.format = Arc::new(format);
+        self
+    }
+
+    /// Append `text` to the file, rotating it first if it would grow past `max_size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A whole record, such as a banner written to a `String`
+    pub fn append(&self, text: &str) -> std::io::Result<()> {
+        use std::io::Write;
+        self.file.with(|file| {
+            let len = text.len() as u64;
+            if let Some((_, size)) = file
+                && *size > 0
+                && size.saturating_add(len) > self.max_size
+            {
+                *file = None;
+                self.rotate()?;
+            }
+            let (handle, size) = match file {
+                Some(open) => open,
+                None => {
+                    let handle = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+                    let size = handle.metadata()?.len();
+                    file.insert((handle, size))
+                }
+            };
+            handle.write_all(text.as_bytes())?;
+            *size += len;
+            Ok(())
+        })
+    }
+
+    /// The path of the `index`th archive.
+    fn archive(&self, index: usize) -> std::path::PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(std::format!(".{}", index));
+        name.into()
+    }
+
+    /// Move the file and its archives up one number, dropping the oldest.
+    fn rotate(&self) -> std::io::Result<()> {
+        let ignore_missing = |result: std::io::Result<()>| match result {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+        if self.max_files == 0 {
+            return ignore_missing(std::fs::remove_file(&self.path));
+        }
+        ignore_missing(std::fs::remove_file(self.archive(self.max_files)))?;
+        for index in (1..self.max_files).rev() {
+            ignore_missing(std::fs::rename(self.archive(index), self.archive(index + 1)))?;
+        }
+        ignore_missing(std::fs::rename(&self.path, self.archive(1)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<FileSink> for Sink {
+    fn from(file_sink: FileSink) -> Self {
+        Sink::callback(move |record| {
+            let mut text = std::string::String::new();
+            file_sink.format.write(record, &mut text);
+            if !text.is_empty() {
+                let _ = file_sink.append(&text);
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for FileSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileSink")
+            .field("path", &self.path)
+            .field("max_size", &self.max_size)
+            .field("max_files", &self.max_files)
+            .finish_non_exhaustive()
+    }
+}
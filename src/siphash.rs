@@ -0,0 +1,78 @@
+//! Keyed SipHash-1-3, the hash std's `HashMap` uses, available without std.
+
+use core::hash::Hasher;
+
+/// SipHash with one compression and three finalization rounds.
+#[derive(Debug, Clone)]
+pub(crate) struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    /// Bytes not yet compressed, little-endian
+    tail: u64,
+    /// Number of bytes in `tail`
+    ntail: usize,
+    /// Total bytes written
+    length: usize,
+}
+
+impl SipHasher13 {
+    pub(crate) fn new_with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            v0: k0 ^ 0x736f_6d65_7073_6575,
+            v1: k1 ^ 0x646f_7261_6e64_6f6d,
+            v2: k0 ^ 0x6c79_6765_6e65_7261,
+            v3: k1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.length += bytes.len();
+        for &byte in bytes {
+            self.tail |= (byte as u64) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                let word = self.tail;
+                self.compress(word);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        let last = ((self.length as u64 & 0xff) << 56) | self.tail;
+        state.compress(last);
+        state.v2 ^= 0xff;
+        for _ in 0..3 {
+            state.round();
+        }
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+}
@@ -0,0 +1,83 @@
+//! Capabilities shared by every kind of template.
+//!
+//! [`IssueTemplate`] and [`TemplateFile`] both implement [`TemplateSource`],
+//! so code that checks parameters or lists placeholders works the same way
+//! for templates declared inline and templates loaded from files. Reports
+//! from either kind are checked alike too: with `strict_params` set on the
+//! configuration, inline templates are validated before filling exactly as
+//! template files are.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{IssueTemplate, Params, TemplateFile, TemplateSource};
+//!
+//! fn check(template: &dyn TemplateSource, params: &Params) -> Result<(), String> {
+//!     template.validate_params(params)
+//! }
+//!
+//! let mut params = Params::new();
+//! params.insert("screen", "settings");
+//!
+//! let inline = IssueTemplate::new("Crash in {screen}", "{details}");
+//! let file = TemplateFile::new("Crash in {screen}\n{details}");
+//! assert_eq!(check(&inline, &params).unwrap_err(), "Missing required parameter: details");
+//! assert_eq!(check(&file, &params).unwrap_err(), "Missing required parameter: details");
+//! ```
+
+use crate::{fill, IssueTemplate, Params, TemplateFile};
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// A template that can be checked and turned into an [`IssueTemplate`].
+pub trait TemplateSource {
+    /// Unique placeholder names in the title, body, labels, and assignees,
+    /// in order of appearance.
+    fn placeholders(&self) -> Vec<String>;
+
+    /// Check that `params` provides exactly the template's placeholders.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Map of parameter names to values
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - All parameters are valid
+    /// * `Err(String)` - Error describing missing or unused parameters
+    fn validate_params(&self, params: &Params) -> Result<(), String> {
+        fill::check_strict(&self.placeholders(), params)
+    }
+
+    /// The template as an `IssueTemplate`, parsing it if needed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IssueTemplate)` - The unfilled template
+    /// * `Err(String)` - Error message if the template is malformed
+    fn to_issue_template(&self) -> Result<IssueTemplate, String>;
+}
+
+impl TemplateSource for IssueTemplate {
+    fn placeholders(&self) -> Vec<String> {
+        IssueTemplate::placeholders(self)
+    }
+
+    fn to_issue_template(&self) -> Result<IssueTemplate, String> {
+        Ok(self.clone())
+    }
+}
+
+impl TemplateSource for TemplateFile {
+    fn placeholders(&self) -> Vec<String> {
+        TemplateFile::placeholders(self)
+    }
+
+    fn to_issue_template(&self) -> Result<IssueTemplate, String> {
+        self.parse()
+    }
+}
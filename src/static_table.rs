@@ -0,0 +1,126 @@
+//! Template tables declared at compile time with [`static_templates!`](crate::static_templates).
+//!
+//! Large applications can have dozens of templates, and registering each
+//! one with `add_template` costs a `String` per field at startup and a
+//! string literal per call site that may be misspelled. The
+//! `static_templates!` macro declares them once, as a `const` table of
+//! [`StaticTemplate`]s and an enum with one variant per template, and
+//! builds a [`TypedHandle`](crate::typed::TypedHandle) for the whole table
+//! in one call. It is plain `macro_rules!`, so it needs no proc-macro
+//! dependency.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::Params;
+//!
+//! bug::static_templates! {
+//!     pub enum Report {
+//!         CRASH => ("Crash in {screen}", "{details}", ["bug", "crash"]),
+//!         SLOW_FRAME => ("Slow frame", "Took {ms} ms", []),
+//!     }
+//! }
+//!
+//! assert_eq!(Report::TABLE.len(), 2);
+//! assert_eq!(Report::CRASH.name(), "CRASH");
+//! assert_eq!(Report::SLOW_FRAME.template().title, "Slow frame");
+//!
+//! let handle = Report::handle("owner", "repo");
+//! let mut params = Params::new();
+//! params.insert("ms", "48");
+//! let report = handle.create_report(&Report::SLOW_FRAME, &params).unwrap();
+//! assert_eq!(report.template_name, "SLOW_FRAME");
+//! assert_eq!(report.body, "Took 48 ms");
+//! ```
+
+use crate::IssueTemplate;
+
+#[cfg(feature = "std")]
+use std::string::ToString;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::ToString;
+
+/// A template whose fields are all `'static`, so tables of them can be `const`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticTemplate {
+    /// Name the template is registered under
+    pub name: &'static str,
+    /// The title template for the GitHub issue
+    pub title: &'static str,
+    /// The body template for the GitHub issue
+    pub body: &'static str,
+    /// Labels to apply to the GitHub issue
+    pub labels: &'static [&'static str],
+}
+
+impl StaticTemplate {
+    /// The template as an `IssueTemplate`, for registering on a handle.
+    pub fn to_issue_template(&self) -> IssueTemplate {
+        IssueTemplate::new(self.title, self.body)
+            .with_labels(self.labels.iter().map(|label| label.to_string()).collect())
+    }
+}
+
+/// Declare a `const` template table and an enum naming its templates.
+///
+/// Each entry is `NAME => (title, body, [labels...])`. The enum gets one
+/// variant per entry, named and registered as written, and these items:
+///
+/// - `ALL`, every variant in declaration order
+/// - `TABLE`, the `StaticTemplate` of every variant in the same order
+/// - `name(self)` and `template(self)`, a variant's name and template
+/// - `handle(owner, repo)`, a `TypedHandle` with every template registered
+///
+/// See the [`static_table`](crate::static_table) module for an example.
+#[macro_export]
+macro_rules! static_templates {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $enum_name:ident {
+            $($variant:ident => ($title:expr, $body:expr, [$($label:expr),* $(,)?])),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[allow(non_camel_case_types)]
+        $vis enum $enum_name {
+            $($variant),*
+        }
+
+        #[allow(dead_code)]
+        impl $enum_name {
+            /// Every template, in declaration order.
+            pub const ALL: &'static [$enum_name] = &[$($enum_name::$variant),*];
+
+            /// The template of every variant, in declaration order.
+            pub const TABLE: &'static [$crate::static_table::StaticTemplate] = &[
+                $($crate::static_table::StaticTemplate {
+                    name: stringify!($variant),
+                    title: $title,
+                    body: $body,
+                    labels: &[$($label),*],
+                }),*
+            ];
+
+            /// The name the template is registered under.
+            pub const fn name(self) -> &'static str {
+                Self::TABLE[self as usize].name
+            }
+
+            /// The template of this variant.
+            pub const fn template(self) -> &'static $crate::static_table::StaticTemplate {
+                &Self::TABLE[self as usize]
+            }
+
+            /// A handle for `owner/repo` with every template registered.
+            pub fn handle(owner: &str, repo: &str) -> $crate::typed::TypedHandle<$enum_name> {
+                let mut handle = $crate::typed::TypedHandle::new($crate::init_handle(owner, repo));
+                for variant in Self::ALL {
+                    handle = handle.add_template(*variant, variant.template().to_issue_template());
+                }
+                handle
+            }
+        }
+    };
+}
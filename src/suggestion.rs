@@ -0,0 +1,160 @@
+//! Reports that carry a proposed fix as a unified diff.
+//!
+//! Tools that detect fixable problems in a user's project, in the manner of
+//! clippy's machine-applicable suggestions, can let users file the problem
+//! upstream together with the patch. Describe each change as a
+//! [`SuggestionEdit`] and pass them to `create_suggestion` on a handle,
+//! which fills the template's `{diff}` placeholder with [`render_diff`].
+//!
+//! # Examples
+//!
+//! ````
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::suggestion::SuggestionEdit;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("lint_fix", IssueTemplate::new("Suggested fix for {lint}", "{diff}"));
+//!
+//! let edit = SuggestionEdit::new("src/main.rs", 3..4, "    let x = vec.len() == 0;\n", "    let x = vec.is_empty();\n");
+//! let mut params = Params::new();
+//! params.insert("lint", "len_zero");
+//!
+//! let report = handle.create_suggestion("lint_fix", &params, &[edit]).unwrap();
+//! assert_eq!(report.body, "\
+//! ```diff
+//! --- a/src/main.rs
+//! +++ b/src/main.rs
+//! @@ -3 +3 @@
+//! -    let x = vec.len() == 0;
+//! +    let x = vec.is_empty();
+//! ```");
+//! ````
+
+use core::fmt::Write;
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use std::{format, string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+/// Placeholder filled with the rendered diff by `create_suggestion`.
+pub const DIFF_PARAM: &str = "diff";
+
+/// A replacement of some lines of one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestionEdit {
+    /// Path of the file, relative to the project root
+    pub file: String,
+    /// Lines replaced, counting from 1, end exclusive; empty to insert before `start`
+    pub line_range: Range<usize>,
+    /// The text of the replaced lines
+    pub original: String,
+    /// The text replacing them; empty to delete the lines
+    pub replacement: String,
+}
+
+impl SuggestionEdit {
+    /// Create an edit.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - Path of the file, relative to the project root
+    /// * `line_range` - Lines replaced, counting from 1, end exclusive
+    /// * `original` - The text of the replaced lines
+    /// * `replacement` - The text replacing them
+    pub fn new(
+        file: impl Into<String>,
+        line_range: Range<usize>,
+        original: impl Into<String>,
+        replacement: impl Into<String>,
+    ) -> Self {
+        Self {
+            file: file.into(),
+            line_range,
+            original: original.into(),
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Render edits as a unified diff inside a ```` ```diff ```` fence.
+///
+/// Edits are grouped by file in the order each file first appears, and
+/// sorted by line within a file. Hunks have no context lines.
+///
+/// # Arguments
+///
+/// * `edits` - The edits to render
+///
+/// # Examples
+///
+/// ````
+/// use bug::suggestion::{render_diff, SuggestionEdit};
+///
+/// let diff = render_diff(&[
+///     SuggestionEdit::new("src/lib.rs", 20..22, "a\nb\n", "c\n"),
+///     SuggestionEdit::new("src/lib.rs", 5..5, "", "use std::fmt;\n"),
+/// ]);
+/// assert_eq!(diff, "\
+/// ```diff
+/// --- a/src/lib.rs
+/// +++ b/src/lib.rs
+/// @@ -4,0 +5 @@
+/// +use std::fmt;
+/// @@ -20,2 +21 @@
+/// -a
+/// -b
+/// +c
+/// ```");
+/// ````
+pub fn render_diff(edits: &[SuggestionEdit]) -> String {
+    let mut files: Vec<&str> = Vec::new();
+    for edit in edits {
+        if !files.contains(&edit.file.as_str()) {
+            files.push(&edit.file);
+        }
+    }
+
+    let mut output = String::from("```diff\n");
+    for file in files {
+        let mut file_edits: Vec<&SuggestionEdit> = edits.iter().filter(|edit| edit.file == file).collect();
+        file_edits.sort_by_key(|edit| edit.line_range.start);
+
+        let _ = writeln!(output, "--- a/{}\n+++ b/{}", file, file);
+        let mut offset: isize = 0;
+        for edit in file_edits {
+            let old_count = edit.line_range.len();
+            let new_count = edit.replacement.lines().count();
+            let old_start = edit.line_range.start;
+            let new_start = (old_start as isize + offset).max(1) as usize;
+            let _ = writeln!(
+                output,
+                "@@ -{} +{} @@",
+                hunk_range(old_start, old_count),
+                hunk_range(new_start, new_count),
+            );
+            for line in edit.original.lines().take(old_count) {
+                let _ = writeln!(output, "-{}", line);
+            }
+            for line in edit.replacement.lines() {
+                let _ = writeln!(output, "+{}", line);
+            }
+            offset += new_count as isize - old_count as isize;
+        }
+    }
+    output.push_str("```");
+    output
+}
+
+/// A hunk header range: `start` alone for one line, `start,count` otherwise.
+///
+/// Empty ranges name the line before the change, as `diff -u` does.
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{},0", start.saturating_sub(1)),
+        1 => format!("{}", start),
+        _ => format!("{},{}", start, count),
+    }
+}
@@ -0,0 +1,207 @@
+//! Collecting reports into one summary block.
+//!
+//! A batch job that hits the same bug thousands of times is better served
+//! by one block at the end than by a banner per occurrence. With
+//! `summarize(true)` on the configuration, reported bugs are recorded in a
+//! process-wide [`ReportAggregator`] instead of being printed, and
+//! [`flush_summary`](crate::flush_summary) prints a single block with the
+//! count per template and a few sample URLs.
+//!
+//! Reports that fail to generate are still printed immediately, since they
+//! point at a problem with the templates rather than with the program.
+
+use crate::sync::Lock;
+use crate::{BugReport, Output};
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// How many distinct URLs a summary keeps per template.
+pub const MAX_SAMPLE_URLS: usize = 3;
+
+static GLOBAL: ReportAggregator = ReportAggregator::new();
+
+/// The aggregator that summarizing configurations record into.
+pub fn global() -> &'static ReportAggregator {
+    &GLOBAL
+}
+
+/// How often one template was reported, with example URLs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSummary {
+    /// Name of the template
+    pub template_name: String,
+    /// Number of reports recorded
+    pub count: u64,
+    /// Up to `MAX_SAMPLE_URLS` distinct URLs, in the order first seen
+    pub sample_urls: Vec<String>,
+}
+
+/// Collects reports and summarizes them per template.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params, Output};
+/// use bug::summary::ReportAggregator;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("timeout", IssueTemplate::new("Timeout in {stage}", ""));
+///
+/// let aggregator = ReportAggregator::new();
+/// for stage in ["fetch", "fetch", "parse"] {
+///     let mut params = Params::new();
+///     params.insert("stage".to_string(), stage.to_string());
+///     aggregator.record(&handle.create_report("timeout", &params).unwrap());
+/// }
+///
+/// let summary = aggregator.summary();
+/// assert_eq!(summary[0].count, 3);
+/// assert_eq!(summary[0].sample_urls.len(), 2);
+///
+/// let mut output = String::new();
+/// assert_eq!(aggregator.flush(&mut output), 3);
+/// assert!(output.starts_with("🐛 BUG SUMMARY: 3 reports from 1 template\n   timeout: 3 reports\n"));
+/// assert!(aggregator.is_empty());
+/// ```
+pub struct ReportAggregator {
+    entries: Lock<Vec<TemplateSummary>>,
+}
+
+impl ReportAggregator {
+    /// Create an empty aggregator.
+    pub const fn new() -> Self {
+        Self {
+            entries: Lock::new(Vec::new()),
+        }
+    }
+
+    /// Record one report.
+    ///
+    /// Reports with an empty URL, such as those withheld for lack of
+    /// consent, are only counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `report` - The generated report
+    pub fn record(&self, report: &BugReport) {
+        self.entries.with(|entries| {
+            let index = match entries.iter().position(|entry| entry.template_name == report.template_name) {
+                Some(index) => index,
+                None => {
+                    entries.push(TemplateSummary {
+                        template_name: report.template_name.clone(),
+                        count: 0,
+                        sample_urls: Vec::new(),
+                    });
+                    entries.len() - 1
+                }
+            };
+            let entry = &mut entries[index];
+            entry.count += 1;
+            if !report.url.is_empty()
+                && entry.sample_urls.len() < MAX_SAMPLE_URLS
+                && !entry.sample_urls.contains(&report.url)
+            {
+                entry.sample_urls.push(report.url.clone());
+            }
+        });
+    }
+
+    /// Whether no reports have been recorded since the last flush.
+    pub fn is_empty(&self) -> bool {
+        self.entries.with(|entries| entries.is_empty())
+    }
+
+    /// The recorded reports per template, in the order templates were first seen.
+    pub fn summary(&self) -> Vec<TemplateSummary> {
+        self.entries.with(|entries| entries.clone())
+    }
+
+    /// Write the summary block to `output` and clear the recorded reports.
+    ///
+    /// Nothing is written if no reports were recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - Where to write the summary
+    ///
+    /// # Returns
+    ///
+    /// The number of reports summarized.
+    pub fn flush(&self, output: &mut dyn Output) -> u64 {
+        let entries = self.entries.with(core::mem::take);
+        let total: u64 = entries.iter().map(|entry| entry.count).sum();
+        if total == 0 {
+            return 0;
+        }
+
+        output.write_fmt(format_args!(
+            "🐛 BUG SUMMARY: {} {} from {} {}\n",
+            total,
+            plural(total, "report", "reports"),
+            entries.len(),
+            plural(entries.len() as u64, "template", "templates"),
+        ));
+        for entry in &entries {
+            output.write_fmt(format_args!(
+                "   {}: {} {}\n",
+                entry.template_name,
+                entry.count,
+                plural(entry.count, "report", "reports"),
+            ));
+            for url in &entry.sample_urls {
+                output.write_fmt(format_args!("     {}\n", url));
+            }
+        }
+        output.write_str("\n");
+        total
+    }
+}
+
+impl Default for ReportAggregator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for ReportAggregator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReportAggregator")
+            .field("entries", &self.summary())
+            .finish()
+    }
+}
+
+fn plural(count: u64, one: &'static str, many: &'static str) -> &'static str {
+    if count == 1 { one } else { many }
+}
+
+/// Flushes collected reports to stderr when dropped (std only).
+///
+/// Returned by [`flush_on_exit`](crate::flush_on_exit). The guard also
+/// flushes while unwinding from a panic, so reports collected before the
+/// panic are not lost.
+#[cfg(feature = "std")]
+#[must_use = "the summary is flushed when the guard is dropped, so bind it with `let _guard = ...`"]
+#[derive(Debug)]
+pub struct FlushGuard {
+    _private: (),
+}
+
+#[cfg(feature = "std")]
+impl FlushGuard {
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        crate::flush_summary();
+    }
+}
@@ -0,0 +1,235 @@
+//! Suppressing reports for known-benign conditions.
+//!
+//! Rather than wrapping call sites in ad-hoc `if`s, conditions that should
+//! never produce a report are registered on the configuration, either as
+//! predicates with `suppress_if` or as declarative [`SuppressionRule`]s with
+//! `suppress`. Rules can also be kept in a text file and loaded with
+//! [`SuppressionRule::parse_rules`].
+//!
+//! Suppression applies where bugs are reported (`bug!`, `report_bug_at`,
+//! `Reporter::report`, and friends) and sees the parameters given at the
+//! call site. `create_report` and `generate_url` are never suppressed.
+
+use crate::{glob, Params};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+/// A declarative condition under which reports are suppressed.
+///
+/// A rule matches when its template glob matches the template name and
+/// every parameter glob matches the value of that parameter. A parameter
+/// missing from the report never matches. In globs, `*` matches any run of
+/// characters and `?` matches one.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, Params, Location};
+/// use bug::suppress::SuppressionRule;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("db_error", IssueTemplate::new("Database error", "{message}"))
+///     .suppress(SuppressionRule::new("db_*").with_param("message", "*connection reset*"));
+///
+/// let mut params = Params::new();
+/// params.insert("message".to_string(), "read failed: connection reset by peer".to_string());
+/// assert!(handle.report_bug_at("db_error", &params, Location::new(file!(), line!())).is_empty());
+///
+/// params.insert("message".to_string(), "constraint violated".to_string());
+/// assert!(!handle.report_bug_at("db_error", &params, Location::new(file!(), line!())).is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuppressionRule {
+    /// Glob matched against the template name
+    pub template: String,
+    /// Parameter names and the globs their values must match
+    pub params: Vec<(String, String)>,
+}
+
+impl SuppressionRule {
+    /// Create a rule matching every report from templates matching `template`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - Glob matched against the template name
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            params: Vec::new(),
+        }
+    }
+
+    /// Also require a parameter's value to match a glob.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the parameter
+    /// * `value` - Glob matched against the parameter's value
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Whether a report from `template_name` with `params` is suppressed by this rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template being reported
+    /// * `params` - Parameters given at the call site
+    pub fn matches(&self, template_name: &str, params: &Params) -> bool {
+        glob::matches(&self.template, template_name)
+            && self.params.iter().all(|(name, pattern)| {
+                params
+                    .get(name)
+                    .is_some_and(|value| glob::matches(pattern, value))
+            })
+    }
+
+    /// Parse suppression rules from text, one rule per line.
+    ///
+    /// Each line is a template glob followed by any number of
+    /// `param=glob` matchers, separated by whitespace. Values containing
+    /// whitespace can be written in double quotes, with `\"` and `\\` as
+    /// escapes. Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The rules, usually from `include_str!`
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<SuppressionRule>)` - The rules in order
+    /// * `Err(String)` - The first malformed line and what is wrong with it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::suppress::SuppressionRule;
+    ///
+    /// let rules = SuppressionRule::parse_rules(r#"
+    ///     ## Flaky network on CI runners
+    ///     net_*          message="*timed out*" host=ci-*
+    ///     shutdown_race
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(rules.len(), 2);
+    /// assert_eq!(rules[0], SuppressionRule::new("net_*")
+    ///     .with_param("message", "*timed out*")
+    ///     .with_param("host", "ci-*"));
+    ///
+    /// let error = SuppressionRule::parse_rules("net_* message").unwrap_err();
+    /// assert_eq!(error, "line 1: expected 'param=glob', found 'message'");
+    /// ```
+    pub fn parse_rules(text: &str) -> Result<Vec<Self>, String> {
+        let mut rules = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens = tokenize(line).map_err(|e| format!("line {}: {}", line_number, e))?;
+            let mut tokens = tokens.into_iter();
+            let Some(template) = tokens.next() else {
+                continue;
+            };
+            let mut rule = SuppressionRule::new(template);
+            for token in tokens {
+                match token.split_once('=') {
+                    Some((name, value)) if !name.is_empty() => {
+                        rule.params.push((name.to_string(), value.to_string()));
+                    }
+                    _ => return Err(format!("line {}: expected 'param=glob', found '{}'", line_number, token)),
+                }
+            }
+            rules.push(rule);
+        }
+        Ok(rules)
+    }
+}
+
+/// Split a rule line on whitespace, honoring double quotes.
+fn tokenize(line: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut in_quotes = false;
+    let mut chars = line.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                in_token = true;
+            }
+            '\\' if in_quotes => match chars.next() {
+                Some(escaped) => current.push(escaped),
+                None => return Err("unterminated escape".to_string()),
+            },
+            ch if ch.is_whitespace() && !in_quotes => {
+                if in_token {
+                    tokens.push(core::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err("unclosed '\"'".to_string());
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+type Predicate = dyn Fn(&str, &Params) -> bool + Send + Sync;
+
+/// The rules and predicates registered on a configuration.
+#[derive(Clone, Default)]
+pub(crate) struct Suppressions {
+    pub(crate) rules: Vec<SuppressionRule>,
+    predicates: Vec<Arc<Predicate>>,
+}
+
+impl Suppressions {
+    pub(crate) fn add_predicate(&mut self, predicate: impl Fn(&str, &Params) -> bool + Send + Sync + 'static) {
+        self.predicates.push(Arc::new(predicate));
+    }
+
+    /// Whether any rule or predicate suppresses the report.
+    pub(crate) fn suppresses(&self, template_name: &str, params: &Params) -> bool {
+        self.rules.iter().any(|rule| rule.matches(template_name, params))
+            || self.predicates.iter().any(|predicate| predicate(template_name, params))
+    }
+}
+
+impl fmt::Debug for Suppressions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Suppressions")
+            .field("rules", &self.rules)
+            .field("predicates", &self.predicates.len())
+            .finish()
+    }
+}
@@ -0,0 +1,207 @@
+//! Interior mutability that works in both std and no_std builds.
+//!
+//! With `std` this is a `std::sync::Mutex`; without it, a
+//! `critical_section::Mutex`, so no_std users must provide a
+//! critical-section implementation for their target.
+//!
+//! Built with `--cfg loom`, `OnceSlot` uses loom's atomics, cell, and a
+//! loom mutex for the critical section, so its tests can explore every
+//! interleaving of concurrent `set` and `get` calls:
+//!
+//! ```text
+//! RUSTFLAGS="--cfg loom" cargo test --lib --release --no-default-features --features global-nostd sync::
+//! ```
+
+#[cfg(not(feature = "std"))]
+use core::cell::RefCell;
+
+#[cfg(all(not(feature = "std"), feature = "global-nostd", not(loom)))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(all(not(feature = "std"), feature = "global-nostd", loom))]
+use loom::sync::atomic::{AtomicBool, Ordering};
+
+/// A lock around shared state, accessed through a closure.
+pub(crate) struct Lock<T> {
+    #[cfg(feature = "std")]
+    inner: std::sync::Mutex<T>,
+    #[cfg(not(feature = "std"))]
+    inner: critical_section::Mutex<RefCell<T>>,
+}
+
+impl<T> Lock<T> {
+    /// Create a lock holding `value`.
+    pub(crate) const fn new(value: T) -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            inner: std::sync::Mutex::new(value),
+            #[cfg(not(feature = "std"))]
+            inner: critical_section::Mutex::new(RefCell::new(value)),
+        }
+    }
+
+    /// Run `f` with exclusive access to the value.
+    ///
+    /// A poisoned std mutex is recovered rather than propagated: reporting a
+    /// bug must not fail because an earlier report panicked.
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        #[cfg(feature = "std")]
+        {
+            let mut guard = self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            f(&mut guard)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            critical_section::with(|cs| f(&mut self.inner.borrow_ref_mut(cs)))
+        }
+    }
+}
+
+/// A value set at most once and then shared for the rest of the program.
+///
+/// This is the global configuration of no_std builds, in place of a
+/// `static mut`. Setting runs in a critical section, so two calls can't
+/// both write, and readers only see the value after it is completely
+/// written. Only atomic loads and stores are needed, which every target
+/// `critical_section` supports has.
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+pub(crate) struct OnceSlot<T> {
+    ready: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+    /// Stands in for the critical section, which loom can't see into
+    #[cfg(loom)]
+    critical_section: loom::sync::Mutex<()>,
+}
+
+// SAFETY: the value is written once, inside a critical section and before
+// `ready` is released, and only read through shared references after that.
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+unsafe impl<T: Send + Sync> Sync for OnceSlot<T> {}
+
+#[cfg(all(not(feature = "std"), feature = "global-nostd"))]
+impl<T> OnceSlot<T> {
+    /// Create an empty slot.
+    #[cfg(not(loom))]
+    pub(crate) const fn new() -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Create an empty slot.
+    #[cfg(loom)]
+    pub(crate) fn new() -> Self {
+        Self {
+            ready: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+            critical_section: loom::sync::Mutex::new(()),
+        }
+    }
+
+    /// Store `value`, or give it back if the slot is already set.
+    pub(crate) fn set(&self, value: T) -> Result<(), T> {
+        self.critical_section(|| {
+            if self.ready.load(Ordering::Acquire) {
+                return Err(value);
+            }
+            // SAFETY: `ready` is still false, so no reader holds a reference,
+            // and the critical section keeps other writers out.
+            self.value.with_mut(|slot| unsafe { *slot = Some(value) });
+            self.ready.store(true, Ordering::Release);
+            Ok(())
+        })
+    }
+
+    /// The value, once it has been set.
+    pub(crate) fn get(&self) -> Option<&T> {
+        if self.ready.load(Ordering::Acquire) {
+            // SAFETY: once `ready` is set the value is never written again.
+            self.value.with(|slot| unsafe { (*slot).as_ref() })
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(loom))]
+    fn critical_section<R>(&self, f: impl FnOnce() -> R) -> R {
+        critical_section::with(|_| f())
+    }
+
+    #[cfg(loom)]
+    fn critical_section<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.critical_section.lock().unwrap();
+        f()
+    }
+}
+
+/// `core::cell::UnsafeCell` with the closure-based access of loom's, so
+/// `OnceSlot` is the same code with and without loom.
+#[cfg(all(not(feature = "std"), feature = "global-nostd", not(loom)))]
+struct UnsafeCell<T>(core::cell::UnsafeCell<T>);
+
+#[cfg(all(not(feature = "std"), feature = "global-nostd", not(loom)))]
+impl<T> UnsafeCell<T> {
+    const fn new(value: T) -> Self {
+        Self(core::cell::UnsafeCell::new(value))
+    }
+
+    fn with<R>(&self, f: impl FnOnce(*const T) -> R) -> R {
+        f(self.0.get())
+    }
+
+    fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+        f(self.0.get())
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "global-nostd", loom))]
+use loom::cell::UnsafeCell;
+
+#[cfg(all(test, loom, not(feature = "std"), feature = "global-nostd"))]
+mod loom_tests {
+    use super::OnceSlot;
+    use loom::sync::Arc;
+    use loom::thread;
+
+    #[test]
+    fn only_one_set_wins() {
+        loom::model(|| {
+            let slot = Arc::new(OnceSlot::new());
+            let other = {
+                let slot = slot.clone();
+                thread::spawn(move || slot.set(1).is_ok())
+            };
+            let won_here = slot.set(0).is_ok();
+            let won_there = other.join().unwrap();
+            assert_ne!(won_here, won_there);
+            assert_eq!(slot.get(), Some(if won_here { &0 } else { &1 }));
+        });
+    }
+
+    #[test]
+    fn get_sees_nothing_or_the_whole_value() {
+        loom::model(|| {
+            let slot = Arc::new(OnceSlot::new());
+            let reader = {
+                let slot = slot.clone();
+                thread::spawn(move || slot.get().cloned())
+            };
+            assert_eq!(slot.set(alloc::vec![1, 2, 3]), Ok(()));
+            if let Some(value) = reader.join().unwrap() {
+                assert_eq!(value, [1, 2, 3]);
+            }
+            assert_eq!(slot.get().map(|value| value.len()), Some(3));
+        });
+    }
+
+    #[test]
+    fn set_after_set_gives_the_value_back() {
+        loom::model(|| {
+            let slot = OnceSlot::new();
+            assert_eq!(slot.set(1), Ok(()));
+            assert_eq!(slot.set(2), Err(2));
+            assert_eq!(slot.get(), Some(&1));
+        });
+    }
+}
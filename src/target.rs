@@ -0,0 +1,151 @@
+//! Filing one template's reports somewhere other than the handle's repository.
+//!
+//! In a monorepo one handle often covers components tracked in different
+//! places: the CLI's bugs go to one repository, the server's to another,
+//! and the enterprise build's to a self-hosted GitHub. A [`TemplateTarget`]
+//! registered with `template_target` overrides, for one template, the
+//! repository reports are filed in, the host their URLs point at, and the
+//! hyperlink mode of their banners.
+//!
+//! Targets are resolved when a report is generated, for the template the
+//! report is made from, which is the fallback's when a template falls
+//! back. Each setting is taken from the first of:
+//!
+//! 1. the template's target, if it sets it
+//! 2. the handle, as set with `init_handle`, `base_url`, and `hyperlinks`
+//! 3. the default: `github::HOST`, `https://github.com`, for the host
+//!
+//! Other links, such as the issue chooser for an unknown template name,
+//! use the handle's settings. Only GitHub's issue form is supported
+//! as a tracker, so a host has to serve the same `/issues/new` URLs.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, HyperlinkMode, IssueTemplate, Params, TemplateTarget};
+//!
+//! let handle = init_handle("acme", "monorepo")
+//!     .add_template("cli_crash", IssueTemplate::new("CLI crash", ""))
+//!     .add_template("server_crash", IssueTemplate::new("Server crash", ""))
+//!     .template_target(
+//!         "server_crash",
+//!         TemplateTarget::new()
+//!             .repository("acme-infra", "server")
+//!             .host("https://github.acme.example")
+//!             .hyperlinks(HyperlinkMode::Never),
+//!     );
+//!
+//! let cli = handle.create_report("cli_crash", &Params::new()).unwrap();
+//! assert!(cli.url.starts_with("https://github.com/acme/monorepo/issues/new?"));
+//! let server = handle.create_report("server_crash", &Params::new()).unwrap();
+//! assert!(server.url.starts_with("https://github.acme.example/acme-infra/server/issues/new?"));
+//! ```
+
+use crate::HyperlinkMode;
+
+#[cfg(feature = "std")]
+use std::{format, string::String};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+/// Where one template's reports are filed, overriding the handle's settings.
+///
+/// Settings left unset fall back to the handle's; see the module
+/// documentation for the precedence.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateTarget {
+    /// The repository owner and name, or `None` for the handle's
+    pub repository: Option<(String, String)>,
+    /// The host issue URLs start with, such as `https://github.example.com`, or `None` for the handle's
+    pub host: Option<String>,
+    /// How the banner shows the URL, or `None` for the handle's
+    pub hyperlinks: Option<HyperlinkMode>,
+}
+
+impl TemplateTarget {
+    /// A target overriding nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// File the reports in another repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner` - GitHub username or organization
+    /// * `repo` - GitHub repository name
+    pub fn repository(mut self, owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        self.repository = Some((owner.into(), repo.into()));
+        self
+    }
+
+    /// Point the issue URLs at another host, such as a GitHub Enterprise server.
+    ///
+    /// # Arguments
+    ///
+    /// * `host` - Scheme and host, such as `https://github.example.com`; a trailing `/` is ignored
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        let host: String = host.into();
+        self.host = Some(String::from(host.trim_end_matches('/')));
+        self
+    }
+
+    /// Show the URLs in banners with this hyperlink mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - The hyperlink mode for this template's banners
+    pub fn hyperlinks(mut self, mode: HyperlinkMode) -> Self {
+        self.hyperlinks = Some(mode);
+        self
+    }
+}
+
+/// Check that `url` can start issue URLs: `https://` or `http://`, then a
+/// host name or bracketed IPv6 address with an optional port and path, and
+/// no query or fragment.
+pub(crate) fn check_base_url(url: &str) -> Result<(), String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| format!("Base URL '{}' must start with https:// or http://", url))?;
+    if let Some(c) = url.chars().find(|c| c.is_whitespace() || c.is_control() || matches!(c, '?' | '#')) {
+        return Err(format!("Base URL '{}' can't contain {:?}", url, c));
+    }
+    let authority = rest.split('/').next().unwrap_or("");
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(bracketed) => {
+            let (address, after) = bracketed
+                .split_once(']')
+                .ok_or_else(|| format!("Base URL '{}' has an unclosed IPv6 address", url))?;
+            if address.parse::<core::net::Ipv6Addr>().is_err() {
+                return Err(format!("Base URL '{}' has an invalid IPv6 address '{}'", url, address));
+            }
+            let port = match after {
+                "" => None,
+                after => Some(after.strip_prefix(':').ok_or_else(|| format!("Base URL '{}' has an invalid port", url))?),
+            };
+            (None, port)
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (Some(host), Some(port)),
+            None => (Some(authority), None),
+        },
+    };
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if let Some(host) = host
+        && !host.split('.').all(valid_label)
+    {
+        return Err(format!("Base URL '{}' has an invalid host '{}'", url, host));
+    }
+    if port.is_some_and(|port| port.parse::<u16>().is_err()) {
+        return Err(format!("Base URL '{}' has an invalid port", url));
+    }
+    Ok(())
+}
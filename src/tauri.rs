@@ -0,0 +1,115 @@
+//! Command handlers for Tauri and other webview frontends (requires the `tauri` feature).
+//!
+//! Webview frontends send the template name and a JSON object of parameters.
+//! The handlers here convert that object into template parameters, run it
+//! through a configured [`BugReportHandle`], and return the issue URL.
+//!
+//! Tauri commands must be declared in the application crate (they are
+//! registered with `tauri::generate_handler!` alongside the app's own state),
+//! so this module exposes plain functions for a one-line command wrapper:
+//!
+//! ```ignore
+//! #[tauri::command]
+//! fn report_bug(
+//!     handle: tauri::State<'_, bug::BugReportHandle>,
+//!     template: String,
+//!     params: serde_json::Value,
+//! ) -> Result<String, String> {
+//!     bug::tauri::report_bug(&handle, &template, params)
+//! }
+//! ```
+//!
+//! On the frontend this is invoked as
+//! `invoke("report_bug", { template: "crash", params: { error: "..." } })`.
+
+use crate::param::ParamHasher;
+use crate::{browser, BugReportHandle, Params};
+use serde_json::Value;
+
+/// Generate an issue URL from a frontend request.
+///
+/// # Arguments
+///
+/// * `handle` - The configured handle to generate the report with
+/// * `template` - Name of the template to use
+/// * `params` - A JSON object of parameters (or `null` for none)
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated GitHub issue URL
+/// * `Err(String)` - The parameters were not an object, or generation failed
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate};
+/// use serde_json::json;
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash: {error}", "Code {code}"));
+///
+/// let url = bug::tauri::report_bug(&handle, "crash", json!({
+///     "error": "render failed",
+///     "code": 3,
+/// })).unwrap();
+/// assert!(url.contains("title=Crash%3A+render+failed"));
+/// assert!(url.contains("body=Code%203"));
+///
+/// assert!(bug::tauri::report_bug(&handle, "crash", json!(["not", "an", "object"])).is_err());
+/// ```
+pub fn report_bug(handle: &BugReportHandle, template: &str, params: Value) -> Result<String, String> {
+    let params = json_to_params(params)?;
+    handle.generate_url(template, &params)
+}
+
+/// Generate an issue URL from a frontend request and open it in the browser.
+///
+/// # Arguments
+///
+/// * `handle` - The configured handle to generate the report with
+/// * `template` - Name of the template to use
+/// * `params` - A JSON object of parameters (or `null` for none)
+///
+/// # Returns
+///
+/// * `Ok(String)` - The generated URL, after the browser was launched
+/// * `Err(String)` - Generation failed or the browser could not be opened
+pub fn report_bug_and_open(handle: &BugReportHandle, template: &str, params: Value) -> Result<String, String> {
+    let url = report_bug(handle, template, params)?;
+    browser::open(&url).map_err(|e| format!("Failed to open browser: {}", e))?;
+    Ok(url)
+}
+
+/// Convert a JSON object into template parameters.
+///
+/// Strings are used as-is, `null` becomes an empty string, and numbers,
+/// booleans, arrays, and nested objects use their JSON text. The keys come
+/// from the webview, so the parameters hash them with random SipHash keys.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let params = bug::tauri::json_to_params(json!({ "name": "parser", "retries": 2 })).unwrap();
+/// assert_eq!(params.get("name"), Some("parser"));
+/// assert_eq!(params.get("retries"), Some("2"));
+/// ```
+pub fn json_to_params(params: Value) -> Result<Params, String> {
+    let object = match params {
+        Value::Object(object) => object,
+        Value::Null => return Ok(Params::new()),
+        other => return Err(format!("Bug report parameters must be a JSON object, got {}", other)),
+    };
+
+    let mut converted = Params::with_hasher(ParamHasher::random());
+    converted.extend(object.into_iter().map(|(key, value)| {
+        let value = match value {
+            Value::String(s) => s,
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        (key, value)
+    }));
+    Ok(converted)
+}
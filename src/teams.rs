@@ -0,0 +1,112 @@
+//! Assigning reports to teams.
+//!
+//! Teams are named lists of GitHub users registered on the configuration.
+//! A template that calls `assign_team` has the team expanded into issue
+//! assignees when a report is generated, either the whole team or one
+//! member at a time in rotation.
+
+use crate::sync::Lock;
+use crate::FxHashMap;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// The most assignees GitHub accepts on one issue.
+///
+/// Assignees past this limit are dropped from generated reports.
+pub const MAX_ASSIGNEES: usize = 10;
+
+/// How a team is expanded into assignees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TeamAssignment {
+    /// Assign every member of the team.
+    #[default]
+    All,
+    /// Assign one member per report, cycling through the team.
+    RoundRobin,
+}
+
+/// Per-team position of the next round-robin assignee.
+pub(crate) struct Rotation {
+    next: Lock<FxHashMap<String, usize>>,
+}
+
+impl Rotation {
+    pub(crate) fn new() -> Self {
+        Self {
+            next: Lock::new(FxHashMap::default()),
+        }
+    }
+
+    /// Take the next member of `team`, advancing the rotation.
+    fn take<'a>(&self, team: &str, members: &'a [String]) -> Option<&'a String> {
+        if members.is_empty() {
+            return None;
+        }
+        let index = self.next.with(|next| {
+            let slot = next.entry_ref(team).or_insert(0);
+            let index = *slot % members.len();
+            *slot = index + 1;
+            index
+        });
+        members.get(index)
+    }
+}
+
+impl Clone for Rotation {
+    fn clone(&self) -> Self {
+        Self {
+            next: Lock::new(self.next.with(|next| next.clone())),
+        }
+    }
+}
+
+impl fmt::Debug for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rotation").finish_non_exhaustive()
+    }
+}
+
+/// Add the members of `team_names` to `assignees`, without duplicates and
+/// up to `MAX_ASSIGNEES` in total.
+///
+/// Unknown teams contribute no assignees; `BugReportConfig::validate`
+/// reports them. Returns how many assignees were dropped over the limit.
+pub(crate) fn expand(
+    teams: &FxHashMap<String, Vec<String>>,
+    team_names: &[String],
+    mode: TeamAssignment,
+    rotation: &Rotation,
+    assignees: &mut Vec<String>,
+) -> usize {
+    for team_name in team_names {
+        let Some(members) = teams.get(team_name) else {
+            continue;
+        };
+        match mode {
+            TeamAssignment::All => {
+                for member in members {
+                    push_unique(assignees, member);
+                }
+            }
+            TeamAssignment::RoundRobin => {
+                if let Some(member) = rotation.take(team_name, members) {
+                    push_unique(assignees, member);
+                }
+            }
+        }
+    }
+    let dropped = assignees.len().saturating_sub(MAX_ASSIGNEES);
+    assignees.truncate(MAX_ASSIGNEES);
+    dropped
+}
+
+fn push_unique(assignees: &mut Vec<String>, member: &String) {
+    if !assignees.contains(member) {
+        assignees.push(member.clone());
+    }
+}
@@ -0,0 +1,78 @@
+//! Bulk registration of templates embedded with [`include_dir`](https://docs.rs/include_dir)
+//! (requires the `include_dir` feature).
+//!
+//! Every UTF-8 file in the embedded directory becomes a [`TemplateFile`],
+//! named after its path relative to the directory without the extension:
+//! `templates/crash.md` is registered as `crash` and
+//! `templates/net/timeout.md` as `net/timeout`. Files whose name starts with
+//! a dot (such as `.gitkeep`) and files that are not valid UTF-8 are skipped.
+//!
+//! The `include_dir!` macro must come from the same `include_dir` version as
+//! this crate uses, which is re-exported as `bug::include_dir`.
+//!
+//! # Examples
+//!
+//! ```
+//! use include_dir::{include_dir, Dir};
+//!
+//! static TEMPLATES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+//!
+//! let handle = bug::init_handle("owner", "repo").add_template_dir(&TEMPLATES);
+//! assert!(handle.template_id("crash_report").is_some());
+//! ```
+
+use crate::TemplateFile;
+use include_dir::{Dir, DirEntry};
+
+/// Collect `(name, template)` pairs for every template file in `dir`, recursively.
+///
+/// Entries are returned sorted by name so registration order (and thus
+/// template ids) is stable across builds.
+///
+/// # Examples
+///
+/// ```
+/// use include_dir::{include_dir, Dir};
+///
+/// static TEMPLATES: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/templates");
+///
+/// let names: Vec<String> = bug::template_dir::templates(&TEMPLATES)
+///     .into_iter()
+///     .map(|(name, _)| name)
+///     .collect();
+/// assert_eq!(names, vec!["crash_report".to_string(), "performance_issue".to_string()]);
+/// ```
+pub fn templates(dir: &'static Dir<'static>) -> Vec<(String, TemplateFile)> {
+    let mut templates = Vec::new();
+    collect(dir, &mut templates);
+    templates.sort_by(|a, b| a.0.cmp(&b.0));
+    templates
+}
+
+fn collect(dir: &'static Dir<'static>, templates: &mut Vec<(String, TemplateFile)>) {
+    for entry in dir.entries() {
+        match entry {
+            DirEntry::Dir(subdir) => collect(subdir, templates),
+            DirEntry::File(file) => {
+                let path = file.path();
+                let is_hidden = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_none_or(|name| name.starts_with('.'));
+                if is_hidden {
+                    continue;
+                }
+
+                let (Some(name), Some(content)) = (path.with_extension("").to_str().map(normalize), file.contents_utf8()) else {
+                    continue;
+                };
+                templates.push((name, TemplateFile::new(content)));
+            }
+        }
+    }
+}
+
+/// Use `/` as the separator regardless of the host platform.
+fn normalize(name: &str) -> String {
+    name.replace('\\', "/")
+}
@@ -0,0 +1,239 @@
+//! Helpers for snapshot-testing templates.
+//!
+//! Rendering every registered template in a test and comparing the result
+//! against a checked-in snapshot catches accidental template regressions in
+//! CI: a changed placeholder, a lost label, or a reworded title all show up
+//! as a reviewable diff.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash: {error}", "Error {error} in {module}"));
+//!
+//! let mut samples = Params::new();
+//! samples.insert("error".to_string(), "EOF".to_string());
+//!
+//! let snapshot = bug::testing::snapshot(&handle, &samples).unwrap();
+//! assert_eq!(snapshot, "\
+//! === crash ===
+//! title: Crash: EOF
+//! body:
+//! Error EOF in <module>
+//! url: https://github.com/owner/repo/issues/new?title=Crash%3A+EOF&body=Error%20EOF%20in%20%3Cmodule%3E
+//! ");
+//! ```
+
+use crate::sync::Lock;
+use crate::{BugReportHandle, Location, Params, ReportOutcome, Reporter};
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+    format,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+    format,
+};
+
+/// The rendered output of one template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedTemplate {
+    /// The filled issue title
+    pub title: String,
+    /// The filled issue body
+    pub body: String,
+    /// The generated issue URL
+    pub url: String,
+}
+
+/// Render every template registered on `handle`.
+///
+/// Each template receives the entries of `sample_params` matching its
+/// placeholders. Placeholders without a sample value are filled with
+/// `<name>`, so every template renders even when the samples are incomplete
+/// and unfilled placeholders remain visible in the snapshot.
+///
+/// # Arguments
+///
+/// * `handle` - The handle whose templates to render
+/// * `sample_params` - Representative parameter values shared by all templates
+///
+/// # Returns
+///
+/// * `Ok(BTreeMap)` - Rendered output keyed by template name
+/// * `Err(String)` - The first template that failed to render, with its name
+///
+/// # Examples
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate, TemplateFile, Params};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash: {error}", ""))
+///     .add_template_file("perf", TemplateFile::new("Slow {operation}\nTook {ms}ms"));
+///
+/// let mut samples = Params::new();
+/// samples.insert("error".to_string(), "EOF".to_string());
+/// samples.insert("operation".to_string(), "query".to_string());
+///
+/// let rendered = bug::testing::render_all(&handle, &samples).unwrap();
+/// assert_eq!(rendered["crash"].title, "Crash: EOF");
+/// assert_eq!(rendered["perf"].body, "Took <ms>ms");
+/// ```
+pub fn render_all(
+    handle: &BugReportHandle,
+    sample_params: &Params,
+) -> Result<BTreeMap<String, RenderedTemplate>, String> {
+    let config = handle.config();
+    let mut rendered = BTreeMap::new();
+
+    for name in config.template_names() {
+        let params: Params = config
+            .placeholders(name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|placeholder| {
+                let value = sample_params
+                    .get(&placeholder)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("<{}>", placeholder));
+                (placeholder, value)
+            })
+            .collect();
+
+        let report = handle
+            .create_report(name, &params)
+            .map_err(|e| format!("Template '{}' failed to render: {}", name, e))?;
+        rendered.insert(
+            name.to_string(),
+            RenderedTemplate {
+                title: report.title,
+                body: report.body,
+                url: report.url,
+            },
+        );
+    }
+
+    Ok(rendered)
+}
+
+/// Render every template into one diff-friendly text block.
+///
+/// Templates appear in name order, each as a `=== name ===` header followed
+/// by its title, body, and URL. Store the result as a golden file and
+/// compare against it in tests.
+///
+/// # Arguments
+///
+/// * `handle` - The handle whose templates to render
+/// * `sample_params` - Representative parameter values shared by all templates
+pub fn snapshot(handle: &BugReportHandle, sample_params: &Params) -> Result<String, String> {
+    let mut out = String::new();
+    for (name, rendered) in render_all(handle, sample_params)? {
+        out.push_str(&format!(
+            "=== {} ===\ntitle: {}\nbody:\n{}\nurl: {}\n",
+            name, rendered.title, rendered.body, rendered.url
+        ));
+    }
+    Ok(out)
+}
+
+/// One call recorded by a [`RecordingReporter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedReport {
+    /// Name of the template that was reported
+    pub template: String,
+    /// Parameters passed with the report
+    pub params: Params,
+    /// Where the report was made
+    pub location: Location,
+}
+
+/// A [`Reporter`] that records every report instead of generating it.
+///
+/// Pass it where code under test expects a `&dyn Reporter`, then assert on
+/// [`reports`](Self::reports). Every report is answered with
+/// `ReportOutcome::Suppressed`.
+///
+/// # Examples
+///
+/// ```
+/// use bug::{Location, Params, Reporter};
+/// use bug::testing::RecordingReporter;
+///
+/// fn check(value: i32, reporter: &dyn Reporter) {
+///     if value < 0 {
+///         let mut params = Params::default();
+///         params.insert("value".to_string(), value.to_string());
+///         reporter.report("negative", &params, Location::new(file!(), line!()));
+///     }
+/// }
+///
+/// let reporter = RecordingReporter::new();
+/// check(1, &reporter);
+/// check(-1, &reporter);
+///
+/// let reports = reporter.reports();
+/// assert_eq!(reports.len(), 1);
+/// assert_eq!(reports[0].template, "negative");
+/// assert_eq!(reports[0].params.get("value"), Some("-1"));
+/// ```
+pub struct RecordingReporter {
+    reports: Lock<Vec<RecordedReport>>,
+}
+
+impl RecordingReporter {
+    /// Create a reporter with no recorded reports.
+    pub const fn new() -> Self {
+        Self {
+            reports: Lock::new(Vec::new()),
+        }
+    }
+
+    /// The reports recorded so far, in order.
+    pub fn reports(&self) -> Vec<RecordedReport> {
+        self.reports.with(|reports| reports.clone())
+    }
+
+    /// Forget all recorded reports.
+    pub fn clear(&self) {
+        self.reports.with(|reports| reports.clear());
+    }
+}
+
+impl Reporter for RecordingReporter {
+    fn report(&self, template: &str, params: &Params, loc: Location) -> ReportOutcome {
+        let recorded = RecordedReport {
+            template: template.to_string(),
+            params: params.clone(),
+            location: loc,
+        };
+        self.reports.with(|reports| reports.push(recorded));
+        ReportOutcome::Suppressed
+    }
+}
+
+impl Default for RecordingReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for RecordingReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingReporter")
+            .field("reports", &self.reports())
+            .finish()
+    }
+}
@@ -0,0 +1,167 @@
+//! Timestamps for the `{timestamp}` placeholder.
+//!
+//! Embedded targets rarely have a real-time clock, but a tick counter is
+//! nearly always at hand, and knowing that a bug happened 12345 seconds
+//! after boot is still useful in a report. A [`Clock`] set on the
+//! configuration with `clock` is read for every report whose template has
+//! a `{timestamp}` placeholder, and the [`Timestamp`] it returns is
+//! written as `T+12345.678s`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params, Location};
+//! use bug::time::Timestamp;
+//!
+//! // A 32.768 kHz tick counter, as on many microcontrollers.
+//! fn read_ticks() -> u64 {
+//!     404_543_210
+//! }
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("watchdog", IssueTemplate::new("Watchdog fired", "At {timestamp}"))
+//!     .clock(|| Timestamp::from_ticks(read_ticks(), 32_768));
+//!
+//! let url = handle.report_bug_at("watchdog", &Params::new(), Location::new(file!(), line!()));
+//! assert!(url.contains("body=At%20T%2B12345.679s"));
+//! ```
+
+use core::fmt;
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
+/// Time elapsed since a fixed point such as boot, without a calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Timestamp {
+    elapsed: Duration,
+}
+
+impl Timestamp {
+    /// A timestamp from a tick counter.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticks` - Ticks counted since the reference point
+    /// * `tick_hz` - Ticks per second; 0 is treated as 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::time::Timestamp;
+    ///
+    /// assert_eq!(Timestamp::from_ticks(12_345_678, 1_000).to_string(), "T+12345.678s");
+    /// assert_eq!(Timestamp::from_ticks(3, 4).to_string(), "T+0.750s");
+    /// ```
+    pub const fn from_ticks(ticks: u64, tick_hz: u64) -> Self {
+        let tick_hz = if tick_hz == 0 { 1 } else { tick_hz };
+        let secs = ticks / tick_hz;
+        let nanos = ((ticks % tick_hz) as u128 * 1_000_000_000 / tick_hz as u128) as u32;
+        Self {
+            elapsed: Duration::new(secs, nanos),
+        }
+    }
+
+    /// A timestamp from elapsed time.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - Time since the reference point
+    pub const fn from_duration(elapsed: Duration) -> Self {
+        Self { elapsed }
+    }
+
+    /// Time since the reference point.
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// Written as `T+<seconds>.<milliseconds>s`.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "T+{}.{:03}s", self.elapsed.as_secs(), self.elapsed.subsec_millis())
+    }
+}
+
+/// A source of timestamps for reports.
+///
+/// Implemented for closures returning a [`Timestamp`].
+pub trait Clock: Send + Sync {
+    /// The current time.
+    fn now(&self) -> Timestamp;
+}
+
+impl<F: Fn() -> Timestamp + Send + Sync> Clock for F {
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
+
+/// Time since the clock was created (std only).
+///
+/// # Examples
+///
+/// ```
+/// use bug::time::{Clock, MonotonicClock};
+///
+/// let clock = MonotonicClock::new();
+/// assert!(clock.now().to_string().starts_with("T+0."));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct MonotonicClock {
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl MonotonicClock {
+    /// Start a clock at the current instant.
+    pub fn new() -> Self {
+        Self {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for MonotonicClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::from_duration(self.start.elapsed())
+    }
+}
+
+/// The clock a configuration reads, if any.
+#[derive(Clone, Default)]
+pub(crate) struct TimeSource {
+    clock: Option<Arc<dyn Clock>>,
+}
+
+impl TimeSource {
+    pub(crate) fn set(&mut self, clock: impl Clock + 'static) {
+        self.clock = Some(Arc::new(clock));
+    }
+
+    pub(crate) fn now(&self) -> Option<Timestamp> {
+        self.clock.as_ref().map(|clock| clock.now())
+    }
+}
+
+impl fmt::Debug for TimeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeSource")
+            .field("clock", &self.clock.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,207 @@
+//! An interactive debug menu for filing reports by hand (std only).
+//!
+//! Dev builds of an application can embed a [`DebugMenu`] behind a hidden
+//! key or command: it lists the handle's registered templates, asks for
+//! each placeholder in turn, shows the filled issue, and then opens the
+//! URL in the browser or copies it to the clipboard. The menu reads lines
+//! and writes plain text, so it works in any terminal and over SSH; run
+//! `cargo run --example report_tui` to try it.
+//!
+//! Copying uses the OSC 52 escape sequence, which most terminal emulators
+//! (and tmux with `set-clipboard on`) pass to the system clipboard.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate};
+//! use bug::tui::DebugMenu;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {screen}", "Steps: {steps}"));
+//!
+//! // Pick template 1, fill both placeholders, copy the URL, then quit.
+//! let mut input = "1\nSettings\nTap save\nc\nq\n".as_bytes();
+//! let mut output = Vec::new();
+//! let url = DebugMenu::new(&handle).run_with(&mut input, &mut output).unwrap();
+//!
+//! assert!(url.unwrap().contains("title=Crash+in+Settings"));
+//! let screen = String::from_utf8(output).unwrap();
+//! assert!(screen.contains("  1) crash  {screen}, {steps}"));
+//! assert!(screen.contains("Title: Crash in Settings"));
+//! ```
+//!
+//! Descriptions and parameter docs on the templates are shown in the list
+//! and when asking for a value:
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate};
+//! use bug::tui::DebugMenu;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {screen}", "")
+//!         .with_description("The app closed unexpectedly")
+//!         .document_param("screen", "The screen that was open"));
+//!
+//! let mut input = "1\nSettings\nq\n".as_bytes();
+//! let mut output = Vec::new();
+//! DebugMenu::new(&handle).run_with(&mut input, &mut output).unwrap();
+//!
+//! let screen = String::from_utf8(output).unwrap();
+//! assert!(screen.contains("  1) crash  {screen}\n     The app closed unexpectedly\n"));
+//! assert!(screen.contains("screen (The screen that was open): "));
+//! ```
+
+use crate::{BugReportHandle, Params};
+use std::io::{self, BufRead, Write};
+
+/// A line-based menu for filling a handle's templates interactively.
+pub struct DebugMenu<'a> {
+    handle: &'a BugReportHandle,
+}
+
+/// What to do with a generated report.
+enum Action {
+    Open,
+    Copy,
+    Back,
+    Quit,
+}
+
+impl<'a> DebugMenu<'a> {
+    /// A menu over the templates registered on `handle`.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle whose templates are listed
+    pub fn new(handle: &'a BugReportHandle) -> Self {
+        Self { handle }
+    }
+
+    /// Run the menu on stdin and stdout until the user quits.
+    ///
+    /// # Returns
+    ///
+    /// The last issue URL generated, if any.
+    pub fn run(&self) -> io::Result<Option<String>> {
+        self.run_with(&mut io::stdin().lock(), &mut io::stdout().lock())
+    }
+
+    /// Run the menu on the given input and output until the user quits or
+    /// the input ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - Where the user's answers are read from, one per line
+    /// * `output` - Where the menu is written
+    ///
+    /// # Returns
+    ///
+    /// The last issue URL generated, if any.
+    pub fn run_with(&self, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<Option<String>> {
+        let config = self.handle.config();
+        let names: Vec<&str> = config.template_names().collect();
+        let mut last_url = None;
+
+        loop {
+            if names.is_empty() {
+                writeln!(output, "No templates are registered.")?;
+                return Ok(last_url);
+            }
+            writeln!(output, "Bug report templates:")?;
+            for (i, name) in names.iter().enumerate() {
+                let placeholders = config.placeholders(name).unwrap_or_default();
+                if placeholders.is_empty() {
+                    writeln!(output, "  {}) {}", i + 1, name)?;
+                } else {
+                    let list: Vec<String> = placeholders.iter().map(|p| format!("{{{}}}", p)).collect();
+                    writeln!(output, "  {}) {}  {}", i + 1, name, list.join(", "))?;
+                }
+                if let Some(description) = config.description(name) {
+                    writeln!(output, "     {}", description)?;
+                }
+            }
+
+            let Some(choice) = prompt(input, output, "Choose a template (q to quit): ")? else {
+                return Ok(last_url);
+            };
+            if choice == "q" {
+                return Ok(last_url);
+            }
+            let Some(name) = choice
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| names.get(i))
+                .or_else(|| names.iter().find(|name| **name == choice))
+            else {
+                writeln!(output, "No template '{}'.\n", choice)?;
+                continue;
+            };
+
+            let mut params = Params::new();
+            for placeholder in config.placeholders(name).unwrap_or_default() {
+                let question = match config.param_doc(name, &placeholder) {
+                    Some(doc) => format!("{} ({}): ", placeholder, doc),
+                    None => format!("{}: ", placeholder),
+                };
+                let Some(value) = prompt(input, output, &question)? else {
+                    return Ok(last_url);
+                };
+                params.insert(placeholder, value);
+            }
+
+            let report = match self.handle.create_report(name, &params) {
+                Ok(report) => report,
+                Err(error) => {
+                    writeln!(output, "Could not generate the report: {}\n", error)?;
+                    continue;
+                }
+            };
+            writeln!(output, "\nTitle: {}", report.title)?;
+            if !report.labels.is_empty() {
+                writeln!(output, "Labels: {}", report.labels.join(", "))?;
+            }
+            writeln!(output, "URL: {}", report.url)?;
+
+            let action = loop {
+                let Some(answer) = prompt(input, output, "[o]pen, [c]opy, [b]ack, [q]uit: ")? else {
+                    break Action::Quit;
+                };
+                match answer.as_str() {
+                    "o" => break Action::Open,
+                    "c" => break Action::Copy,
+                    "b" | "" => break Action::Back,
+                    "q" => break Action::Quit,
+                    _ => {}
+                }
+            };
+            match action {
+                Action::Open => {
+                    if let Err(error) = crate::browser::open(&report.url) {
+                        writeln!(output, "Could not open the browser: {}", error)?;
+                    }
+                }
+                Action::Copy => {
+                    write!(output, "{}", crate::browser::osc52(&report.url))?;
+                    writeln!(output, "Copied the URL to the clipboard.")?;
+                }
+                Action::Back => {}
+                Action::Quit => return Ok(Some(report.url)),
+            }
+            writeln!(output)?;
+            last_url = Some(report.url);
+        }
+    }
+}
+
+/// Write `question` and read one trimmed line, or `None` at end of input.
+fn prompt(input: &mut dyn BufRead, output: &mut dyn Write, question: &str) -> io::Result<Option<String>> {
+    write!(output, "{}", question)?;
+    output.flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
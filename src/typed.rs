@@ -0,0 +1,172 @@
+//! Templates keyed by an application type instead of strings.
+//!
+//! A misspelled template name is only caught when the report it belongs to
+//! is generated, which for a rare bug can be never. A [`TypedHandle`] keys
+//! templates by any `Hash + Eq` type, typically an enum of the
+//! application's report kinds, so every report names one of its variants
+//! and the compiler rejects anything else. The string-keyed
+//! `BugReportHandle` underneath is still reachable with
+//! [`handle`](TypedHandle::handle).
+//!
+//! Templates are registered under the `Debug` form of their key, which is
+//! the name banners and summaries show.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::typed::TypedHandle;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+//! enum Report {
+//!     Crash,
+//!     SlowFrame,
+//! }
+//!
+//! let handle = TypedHandle::new(init_handle("owner", "repo"))
+//!     .add_template(Report::Crash, IssueTemplate::new("Crash in {screen}", ""))
+//!     .add_template(Report::SlowFrame, IssueTemplate::new("Slow frame", "{ms} ms"));
+//!
+//! let mut params = Params::new();
+//! params.insert("screen", "settings");
+//! let report = handle.create_report(&Report::Crash, &params).unwrap();
+//! assert_eq!(report.template_name, "Crash");
+//! assert_eq!(report.title, "Crash in settings");
+//! ```
+
+use crate::{BugReport, BugReportHandle, FxHashMap, IssueTemplate, Location, NoOutput, Output, Params, TemplateFile};
+use core::fmt::Debug;
+use core::hash::Hash;
+
+#[cfg(feature = "std")]
+use std::{format, string::String};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+/// A bug report handle whose templates are keyed by `K`.
+#[derive(Debug, Clone)]
+pub struct TypedHandle<K> {
+    handle: BugReportHandle,
+    names: FxHashMap<K, String>,
+}
+
+impl<K: Hash + Eq + Debug> TypedHandle<K> {
+    /// Key the templates of `handle` by `K`.
+    ///
+    /// Templates already registered on `handle` keep their names and are
+    /// only reachable through [`handle`](Self::handle).
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle to register templates on
+    pub fn new(handle: BugReportHandle) -> Self {
+        Self {
+            handle,
+            names: FxHashMap::default(),
+        }
+    }
+
+    /// Add a template for `key`, replacing any template it already has.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key reports select the template with
+    /// * `template` - The template to add
+    pub fn add_template(mut self, key: K, template: IssueTemplate) -> Self {
+        let name = self.name_for(key);
+        self.handle.register_template(name, template);
+        self
+    }
+
+    /// Add a template file for `key`, replacing any template it already has.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key reports select the template with
+    /// * `template_file` - The template file to add
+    pub fn add_template_file(mut self, key: K, template_file: TemplateFile) -> Self {
+        let name = self.name_for(key);
+        self.handle.register_template_file(name, template_file);
+        self
+    }
+
+    /// Generate the GitHub issue URL for the template of `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key of the template to use
+    /// * `params` - Parameters to substitute in the template
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` - The generated GitHub issue URL
+    /// * `Err(String)` - Error message if `key` has no template or validation fails
+    pub fn generate_url(&self, key: &K, params: &Params) -> Result<String, String> {
+        self.create_report(key, params).map(|report| report.url)
+    }
+
+    /// Create a structured bug report from the template of `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key of the template to use
+    /// * `params` - Parameters to substitute in the template
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BugReport)` - The filled report and its GitHub issue URL
+    /// * `Err(String)` - Error message if `key` has no template or validation fails
+    pub fn create_report(&self, key: &K, params: &Params) -> Result<BugReport, String> {
+        self.handle.create_report(self.name(key)?, params)
+    }
+
+    /// Report a bug from the template of `key` with no output.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `loc` - Where the bug was encountered
+    pub fn report_bug_at(&self, key: &K, params: &Params, loc: Location) -> String {
+        self.report_bug_with_output_at(key, params, loc, &mut NoOutput)
+    }
+
+    /// Report a bug from the template of `key`, writing the banner to `output`.
+    ///
+    /// A key without a template is reported like an unknown template name.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key of the template to use
+    /// * `params` - Parameters to substitute in the template
+    /// * `loc` - Where the bug was encountered
+    /// * `output` - Where the banner is written
+    pub fn report_bug_with_output_at(&self, key: &K, params: &Params, loc: Location, output: &mut dyn Output) -> String {
+        let name = match self.names.get(key) {
+            Some(name) => name.clone(),
+            None => format!("{:?}", key),
+        };
+        self.handle.report_bug_with_output_at(&name, params, loc, output)
+    }
+
+    /// The string-keyed handle the templates are registered on.
+    pub fn handle(&self) -> &BugReportHandle {
+        &self.handle
+    }
+
+    /// The template name of `key`.
+    fn name(&self, key: &K) -> Result<&str, String> {
+        self.names
+            .get(key)
+            .map(String::as_str)
+            .ok_or_else(|| format!("No template is registered for key {:?}", key))
+    }
+
+    /// Record the template name of `key`, which is its `Debug` form.
+    fn name_for(&mut self, key: K) -> String {
+        let name = format!("{:?}", key);
+        self.names.insert(key, name.clone());
+        name
+    }
+}
@@ -2,20 +2,175 @@
 //!
 //! This module provides URL encoding functionality that works in both std and no_std environments.
 //! The encoding follows RFC 3986 standards for percent-encoding.
+//!
+//! # Unicode
+//!
+//! Text is encoded byte by byte from its UTF-8 form, so every string comes
+//! back from [`decode`] exactly as it went in: characters outside the Basic
+//! Multilingual Plane such as emoji, combining marks, joiner sequences, and
+//! right-to-left text are never normalized, reordered, or split. Nothing in
+//! the crate shortens text by bytes or characters either; summaries such as
+//! `ReportPresentation` drop whole lines, so a grapheme cluster is always
+//! kept or dropped entirely.
+//!
+//! ```
+//! use bug::{init_handle, BugReport, IssueTemplate, Params};
+//! use bug::url_encode::{decode, encode};
+//!
+//! let samples = [
+//!     "🐛 crash",                // astral-plane emoji
+//!     "👩‍👩‍👧 family",            // joined with U+200D
+//!     "🇳🇿 flag",                // regional indicator pair
+//!     "e\u{0301}te\u{0301}",      // combining acute accents
+//!     "שגיאה בשמירה",           // right-to-left
+//!     "\u{202E}txt.exe",         // bidi override, kept as written
+//! ];
+//! for sample in samples {
+//!     assert_eq!(decode(&encode(sample)).unwrap(), sample);
+//! }
+//!
+//! // Round trip through a generated new-issue URL
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("{title}", "{body}"));
+//! for sample in samples {
+//!     let mut params = Params::new();
+//!     params.insert("title", sample);
+//!     params.insert("body", sample);
+//!     let parsed = BugReport::parse_url(&handle.generate_url("crash", &params).unwrap()).unwrap();
+//!     assert_eq!(parsed.title, sample);
+//!     assert_eq!(parsed.body, sample);
+//! }
+//! ```
+//!
+//! Arbitrary UTF-8 round-trips too, and the length functions agree with
+//! the encoders on it:
+//!
+//! ```
+//! use bug::url_encode::{decode, encode, encode_into, encode_with, encoded_len, encoded_len_with, SpaceEncoding};
+//!
+//! // A fixed xorshift sequence, so every run checks the same strings
+//! let mut state: u32 = 0x9E37_79B9;
+//! let mut next = move || {
+//!     state ^= state << 13;
+//!     state ^= state >> 17;
+//!     state ^= state << 5;
+//!     state
+//! };
+//! for _ in 0..500 {
+//!     let len = next() % 24;
+//!     let input: String = (0..len)
+//!         .filter_map(|_| {
+//!             // Mostly ASCII, with the rest spread over every plane
+//!             let raw = next();
+//!             let code = if raw % 3 == 0 { raw % 0x80 } else { raw % 0x11_0000 };
+//!             char::from_u32(code)
+//!         })
+//!         .collect();
+//!
+//!     for spaces in [SpaceEncoding::Percent, SpaceEncoding::Plus] {
+//!         let encoded = encode_with(&input, spaces);
+//!         assert!(encoded.bytes().all(|b| b.is_ascii_graphic()));
+//!         assert_eq!(encoded.len(), encoded_len_with(&input, spaces));
+//!         assert_eq!(decode(&encoded).unwrap(), input);
+//!     }
+//!     let mut buffer = vec![0u8; encoded_len(&input)];
+//!     let written = encode_into(&input, &mut buffer).unwrap();
+//!     assert_eq!(&buffer[..written], encode(&input).as_bytes());
+//! }
+//! ```
 
-use core::fmt::Write;
+use crate::Output;
 
 #[cfg(feature = "std")]
-use std::string::String;
+use std::{format, string::String, vec::Vec};
 
 #[cfg(not(feature = "std"))]
-use alloc::string::String;
+use alloc::{format, string::String, vec::Vec};
+
+/// How spaces are written by [`encode_with`].
+///
+/// GitHub decodes both forms to a space in a new-issue URL. Other targets
+/// differ: `mailto:` links and some trackers keep `+` as a literal plus
+/// sign, so only `%20` is read the same way everywhere.
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{decode, encode_with, SpaceEncoding};
+///
+/// let body = "let total = a + b;";
+/// let percent = encode_with(body, SpaceEncoding::Percent);
+/// let plus = encode_with(body, SpaceEncoding::Plus);
+/// assert_eq!(percent, "let%20total%20%3D%20a%20%2B%20b%3B");
+/// assert_eq!(plus, "let+total+%3D+a+%2B+b%3B");
+///
+/// // A query-string parser such as GitHub's reads both back the same.
+/// assert_eq!(decode(&percent).unwrap(), body);
+/// assert_eq!(decode(&plus).unwrap(), body);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpaceEncoding {
+    /// `%20`, read as a space by every URL parser
+    #[default]
+    Percent,
+    /// `+`, the form encoding, read as a space only by query-string parsers
+    Plus,
+}
+
+/// How a list of labels is written into a new-issue URL.
+///
+/// GitHub reads labels from one comma-separated `labels` parameter, so a
+/// label can't contain a comma there: it is split into two labels. Other
+/// trackers take the parameter once per label, which can carry any label.
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{encode_labels, LabelEncoding};
+///
+/// let labels = ["needs triage", "p1"];
+/// assert_eq!(encode_labels(&labels, LabelEncoding::Joined), "labels=needs+triage,p1");
+/// assert_eq!(encode_labels(&labels, LabelEncoding::Repeated), "labels[]=needs+triage&labels[]=p1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelEncoding {
+    /// One `labels` parameter, each label encoded and separated by a literal comma
+    #[default]
+    Joined,
+    /// A `labels[]` parameter per label
+    Repeated,
+}
+
+/// Write `labels` as new-issue query parameters, encoding each label on its own.
+///
+/// A comma inside a label is encoded as `%2C`, so it is never mistaken for
+/// a separator by trackers that split before decoding.
+///
+/// # Arguments
+///
+/// * `labels` - The labels, in order
+/// * `encoding` - How the list is written
+///
+/// # Returns
+///
+/// The parameters without a leading `&`, or an empty string for no labels.
+pub fn encode_labels<S: AsRef<str>>(labels: &[S], encoding: LabelEncoding) -> String {
+    let encoded: Vec<String> = labels.iter().map(|label| encode(label.as_ref())).collect();
+    match encoding {
+        LabelEncoding::Joined if encoded.is_empty() => String::new(),
+        LabelEncoding::Joined => format!("labels={}", encoded.join(",")),
+        LabelEncoding::Repeated => {
+            let pairs: Vec<String> = encoded.iter().map(|label| format!("labels[]={}", label)).collect();
+            pairs.join("&")
+        }
+    }
+}
 
 /// URL encode a string according to RFC 3986.
 ///
 /// This function percent-encodes all characters except unreserved characters
 /// (ALPHA / DIGIT / "-" / "." / "_" / "~"). Spaces are encoded as '+' for
-/// form-encoded data compatibility.
+/// form-encoded data compatibility; use [`encode_with`] to choose.
 ///
 /// # Arguments
 ///
@@ -43,24 +198,269 @@ use alloc::string::String;
 /// assert_eq!(encode("café"), "caf%C3%A9");
 /// ```
 pub fn encode(input: &str) -> String {
-    let mut output = String::new();
+    encode_with(input, SpaceEncoding::Plus)
+}
+
+/// URL encode a string, writing spaces as `spaces` says.
+///
+/// Literal `+` characters are always written as `%2B`, so they can't be
+/// mistaken for an encoded space. Bytes are pushed straight into the
+/// output, which is sized up front with [`encoded_len_with`], so encoding
+/// never fails or panics.
+///
+/// # Arguments
+///
+/// * `input` - The string to be URL encoded
+/// * `spaces` - How to encode spaces
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{encode_with, SpaceEncoding};
+///
+/// assert_eq!(encode_with("a + b", SpaceEncoding::Percent), "a%20%2B%20b");
+/// assert_eq!(encode_with("a + b", SpaceEncoding::Plus), "a+%2B+b");
+/// ```
+pub fn encode_with(input: &str, spaces: SpaceEncoding) -> String {
+    let mut output = String::with_capacity(encoded_len_with(input, spaces));
     
     for byte in input.bytes() {
+        if is_unreserved(byte) {
+            output.push(byte as char);
+        } else if byte == b' ' && spaces == SpaceEncoding::Plus {
+            output.push('+');
+        } else {
+            let escaped = encode_byte(byte);
+            output.extend(escaped.iter().map(|&b| b as char));
+        }
+    }
+    
+    output
+}
+
+/// Whether `encode` keeps a byte as it is.
+///
+/// Unreserved characters are ALPHA / DIGIT / "-" / "." / "_" / "~".
+///
+/// # Arguments
+///
+/// * `byte` - The byte to check
+pub const fn is_unreserved(byte: u8) -> bool {
+    matches!(byte, b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~')
+}
+
+/// Percent-encode one byte as `%XX` with uppercase hex digits.
+///
+/// This is how `encode` writes every byte that is neither unreserved nor a
+/// space. Together with [`is_unreserved`] and [`encoded_len`] it lets
+/// callers encode into fixed buffers without allocating.
+///
+/// # Arguments
+///
+/// * `byte` - The byte to encode
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::encode_byte;
+///
+/// assert_eq!(&encode_byte(b'@'), b"%40");
+/// assert_eq!(&encode_byte(0xE9), b"%E9");
+/// ```
+pub const fn encode_byte(byte: u8) -> [u8; 3] {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    [b'%', HEX[(byte >> 4) as usize], HEX[(byte & 0x0F) as usize]]
+}
+
+/// The exact length of `encode(input)`, computed without allocating.
+///
+/// # Arguments
+///
+/// * `input` - The string to be URL encoded
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{encode, encoded_len};
+///
+/// for input in ["hello world", "hello@world.com", "café", ""] {
+///     assert_eq!(encoded_len(input), encode(input).len());
+/// }
+/// ```
+pub const fn encoded_len(input: &str) -> usize {
+    encoded_len_with(input, SpaceEncoding::Plus)
+}
+
+/// The exact length of `encode_with(input, spaces)`, computed without allocating.
+///
+/// # Arguments
+///
+/// * `input` - The string to be URL encoded
+/// * `spaces` - How spaces are encoded
+pub const fn encoded_len_with(input: &str, spaces: SpaceEncoding) -> usize {
+    let plus = matches!(spaces, SpaceEncoding::Plus);
+    let bytes = input.as_bytes();
+    let mut len = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        len += if is_unreserved(byte) || (plus && byte == b' ') { 1 } else { 3 };
+        index += 1;
+    }
+    len
+}
+
+/// URL encode `input` into `buffer`, like `encode` but without allocating.
+///
+/// # Arguments
+///
+/// * `input` - The string to be URL encoded
+/// * `buffer` - Where to write the encoded bytes
+///
+/// # Returns
+///
+/// The number of bytes written, or `None` if `buffer` is shorter than
+/// `encoded_len(input)`; nothing is written in that case.
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::encode_into;
+///
+/// let mut buffer = [0u8; 32];
+/// let len = encode_into("disk full: /var", &mut buffer).unwrap();
+/// assert_eq!(&buffer[..len], b"disk+full%3A+%2Fvar");
+/// assert_eq!(encode_into("too long", &mut [0u8; 4]), None);
+/// ```
+pub fn encode_into(input: &str, buffer: &mut [u8]) -> Option<usize> {
+    let len = encoded_len(input);
+    let buffer = buffer.get_mut(..len)?;
+
+    let mut written = 0;
+    for byte in input.bytes() {
+        if is_unreserved(byte) {
+            buffer[written] = byte;
+            written += 1;
+        } else if byte == b' ' {
+            buffer[written] = b'+';
+            written += 1;
+        } else {
+            buffer[written..written + 3].copy_from_slice(&encode_byte(byte));
+            written += 3;
+        }
+    }
+    Some(written)
+}
+
+/// URL encode `input` into `output`, like `encode_with` but in chunks.
+///
+/// The encoded text is written through a small stack buffer, so the whole
+/// encoded string is never held in memory; only the chunk being written.
+///
+/// # Arguments
+///
+/// * `input` - The string to be URL encoded
+/// * `spaces` - How to encode spaces
+/// * `output` - Where the encoded text is written
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{encode_to, encode_with, SpaceEncoding};
+///
+/// let input = "disk full: /var ".repeat(100);
+/// let mut output = String::new();
+/// encode_to(&input, SpaceEncoding::Percent, &mut output);
+/// assert_eq!(output, encode_with(&input, SpaceEncoding::Percent));
+/// ```
+pub fn encode_to(input: &str, spaces: SpaceEncoding, output: &mut dyn Output) {
+    let mut chunk = [0u8; 128];
+    let mut len = 0;
+    for byte in input.bytes() {
+        if len + 3 > chunk.len() {
+            write_ascii(&chunk[..len], output);
+            len = 0;
+        }
+        if is_unreserved(byte) {
+            chunk[len] = byte;
+            len += 1;
+        } else if byte == b' ' && spaces == SpaceEncoding::Plus {
+            chunk[len] = b'+';
+            len += 1;
+        } else {
+            chunk[len..len + 3].copy_from_slice(&encode_byte(byte));
+            len += 3;
+        }
+    }
+    write_ascii(&chunk[..len], output);
+}
+
+/// Write encoded bytes, which are always ASCII, to `output`.
+fn write_ascii(bytes: &[u8], output: &mut dyn Output) {
+    if let Ok(text) = core::str::from_utf8(bytes)
+        && !text.is_empty()
+    {
+        output.write_str(text);
+    }
+}
+
+/// Decode a URL-encoded string, the inverse of [`encode`].
+///
+/// `+` decodes to a space and `%XX` to the byte `XX`. Other characters are
+/// kept as they are, so text that was never encoded decodes to itself.
+///
+/// # Arguments
+///
+/// * `input` - The URL-encoded string
+///
+/// # Returns
+///
+/// * `Ok(String)` - The decoded string
+/// * `Err(String)` - If a `%` escape is malformed or the bytes aren't UTF-8
+///
+/// # Examples
+///
+/// ```
+/// use bug::url_encode::{decode, encode};
+///
+/// assert_eq!(decode("hello+world").unwrap(), "hello world");
+/// assert_eq!(decode("caf%C3%A9").unwrap(), "café");
+/// assert_eq!(decode(&encode("a+b = {c}")).unwrap(), "a+b = {c}");
+/// assert!(decode("100%").is_err());
+/// ```
+pub fn decode(input: &str) -> Result<String, String> {
+    let bytes = decode_bytes(input.as_bytes(), true).ok_or_else(|| format!("Invalid percent escape in URL: {}", input))?;
+    String::from_utf8(bytes).map_err(|_| format!("URL does not decode to valid UTF-8: {}", input))
+}
+
+/// Decode `%XX` escapes in `input`, and `+` to a space if `plus_is_space`.
+///
+/// `None` if a `%` escape is malformed.
+pub(crate) fn decode_bytes(input: &[u8], plus_is_space: bool) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some((&byte, tail)) = rest.split_first() {
         match byte {
-            // Unreserved characters (ALPHA / DIGIT / "-" / "." / "_" / "~")
-            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
-                output.push(byte as char);
+            b'+' if plus_is_space => {
+                bytes.push(b' ');
+                rest = tail;
             }
-            // Space encoded as +
-            b' ' => {
-                output.push('+');
+            b'%' => {
+                let decoded = tail
+                    .get(..2)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| core::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())?;
+                bytes.push(decoded);
+                rest = &tail[2..];
             }
-            // Everything else percent-encoded
             _ => {
-                write!(&mut output, "%{:02X}", byte).unwrap();
+                bytes.push(byte);
+                rest = tail;
             }
         }
     }
-    
-    output
-}
\ No newline at end of file
+
+    Some(bytes)
+}
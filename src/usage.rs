@@ -0,0 +1,262 @@
+//! Template usage statistics (requires the `usage-stats` feature).
+//!
+//! Every generated report increments a process-wide counter for its
+//! template. [`usage_report`] returns the counts, and
+//! [`UsageReport::unused`] lists templates that were registered but never
+//! used, which helps prune dead templates.
+//!
+//! With `std`, [`unreferenced_templates`] also scans source files for the
+//! literals of `bug!` and the other reporting macros, so templates that no
+//! call site mentions can be found without running the program, for
+//! example from a test or build script.
+
+use crate::sync::Lock;
+use crate::{BugReportConfig, FxHashMap};
+use core::hash::BuildHasherDefault;
+
+#[cfg(feature = "std")]
+use std::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+static USAGE: Lock<FxHashMap<String, u64>> = Lock::new(FxHashMap::with_hasher(BuildHasherDefault::new()));
+
+/// Record one use of a template.
+pub(crate) fn record(template_name: &str) {
+    USAGE.with(|usage| match usage.get_mut(template_name) {
+        Some(count) => *count += 1,
+        None => {
+            usage.insert(template_name.to_string(), 1);
+        }
+    });
+}
+
+/// A snapshot of how often each template has been used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageReport {
+    /// Number of reports generated per template name
+    pub counts: BTreeMap<String, u64>,
+}
+
+impl UsageReport {
+    /// Number of reports generated from a template.
+    ///
+    /// A report counts once, for the template it was made from: a template
+    /// that falls back is not counted, and a report served from a handle's
+    /// cache is.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_name` - Name of the template
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .deny_unfilled_placeholders(true)
+    ///     .add_template("usage_detailed", IssueTemplate::new("Failed: {detail}", "").with_fallback("usage_generic"))
+    ///     .add_template("usage_generic", IssueTemplate::new("Something failed", ""));
+    ///
+    /// let report = handle.create_report("usage_detailed", &Params::new()).unwrap();
+    /// assert_eq!(report.template_name, "usage_generic");
+    ///
+    /// let usage = bug::usage_report();
+    /// assert_eq!(usage.count("usage_detailed"), 0);
+    /// assert_eq!(usage.count("usage_generic"), 1);
+    /// ```
+    pub fn count(&self, template_name: &str) -> u64 {
+        self.counts.get(template_name).copied().unwrap_or(0)
+    }
+
+    /// Templates registered in `config` that have not been used yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration whose templates to check
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::{init_handle, IssueTemplate, Params};
+    ///
+    /// let handle = init_handle("owner", "repo")
+    ///     .add_template("usage_used", IssueTemplate::new("Used", ""))
+    ///     .add_template("usage_dead", IssueTemplate::new("Dead", ""));
+    ///
+    /// handle.generate_url("usage_used", &Params::new()).unwrap();
+    ///
+    /// let report = bug::usage_report();
+    /// assert!(report.count("usage_used") >= 1);
+    /// assert_eq!(report.unused(handle.config()), vec!["usage_dead".to_string()]);
+    /// ```
+    pub fn unused(&self, config: &BugReportConfig) -> Vec<String> {
+        config
+            .template_names()
+            .filter(|name| self.count(name) == 0)
+            .map(|name| name.to_string())
+            .collect()
+    }
+}
+
+/// Get the template usage recorded so far in this process.
+pub fn usage_report() -> UsageReport {
+    USAGE.with(|usage| UsageReport {
+        counts: usage.iter().map(|(name, count)| (name.clone(), *count)).collect(),
+    })
+}
+
+/// Clear all recorded usage.
+pub fn reset_usage() {
+    USAGE.with(|usage| usage.clear());
+}
+
+/// List templates in `config` that no reporting macro call under `source_dir` references.
+///
+/// Every `.rs` file under `source_dir` is scanned for the template name
+/// literals of `bug!`, `bug_scoped!`, and `bug_with_handle!` calls, and the
+/// error code literals of `bug_code!` calls, which reference the template
+/// mapped to the code. Templates selected by a non-literal expression can't
+/// be seen by the scan, so treat the result as candidates to review rather
+/// than a list to delete.
+///
+/// # Arguments
+///
+/// * `config` - The configuration whose templates to check
+/// * `source_dir` - Directory to scan recursively, usually `src`
+///
+/// # Examples
+///
+/// ```no_run
+/// use bug::{init_handle, IssueTemplate};
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""));
+///
+/// let dead = bug::unreferenced_templates(handle.config(), concat!(env!("CARGO_MANIFEST_DIR"), "/src")).unwrap();
+/// assert!(dead.is_empty(), "unreferenced templates: {:?}", dead);
+/// ```
+///
+/// Error codes count for the template they are mapped to, and other macros
+/// whose names end like a reporting macro's don't count:
+///
+/// ```
+/// use bug::{init_handle, IssueTemplate};
+///
+/// let dir = std::env::temp_dir().join(format!("bug-usage-scan-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir).unwrap();
+/// std::fs::write(dir.join("main.rs"), r#"
+///     log::debug!("crash");
+///     bug::bug!("parse_error", { line = 3 });
+///     bug_scoped!("timeout");
+///     bug_with_handle!(handle, "disk_full");
+///     bug_code!(handle, "E0420", { offset = 17 });
+/// "#).unwrap();
+///
+/// let handle = init_handle("owner", "repo")
+///     .add_template("crash", IssueTemplate::new("Crash", ""))
+///     .add_template("parse_error", IssueTemplate::new("Parse error", ""))
+///     .add_template("timeout", IssueTemplate::new("Timeout", ""))
+///     .add_template("disk_full", IssueTemplate::new("Disk full", ""))
+///     .add_template("parser_crash", IssueTemplate::new("Parser crash", ""))
+///     .map_error_code("E0420", "parser_crash");
+///
+/// let dead = bug::unreferenced_templates(handle.config(), &dir).unwrap();
+/// std::fs::remove_dir_all(&dir).unwrap();
+/// assert_eq!(dead, ["crash"]);
+/// ```
+#[cfg(feature = "std")]
+pub fn unreferenced_templates(config: &BugReportConfig, source_dir: impl AsRef<std::path::Path>) -> std::io::Result<Vec<String>> {
+    let mut literals = Vec::new();
+    scan_dir(source_dir.as_ref(), &mut literals)?;
+    Ok(unreferenced(config, &literals))
+}
+
+/// The templates of `config` that none of `literals` references.
+#[cfg(feature = "std")]
+fn unreferenced(config: &BugReportConfig, literals: &[MacroLiteral]) -> Vec<String> {
+    let referenced: Vec<&str> = literals
+        .iter()
+        .filter_map(|literal| match literal {
+            MacroLiteral::Template(name) => Some(name.as_str()),
+            MacroLiteral::ErrorCode(code) => config.error_codes.get(code).map(String::as_str),
+        })
+        .collect();
+    config
+        .template_names()
+        .filter(|name| !referenced.contains(name))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(feature = "std")]
+fn scan_dir(dir: &std::path::Path, literals: &mut Vec<MacroLiteral>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            scan_dir(&path, literals)?;
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            literals.extend(macro_literals(&std::fs::read_to_string(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// What the string literal of a reporting macro call names.
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Eq)]
+enum MacroLiteral {
+    Template(String),
+    ErrorCode(String),
+}
+
+/// Extract the literals naming templates or error codes in the reporting macro calls of `source`.
+///
+/// A macro name only counts on its own, so `debug!(` is not a `bug!` call.
+#[cfg(feature = "std")]
+fn macro_literals(source: &str) -> Vec<MacroLiteral> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut literals = Vec::new();
+    for macro_name in ["bug", "bug_scoped", "bug_with_handle", "bug_code"] {
+        let mut offset = 0;
+        while let Some(found) = source[offset..].find(macro_name) {
+            let start = offset + found;
+            offset = start + macro_name.len();
+            if source[..start].chars().next_back().is_some_and(is_ident) {
+                continue;
+            }
+            let Some(args) = source[offset..]
+                .strip_prefix('!')
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix(['(', '[', '{']))
+            else {
+                continue;
+            };
+            let mut args = args.trim_start();
+            // These forms take the handle first; `bug_code!` only sometimes
+            if macro_name == "bug_with_handle" || (macro_name == "bug_code" && !args.starts_with('"')) {
+                args = args.find(',').map_or("", |comma| args[comma + 1..].trim_start());
+            }
+            if let Some(literal) = args.strip_prefix('"')
+                && let Some(end) = literal.find('"')
+            {
+                let literal = literal[..end].to_string();
+                literals.push(match macro_name {
+                    "bug_code" => MacroLiteral::ErrorCode(literal),
+                    _ => MacroLiteral::Template(literal),
+                });
+            }
+        }
+    }
+    literals
+}
@@ -0,0 +1,152 @@
+//! Non-fatal issues found while generating a report.
+//!
+//! Some problems don't stop a report from being generated but usually mean
+//! the configuration has drifted from the code that reports: a parameter no
+//! placeholder uses, a placeholder left without a value, a label the
+//! allowlist drops. Each generated [`BugReport`](crate::BugReport) carries
+//! the [`Warnings`] found while generating it, and the banner lists them
+//! with `show_warnings(true)`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::warning::Warning;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {module}", "{details}")
+//!         .with_labels(vec!["urgnet".to_string()]))
+//!     .allowed_labels(["urgent"]);
+//!
+//! let mut params = Params::new();
+//! params.insert("module", "parser");
+//! params.insert("detail", "stack overflow");
+//!
+//! let report = handle.create_report("crash", &params).unwrap();
+//! let warnings: Vec<String> = report.warnings.iter().map(Warning::to_string).collect();
+//! assert_eq!(warnings, [
+//!     "parameter 'detail' is not used by the template",
+//!     "placeholder 'details' has no value",
+//!     "label 'urgnet' is not in the allowed labels",
+//! ]);
+//! ```
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{string::String, vec::Vec};
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+/// One non-fatal issue with a generated report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Warning {
+    /// A parameter was given that no placeholder of the template uses.
+    UnusedParameter(String),
+    /// A placeholder of the template was left in place without a value.
+    UnfilledPlaceholder(String),
+    /// A label was dropped because it is not in the allowed labels.
+    DroppedLabel(String),
+    /// A label contains a comma, so GitHub splits it into several labels.
+    CommaInLabel(String),
+    /// Assignees past `teams::MAX_ASSIGNEES` were dropped.
+    AssigneesTruncated {
+        /// How many assignees were dropped
+        dropped: usize,
+    },
+    /// A template could not be filled, so a fallback template was used.
+    UsedFallback {
+        /// The template that could not be used
+        template: String,
+        /// Why it could not be used
+        error: String,
+    },
+    /// No template has the requested name, so the report links to the issue template chooser.
+    LinkedChooser(String),
+    /// The base URL or template target host the URL starts with is malformed.
+    MalformedBaseUrl(String),
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::UnusedParameter(name) => write!(f, "parameter '{}' is not used by the template", name),
+            Warning::UnfilledPlaceholder(name) => write!(f, "placeholder '{}' has no value", name),
+            Warning::DroppedLabel(label) => write!(f, "label '{}' is not in the allowed labels", label),
+            Warning::CommaInLabel(label) => write!(f, "label '{}' contains a comma and will be split", label),
+            Warning::AssigneesTruncated { dropped } => {
+                write!(f, "{} assignees dropped over the limit of {}", dropped, crate::teams::MAX_ASSIGNEES)
+            }
+            Warning::UsedFallback { template, error } => {
+                write!(f, "used a fallback for template '{}': {}", template, error)
+            }
+            Warning::LinkedChooser(name) => {
+                write!(f, "template '{}' not found, so the report links to the issue template chooser", name)
+            }
+            Warning::MalformedBaseUrl(error) => f.write_str(error),
+        }
+    }
+}
+
+/// The warnings attached to a report, in the order they were found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Warnings {
+    items: Vec<Warning>,
+}
+
+impl Warnings {
+    /// Create an empty collection.
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Add a warning, unless the same warning is already present.
+    pub(crate) fn push(&mut self, warning: Warning) {
+        if !self.items.contains(&warning) {
+            self.items.push(warning);
+        }
+    }
+
+    /// Whether there are no warnings.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Number of warnings.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Iterate over the warnings in order.
+    pub fn iter(&self) -> core::slice::Iter<'_, Warning> {
+        self.items.iter()
+    }
+}
+
+impl Extend<Warning> for Warnings {
+    fn extend<I: IntoIterator<Item = Warning>>(&mut self, iter: I) {
+        for warning in iter {
+            self.push(warning);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Warnings {
+    type Item = &'a Warning;
+    type IntoIter = core::slice::Iter<'a, Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Warning;
+    type IntoIter = <Vec<Warning> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
@@ -0,0 +1,161 @@
+//! Browser context for reports from web apps (feature `wasm`).
+//!
+//! A bug report from a web app is hard to triage without knowing the
+//! browser it came from. With a [`BrowserEnvironment`] on the
+//! configuration, templates can use the implicit placeholders
+//! `{user_agent}`, `{platform}`, and `{viewport}`, read when each bug is
+//! reported so a resized window shows its current size. They are filled
+//! in every report, a parameter passed in takes precedence, and templates
+//! that don't use them never read the browser.
+//!
+//! The crate doesn't depend on `web-sys`, so the environment is a closure
+//! the app provides. With `web-sys` (features `Window` and `Navigator`)
+//! it is:
+//!
+//! ```ignore
+//! use bug::wasm::BrowserContext;
+//!
+//! let handle = bug::init_handle("owner", "repo").browser_environment(|| {
+//!     let window = web_sys::window().expect("no window");
+//!     let navigator = window.navigator();
+//!     let size = |value: Result<wasm_bindgen::JsValue, _>| value.ok().and_then(|v| v.as_f64()).unwrap_or(0.0) as u32;
+//!     BrowserContext {
+//!         user_agent: navigator.user_agent().unwrap_or_default(),
+//!         platform: navigator.platform().unwrap_or_default(),
+//!         viewport: Some((size(window.inner_width()), size(window.inner_height()))),
+//!     }
+//! });
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Params};
+//! use bug::wasm::BrowserContext;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("render", IssueTemplate::new(
+//!         "Rendering glitch",
+//!         "Browser: {user_agent}\nPlatform: {platform}\nViewport: {viewport}",
+//!     ))
+//!     .browser_environment(|| BrowserContext {
+//!         user_agent: "Mozilla/5.0 (X11; Linux x86_64) Firefox/131.0".to_string(),
+//!         platform: "Linux x86_64".to_string(),
+//!         viewport: Some((1280, 720)),
+//!     });
+//!
+//! let report = handle.create_report("render", &Params::new()).unwrap();
+//! assert_eq!(
+//!     report.body,
+//!     "Browser: Mozilla/5.0 (X11; Linux x86_64) Firefox/131.0\nPlatform: Linux x86_64\nViewport: 1280x720"
+//! );
+//! ```
+
+use crate::Params;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+};
+
+/// Placeholder filled with the browser's user agent string.
+pub const USER_AGENT_PARAM: &str = "user_agent";
+
+/// Placeholder filled with the browser's platform, such as `MacIntel`.
+pub const PLATFORM_PARAM: &str = "platform";
+
+/// Placeholder filled with the window's inner size, as `WIDTHxHEIGHT`.
+pub const VIEWPORT_PARAM: &str = "viewport";
+
+/// What a web app knows about the browser it runs in.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BrowserContext {
+    /// `navigator.userAgent`
+    pub user_agent: String,
+    /// `navigator.platform`
+    pub platform: String,
+    /// `window.innerWidth` and `window.innerHeight` in CSS pixels, if known
+    pub viewport: Option<(u32, u32)>,
+}
+
+impl BrowserContext {
+    /// The viewport as `WIDTHxHEIGHT`, or `unknown`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bug::wasm::BrowserContext;
+    ///
+    /// let context = BrowserContext { viewport: Some((390, 844)), ..BrowserContext::default() };
+    /// assert_eq!(context.viewport_text(), "390x844");
+    /// assert_eq!(BrowserContext::default().viewport_text(), "unknown");
+    /// ```
+    pub fn viewport_text(&self) -> String {
+        match self.viewport {
+            Some((width, height)) => format!("{}x{}", width, height),
+            None => "unknown".to_string(),
+        }
+    }
+}
+
+/// Reads the browser context when a bug is reported.
+///
+/// Implemented for closures returning a `BrowserContext`.
+pub trait BrowserEnvironment: Send + Sync {
+    /// The browser context right now.
+    fn collect(&self) -> BrowserContext;
+}
+
+impl<F: Fn() -> BrowserContext + Send + Sync> BrowserEnvironment for F {
+    fn collect(&self) -> BrowserContext {
+        self()
+    }
+}
+
+/// The browser environment of a configuration.
+#[derive(Clone)]
+pub(crate) struct BrowserSource(Arc<dyn BrowserEnvironment>);
+
+impl BrowserSource {
+    pub(crate) fn new(environment: impl BrowserEnvironment + 'static) -> Self {
+        Self(Arc::new(environment))
+    }
+
+    /// Add the browser placeholders among `placeholders` that `params` lacks.
+    ///
+    /// The browser is only read if one of them is needed.
+    pub(crate) fn fill(&self, placeholders: &[String], params: &Params, extended: &mut Option<Params>) {
+        let wanted = |key: &str| placeholders.iter().any(|p| p == key) && !params.contains_key(key);
+        if ![USER_AGENT_PARAM, PLATFORM_PARAM, VIEWPORT_PARAM].into_iter().any(wanted) {
+            return;
+        }
+        let context = self.0.collect();
+        let viewport = context.viewport_text();
+        let values = [
+            (USER_AGENT_PARAM, context.user_agent),
+            (PLATFORM_PARAM, context.platform),
+            (VIEWPORT_PARAM, viewport),
+        ];
+        for (key, value) in values {
+            if wanted(key) {
+                extended.get_or_insert_with(|| params.clone()).insert(key, value);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for BrowserSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BrowserSource").finish_non_exhaustive()
+    }
+}
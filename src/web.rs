@@ -0,0 +1,318 @@
+//! Tower middleware that reports server errors (requires the `web` feature).
+//!
+//! [`BugReportLayer`] wraps any `tower` HTTP service, including axum routers.
+//! When the inner service responds with a 5xx status, or panics while
+//! handling a request, the layer fills a configured template with details
+//! about the request, logs the issue URL, and can optionally put the URL in
+//! the error response body.
+//!
+//! The template may use any of these placeholders:
+//!
+//! - `{method}` - Request method, e.g. `GET`
+//! - `{route}` - Request path, e.g. `/users/42`
+//! - `{request_id}` - Value of the `x-request-id` header, or `unknown`
+//! - `{status}` - Response status, e.g. `500 Internal Server Error`
+//!
+//! Placeholders the template doesn't use are not passed to it, so template
+//! files with strict validation work as well.
+//!
+//! # Examples
+//!
+//! ```ignore
+//! use bug::{init_handle, IssueTemplate};
+//! use bug::web::BugReportLayer;
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("server_error", IssueTemplate::new(
+//!         "Internal error in {method} {route}",
+//!         "Request {request_id} failed with {status}",
+//!     ));
+//!
+//! let app = axum::Router::new()
+//!     .route("/", axum::routing::get(|| async { "hello" }))
+//!     .layer(BugReportLayer::new(handle, "server_error").attach_to_response(true));
+//! ```
+
+use crate::{BugReport, BugReportHandle, Output, Params};
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use http::{header, HeaderValue, Request, Response, StatusCode};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Header used to correlate reports with requests.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Details about the failed request, passed to report callbacks.
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    /// The request method
+    pub method: String,
+    /// The request path
+    pub route: String,
+    /// The request id header value, if one was sent
+    pub request_id: Option<String>,
+    /// The response status (500 when the handler panicked)
+    pub status: StatusCode,
+    /// Whether the handler panicked
+    pub panicked: bool,
+}
+
+type ReportCallback = dyn Fn(&BugReport, &RequestInfo) + Send + Sync;
+
+#[derive(Clone)]
+struct Shared {
+    handle: BugReportHandle,
+    template: String,
+    attach_to_response: bool,
+    on_report: Option<Arc<ReportCallback>>,
+}
+
+/// Layer that reports 5xx responses and panics from the wrapped service.
+///
+/// By default reports are logged to stderr. Use [`BugReportLayer::on_report`]
+/// to send them to your own logging instead.
+#[derive(Clone)]
+pub struct BugReportLayer {
+    shared: Arc<Shared>,
+}
+
+impl BugReportLayer {
+    /// Create a layer that fills `template` from `handle` for failed requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle holding the report template
+    /// * `template` - Name of the template to fill
+    pub fn new(handle: BugReportHandle, template: impl Into<String>) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                handle,
+                template: template.into(),
+                attach_to_response: false,
+                on_report: None,
+            }),
+        }
+    }
+
+    /// Replace the body of failed responses with a message containing the issue URL.
+    ///
+    /// Panicking handlers are answered with a 500 response either way; this
+    /// controls whether its body, and those of 5xx responses returned by
+    /// handlers, carry the message or just the status text.
+    ///
+    /// # Arguments
+    ///
+    /// * `attach` - Whether to rewrite error response bodies
+    pub fn attach_to_response(mut self, attach: bool) -> Self {
+        self.shared_mut().attach_to_response = attach;
+        self
+    }
+
+    /// Send generated reports to a callback instead of stderr.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Called with the report and the failed request's details
+    pub fn on_report(mut self, callback: impl Fn(&BugReport, &RequestInfo) + Send + Sync + 'static) -> Self {
+        self.shared_mut().on_report = Some(Arc::new(callback));
+        self
+    }
+
+    /// The settings for changing, copied first if a clone of the layer shares them.
+    fn shared_mut(&mut self) -> &mut Shared {
+        Arc::make_mut(&mut self.shared)
+    }
+}
+
+impl<S> Layer<S> for BugReportLayer {
+    type Service = BugReportService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BugReportService {
+            inner,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Service produced by [`BugReportLayer`].
+#[derive(Clone)]
+pub struct BugReportService<S> {
+    inner: S,
+    shared: Arc<Shared>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for BugReportService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: From<String>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = BugReportFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let method = request.method().to_string();
+        let route = request.uri().path().to_string();
+        let request_id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        let inner = &mut self.inner;
+        let future = catch_unwind(AssertUnwindSafe(|| inner.call(request))).ok();
+
+        BugReportFuture {
+            future,
+            pending: Some(Pending {
+                shared: self.shared.clone(),
+                method,
+                route,
+                request_id,
+            }),
+        }
+    }
+}
+
+struct Pending {
+    shared: Arc<Shared>,
+    method: String,
+    route: String,
+    request_id: Option<String>,
+}
+
+pin_project_lite::pin_project! {
+    /// Response future of [`BugReportService`].
+    pub struct BugReportFuture<F> {
+        #[pin]
+        future: Option<F>,
+        pending: Option<Pending>,
+    }
+}
+
+impl<F, ResBody, E> Future for BugReportFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+    ResBody: From<String>,
+{
+    type Output = Result<Response<ResBody>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let outcome = match this.future.as_pin_mut() {
+            Some(future) => catch_unwind(AssertUnwindSafe(|| future.poll(cx))).map_err(|_| ()),
+            // The service panicked before returning a future.
+            None => Err(()),
+        };
+
+        let response = match outcome {
+            Ok(Poll::Pending) => return Poll::Pending,
+            Ok(Poll::Ready(Err(e))) => return Poll::Ready(Err(e)),
+            Ok(Poll::Ready(Ok(response))) => response,
+            Err(_) => {
+                let pending = this.pending.take().expect("BugReportFuture polled after completion");
+                let status = StatusCode::INTERNAL_SERVER_ERROR;
+                let message = pending.report(status, true);
+                let body = if pending.shared.attach_to_response { message } else { status_text(status) };
+                return Poll::Ready(Ok(error_response(status, body, None)));
+            }
+        };
+
+        if !response.status().is_server_error() {
+            return Poll::Ready(Ok(response));
+        }
+
+        let pending = this.pending.take().expect("BugReportFuture polled after completion");
+        let message = pending.report(response.status(), false);
+        if pending.shared.attach_to_response {
+            let status = response.status();
+            let (parts, _) = response.into_parts();
+            return Poll::Ready(Ok(error_response(status, message, Some(parts))));
+        }
+        Poll::Ready(Ok(response))
+    }
+}
+
+impl Pending {
+    /// Generate and log the report, returning the response body text.
+    fn report(&self, status: StatusCode, panicked: bool) -> String {
+        let info = RequestInfo {
+            method: self.method.clone(),
+            route: self.route.clone(),
+            request_id: self.request_id.clone(),
+            status,
+            panicked,
+        };
+
+        let fallback = status_text(status);
+        let report = match self.shared.handle.create_report(&self.shared.template, &self.params(&info)) {
+            Ok(report) => report,
+            Err(e) => {
+                // Not eprintln!, which panics when stderr is a closed pipe
+                Output::write_fmt(
+                    &mut std::io::stderr(),
+                    format_args!(
+                        "🐛 BUG ENCOUNTERED handling {} {}\n   Error generating bug report: {}\n\n",
+                        info.method, info.route, e
+                    ),
+                );
+                return fallback;
+            }
+        };
+
+        match &self.shared.on_report {
+            Some(callback) => callback(&report, &info),
+            None => {
+                let mut stderr = std::io::stderr();
+                Output::write_fmt(
+                    &mut stderr,
+                    format_args!("🐛 BUG ENCOUNTERED handling {} {}\n   Status: {}\n", info.method, info.route, info.status),
+                );
+                if let Some(request_id) = &info.request_id {
+                    Output::write_fmt(&mut stderr, format_args!("   Request id: {}\n", request_id));
+                }
+                Output::write_fmt(&mut stderr, format_args!("   File a bug report: {}\n\n", report.url));
+            }
+        }
+
+        format!("{}\n\nPlease report this problem: {}\n", fallback, report.url)
+    }
+
+    /// Build template parameters, keeping only the placeholders the template uses.
+    fn params(&self, info: &RequestInfo) -> Params {
+        let available = [
+            ("method", info.method.clone()),
+            ("route", info.route.clone()),
+            ("request_id", info.request_id.clone().unwrap_or_else(|| "unknown".to_string())),
+            ("status", info.status.to_string()),
+        ];
+        self.shared.handle.config().used_params(&self.shared.template, available)
+    }
+}
+
+/// The text of `status`, used as the body when no report URL is attached.
+fn status_text(status: StatusCode) -> String {
+    status.canonical_reason().unwrap_or("Internal Server Error").to_string()
+}
+
+fn error_response<B: From<String>>(status: StatusCode, message: String, parts: Option<http::response::Parts>) -> Response<B> {
+    let mut response = match parts {
+        Some(parts) => Response::from_parts(parts, B::from(message)),
+        None => Response::new(B::from(message)),
+    };
+    *response.status_mut() = status;
+    let headers = response.headers_mut();
+    headers.remove(header::CONTENT_LENGTH);
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain; charset=utf-8"));
+    response
+}
@@ -0,0 +1,294 @@
+//! Posting reported bugs to a chat channel (feature `webhook`).
+//!
+//! A crash nobody clicks the link for is a crash nobody hears about. A
+//! [`Webhook`] is a [`Sink`] that posts each reported bug's title, the
+//! start of its body, and its issue URL to a Slack, Discord, or Microsoft
+//! Teams incoming webhook, so an on-call channel sees it as it happens.
+//! `only_templates` and `only_if` pick the reports worth a message, such
+//! as crashes or anything reported with a critical severity.
+//!
+//! Like every sink, a webhook gets bugs after suppression, sampling, and
+//! middleware, and only full records are posted: reminders shortened by
+//! backoff, reports withheld for lack of consent, and reports that failed
+//! to generate are not. Each post runs on its own thread so reporting
+//! never waits for the network; a failed post prints a warning. As with
+//! the `api` and `remote` features, the crate makes no network requests
+//! itself: posts go through an [`HttpPost`].
+//!
+//! # Examples
+//!
+//! ```
+//! use bug::{init_handle, IssueTemplate, Location, NoOutput, Params};
+//! use bug::webhook::Webhook;
+//! use std::sync::mpsc;
+//!
+//! // Stands in for a real client such as `ureq` or `reqwest::blocking`.
+//! let (sent, received) = mpsc::channel();
+//! let client = move |url: &str, _headers: &[(&str, &str)], body: &str| -> Result<(), String> {
+//!     sent.send((url.to_string(), body.to_string())).unwrap();
+//!     Ok(())
+//! };
+//!
+//! let handle = init_handle("owner", "repo")
+//!     .add_template("crash", IssueTemplate::new("Crash in {screen}", "Severity: {severity}"))
+//!     .add_sink(
+//!         Webhook::slack("https://hooks.slack.com/services/T0/B0/XXXX", client)
+//!             .only_if(|record| record.params.get("severity") == Some("critical"))
+//!             .into(),
+//!     );
+//!
+//! let loc = Location::new("main.rs", 7);
+//! let params = Params::new().with("screen", "settings").with("severity", "minor");
+//! handle.report_bug_with_output_at("crash", &params, loc, &mut NoOutput);
+//! let params = params.with("severity", "critical");
+//! handle.report_bug_with_output_at("crash", &params, loc, &mut NoOutput);
+//!
+//! let (url, payload) = received.recv().unwrap();
+//! assert_eq!(url, "https://hooks.slack.com/services/T0/B0/XXXX");
+//! assert!(payload.starts_with(r#"{"text":"🐛 *Crash in settings*\nSeverity: critical\n<https://github.com/owner/repo/issues/new?"#));
+//! assert!(received.try_recv().is_err());
+//! ```
+
+pub use crate::http::HttpPost;
+
+use crate::backoff::Verbosity;
+use crate::http::USER_AGENT;
+use crate::sink::{write_json_string, Sink, SinkRecord};
+use crate::{BugReport, Output};
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+
+/// Characters of the body posted when `excerpt` isn't set.
+const DEFAULT_EXCERPT_CHARS: usize = 500;
+
+/// Decides which records a webhook posts.
+type Filter = dyn Fn(&SinkRecord<'_>) -> bool + Send + Sync;
+
+/// The chat service a webhook posts to, which decides the message layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookService {
+    /// A Slack incoming webhook: a `text` message with a link
+    Slack,
+    /// A Discord webhook: an embed titled and linked to the issue URL
+    Discord,
+    /// A Microsoft Teams incoming webhook: a message card with a button
+    Teams,
+}
+
+/// A sink posting reported bugs to a chat webhook.
+///
+/// Convert it into a [`Sink`] to add it with `add_sink`.
+#[derive(Clone)]
+pub struct Webhook {
+    url: String,
+    service: WebhookService,
+    client: Arc<dyn HttpPost>,
+    templates: Option<Vec<String>>,
+    filter: Option<Arc<Filter>>,
+    excerpt_chars: usize,
+}
+
+impl Webhook {
+    /// Post to the `service` webhook at `url` through `client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The chat service the webhook belongs to
+    /// * `url` - The webhook URL the service gave
+    /// * `client` - Sends the requests
+    pub fn new(service: WebhookService, url: impl Into<String>, client: impl HttpPost + 'static) -> Self {
+        Self {
+            url: url.into(),
+            service,
+            client: Arc::new(client),
+            templates: None,
+            filter: None,
+            excerpt_chars: DEFAULT_EXCERPT_CHARS,
+        }
+    }
+
+    /// Post to a Slack incoming webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The webhook URL, `https://hooks.slack.com/services/...`
+    /// * `client` - Sends the requests
+    pub fn slack(url: impl Into<String>, client: impl HttpPost + 'static) -> Self {
+        Self::new(WebhookService::Slack, url, client)
+    }
+
+    /// Post to a Discord webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The webhook URL, `https://discord.com/api/webhooks/...`
+    /// * `client` - Sends the requests
+    pub fn discord(url: impl Into<String>, client: impl HttpPost + 'static) -> Self {
+        Self::new(WebhookService::Discord, url, client)
+    }
+
+    /// Post to a Microsoft Teams incoming webhook.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The webhook URL the channel's connector gave
+    /// * `client` - Sends the requests
+    pub fn teams(url: impl Into<String>, client: impl HttpPost + 'static) -> Self {
+        Self::new(WebhookService::Teams, url, client)
+    }
+
+    /// Only post reports from these templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `templates` - Names of the templates to post
+    pub fn only_templates<I, S>(mut self, templates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.templates = Some(templates.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only post reports `filter` accepts, such as those with a critical severity.
+    ///
+    /// Applies after `only_templates`.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Called with each record; `true` posts it
+    pub fn only_if(mut self, filter: impl Fn(&SinkRecord<'_>) -> bool + Send + Sync + 'static) -> Self {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Post at most `chars` characters of the body.
+    ///
+    /// Longer bodies are cut and end with `…`; the full body is in the
+    /// issue URL. 0 leaves the body out.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - The most characters of the body to post
+    pub fn excerpt(mut self, chars: usize) -> Self {
+        self.excerpt_chars = chars;
+        self
+    }
+
+    /// Whether `record` is one this webhook posts.
+    fn accepts(&self, record: &SinkRecord<'_>) -> bool {
+        record.verbosity == Verbosity::Full
+            && record.result.is_ok()
+            && !record.url.is_empty()
+            && self
+                .templates
+                .as_ref()
+                .is_none_or(|templates| templates.iter().any(|name| name == record.template))
+            && self.filter.as_ref().is_none_or(|filter| filter(record))
+    }
+
+    /// The JSON message for `report`.
+    fn payload(&self, report: &BugReport, url: &str) -> String {
+        let body = excerpt(&report.body, self.excerpt_chars);
+        let mut json = String::new();
+        match self.service {
+            WebhookService::Slack => {
+                // Slack reads `&`, `<`, and `>` as markup
+                let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+                let mut text = format!("🐛 *{}*\n", escape(&report.title));
+                if !body.is_empty() {
+                    text.push_str(&escape(&body));
+                    text.push('\n');
+                }
+                text.push_str(&format!("<{}|File a bug report>", url));
+                json.write_str("{\"text\":");
+                write_json_string(&mut json, &text);
+                json.write_str("}");
+            }
+            WebhookService::Discord => {
+                // Embed titles are limited to 256 characters
+                json.write_str("{\"embeds\":[{\"title\":");
+                write_json_string(&mut json, &format!("🐛 {}", excerpt(&report.title, 250)));
+                json.write_str(",\"description\":");
+                write_json_string(&mut json, &body);
+                json.write_str(",\"url\":");
+                write_json_string(&mut json, url);
+                json.write_str("}]}");
+            }
+            WebhookService::Teams => {
+                json.write_str("{\"@type\":\"MessageCard\",\"@context\":\"https://schema.org/extensions\",\"summary\":");
+                write_json_string(&mut json, &report.title);
+                json.write_str(",\"title\":");
+                write_json_string(&mut json, &format!("🐛 {}", report.title));
+                json.write_str(",\"text\":");
+                write_json_string(&mut json, &body);
+                json.write_str(
+                    ",\"potentialAction\":[{\"@type\":\"OpenUri\",\"name\":\"File a bug report\",\"targets\":[{\"os\":\"default\",\"uri\":",
+                );
+                write_json_string(&mut json, url);
+                json.write_str("}]}]}");
+            }
+        }
+        json
+    }
+
+    /// Post `record` on a new thread, if this webhook accepts it.
+    fn post(&self, record: &SinkRecord<'_>) {
+        let Ok(report) = record.result else {
+            return;
+        };
+        if !self.accepts(record) {
+            return;
+        }
+        let payload = self.payload(report, record.url);
+        let client = Arc::clone(&self.client);
+        let url = self.url.clone();
+        let spawned = thread::Builder::new().name("bug-webhook".to_string()).spawn(move || {
+            let headers = [("Content-Type", "application/json"), ("User-Agent", USER_AGENT)];
+            if let Err(e) = client.post(&url, &headers, &payload) {
+                Output::write_fmt(
+                    &mut std::io::stderr(),
+                    format_args!("warning: failed to post bug report to webhook: {}\n", e),
+                );
+            }
+        });
+        if let Err(e) = spawned {
+            Output::write_fmt(
+                &mut std::io::stderr(),
+                format_args!("warning: failed to start posting bug report to webhook: {}\n", e),
+            );
+        }
+    }
+}
+
+impl From<Webhook> for Sink {
+    fn from(webhook: Webhook) -> Self {
+        Sink::callback(move |record| webhook.post(record))
+    }
+}
+
+impl fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The URL is a secret: anyone holding it can post to the channel
+        f.debug_struct("Webhook")
+            .field("service", &self.service)
+            .field("templates", &self.templates)
+            .field("filter", &self.filter.is_some())
+            .field("excerpt_chars", &self.excerpt_chars)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The first `chars` characters of `body`, trimmed, ending with `…` if cut.
+fn excerpt(body: &str, chars: usize) -> String {
+    let body = body.trim();
+    if chars == 0 {
+        return String::new();
+    }
+    match body.char_indices().nth(chars) {
+        Some((end, _)) => format!("{}…", body[..end].trim_end()),
+        None => body.to_string(),
+    }
+}
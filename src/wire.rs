@@ -0,0 +1,586 @@
+//! A compact binary encoding of handles, for reuse in other processes.
+//!
+//! A parent process can configure its templates once, encode the handle
+//! with `BugReportHandle::to_bytes`, and pass the bytes to short-lived
+//! worker processes or a WASM plugin sandbox, which rebuild the handle with
+//! `BugReportHandle::from_bytes` instead of declaring every template again.
+//!
+//! Everything declarative is carried: the repository and its base URL,
+//! templates and template files in registration order (so `TemplateId`s
+//! stay the same) with their milestones, projects, descriptions and
+//! parameter docs, error codes, label rules, name matching and the missing
+//! template policy, body messages, teams, sample rates, template targets,
+//! parameter policies and their hash key, output settings, backoff, the
+//! quiet period, the namespace, and cache size. Closures can't be encoded,
+//! so `suppress_if` conditions, middleware, sinks, the clock, the GitHub
+//! API client, the screenshot provider, and CODEOWNERS rules have to be set
+//! again after decoding. Runtime state such as the report cache, occurrence
+//! counts, the time of the last banner, and round-robin positions starts
+//! fresh.
+//!
+//! Integers are LEB128 varints, strings are a length followed by UTF-8, and
+//! the encoding starts with a magic number and a format version.
+
+use crate::backoff::{BackoffPolicy, Interval};
+use crate::messages::Messages;
+use crate::param::ParamPolicy;
+use crate::registry::SlotSource;
+use crate::url_encode::{LabelEncoding, SpaceEncoding};
+use crate::{BugReportConfig, HyperlinkMode, IssueTemplate, NameMatching, OnMissingTemplate, TeamAssignment, TemplateFile, TemplateTarget};
+
+#[cfg(feature = "std")]
+use std::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Bytes every encoded handle starts with.
+const MAGIC: &[u8; 4] = b"BUGH";
+
+/// Version of the encoding, bumped whenever the layout changes.
+const VERSION: u8 = 20;
+
+/// Encode a configuration and the cache size of its handle.
+pub(crate) fn encode(config: &BugReportConfig, cache_size: usize) -> Vec<u8> {
+    let mut w = Writer(Vec::new());
+    w.0.extend_from_slice(MAGIC);
+    w.0.push(VERSION);
+
+    w.str(&config.github_owner);
+    w.str(&config.github_repo);
+    w.bool(config.base_url.is_some());
+    if let Some(base_url) = &config.base_url {
+        w.str(base_url);
+    }
+    w.u8(match config.use_hyperlinks {
+        HyperlinkMode::Auto => 0,
+        HyperlinkMode::Always => 1,
+        HyperlinkMode::Never => 2,
+    });
+
+    let slots = config.registry.slots();
+    w.len(slots.len());
+    for slot in slots {
+        w.str(&slot.name);
+        let (labels, assignees, teams, milestone, projects, description, param_docs) = match &slot.source {
+            SlotSource::Inline(template) => {
+                w.u8(0);
+                w.str(&template.title);
+                w.str(&template.body);
+                (
+                    &template.labels,
+                    &template.assignees,
+                    &template.teams,
+                    &template.milestone,
+                    &template.projects,
+                    &template.description,
+                    &template.param_docs,
+                )
+            }
+            SlotSource::File(template_file) => {
+                w.u8(1);
+                w.str(template_file.content);
+                w.bool(template_file.sha256.is_some());
+                if let Some(sha256) = &template_file.sha256 {
+                    w.str(sha256);
+                }
+                (
+                    &template_file.labels,
+                    &template_file.assignees,
+                    &template_file.teams,
+                    &template_file.milestone,
+                    &template_file.projects,
+                    &template_file.description,
+                    &template_file.param_docs,
+                )
+            }
+        };
+        w.strs(labels);
+        w.strs(assignees);
+        w.strs(teams);
+        w.bool(milestone.is_some());
+        if let Some(milestone) = milestone {
+            w.str(milestone);
+        }
+        w.strs(projects);
+        w.bool(description.is_some());
+        if let Some(description) = description {
+            w.str(description);
+        }
+        w.len(param_docs.len());
+        for (placeholder, doc) in param_docs {
+            w.str(placeholder);
+            w.str(doc);
+        }
+        w.bool(slot.fallback().is_some());
+        if let Some(fallback) = slot.fallback() {
+            w.str(fallback);
+        }
+    }
+
+    let error_codes = sorted(config.error_codes.iter());
+    w.len(error_codes.len());
+    for (code, template_name) in error_codes {
+        w.str(code);
+        w.str(template_name);
+    }
+
+    w.bool(config.allowed_labels.is_some());
+    if let Some(allowed) = &config.allowed_labels {
+        w.strs(allowed);
+    }
+    w.strs(&config.allowed_label_prefixes);
+    w.bool(config.warn_on_disallowed_labels);
+
+    let teams = sorted(config.teams.iter());
+    w.len(teams.len());
+    for (team, members) in teams {
+        w.str(team);
+        w.strs(members);
+    }
+    w.u8(match config.team_assignment {
+        TeamAssignment::All => 0,
+        TeamAssignment::RoundRobin => 1,
+    });
+
+    let sample_rates = sorted(config.sample_rates.iter());
+    w.len(sample_rates.len());
+    for (template_name, rate) in sample_rates {
+        w.str(template_name);
+        w.u64(rate.to_bits());
+    }
+
+    let targets = sorted(config.template_targets.iter());
+    w.len(targets.len());
+    for (template_name, target) in targets {
+        w.str(template_name);
+        w.bool(target.repository.is_some());
+        if let Some((owner, repo)) = &target.repository {
+            w.str(owner);
+            w.str(repo);
+        }
+        w.bool(target.host.is_some());
+        if let Some(host) = &target.host {
+            w.str(host);
+        }
+        w.u8(match target.hyperlinks {
+            None => 0,
+            Some(HyperlinkMode::Auto) => 1,
+            Some(HyperlinkMode::Always) => 2,
+            Some(HyperlinkMode::Never) => 3,
+        });
+    }
+
+    let policies = &config.param_policies;
+    let global = sorted(policies.global().iter());
+    w.len(global.len());
+    for (placeholder, policy) in global {
+        w.str(placeholder);
+        w.policy(*policy);
+    }
+    let per_template = sorted(policies.per_template().iter());
+    w.len(per_template.len());
+    for (template_name, template_policies) in per_template {
+        w.str(template_name);
+        let template_policies = sorted(template_policies.iter());
+        w.len(template_policies.len());
+        for (placeholder, policy) in template_policies {
+            w.str(placeholder);
+            w.policy(*policy);
+        }
+    }
+    let (k0, k1) = policies.key();
+    w.u64(k0);
+    w.u64(k1);
+
+    w.bool(config.include_source_excerpt);
+    w.bool(config.summarize);
+    w.bool(config.show_warnings);
+    w.bool(config.show_config_digest);
+    w.bool(config.deny_unfilled_placeholders);
+    w.bool(config.strict_params);
+    w.bool(config.minify_body);
+    w.u8(match config.body_space_encoding {
+        SpaceEncoding::Percent => 0,
+        SpaceEncoding::Plus => 1,
+    });
+    w.u8(match config.label_encoding {
+        LabelEncoding::Joined => 0,
+        LabelEncoding::Repeated => 1,
+    });
+    w.u8(match config.registry.matching() {
+        NameMatching::Exact => 0,
+        NameMatching::CaseInsensitive => 1,
+        NameMatching::Normalized => 2,
+    });
+    w.u8(match config.on_missing_template {
+        OnMissingTemplate::Error => 0,
+        OnMissingTemplate::LinkChooser => 1,
+    });
+    let messages = &config.messages;
+    w.str(&messages.sampled_note);
+    w.str(&messages.source_unavailable);
+    w.str(&messages.steps_heading);
+    w.str(&messages.expected_heading);
+    w.str(&messages.actual_heading);
+    w.str(&messages.screenshot_heading);
+    w.str(&messages.screenshot_prompt);
+    w.str(&messages.screenshot_saved_prompt);
+
+    w.bool(config.backoff.is_some());
+    if let Some(policy) = &config.backoff {
+        w.u64(policy.brief_until);
+        match policy.interval {
+            Interval::Every(n) => {
+                w.u8(0);
+                w.u64(n);
+            }
+            Interval::Doubling => w.u8(1),
+        }
+    }
+
+    w.bool(config.quiet_period.is_some());
+    if let Some(period) = config.quiet_period {
+        w.u64(period.as_secs());
+        w.u64(u64::from(period.subsec_nanos()));
+    }
+
+    w.bool(config.namespace.is_some());
+    if let Some(namespace) = &config.namespace {
+        w.str(namespace);
+    }
+
+    w.len(cache_size);
+    w.0
+}
+
+/// Decode a configuration and the cache size of its handle.
+pub(crate) fn decode(bytes: &[u8]) -> Result<(BugReportConfig, usize), String> {
+    let mut r = Reader(bytes);
+    if r.take(MAGIC.len())? != MAGIC {
+        return Err("Not an encoded bug report handle".to_string());
+    }
+    let version = r.u8()?;
+    if version != VERSION {
+        return Err(format!(
+            "Encoded handle has format version {}, but this version of bug reads version {}",
+            version, VERSION
+        ));
+    }
+
+    let mut config = BugReportConfig::new(r.string()?, r.string()?);
+    config.base_url = r.optional_string()?;
+    config.use_hyperlinks = match r.u8()? {
+        0 => HyperlinkMode::Auto,
+        1 => HyperlinkMode::Always,
+        2 => HyperlinkMode::Never,
+        other => return Err(format!("Unknown hyperlink mode {} in encoded handle", other)),
+    };
+
+    for _ in 0..r.len()? {
+        let name = r.string()?;
+        match r.u8()? {
+            0 => {
+                let mut template = IssueTemplate::new(r.string()?, r.string()?);
+                template.labels = r.strings()?;
+                template.assignees = r.strings()?;
+                template.teams = r.strings()?;
+                template.milestone = r.optional_string()?;
+                template.projects = r.strings()?;
+                template.description = r.optional_string()?;
+                template.param_docs = r.param_docs()?;
+                template.fallback = r.optional_string()?;
+                config.register_template(name, template);
+            }
+            1 => {
+                // Template files borrow their contents for 'static
+                let content: &'static str = Box::leak(r.string()?.into_boxed_str());
+                let mut template_file = TemplateFile::new(content);
+                template_file.sha256 = r.optional_string()?;
+                template_file.labels = r.strings()?;
+                template_file.assignees = r.strings()?;
+                template_file.teams = r.strings()?;
+                template_file.milestone = r.optional_string()?;
+                template_file.projects = r.strings()?;
+                template_file.description = r.optional_string()?;
+                template_file.param_docs = r.param_docs()?;
+                template_file.fallback = r.optional_string()?;
+                config.register_template_file(name, template_file);
+            }
+            other => return Err(format!("Unknown template kind {} in encoded handle", other)),
+        }
+    }
+
+    for _ in 0..r.len()? {
+        let code = r.string()?;
+        config.error_codes.insert(code, r.string()?);
+    }
+
+    if r.bool()? {
+        config.allowed_labels = Some(r.strings()?);
+    }
+    config.allowed_label_prefixes = r.strings()?;
+    config.warn_on_disallowed_labels = r.bool()?;
+
+    for _ in 0..r.len()? {
+        let team = r.string()?;
+        config.teams.insert(team, r.strings()?);
+    }
+    config.team_assignment = match r.u8()? {
+        0 => TeamAssignment::All,
+        1 => TeamAssignment::RoundRobin,
+        other => return Err(format!("Unknown team assignment {} in encoded handle", other)),
+    };
+
+    for _ in 0..r.len()? {
+        let template_name = r.string()?;
+        config.sample_rates.insert(template_name, f64::from_bits(r.u64()?));
+    }
+
+    for _ in 0..r.len()? {
+        let template_name = r.string()?;
+        let mut target = TemplateTarget::new();
+        if r.bool()? {
+            target.repository = Some((r.string()?, r.string()?));
+        }
+        target.host = r.optional_string()?;
+        target.hyperlinks = match r.u8()? {
+            0 => None,
+            1 => Some(HyperlinkMode::Auto),
+            2 => Some(HyperlinkMode::Always),
+            3 => Some(HyperlinkMode::Never),
+            other => return Err(format!("Unknown hyperlink mode {} in encoded handle", other)),
+        };
+        config.template_targets.insert(template_name, target);
+    }
+
+    for _ in 0..r.len()? {
+        let placeholder = r.string()?;
+        config.param_policies.set(placeholder, r.policy()?);
+    }
+    for _ in 0..r.len()? {
+        let template_name = r.string()?;
+        for _ in 0..r.len()? {
+            let placeholder = r.string()?;
+            let policy = r.policy()?;
+            config
+                .param_policies
+                .set_for_template(template_name.clone(), placeholder, policy);
+        }
+    }
+    let k0 = r.u64()?;
+    config.param_policies.set_key(k0, r.u64()?);
+
+    config.include_source_excerpt = r.bool()?;
+    config.summarize = r.bool()?;
+    config.show_warnings = r.bool()?;
+    config.show_config_digest = r.bool()?;
+    config.deny_unfilled_placeholders = r.bool()?;
+    config.strict_params = r.bool()?;
+    config.minify_body = r.bool()?;
+    config.body_space_encoding = match r.u8()? {
+        0 => SpaceEncoding::Percent,
+        1 => SpaceEncoding::Plus,
+        other => return Err(format!("Unknown space encoding {} in encoded handle", other)),
+    };
+    config.label_encoding = match r.u8()? {
+        0 => LabelEncoding::Joined,
+        1 => LabelEncoding::Repeated,
+        other => return Err(format!("Unknown label encoding {} in encoded handle", other)),
+    };
+    config.registry.set_matching(match r.u8()? {
+        0 => NameMatching::Exact,
+        1 => NameMatching::CaseInsensitive,
+        2 => NameMatching::Normalized,
+        other => return Err(format!("Unknown name matching {} in encoded handle", other)),
+    });
+    config.on_missing_template = match r.u8()? {
+        0 => OnMissingTemplate::Error,
+        1 => OnMissingTemplate::LinkChooser,
+        other => return Err(format!("Unknown missing template policy {} in encoded handle", other)),
+    };
+    config.messages = Messages {
+        sampled_note: r.string()?,
+        source_unavailable: r.string()?,
+        steps_heading: r.string()?,
+        expected_heading: r.string()?,
+        actual_heading: r.string()?,
+        screenshot_heading: r.string()?,
+        screenshot_prompt: r.string()?,
+        screenshot_saved_prompt: r.string()?,
+    };
+
+    if r.bool()? {
+        let brief_until = r.u64()?;
+        let interval = match r.u8()? {
+            0 => Interval::Every(r.u64()?),
+            1 => Interval::Doubling,
+            other => return Err(format!("Unknown backoff interval {} in encoded handle", other)),
+        };
+        config.backoff = Some(BackoffPolicy { brief_until, interval });
+    }
+
+    if r.bool()? {
+        let secs = r.u64()?;
+        let nanos = u32::try_from(r.u64()?)
+            .ok()
+            .filter(|nanos| *nanos < 1_000_000_000)
+            .ok_or_else(|| "Invalid quiet period in encoded handle".to_string())?;
+        config.quiet_period = Some(core::time::Duration::new(secs, nanos));
+    }
+
+    config.namespace = r.optional_string()?;
+
+    let cache_size = r.len()?;
+    if !r.0.is_empty() {
+        return Err(format!("{} unexpected bytes after encoded handle", r.0.len()));
+    }
+    Ok((config, cache_size))
+}
+
+/// Map entries sorted by key, so equal configurations encode to equal bytes.
+fn sorted<'a, V>(entries: impl Iterator<Item = (&'a String, V)>) -> Vec<(&'a String, V)> {
+    let mut entries: Vec<_> = entries.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn u64(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.0.push(byte);
+                return;
+            }
+            self.0.push(byte | 0x80);
+        }
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u64(len as u64);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.len(value.len());
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn strs(&mut self, values: &[String]) {
+        self.len(values.len());
+        for value in values {
+            self.str(value);
+        }
+    }
+
+    fn policy(&mut self, policy: ParamPolicy) {
+        self.u8(match policy {
+            ParamPolicy::Plain => 0,
+            ParamPolicy::HashValue => 1,
+        });
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.0.len() < n {
+            return Err("Encoded handle is truncated".to_string());
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Integer too large in encoded handle".to_string())
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        usize::try_from(self.u64()?).map_err(|_| "Length too large in encoded handle".to_string())
+    }
+
+    fn bool(&mut self) -> Result<bool, String> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(format!("Invalid boolean {} in encoded handle", other)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.len()?;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| "Invalid UTF-8 in encoded handle".to_string())
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, String> {
+        if self.bool()? {
+            self.string().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, String> {
+        let len = self.len()?;
+        // Every string takes at least one byte, which bounds the allocation
+        if len > self.0.len() {
+            return Err("Encoded handle is truncated".to_string());
+        }
+        (0..len).map(|_| self.string()).collect()
+    }
+
+    fn param_docs(&mut self) -> Result<Vec<(String, String)>, String> {
+        let len = self.len()?;
+        // Every pair takes at least two bytes, which bounds the allocation
+        if len > self.0.len() / 2 {
+            return Err("Encoded handle is truncated".to_string());
+        }
+        (0..len).map(|_| Ok((self.string()?, self.string()?))).collect()
+    }
+
+    fn policy(&mut self) -> Result<ParamPolicy, String> {
+        match self.u8()? {
+            0 => Ok(ParamPolicy::Plain),
+            1 => Ok(ParamPolicy::HashValue),
+            other => Err(format!("Unknown parameter policy {} in encoded handle", other)),
+        }
+    }
+}
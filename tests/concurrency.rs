@@ -0,0 +1,74 @@
+//! Many threads installing the global configuration and reporting at once.
+//!
+//! Also run against the no_std global with
+//! `cargo test --no-default-features --features global-nostd --test concurrency`.
+#![cfg(any(feature = "std", feature = "global-nostd"))]
+
+use bug::{bug, init, init_handle, IssueTemplate, Params};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+const THREADS: usize = 16;
+const REPORTS: usize = 500;
+
+#[test]
+fn global_build_and_reports_race() {
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let threads: Vec<_> = (0..THREADS)
+        .map(|worker| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                let installed = init("owner", "repo")
+                    .add_template("stress", IssueTemplate::new("Stress {worker}", "{iteration}"))
+                    .summarize(true)
+                    .build()
+                    .is_ok();
+                let urls: Vec<String> = (0..REPORTS)
+                    .map(|iteration| bug!("stress", { worker = worker, iteration = iteration }))
+                    .collect();
+                (installed, urls)
+            })
+        })
+        .collect();
+
+    let mut installed = 0;
+    for (worker, thread) in threads.into_iter().enumerate() {
+        let (won, urls) = thread.join().unwrap();
+        installed += usize::from(won);
+        for (iteration, url) in urls.iter().enumerate() {
+            let expected = format!("/issues/new?title=Stress+{}&body={}", worker, iteration);
+            assert!(url.ends_with(&expected), "{} doesn't end with {}", url, expected);
+        }
+    }
+    assert_eq!(installed, 1);
+}
+
+#[test]
+fn shared_handle_reports_race() {
+    let handle = Arc::new(
+        init_handle("owner", "repo")
+            .add_template("stress", IssueTemplate::new("Stress {key}", "From {worker}"))
+            .cache_size(8),
+    );
+    let threads: Vec<_> = (0..THREADS)
+        .map(|worker| {
+            let handle = handle.clone();
+            thread::spawn(move || {
+                for iteration in 0..REPORTS {
+                    let key = (worker * 7 + iteration) % 32;
+                    let mut params = Params::new();
+                    params.insert("key", key.to_string());
+                    params.insert("worker", (worker % 2).to_string());
+                    let report = handle.create_report("stress", &params).unwrap();
+                    assert_eq!(report.title, format!("Stress {}", key));
+                    assert_eq!(report.body, format!("From {}", worker % 2));
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(handle.cached_reports(), 8);
+}
@@ -0,0 +1,131 @@
+//! `GrpcBugReportLayer` around a hand-written tower service.
+
+use bug::grpc::{CallInfo, GrpcBugReportLayer};
+use bug::{init_handle, IssueTemplate};
+use http::{Request, Response};
+use std::convert::Infallible;
+use std::future::{ready, Future, Ready};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A service answering every request with a trailers-only response carrying `headers`.
+struct TrailersOnly(&'static [(&'static str, &'static str)]);
+
+impl Service<Request<()>> for TrailersOnly {
+    type Response = Response<()>;
+    type Error = Infallible;
+    type Future = Ready<Result<Response<()>, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _request: Request<()>) -> Self::Future {
+        let mut response = Response::builder().header("content-type", "application/grpc");
+        for (name, value) in self.0 {
+            response = response.header(*name, *value);
+        }
+        ready(Ok(response.body(()).unwrap()))
+    }
+}
+
+/// The title and call of each report.
+type Reported = Arc<Mutex<Vec<(String, CallInfo)>>>;
+
+/// A layer recording the calls it reported.
+///
+/// Its templates are strict, so passing an unused placeholder fails the report.
+fn layer(template: IssueTemplate) -> (GrpcBugReportLayer, Reported) {
+    let handle = init_handle("owner", "repo")
+        .strict_params(true)
+        .add_template("grpc_internal", template);
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = reported.clone();
+    let layer = GrpcBugReportLayer::new(handle, "grpc_internal")
+        .on_report(move |report, info| sink.lock().unwrap().push((report.title.clone(), info.clone())));
+    (layer, reported)
+}
+
+/// Send one call through `layer` to a service answering with `headers`.
+fn send(layer: &GrpcBugReportLayer, headers: &'static [(&'static str, &'static str)]) {
+    let mut service = layer.layer(TrailersOnly(headers));
+    let request = Request::post("/shop.Orders/Create").body(()).unwrap();
+    let mut future = std::pin::pin!(service.call(request));
+    assert!(future.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_ready());
+}
+
+#[test]
+fn internal_error_is_reported() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error in {method}", "Code {code}: {message}"));
+    send(&layer, &[("grpc-status", "13"), ("grpc-message", "database%20unavailable%3A%20100%25%20busy")]);
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 1);
+    let (title, info) = &reported[0];
+    assert_eq!(title, "Internal error in /shop.Orders/Create");
+    assert_eq!(info.method, "/shop.Orders/Create");
+    assert_eq!(info.code, 13);
+    assert_eq!(info.message, "database unavailable: 100% busy");
+}
+
+#[test]
+fn message_keeps_malformed_escapes() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error", "{message}"));
+    send(&layer, &[("grpc-status", " 13 "), ("grpc-message", "bad %zz escape %4")]);
+    send(&layer, &[("grpc-status", "13"), ("grpc-message", "signed %+5 escape")]);
+    send(&layer, &[("grpc-status", "13"), ("grpc-message", "a+b%20%FF")]);
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported[0].1.message, "bad %zz escape %4");
+    assert_eq!(reported[1].1.message, "signed %+5 escape");
+    assert_eq!(reported[2].1.message, "a+b \u{fffd}");
+}
+
+#[test]
+fn missing_message_is_empty() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error", "{message}"));
+    send(&layer, &[("grpc-status", "13")]);
+    assert_eq!(reported.lock().unwrap()[0].1.message, "");
+}
+
+#[test]
+fn other_codes_are_not_reported() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error", ""));
+    send(&layer, &[("grpc-status", "0")]);
+    send(&layer, &[("grpc-status", "5"), ("grpc-message", "not found")]);
+    send(&layer, &[("grpc-status", "internal")]);
+    send(&layer, &[]);
+    assert!(reported.lock().unwrap().is_empty());
+}
+
+#[test]
+fn report_codes_replaces_the_default() {
+    let (layer, reported) = layer(IssueTemplate::new("Unavailable", ""));
+    let layer = layer.report_codes([14]);
+    send(&layer, &[("grpc-status", "13")]);
+    send(&layer, &[("grpc-status", "14")]);
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0].1.code, 14);
+}
+
+#[test]
+fn unused_placeholders_are_not_passed_to_strict_templates() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error in {method}", ""));
+    send(&layer, &[("grpc-status", "13"), ("grpc-message", "boom")]);
+    assert_eq!(reported.lock().unwrap()[0].0, "Internal error in /shop.Orders/Create");
+}
+
+#[test]
+fn configuring_a_cloned_layer_leaves_the_clone_alone() {
+    let (layer, reported) = layer(IssueTemplate::new("Internal error", ""));
+    let internal = layer.clone();
+    let unavailable = layer.report_codes([14]);
+    send(&internal, &[("grpc-status", "14")]);
+    send(&unavailable, &[("grpc-status", "14")]);
+    send(&unavailable, &[("grpc-status", "13")]);
+    assert_eq!(reported.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,140 @@
+//! `BugReportLayer` around a hand-written tower service.
+
+use bug::web::{BugReportLayer, RequestInfo};
+use bug::{init_handle, IssueTemplate};
+use http::{Request, Response, StatusCode};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type BoxFuture = Pin<Box<dyn Future<Output = Result<Response<String>, Infallible>> + Send>>;
+
+/// A service answering every request with `handler`.
+struct Handler<F>(F);
+
+impl<F: FnMut(Request<String>) -> BoxFuture> Service<Request<String>> for Handler<F> {
+    type Response = Response<String>;
+    type Error = Infallible;
+    type Future = BoxFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<String>) -> BoxFuture {
+        (self.0)(request)
+    }
+}
+
+fn respond(status: StatusCode, body: &str) -> BoxFuture {
+    let response = Response::builder().status(status).body(body.to_string()).unwrap();
+    Box::pin(async move { Ok(response) })
+}
+
+/// A layer recording the requests it reported.
+fn layer(attach: bool) -> (BugReportLayer, Arc<Mutex<Vec<RequestInfo>>>) {
+    let handle = init_handle("owner", "repo").add_template(
+        "server_error",
+        IssueTemplate::new("Internal error in {method} {route}", "Request {request_id} failed with {status}"),
+    );
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let sink = reported.clone();
+    let layer = BugReportLayer::new(handle, "server_error")
+        .attach_to_response(attach)
+        .on_report(move |_, info| sink.lock().unwrap().push(info.clone()));
+    (layer, reported)
+}
+
+/// Send one request through `layer` around `handler`.
+fn send(layer: &BugReportLayer, handler: impl FnMut(Request<String>) -> BoxFuture) -> Response<String> {
+    let mut service = layer.layer(Handler(handler));
+    let request = Request::post("/orders/7")
+        .header("x-request-id", "req-1")
+        .body(String::new())
+        .unwrap();
+    let mut future = std::pin::pin!(service.call(request));
+    match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(Ok(response)) => response,
+        Poll::Ready(Err(never)) => match never {},
+        Poll::Pending => panic!("the test services are always ready"),
+    }
+}
+
+#[test]
+fn success_passes_through() {
+    let (layer, reported) = layer(true);
+    let response = send(&layer, |_| respond(StatusCode::OK, "created"));
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.body(), "created");
+    assert!(reported.lock().unwrap().is_empty());
+}
+
+#[test]
+fn server_error_is_reported() {
+    let (layer, reported) = layer(false);
+    let response = send(&layer, |_| respond(StatusCode::BAD_GATEWAY, "upstream down"));
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(response.body(), "upstream down");
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 1);
+    assert_eq!(reported[0].method, "POST");
+    assert_eq!(reported[0].route, "/orders/7");
+    assert_eq!(reported[0].request_id.as_deref(), Some("req-1"));
+    assert_eq!(reported[0].status, StatusCode::BAD_GATEWAY);
+    assert!(!reported[0].panicked);
+}
+
+#[test]
+fn server_error_body_is_replaced_when_attaching() {
+    let (layer, _) = layer(true);
+    let response = send(&layer, |_| respond(StatusCode::INTERNAL_SERVER_ERROR, "oops"));
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(response.body().starts_with("Internal Server Error\n\nPlease report this problem: https://github.com/owner/repo/issues/new?"));
+    assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+}
+
+#[test]
+fn panic_in_call_is_reported() {
+    let (layer, reported) = layer(false);
+    let response = send(&layer, |_| panic!("handler panicked before returning a future"));
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.body(), "Internal Server Error");
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(reported.len(), 1);
+    assert!(reported[0].panicked);
+}
+
+#[test]
+fn panic_in_poll_is_reported() {
+    let (layer, reported) = layer(false);
+    let response = send(&layer, |_| Box::pin(async { panic!("handler panicked while running") }));
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(response.body(), "Internal Server Error");
+    assert!(reported.lock().unwrap()[0].panicked);
+}
+
+#[test]
+fn panic_response_carries_url_when_attaching() {
+    let (layer, _) = layer(true);
+    let response = send(&layer, |_| Box::pin(async { panic!("handler panicked while running") }));
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(response.body().contains("Please report this problem: https://github.com/owner/repo/issues/new?"));
+}
+
+#[test]
+fn configuring_a_cloned_layer_leaves_the_clone_alone() {
+    let (layer, _) = layer(false);
+    let plain = layer.clone();
+    let attaching = layer.attach_to_response(true);
+
+    let response = send(&plain, |_| respond(StatusCode::INTERNAL_SERVER_ERROR, "oops"));
+    assert_eq!(response.body(), "oops");
+    let response = send(&attaching, |_| respond(StatusCode::INTERNAL_SERVER_ERROR, "oops"));
+    assert!(response.body().contains("Please report this problem:"));
+}